@@ -1,8 +1,16 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
 pub fn to_ram_address(index: usize) -> usize {
     index * constants::BYTES_PER_INSTRUCTION + constants::RAM_START_ADDRESS as usize
 }
 
 pub mod constants;
+#[cfg(feature = "std")]
 pub mod parallel;
+pub mod precompiles;
+#[cfg(feature = "std")]
 pub mod rv_trace;
+#[cfg(feature = "std")]
 pub mod serializable;
+#[cfg(feature = "std")]
+pub mod syscall;
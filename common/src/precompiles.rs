@@ -0,0 +1,112 @@
+//! Reference implementations of the precompile instructions, used by the tracer to
+//! execute them and by jolt-sdk when a guest runs natively. Precompiles live in the
+//! RISC-V custom-0 opcode space (0x0b) and are R-type: `funct3` selects the
+//! precompile, and rs1/rs2 hold pointers to its operands in guest memory.
+//!
+//! Big integers are 256 bits wide, stored as eight little-endian `u32` limbs.
+
+/// `bigint_add rd, rs1, rs2`: adds the integer at address rs2 to the integer at
+/// address rs1, modulo 2^256, and writes the carry out to rd.
+pub const BIGINT_ADD_FUNCT3: u32 = 2;
+
+/// `bigint_mul rs1, rs2`: multiplies the integer in the low eight limbs of the
+/// sixteen-limb buffer at address rs1 by the integer at address rs2, and writes the
+/// 512-bit product to the buffer. The operands must not overlap.
+pub const BIGINT_MUL_FUNCT3: u32 = 3;
+
+/// `bigint_mulmod rs1, rs2`: rs1 points to an integer `a` followed by a modulus `p`.
+/// Overwrites `a` with `a * b mod p`, where `b` is the integer at address rs2. The
+/// product must be less than `2^256 * p`, which holds whenever `a < p` or `b < p`.
+/// The operands must not overlap.
+pub const BIGINT_MULMOD_FUNCT3: u32 = 4;
+
+/// `a += b` modulo 2^256. Returns the carry out.
+pub fn bigint_add(a: &mut [u32; 8], b: &[u32; 8]) -> bool {
+    let mut carry = 0;
+    for (a, b) in a.iter_mut().zip(b) {
+        let sum = *a as u64 + *b as u64 + carry;
+        *a = sum as u32;
+        carry = sum >> 32;
+    }
+    carry == 1
+}
+
+/// The 512-bit product of `a` and `b`.
+pub fn bigint_mul(a: &[u32; 8], b: &[u32; 8]) -> [u32; 16] {
+    let mut product = [0u32; 16];
+    for (i, a) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, b) in b.iter().enumerate() {
+            let value = product[i + j] as u64 + *a as u64 * *b as u64 + carry;
+            product[i + j] = value as u32;
+            carry = value >> 32;
+        }
+        product[i + 8] = carry as u32;
+    }
+    product
+}
+
+/// Returns the quotient and remainder of `a * b` divided by `modulus`.
+///
+/// Panics if `modulus` is zero or the quotient does not fit in 256 bits.
+pub fn bigint_mulmod(a: &[u32; 8], b: &[u32; 8], modulus: &[u32; 8]) -> ([u32; 8], [u32; 8]) {
+    assert!(modulus.iter().any(|limb| *limb != 0), "modulus is zero");
+    let product = bigint_mul(a, b);
+
+    // Binary long division; the remainder is less than the modulus before each shift,
+    // so it needs to be reduced at most once after it
+    let mut quotient = [0u32; 16];
+    let mut remainder = [0u32; 8];
+    for bit in (0..512).rev() {
+        let overflow = remainder[7] >> 31;
+        for i in (1..8).rev() {
+            remainder[i] = (remainder[i] << 1) | (remainder[i - 1] >> 31);
+        }
+        remainder[0] = (remainder[0] << 1) | ((product[bit / 32] >> (bit % 32)) & 1);
+        if overflow == 1 || !less_than(&remainder, modulus) {
+            let mut borrow = 0;
+            for (r, p) in remainder.iter_mut().zip(modulus) {
+                let difference = *r as i64 - *p as i64 - borrow;
+                *r = difference as u32;
+                borrow = (difference < 0) as i64;
+            }
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    assert!(
+        quotient[8..].iter().all(|limb| *limb == 0),
+        "bigint_mulmod operands are not reduced"
+    );
+    (quotient[..8].try_into().unwrap(), remainder)
+}
+
+fn less_than(a: &[u32; 8], b: &[u32; 8]) -> bool {
+    a.iter().rev().lt(b.iter().rev())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bigint_arithmetic() {
+        let max = [u32::MAX; 8];
+        let mut a = max;
+        assert!(bigint_add(&mut a, &[1, 0, 0, 0, 0, 0, 0, 0]));
+        assert_eq!(a, [0; 8]);
+
+        // (2^256 - 1)^2 = 2^512 - 2^257 + 1
+        let product = bigint_mul(&max, &max);
+        assert_eq!(product[0], 1);
+        assert_eq!(&product[1..8], &[0; 7]);
+        assert_eq!(product[8], u32::MAX - 1);
+        assert_eq!(&product[9..], &[u32::MAX; 7]);
+
+        // 2^255 * 6 = 3 * 2^256, so mod 2^256 - 1 the result is 3
+        let mut a = [0; 8];
+        a[7] = 1 << 31;
+        let (quotient, remainder) = bigint_mulmod(&a, &[6, 0, 0, 0, 0, 0, 0, 0], &max);
+        assert_eq!(quotient, [3, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(remainder, [3, 0, 0, 0, 0, 0, 0, 0]);
+    }
+}
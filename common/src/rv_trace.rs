@@ -10,6 +10,9 @@ pub struct RVTraceRow {
     pub instruction: ELFInstruction,
     pub register_state: RegisterState,
     pub memory_state: Option<MemoryState>,
+    /// The memory words read by a precompile instruction, which its virtual
+    /// sequence needs to reproduce the loads.
+    pub precompile_input: Option<Vec<u32>>,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
@@ -113,6 +116,15 @@ impl From<&RVTraceRow> for [MemoryOp; MEMORY_OPS_PER_INSTRUCTION] {
                 | RV32IM::SLTI
                 | RV32IM::SLTIU
                 | RV32IM::JALR
+                | RV32IM::CLZ
+                | RV32IM::CTZ
+                | RV32IM::CPOP
+                | RV32IM::SEXT_B
+                | RV32IM::SEXT_H
+                | RV32IM::ORC_B
+                | RV32IM::REV8
+                | RV32IM::RORI
+                | RV32IM::ZEXT_H
                 | RV32IM::VIRTUAL_MOVSIGN => [
                     rs1_read(),
                     MemoryOp::noop_read(),
@@ -261,6 +273,15 @@ impl ELFInstruction {
             | RV32IM::SRAI
             | RV32IM::SLTI
             | RV32IM::SLTIU
+            | RV32IM::CLZ
+            | RV32IM::CTZ
+            | RV32IM::CPOP
+            | RV32IM::SEXT_B
+            | RV32IM::SEXT_H
+            | RV32IM::ORC_B
+            | RV32IM::REV8
+            | RV32IM::RORI
+            | RV32IM::ZEXT_H
             | RV32IM::AUIPC
             | RV32IM::JAL
             | RV32IM::JALR,
@@ -300,7 +321,9 @@ impl ELFInstruction {
             | RV32IM::BGEU
             | RV32IM::JAL
             | RV32IM::JALR
-            | RV32IM::LUI,
+            | RV32IM::LUI
+            | RV32IM::VIRTUAL_ASSERT_EQ
+            | RV32IM::VIRTUAL_ASSERT_LTU,
         );
 
         let mask = 1u32 << 31;
@@ -329,10 +352,30 @@ impl ELFInstruction {
             | RV32IM::BLT
             | RV32IM::BGE
             | RV32IM::BLTU
-            | RV32IM::BGEU,
+            | RV32IM::BGEU
+            | RV32IM::VIRTUAL_ASSERT_EQ
+            | RV32IM::VIRTUAL_ASSERT_LTU
+            | RV32IM::ANDN
+            | RV32IM::ORN
+            | RV32IM::XNOR
+            | RV32IM::CLZ
+            | RV32IM::CTZ
+            | RV32IM::CPOP
+            | RV32IM::SEXT_B
+            | RV32IM::SEXT_H
+            | RV32IM::MAX
+            | RV32IM::MAXU
+            | RV32IM::MIN
+            | RV32IM::MINU
+            | RV32IM::ROL
+            | RV32IM::ROR
+            | RV32IM::RORI
+            | RV32IM::ORC_B
+            | RV32IM::REV8
+            | RV32IM::ZEXT_H,
         );
 
-        // TODO(moodlezoup): Use these flags in R1CS constraints
+        // TODO(moodlezoup): Use this flag in R1CS constraints
         flags[9] = match self.virtual_sequence_index {
             // For virtual sequences, we set
             //     virtual PC := ProgARW     (the bytecode `a` value)
@@ -441,6 +484,32 @@ pub enum RV32IM {
     REMU,
     FENCE,
     UNIMPL,
+    // Zbb and Zba
+    ANDN,
+    ORN,
+    XNOR,
+    CLZ,
+    CTZ,
+    CPOP,
+    SEXT_B,
+    SEXT_H,
+    MAX,
+    MAXU,
+    MIN,
+    MINU,
+    ROL,
+    ROR,
+    RORI,
+    ORC_B,
+    REV8,
+    ZEXT_H,
+    SH1ADD,
+    SH2ADD,
+    SH3ADD,
+    // Precompiles
+    BIGINT_ADD,
+    BIGINT_MUL,
+    BIGINT_MULMOD,
     // Virtual instructions
     VIRTUAL_MOVSIGN,
     VIRTUAL_ADVICE,
@@ -506,6 +575,30 @@ impl FromStr for RV32IM {
             "REMU" => Ok(Self::REMU),
             "FENCE" => Ok(Self::FENCE),
             "UNIMPL" => Ok(Self::UNIMPL),
+            "ANDN" => Ok(Self::ANDN),
+            "ORN" => Ok(Self::ORN),
+            "XNOR" => Ok(Self::XNOR),
+            "CLZ" => Ok(Self::CLZ),
+            "CTZ" => Ok(Self::CTZ),
+            "CPOP" => Ok(Self::CPOP),
+            "SEXT_B" => Ok(Self::SEXT_B),
+            "SEXT_H" => Ok(Self::SEXT_H),
+            "MAX" => Ok(Self::MAX),
+            "MAXU" => Ok(Self::MAXU),
+            "MIN" => Ok(Self::MIN),
+            "MINU" => Ok(Self::MINU),
+            "ROL" => Ok(Self::ROL),
+            "ROR" => Ok(Self::ROR),
+            "RORI" => Ok(Self::RORI),
+            "ORC_B" => Ok(Self::ORC_B),
+            "REV8" => Ok(Self::REV8),
+            "ZEXT_H" => Ok(Self::ZEXT_H),
+            "SH1ADD" => Ok(Self::SH1ADD),
+            "SH2ADD" => Ok(Self::SH2ADD),
+            "SH3ADD" => Ok(Self::SH3ADD),
+            "BIGINT_ADD" => Ok(Self::BIGINT_ADD),
+            "BIGINT_MUL" => Ok(Self::BIGINT_MUL),
+            "BIGINT_MULMOD" => Ok(Self::BIGINT_MULMOD),
             _ => Err("Could not match instruction to RV32IM set.".to_string()),
         }
     }
@@ -543,7 +636,22 @@ impl RV32IM {
             RV32IM::DIV    |
             RV32IM::DIVU   |
             RV32IM::REM    |
-            RV32IM::REMU => RV32InstructionFormat::R,
+            RV32IM::REMU   |
+            RV32IM::ANDN   |
+            RV32IM::ORN    |
+            RV32IM::XNOR   |
+            RV32IM::MAX    |
+            RV32IM::MAXU   |
+            RV32IM::MIN    |
+            RV32IM::MINU   |
+            RV32IM::ROL    |
+            RV32IM::ROR    |
+            RV32IM::SH1ADD |
+            RV32IM::SH2ADD |
+            RV32IM::SH3ADD |
+            RV32IM::BIGINT_ADD |
+            RV32IM::BIGINT_MUL |
+            RV32IM::BIGINT_MULMOD => RV32InstructionFormat::R,
 
             RV32IM::ADDI  |
             RV32IM::XORI  |
@@ -555,6 +663,15 @@ impl RV32IM {
             RV32IM::SLTI  |
             RV32IM::FENCE |
             RV32IM::SLTIU |
+            RV32IM::CLZ   |
+            RV32IM::CTZ   |
+            RV32IM::CPOP  |
+            RV32IM::SEXT_B |
+            RV32IM::SEXT_H |
+            RV32IM::ORC_B |
+            RV32IM::REV8  |
+            RV32IM::RORI  |
+            RV32IM::ZEXT_H |
             RV32IM::VIRTUAL_MOVSIGN=> RV32InstructionFormat::I,
 
             RV32IM::LB  |
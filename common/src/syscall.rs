@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::FromRepr;
+
+/// Register holding the syscall number when a guest executes `ECALL` (a7).
+pub const SYSCALL_NUMBER_REGISTER: u64 = 17;
+/// Registers holding the syscall arguments (a0-a6).
+pub const SYSCALL_ARGUMENT_REGISTERS: [u64; 7] = [10, 11, 12, 13, 14, 15, 16];
+/// Register the syscall's return value is written to (a0).
+pub const SYSCALL_RETURN_REGISTER: u64 = 10;
+
+/// File descriptors accepted by `Syscall::Write`.
+pub const STDOUT_FD: u64 = 1;
+pub const STDERR_FD: u64 = 2;
+
+/// Syscalls understood by the Jolt tracer.
+///
+/// A guest invokes a syscall by placing its number in a7 and up to seven arguments
+/// in a0-a6, then executing `ECALL`. The host handler registered for that number
+/// runs in place of the instruction and its return value is written to a0.
+/// All other registers and guest memory are left untouched, so from the prover's
+/// point of view an `ECALL` is a single write of untrusted advice to a0
+/// (see `ECALLInstruction` in jolt-core).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, FromRepr, Serialize, Deserialize, Hash)]
+#[repr(u32)]
+pub enum Syscall {
+    /// Terminates execution. a0: exit code.
+    Exit = 0,
+    /// Writes a guest buffer to the host console. a0: fd, a1: pointer, a2: length.
+    /// Returns the number of bytes written.
+    Write = 1,
+    /// Returns the next word of the host-provided hint stream (0 once exhausted).
+    HintRead = 2,
+    /// Returns the number of instructions executed so far.
+    CycleCount = 3,
+}
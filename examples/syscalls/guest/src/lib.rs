@@ -0,0 +1,20 @@
+#![cfg_attr(feature = "guest", no_std)]
+#![no_main]
+
+use jolt::bigint::U256;
+
+/// Also checks that `root` is a square root of `a`. The root is a hint, so it isn't part of
+/// the public statement.
+#[jolt::provable(guest_only)]
+fn mulmod_with_cycle_count(a: U256, b: U256, #[hint] root: U256) -> (U256, bool, bool, u32) {
+    use jolt::bigint::{self, BN254_SCALAR_MODULUS};
+    use jolt::syscall;
+
+    syscall::print("computing a * b mod r\n");
+    let start = syscall::cycle_count();
+    let mut product = bigint::mulmod(&a, &b, &BN254_SCALAR_MODULUS);
+    let carry = bigint::add(&mut product, &a);
+    let cycles = syscall::cycle_count() - start;
+    let is_root = bigint::mulmod(&root, &root, &BN254_SCALAR_MODULUS) == a;
+    (product, carry, is_root, cycles)
+}
@@ -0,0 +1,16 @@
+use jolt_sdk::bigint::{self, BN254_SCALAR_MODULUS};
+
+pub fn main() {
+    let (prove_mulmod, verify_mulmod) = guest::build_mulmod_with_cycle_count();
+
+    let root = [3u32; 8];
+    let a = bigint::mulmod(&root, &root, &BN254_SCALAR_MODULUS);
+    let b = [0xffff_ffffu32; 8];
+    let ((product, carry, is_root, cycles), proof) = prove_mulmod(a, b, root);
+    let is_valid = verify_mulmod(proof);
+
+    println!("output: {:x?} (carry: {})", product, carry);
+    println!("hinted root is a square root of a: {}", is_root);
+    println!("cycles between the two cycle_count calls: {}", cycles);
+    println!("valid: {}", is_valid);
+}
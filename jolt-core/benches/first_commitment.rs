@@ -0,0 +1,50 @@
+use ark_bn254::{Fr, G1Projective};
+use criterion::{criterion_group, criterion_main, Criterion};
+use jolt_core::{
+    host,
+    jolt::vm::{
+        rv32i_vm::{RV32IJoltVM, C, M},
+        Jolt, JoltPreprocessing,
+    },
+    poly::{commitment::hyrax::HyraxScheme, structured_poly::StructuredCommitment},
+};
+
+type Pcs = HyraxScheme<G1Projective>;
+
+/// Time from starting the guest to holding the commitments to the trace polynomials. The
+/// baseline builds the polynomials once the trace is complete; the streaming pipeline builds
+/// them from each chunk of the trace while the guest is still running.
+fn bench_first_commitment(c: &mut Criterion) {
+    let mut program = host::Program::new("sha2-guest");
+    program.set_input(&vec![5u8; 2048]);
+    let (bytecode, memory_init) = program.decode();
+    let preprocessing: JoltPreprocessing<Fr, Pcs> =
+        RV32IJoltVM::preprocess(bytecode, memory_init, 1 << 20, 1 << 20, 1 << 22, None);
+
+    let mut group = c.benchmark_group("Time to first commitment");
+    group.sample_size(10);
+
+    group.bench_function("trace, then polynomialize (baseline)", |b| {
+        b.iter(|| {
+            let (io_device, mut trace, _) = program.clone().trace::<Fr>();
+            let polynomials = <RV32IJoltVM as Jolt<Fr, Pcs, C, M>>::polynomialize(
+                &io_device,
+                &mut trace,
+                &preprocessing,
+            );
+            polynomials.commit(&preprocessing.generators)
+        })
+    });
+    group.bench_function("polynomialize while tracing", |b| {
+        b.iter(|| {
+            let (_, _, _, polynomials) = program
+                .clone()
+                .trace_and_polynomialize::<Fr, Pcs>(&preprocessing);
+            polynomials.commit(&preprocessing.generators)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_first_commitment);
+criterion_main!(benches);
@@ -0,0 +1,138 @@
+use ark_bn254::Fr;
+use ark_std::test_rng;
+use common::constants::RAM_START_ADDRESS;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use jolt_core::{
+    jolt::vm::rv32i_vm::{C, M, RV32I},
+    poly::{
+        dense_mlpoly::DensePolynomial,
+        eq_poly::{BindingOrder, SplitEqPolynomial},
+        field::JoltField,
+        padded_polynomial::PaddedPolynomial,
+    },
+    r1cs::{
+        constraints::R1CSBuilder,
+        r1cs_shape::R1CSShape,
+        spartan::{SegmentedPaddedWitness, UniformShapeBuilder},
+    },
+    subprotocols::sumcheck::SumcheckInstanceProof,
+    utils::{thread::unsafe_allocate_zero_vec, transcript::ProofTranscript},
+};
+use rand_core::RngCore;
+
+const NUM_STEPS: usize = 1 << 14;
+
+/// The Jolt step shape and a witness for `NUM_STEPS` steps, made of circuit flags and 32-bit
+/// values like the real one. The witness need not satisfy the constraints.
+fn setup() -> (
+    R1CSShape<Fr>,
+    SegmentedPaddedWitness<Fr>,
+    Vec<PaddedPolynomial<i128>>,
+    usize,
+) {
+    let mut jolt_shape = R1CSBuilder::<C, M, RV32I>::default();
+    R1CSBuilder::jolt_r1cs_matrices(&mut jolt_shape, RAM_START_ADDRESS);
+    let shape: R1CSShape<Fr> = jolt_shape.single_step_shape(RAM_START_ADDRESS);
+
+    let mut rng = test_rng();
+    let small_segments: Vec<PaddedPolynomial<i128>> = (0..jolt_shape.num_aux)
+        .map(|i| {
+            let values = (0..NUM_STEPS)
+                .map(|_| match i % 4 {
+                    0 => rng.next_u32() as i128,
+                    _ => rng.next_u32() as i128 % 2,
+                })
+                .collect();
+            PaddedPolynomial::new(NUM_STEPS.ilog2() as usize, values, 0)
+        })
+        .collect();
+    let segments: Vec<PaddedPolynomial<Fr>> = small_segments
+        .iter()
+        .map(|segment| {
+            let values = segment
+                .values()
+                .iter()
+                .map(|&value| Fr::from_i128(value))
+                .collect();
+            PaddedPolynomial::new(segment.get_num_vars(), values, Fr::zero())
+        })
+        .collect();
+    let witness_len = jolt_shape.num_aux.next_power_of_two() * NUM_STEPS;
+    let witness = SegmentedPaddedWitness::new(witness_len, segments);
+    let len = ((jolt_shape.num_constraints + 1) * NUM_STEPS).next_power_of_two();
+    (shape, witness, small_segments, len)
+}
+
+fn bench_spartan_outer(c: &mut Criterion) {
+    let (shape, witness, small_segments, len) = setup();
+    let mut group = c.benchmark_group("Spartan outer");
+    group.sample_size(10);
+
+    group.bench_function("Az/Bz/Cz (field)", |b| {
+        b.iter(|| {
+            let mut a_z: Vec<Fr> = unsafe_allocate_zero_vec(len);
+            let mut b_z: Vec<Fr> = unsafe_allocate_zero_vec(len);
+            let mut c_z: Vec<Fr> = unsafe_allocate_zero_vec(len);
+            shape
+                .multiply_vec_uniform(&witness, NUM_STEPS, &mut a_z, &mut b_z, &mut c_z)
+                .unwrap();
+            (a_z, b_z, c_z)
+        })
+    });
+    group.bench_function("Az/Bz/Cz (small)", |b| {
+        b.iter(|| {
+            shape
+                .multiply_vec_uniform_small(&small_segments, NUM_STEPS, len)
+                .unwrap()
+        })
+    });
+
+    let (a_z, b_z, c_z) = shape
+        .multiply_vec_uniform_small(&small_segments, NUM_STEPS, len)
+        .unwrap();
+    let mut rng = test_rng();
+    let tau: Vec<Fr> = (0..len.ilog2()).map(|_| Fr::random(&mut rng)).collect();
+    let eq = SplitEqPolynomial::new(&tau, BindingOrder::Top);
+    let to_field = |poly: &[i128]| -> DensePolynomial<Fr> {
+        DensePolynomial::new(poly.iter().map(|x| Fr::from_i128(*x)).collect())
+    };
+
+    group.bench_function("sumcheck (field)", |b| {
+        b.iter_batched(
+            || (eq.clone(), to_field(&a_z), to_field(&b_z), to_field(&c_z)),
+            |(mut eq, mut a_z, mut b_z, mut c_z)| {
+                SumcheckInstanceProof::prove_spartan_cubic(
+                    &Fr::zero(),
+                    len.ilog2() as usize,
+                    &mut eq,
+                    &mut a_z,
+                    &mut b_z,
+                    &mut c_z,
+                    &mut ProofTranscript::new(b"bench"),
+                )
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("sumcheck (small)", |b| {
+        b.iter_batched(
+            || (eq.clone(), a_z.clone(), b_z.clone(), c_z.clone()),
+            |(mut eq, a_z, b_z, c_z)| {
+                SumcheckInstanceProof::prove_spartan_cubic_small(
+                    &Fr::zero(),
+                    len.ilog2() as usize,
+                    &mut eq,
+                    a_z,
+                    b_z,
+                    c_z,
+                    &mut ProofTranscript::new(b"bench"),
+                )
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_spartan_outer);
+criterion_main!(benches);
@@ -0,0 +1,370 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, punctuated::Punctuated, Data, DeriveInput, Fields, Ident,
+    Token, Type,
+};
+
+/// Derives `JoltInstruction` and `JoltInstructionSet` for an enum whose variants each wrap a
+/// single `JoltInstruction` type, e.g.
+///
+/// ```ignore
+/// #[derive(Copy, Clone, Debug, EnumIter, EnumCount, Serialize, Deserialize, JoltInstructionSet)]
+/// pub enum MyInstructionSet {
+///     ADD(ADDInstruction<32>),
+///     XOR(XORInstruction),
+/// }
+/// ```
+///
+/// `JoltInstruction` methods are dispatched to the wrapped instruction, and each variant's
+/// type gets a `From` impl. An instruction's `enum_index` is the position of its variant, which
+/// `instruction_index` also resolves from the wrapped type's `TypeId`. The enum implements
+/// `InstructionSubtables` for the subtable sets that contain the subtables of every variant.
+#[proc_macro_derive(JoltInstructionSet)]
+pub fn derive_jolt_instruction_set(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match instruction_set(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derives `LassoSubtable<F>` and `JoltSubtableSet<F>` for an enum, generic over the field `F`,
+/// whose variants each wrap a single `LassoSubtable<F>` type, e.g.
+///
+/// ```ignore
+/// #[derive(EnumIter, EnumCount, JoltSubtableSet)]
+/// pub enum MySubtables<F: JoltField> {
+///     AND(AndSubtable<F>),
+///     IDENTITY(IdentitySubtable<F>),
+/// }
+/// ```
+///
+/// `LassoSubtable` methods are dispatched to the wrapped subtable, and each variant's type
+/// gets a `From` impl and a `ContainsSubtable` impl holding the variant's index, which
+/// instructions' `InstructionSubtables` impls require. Two variants wrapping the same type fail
+/// to compile.
+#[proc_macro_derive(JoltSubtableSet)]
+pub fn derive_jolt_subtable_set(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match subtable_set(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derives `InstructionSubtables` for an instruction from a `#[subtables(...)]` attribute listing
+/// every subtable type its `JoltInstruction::subtables` may return, for any `C` and `M`, with
+/// `F` standing for the field, e.g.
+///
+/// ```ignore
+/// #[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+/// #[subtables(TruncateOverflowSubtable<F, WORD_SIZE>, IdentitySubtable<F>)]
+/// pub struct ADDInstruction<const WORD_SIZE: usize>(pub u64, pub u64);
+/// ```
+///
+/// The instruction implements `InstructionSubtables` for the subtable sets that contain each of
+/// the listed subtables, and looks up a subtable's index in the set from its `ContainsSubtable`
+/// impl.
+#[proc_macro_derive(InstructionSubtables, attributes(subtables))]
+pub fn derive_instruction_subtables(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match instruction_subtables(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Returns the (name, wrapped type) of each variant of the enum.
+fn variants(input: &DeriveInput) -> syn::Result<(Vec<&Ident>, Vec<&Type>)> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "can only be derived for enums",
+        ));
+    };
+    if data.variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "enum must have at least one variant",
+        ));
+    }
+    data.variants
+        .iter()
+        .map(|variant| match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                Ok((&variant.ident, &fields.unnamed[0].ty))
+            }
+            _ => Err(syn::Error::new_spanned(
+                variant,
+                "each variant must wrap exactly one type, e.g. `ADD(ADDInstruction<32>)`",
+            )),
+        })
+        .collect::<syn::Result<Vec<_>>>()
+        .map(|variants| variants.into_iter().unzip())
+}
+
+fn from_impls(input: &DeriveInput, idents: &[&Ident], types: &[&Type]) -> TokenStream2 {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        #(
+            impl #impl_generics ::core::convert::From<#types> for #name #ty_generics #where_clause {
+                fn from(inner: #types) -> Self {
+                    Self::#idents(inner)
+                }
+            }
+        )*
+    }
+}
+
+fn instruction_set(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let (idents, types) = variants(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let indices: Vec<usize> = (0..idents.len()).collect();
+    let from_impls = from_impls(input, &idents, &types);
+
+    // The set uses a subtable set's subtables if each of its instructions does
+    let mut subtables_generics = input.generics.clone();
+    subtables_generics
+        .params
+        .push(parse_quote!(__F: ::jolt_core::poly::field::JoltField));
+    subtables_generics.params.push(parse_quote!(__Subtables));
+    let subtables_where_clause = subtables_generics.make_where_clause();
+    for ty in &types {
+        subtables_where_clause.predicates.push(parse_quote!(
+            #ty: ::jolt_core::jolt::instruction::InstructionSubtables<__F, __Subtables>
+        ));
+    }
+    let (subtables_impl_generics, _, subtables_where_clause) = subtables_generics.split_for_impl();
+
+    Ok(quote! {
+        #from_impls
+
+        impl #impl_generics ::jolt_core::jolt::instruction::JoltInstruction
+            for #name #ty_generics #where_clause
+        {
+            fn operands(&self) -> (u64, u64) {
+                match self {
+                    #(Self::#idents(instruction) => instruction.operands(),)*
+                }
+            }
+
+            fn combine_lookups<F: ::jolt_core::poly::field::JoltField>(
+                &self,
+                vals: &[F],
+                c: usize,
+                m: usize,
+            ) -> F {
+                match self {
+                    #(Self::#idents(instruction) => instruction.combine_lookups(vals, c, m),)*
+                }
+            }
+
+            fn g_poly_degree(&self, c: usize) -> usize {
+                match self {
+                    #(Self::#idents(instruction) => instruction.g_poly_degree(c),)*
+                }
+            }
+
+            fn subtables<F: ::jolt_core::poly::field::JoltField>(
+                &self,
+                c: usize,
+                m: usize,
+            ) -> Vec<(
+                Box<dyn ::jolt_core::jolt::subtable::LassoSubtable<F>>,
+                ::jolt_core::jolt::instruction::SubtableIndices,
+            )> {
+                match self {
+                    #(Self::#idents(instruction) => instruction.subtables::<F>(c, m),)*
+                }
+            }
+
+            fn to_indices(&self, c: usize, log_m: usize) -> Vec<usize> {
+                match self {
+                    #(Self::#idents(instruction) => instruction.to_indices(c, log_m),)*
+                }
+            }
+
+            fn lookup_entry(&self) -> u64 {
+                match self {
+                    #(Self::#idents(instruction) => instruction.lookup_entry(),)*
+                }
+            }
+
+            fn operand_chunks(&self, c: usize, log_m: usize) -> (Vec<u64>, Vec<u64>) {
+                match self {
+                    #(Self::#idents(instruction) => instruction.operand_chunks(c, log_m),)*
+                }
+            }
+
+            fn random(&self, rng: &mut ::jolt_core::jolt::instruction::StdRng) -> Self {
+                match self {
+                    #(Self::#idents(instruction) => Self::#idents(instruction.random(rng)),)*
+                }
+            }
+
+            fn slice_values<'a, F: ::jolt_core::poly::field::JoltField>(
+                &self,
+                vals: &'a [F],
+                c: usize,
+                m: usize,
+            ) -> Vec<&'a [F]> {
+                match self {
+                    #(Self::#idents(instruction) => instruction.slice_values(vals, c, m),)*
+                }
+            }
+        }
+
+        impl #impl_generics ::jolt_core::jolt::instruction::JoltInstructionSet
+            for #name #ty_generics #where_clause
+        {
+            fn enum_index(instruction: &Self) -> usize {
+                match instruction {
+                    #(Self::#idents(_) => #indices,)*
+                }
+            }
+
+            fn instruction_index(instruction_id: ::core::any::TypeId) -> Option<usize> {
+                #(
+                    if instruction_id == ::core::any::TypeId::of::<#types>() {
+                        return Some(#indices);
+                    }
+                )*
+                None
+            }
+        }
+
+        impl #subtables_impl_generics
+            ::jolt_core::jolt::instruction::InstructionSubtables<__F, __Subtables>
+            for #name #ty_generics
+        #subtables_where_clause
+        {
+            fn subtable_index(&self, subtable_id: ::jolt_core::jolt::subtable::SubtableId) -> usize {
+                match self {
+                    #(
+                        Self::#idents(instruction) =>
+                            ::jolt_core::jolt::instruction::InstructionSubtables::<
+                                __F,
+                                __Subtables,
+                            >::subtable_index(instruction, subtable_id),
+                    )*
+                }
+            }
+        }
+    })
+}
+
+fn instruction_subtables(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let mut attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("subtables"));
+    let types = match (attrs.next(), attrs.next()) {
+        (Some(attr), None) => {
+            attr.parse_args_with(Punctuated::<Type, Token![,]>::parse_terminated)?
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "expected a single `#[subtables(...)]` attribute",
+            ))
+        }
+    };
+    let types: Vec<&Type> = types.iter().collect();
+
+    let mut generics = input.generics.clone();
+    generics
+        .params
+        .push(parse_quote!(F: ::jolt_core::poly::field::JoltField));
+    generics.params.push(parse_quote!(__Subtables));
+    let where_clause = generics.make_where_clause();
+    for ty in &types {
+        where_clause.predicates.push(parse_quote!(
+            __Subtables: ::jolt_core::jolt::subtable::ContainsSubtable<#ty>
+        ));
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let missing = format!("`{name}::subtables` returned a subtable missing from `#[subtables]`");
+
+    Ok(quote! {
+        impl #impl_generics ::jolt_core::jolt::instruction::InstructionSubtables<F, __Subtables>
+            for #name #ty_generics
+        #where_clause
+        {
+            fn subtable_index(&self, subtable_id: ::jolt_core::jolt::subtable::SubtableId) -> usize {
+                #(
+                    if subtable_id == ::core::any::TypeId::of::<#types>() {
+                        return <__Subtables as ::jolt_core::jolt::subtable::ContainsSubtable<
+                            #types,
+                        >>::INDEX;
+                    }
+                )*
+                panic!(#missing)
+            }
+        }
+    })
+}
+
+fn subtable_set(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let (idents, types) = variants(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut type_params = input.generics.type_params();
+    let field = match (type_params.next(), type_params.next()) {
+        (Some(field), None) => &field.ident,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.generics,
+                "subtable set must have exactly one type parameter, the field",
+            ))
+        }
+    };
+    let indices: Vec<usize> = (0..idents.len()).collect();
+    let from_impls = from_impls(input, &idents, &types);
+
+    Ok(quote! {
+        #from_impls
+
+        impl #impl_generics ::jolt_core::jolt::subtable::LassoSubtable<#field>
+            for #name #ty_generics #where_clause
+        {
+            fn subtable_id(&self) -> ::jolt_core::jolt::subtable::SubtableId {
+                match self {
+                    #(Self::#idents(subtable) => subtable.subtable_id(),)*
+                }
+            }
+
+            fn materialize(&self, m: usize) -> Vec<#field> {
+                match self {
+                    #(Self::#idents(subtable) => subtable.materialize(m),)*
+                }
+            }
+
+            fn evaluate_mle(&self, point: &[#field]) -> #field {
+                match self {
+                    #(Self::#idents(subtable) => subtable.evaluate_mle(point),)*
+                }
+            }
+        }
+
+        impl #impl_generics ::jolt_core::jolt::subtable::JoltSubtableSet<#field>
+            for #name #ty_generics #where_clause
+        {
+        }
+
+        #(
+            impl #impl_generics ::jolt_core::jolt::subtable::ContainsSubtable<#types>
+                for #name #ty_generics #where_clause
+            {
+                const INDEX: usize = #indices;
+            }
+        )*
+    })
+}
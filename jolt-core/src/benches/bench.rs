@@ -67,7 +67,14 @@ fn prove_example<T: Serialize>(
         let preprocessing: crate::jolt::vm::JoltPreprocessing<
             ark_ff::Fp<ark_ff::MontBackend<ark_bn254::FrConfig, 4>, 4>,
             HyraxScheme<ark_ec::short_weierstrass::Projective<ark_bn254::g1::Config>>,
-        > = RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 22);
+        > = RV32IJoltVM::preprocess(
+            bytecode.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 22,
+            None,
+        );
 
         let (jolt_proof, jolt_commitments) =
             <RV32IJoltVM as Jolt<_, HyraxScheme<G1Projective>, C, M>>::prove(
@@ -114,7 +121,14 @@ fn sha2chain() -> Vec<(tracing::Span, Box<dyn FnOnce()>)> {
         let preprocessing: crate::jolt::vm::JoltPreprocessing<
             ark_ff::Fp<ark_ff::MontBackend<ark_bn254::FrConfig, 4>, 4>,
             HyraxScheme<ark_ec::short_weierstrass::Projective<ark_bn254::g1::Config>>,
-        > = RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 22);
+        > = RV32IJoltVM::preprocess(
+            bytecode.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 22,
+            None,
+        );
 
         let (jolt_proof, jolt_commitments) =
             <RV32IJoltVM as Jolt<_, HyraxScheme<G1Projective>, C, M>>::prove(
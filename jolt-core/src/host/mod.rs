@@ -2,10 +2,12 @@
 
 use core::{str::FromStr, u8};
 use std::{
+    collections::HashMap,
     fs::{self, File},
     io::{self, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Command,
+    sync::mpsc::{sync_channel, SyncSender},
 };
 
 use postcard;
@@ -14,20 +16,31 @@ use serde::Serialize;
 
 use common::{
     constants::{
-        DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
+        BYTES_PER_INSTRUCTION, DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE,
+        DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
     },
     rv_trace::{JoltDevice, NUM_CIRCUIT_FLAGS},
 };
 use strum::EnumCount;
-use tracer::ELFInstruction;
+use tracer::{ELFInstruction, RVTraceRow};
 
 use crate::{
     jolt::{
-        instruction::{mulh::MULHInstruction, VirtualInstructionSequence},
-        vm::{bytecode::BytecodeRow, rv32i_vm::RV32I, JoltTraceStep},
+        instruction::{
+            bigint::{BIGINTADDInstruction, BIGINTMULInstruction, BIGINTMULMODInstruction},
+            ecall::ECALLInstruction,
+            mulh::MULHInstruction,
+            PrecompileRoutine, VirtualInstructionSequence,
+        },
+        vm::{
+            bytecode::{opcode_bits, BytecodeRow},
+            rv32i_vm::{RV32IJoltProof, RV32IJoltVM, C, M, RV32I},
+            Jolt, JoltCommitments, JoltPolynomials, JoltPolynomialsBuilder, JoltPreprocessing,
+            JoltTraceStep,
+        },
     },
-    poly::field::JoltField,
-    utils::thread::unsafe_allocate_zero_vec,
+    poly::{commitment::commitment_scheme::CommitmentScheme, field::JoltField},
+    utils::{memory_budget::MemoryBudget, thread::unsafe_allocate_zero_vec},
 };
 
 use self::{analyze::ProgramSummary, toolchain::install_toolchain};
@@ -35,11 +48,48 @@ use self::{analyze::ProgramSummary, toolchain::install_toolchain};
 pub mod analyze;
 pub mod toolchain;
 
+/// Precompile routines are laid out contiguously after the last instruction of the
+/// program text, one per call site. Returns the routine address of each call site.
+fn precompile_routines(instructions: &[ELFInstruction]) -> HashMap<u64, u64> {
+    let text_end = instructions
+        .iter()
+        .map(|instruction| instruction.address)
+        .max();
+    let mut routine_address = text_end.unwrap_or_default() + BYTES_PER_INSTRUCTION as u64;
+    instructions
+        .iter()
+        .filter_map(|instruction| {
+            let routine_length = match instruction.opcode {
+                tracer::RV32IM::BIGINT_ADD => BIGINTADDInstruction::<32>::ROUTINE_LENGTH,
+                tracer::RV32IM::BIGINT_MUL => BIGINTMULInstruction::<32>::ROUTINE_LENGTH,
+                tracer::RV32IM::BIGINT_MULMOD => BIGINTMULMODInstruction::<32>::ROUTINE_LENGTH,
+                _ => return None,
+            };
+            let call_site = (instruction.address, routine_address);
+            routine_address += (routine_length * BYTES_PER_INSTRUCTION) as u64;
+            Some(call_site)
+        })
+        .collect()
+}
+
+/// Replaces a precompile call site with its `JAL`, and appends its routine to `routines`.
+fn expand_precompile<P: PrecompileRoutine>(
+    instruction: ELFInstruction,
+    routine_addresses: &HashMap<u64, u64>,
+    routines: &mut Vec<ELFInstruction>,
+) -> ELFInstruction {
+    let routine_address = routine_addresses[&instruction.address];
+    let mut sequence = P::virtual_instructions(&instruction, routine_address);
+    routines.extend(sequence.drain(1..));
+    sequence.pop().unwrap()
+}
+
 #[derive(Clone)]
 pub struct Program {
     guest: String,
     func: Option<String>,
     input: Vec<u8>,
+    hints: Vec<u8>,
     memory_size: u64,
     stack_size: u64,
     max_input_size: u64,
@@ -54,6 +104,7 @@ impl Program {
             guest: guest.to_string(),
             func: None,
             input: Vec::new(),
+            hints: Vec::new(),
             memory_size: DEFAULT_MEMORY_SIZE,
             stack_size: DEFAULT_STACK_SIZE,
             max_input_size: DEFAULT_MAX_INPUT_SIZE,
@@ -76,6 +127,18 @@ impl Program {
         self.input.append(&mut serialized);
     }
 
+    /// Appends `hint` to the stream returned by the `HintRead` syscall, for the guest to read
+    /// back with `jolt::syscall::hint`. Unlike inputs, hints are not part of the public
+    /// statement.
+    pub fn set_hint<T: Serialize>(&mut self, hint: &T) {
+        // The guest reads whole words: the length, then the serialized hint padded to a word
+        let mut serialized = postcard::to_stdvec(hint).unwrap();
+        self.hints
+            .extend_from_slice(&(serialized.len() as u32).to_le_bytes());
+        serialized.resize(serialized.len().next_multiple_of(4), 0);
+        self.hints.append(&mut serialized);
+    }
+
     pub fn set_memory_size(&mut self, len: u64) {
         self.memory_size = len;
     }
@@ -105,6 +168,8 @@ impl Program {
                 "passes=loweratomic",
                 "-C",
                 "panic=abort",
+                "-C",
+                "target-feature=+zba,+zbb",
             ];
 
             let toolchain = "riscv32i-jolt-zkvm-elf";
@@ -155,67 +220,183 @@ impl Program {
     pub fn decode(&mut self) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
         self.build();
         let elf = self.elf.as_ref().unwrap();
-        tracer::decode(elf)
+        let (instructions, memory_init) = tracer::decode(elf);
+        let routine_addresses = precompile_routines(&instructions);
+        let mut routines = vec![];
+        let mut instructions: Vec<_> = instructions
+            .into_iter()
+            .map(|instruction| match instruction.opcode {
+                tracer::RV32IM::ECALL => ECALLInstruction::<32>::virtual_instruction(&instruction),
+                tracer::RV32IM::BIGINT_ADD => expand_precompile::<BIGINTADDInstruction<32>>(
+                    instruction,
+                    &routine_addresses,
+                    &mut routines,
+                ),
+                tracer::RV32IM::BIGINT_MUL => expand_precompile::<BIGINTMULInstruction<32>>(
+                    instruction,
+                    &routine_addresses,
+                    &mut routines,
+                ),
+                tracer::RV32IM::BIGINT_MULMOD => expand_precompile::<BIGINTMULMODInstruction<32>>(
+                    instruction,
+                    &routine_addresses,
+                    &mut routines,
+                ),
+                _ => instruction,
+            })
+            .collect();
+        instructions.append(&mut routines);
+        (instructions, memory_init)
     }
 
     // TODO(moodlezoup): Make this generic over InstructionSet
+    /// Runs the program, returning its I/O, its trace and the trace's circuit flags. Virtual
+    /// sequences are expanded and circuit flags extracted while the guest is still running.
     #[tracing::instrument(skip_all, name = "Program::trace")]
     pub fn trace<F: JoltField>(mut self) -> (JoltDevice, Vec<JoltTraceStep<RV32I>>, Vec<F>) {
         self.build();
-        let elf = self.elf.unwrap();
-        let (raw_trace, io_device) =
-            tracer::trace(&elf, &self.input, self.max_input_size, self.max_output_size);
-
-        let trace: Vec<_> = raw_trace
-            .into_par_iter()
-            .flat_map(|row| match row.instruction.opcode {
-                tracer::RV32IM::MULH => MULHInstruction::<32>::virtual_sequence(row),
-                tracer::RV32IM::MULHSU => todo!(),
-                tracer::RV32IM::DIV => todo!(),
-                tracer::RV32IM::DIVU => todo!(),
-                tracer::RV32IM::REM => todo!(),
-                tracer::RV32IM::REMU => todo!(),
-                _ => vec![row],
-            })
-            .map(|row| {
-                let instruction_lookup = if let Ok(jolt_instruction) = RV32I::try_from(&row) {
-                    Some(jolt_instruction)
-                } else {
-                    // Instruction does not use lookups
-                    None
-                };
-
-                JoltTraceStep {
-                    instruction_lookup,
-                    bytecode_row: BytecodeRow::from_instruction::<RV32I>(&row.instruction),
-                    memory_ops: (&row).into(),
-                }
-            })
-            .collect();
-        let padded_trace_len = trace.len().next_power_of_two();
-
-        let mut circuit_flag_trace = unsafe_allocate_zero_vec(padded_trace_len * NUM_CIRCUIT_FLAGS);
-        circuit_flag_trace
-            .par_chunks_mut(padded_trace_len)
-            .enumerate()
-            .for_each(|(flag_index, chunk)| {
-                chunk.iter_mut().zip(trace.iter()).for_each(|(flag, row)| {
-                    let packed_circuit_flags = row.bytecode_row.bitflags >> RV32I::COUNT;
-                    // Check if the flag is set in the packed representation
-                    if (packed_circuit_flags >> (NUM_CIRCUIT_FLAGS - flag_index - 1)) & 1 != 0 {
-                        *flag = F::one();
-                    }
-                });
-            });
+        let elf = self.elf.as_ref().unwrap();
+        let result = process_trace(elf, |_| {}, |sender| self.emulate(sender));
+        result.unwrap()
+    }
+
+    /// Same as `trace`, but also builds the prover's polynomials from each chunk of the trace
+    /// while the guest is still running, instead of once the trace is complete. The returned
+    /// trace has its bytecode addresses compressed, ready for `Jolt::prove_polynomials`.
+    #[tracing::instrument(skip_all, name = "Program::trace_and_polynomialize")]
+    pub fn trace_and_polynomialize<F, PCS>(
+        mut self,
+        preprocessing: &JoltPreprocessing<F, PCS>,
+    ) -> (
+        JoltDevice,
+        Vec<JoltTraceStep<RV32I>>,
+        Vec<F>,
+        JoltPolynomials<F, PCS>,
+    )
+    where
+        F: JoltField,
+        PCS: CommitmentScheme<Field = F>,
+    {
+        self.build();
+        let elf = self.elf.as_ref().unwrap();
+        let mut program_io = JoltDevice::new(self.max_input_size, self.max_output_size);
+        program_io.inputs = self.input.clone();
+        let mut builder =
+            JoltPolynomialsBuilder::<F, PCS, C, M, RV32I>::new(&program_io, preprocessing);
+        let (io_device, trace, circuit_flags) = process_trace(
+            elf,
+            |steps| builder.push_steps(steps),
+            |sender| self.emulate(sender),
+        )
+        .unwrap();
+        let polynomials = builder.finish(&io_device);
+        (io_device, trace, circuit_flags, polynomials)
+    }
+
+    /// Runs the guest, pushing its trace to `sender` in chunks.
+    fn emulate(&self, sender: &mut SyncSender<Vec<RVTraceRow>>) -> io::Result<JoltDevice> {
+        tracer::trace_to_sink(
+            self.elf.as_ref().unwrap(),
+            &self.input,
+            &self.hints,
+            self.max_input_size,
+            self.max_output_size,
+            sender,
+        )
+    }
+
+    /// Runs the program like `trace`, but records the trace to a trace file at `path`
+    /// instead of processing it. `trace_from_file` or `prove_from_trace` can then reproduce
+    /// the execution without running the guest again.
+    #[tracing::instrument(skip_all, name = "Program::record_trace")]
+    pub fn record_trace(mut self, path: &Path, compress: bool) -> io::Result<JoltDevice> {
+        self.build();
+        tracer::trace_to_file(
+            self.elf.as_ref().unwrap(),
+            &self.input,
+            &self.hints,
+            self.max_input_size,
+            self.max_output_size,
+            path,
+            compress,
+        )
+    }
+
+    /// Same as `trace`, but replays the execution recorded by `record_trace` at `path`
+    /// instead of running the guest. The program must be the same one that was recorded:
+    /// traces recorded from any other ELF file are rejected with `InvalidData`.
+    #[tracing::instrument(skip_all, name = "Program::trace_from_file")]
+    pub fn trace_from_file<F: JoltField>(
+        mut self,
+        path: &Path,
+    ) -> io::Result<(JoltDevice, Vec<JoltTraceStep<RV32I>>, Vec<F>)> {
+        self.build();
+        let elf = self.elf.as_ref().unwrap();
+        let program_digest = tracer::program_digest(elf)?;
+        process_trace(
+            elf,
+            |_| {},
+            |sender| tracer::replay_trace_file(path, &program_digest, sender),
+        )
+    }
+
+    /// Proves the execution recorded by `record_trace` at `path`, without running the guest.
+    /// Unlike `prove_with_memory_budget`, the polynomials are only built once the whole trace
+    /// has been replayed, as the recorded inputs are stored at the end of the trace file.
+    pub fn prove_from_trace<F, PCS>(
+        self,
+        path: &Path,
+        preprocessing: JoltPreprocessing<F, PCS>,
+    ) -> io::Result<(RV32IJoltProof<F, PCS>, JoltCommitments<PCS>)>
+    where
+        F: JoltField,
+        PCS: CommitmentScheme<Field = F>,
+    {
+        let (io_device, trace, circuit_flags) = self.trace_from_file(path)?;
+        Ok(<RV32IJoltVM as Jolt<F, PCS, C, M>>::prove(
+            io_device,
+            trace,
+            circuit_flags,
+            preprocessing,
+        ))
+    }
 
-        (io_device, trace, circuit_flag_trace)
+    /// Runs and proves the program, building the prover's polynomials while the guest runs
+    /// (see `trace_and_polynomialize`). If a `memory_budget` is given, the prover's
+    /// intermediate values that aren't needed by the subprotocol currently being proven are
+    /// spilled whenever keeping them resident would exceed it. Fails if a scratch file can't be
+    /// written or read.
+    pub fn prove_with_memory_budget<F, PCS>(
+        self,
+        preprocessing: JoltPreprocessing<F, PCS>,
+        memory_budget: Option<MemoryBudget>,
+    ) -> io::Result<(RV32IJoltProof<F, PCS>, JoltCommitments<PCS>)>
+    where
+        F: JoltField,
+        PCS: CommitmentScheme<Field = F>,
+    {
+        let (io_device, trace, circuit_flags, polynomials) =
+            self.trace_and_polynomialize(&preprocessing);
+        <RV32IJoltVM as Jolt<F, PCS, C, M>>::prove_polynomials(
+            io_device,
+            trace,
+            polynomials,
+            circuit_flags,
+            preprocessing,
+            memory_budget,
+        )
     }
 
     pub fn trace_analyze<F: JoltField>(mut self) -> ProgramSummary {
         self.build();
         let elf = self.elf.as_ref().unwrap();
-        let (raw_trace, _) =
-            tracer::trace(elf, &self.input, self.max_input_size, self.max_output_size);
+        let (raw_trace, _) = tracer::trace(
+            elf,
+            &self.input,
+            &self.hints,
+            self.max_input_size,
+            self.max_output_size,
+        );
 
         let (bytecode, memory_init) = self.decode();
         let (io_device, processed_trace, circuit_flags) = self.trace();
@@ -254,6 +435,150 @@ impl Program {
     }
 }
 
+/// Expands virtual sequences and extracts circuit flags from the trace produced by `emulate`,
+/// which pushes chunks of it to the given sender, and passes each processed chunk to
+/// `on_steps`. `emulate` runs on its own thread, so that processing one chunk overlaps with
+/// producing the next.
+fn process_trace<F: JoltField>(
+    elf: &PathBuf,
+    mut on_steps: impl FnMut(&mut [JoltTraceStep<RV32I>]),
+    emulate: impl FnOnce(&mut SyncSender<Vec<RVTraceRow>>) -> io::Result<JoltDevice> + Send,
+) -> io::Result<(JoltDevice, Vec<JoltTraceStep<RV32I>>, Vec<F>)> {
+    let (instructions, _) = tracer::decode(elf);
+    let routine_addresses = precompile_routines(&instructions);
+    let (sender, receiver) = sync_channel(TRACE_CHANNEL_CAPACITY);
+    let (io_device, trace, packed_circuit_flags) = std::thread::scope(|s| {
+        let emulator = s.spawn(|| {
+            let mut sender = sender;
+            emulate(&mut sender)
+        });
+
+        let mut trace: Vec<JoltTraceStep<RV32I>> = Vec::new();
+        let mut packed_circuit_flags: Vec<u64> = Vec::new();
+        let mut unknown_address = None;
+        for raw_chunk in receiver {
+            // Keep draining the channel after an error, so that the emulator can finish.
+            if unknown_address.is_some() {
+                continue;
+            }
+            // A replayed trace may come from a different program than `elf`, in which case
+            // some of its precompile calls have no routine.
+            unknown_address = raw_chunk
+                .iter()
+                .find(|row| {
+                    PRECOMPILE_OPCODES.contains(&row.instruction.opcode)
+                        && !routine_addresses.contains_key(&row.instruction.address)
+                })
+                .map(|row| row.instruction.address);
+            if unknown_address.is_some() {
+                continue;
+            }
+
+            let mut chunk: Vec<_> = raw_chunk
+                .into_par_iter()
+                .flat_map(|row| match row.instruction.opcode {
+                    tracer::RV32IM::MULH => MULHInstruction::<32>::virtual_sequence(row),
+                    tracer::RV32IM::MULHSU => todo!(),
+                    tracer::RV32IM::DIV => todo!(),
+                    tracer::RV32IM::DIVU => todo!(),
+                    tracer::RV32IM::REM => todo!(),
+                    tracer::RV32IM::REMU => todo!(),
+                    tracer::RV32IM::ECALL => ECALLInstruction::<32>::virtual_sequence(row),
+                    tracer::RV32IM::BIGINT_ADD => {
+                        let routine_address = routine_addresses[&row.instruction.address];
+                        BIGINTADDInstruction::<32>::virtual_sequence(row, routine_address)
+                    }
+                    tracer::RV32IM::BIGINT_MUL => {
+                        let routine_address = routine_addresses[&row.instruction.address];
+                        BIGINTMULInstruction::<32>::virtual_sequence(row, routine_address)
+                    }
+                    tracer::RV32IM::BIGINT_MULMOD => {
+                        let routine_address = routine_addresses[&row.instruction.address];
+                        BIGINTMULMODInstruction::<32>::virtual_sequence(row, routine_address)
+                    }
+                    _ => vec![row],
+                })
+                .map(trace_step)
+                .collect();
+            packed_circuit_flags.par_extend(
+                chunk
+                    .par_iter()
+                    .map(|step| step.bytecode_row.bitflags >> opcode_bits(RV32I::COUNT)),
+            );
+            on_steps(&mut chunk);
+            trace.append(&mut chunk);
+        }
+
+        let io_device = emulator.join().unwrap();
+        (
+            io_device.and_then(|io_device| match unknown_address {
+                Some(address) => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("trace calls a precompile at {address:#x}, which is not in the ELF"),
+                )),
+                None => Ok(io_device),
+            }),
+            trace,
+            packed_circuit_flags,
+        )
+    });
+    let io_device = io_device?;
+    Ok((io_device, trace, circuit_flag_trace(&packed_circuit_flags)))
+}
+
+/// Converts a row of the trace, after virtual sequences are expanded, into a step of the
+/// Jolt trace.
+pub(crate) fn trace_step(row: RVTraceRow) -> JoltTraceStep<RV32I> {
+    let instruction_lookup = if let Ok(jolt_instruction) = RV32I::try_from(&row) {
+        Some(jolt_instruction)
+    } else {
+        // Instruction does not use lookups
+        None
+    };
+
+    JoltTraceStep {
+        instruction_lookup,
+        bytecode_row: BytecodeRow::from_instruction::<RV32I>(&row.instruction),
+        memory_ops: (&row).into(),
+    }
+}
+
+/// Unpacks the circuit flags of each step (as packed into `BytecodeRow::bitflags`, without
+/// the opcode) into one column per flag, each as long as the trace.
+pub(crate) fn circuit_flag_trace<F: JoltField>(packed_circuit_flags: &[u64]) -> Vec<F> {
+    let trace_len = packed_circuit_flags.len();
+
+    let mut circuit_flag_trace = unsafe_allocate_zero_vec(trace_len * NUM_CIRCUIT_FLAGS);
+    if trace_len == 0 {
+        return circuit_flag_trace;
+    }
+    circuit_flag_trace
+        .par_chunks_mut(trace_len)
+        .enumerate()
+        .for_each(|(flag_index, chunk)| {
+            chunk
+                .iter_mut()
+                .zip(packed_circuit_flags.iter())
+                .for_each(|(flag, packed_flags)| {
+                    // Check if the flag is set in the packed representation
+                    if (packed_flags >> (NUM_CIRCUIT_FLAGS - flag_index - 1)) & 1 != 0 {
+                        *flag = F::one();
+                    }
+                });
+        });
+    circuit_flag_trace
+}
+
+/// The opcodes that `decode` backs with a precompile routine.
+const PRECOMPILE_OPCODES: [tracer::RV32IM; 3] = [
+    tracer::RV32IM::BIGINT_ADD,
+    tracer::RV32IM::BIGINT_MUL,
+    tracer::RV32IM::BIGINT_MULMOD,
+];
+
+/// The number of trace chunks the emulator may run ahead of witness generation by.
+const TRACE_CHANNEL_CAPACITY: usize = 4;
+
 const LINKER_SCRIPT_TEMPLATE: &str = r#"
 MEMORY {
   program (rwx) : ORIGIN = 0x80000000, LENGTH = {MEMORY_SIZE}
@@ -0,0 +1,109 @@
+use crate::poly::field::JoltField;
+use ark_std::log2;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
+use crate::jolt::subtable::{identity::IdentitySubtable, LassoSubtable};
+use crate::utils::instruction_utils::{chunk_operand_usize, concatenate_lookups};
+
+/// Writes an untrusted, prover-supplied word to rd. The lookup only range-checks
+/// the value to be `WORD_SIZE` bits; any further constraints on it must be enforced
+/// by the surrounding virtual sequence.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(IdentitySubtable<F>)]
+pub struct ADVICEInstruction<const WORD_SIZE: usize>(pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for ADVICEInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (0, self.0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], _: usize, M: usize) -> F {
+        let log_M = log2(M) as usize;
+        assert!(vals.len() == WORD_SIZE / log_M);
+        concatenate_lookups(vals, WORD_SIZE / log_M, log_M)
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        M: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        // Only the chunks spanned by a `WORD_SIZE`-bit value are looked up
+        let num_chunks = WORD_SIZE / log2(M) as usize;
+        vec![(
+            Box::new(IdentitySubtable::<F>::new()),
+            SubtableIndices::from(C - num_chunks..C),
+        )]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_operand_usize(self.0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        if WORD_SIZE == 32 {
+            self.0 as u32 as u64
+        } else if WORD_SIZE == 64 {
+            self.0
+        } else {
+            panic!("only implemented for u32 / u64")
+        }
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        Self(rng.next_u32() as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use super::ADVICEInstruction;
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    #[test]
+    fn advice_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+
+        for _ in 0..256 {
+            let x = rng.next_u32() as u64;
+            let instruction = ADVICEInstruction::<32>(x);
+            jolt_instruction_test!(instruction);
+        }
+
+        let u32_max: u64 = u32::MAX as u64;
+        let instructions = vec![
+            ADVICEInstruction::<32>(0),
+            ADVICEInstruction::<32>(1),
+            ADVICEInstruction::<32>(u32_max),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+
+    #[test]
+    fn advice_instruction_64_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+
+        for _ in 0..256 {
+            let x = rng.next_u64();
+            let instruction = ADVICEInstruction::<64>(x);
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
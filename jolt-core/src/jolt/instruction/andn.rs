@@ -0,0 +1,81 @@
+use ark_std::log2;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
+use crate::jolt::subtable::{andn::AndnSubtable, LassoSubtable};
+use crate::poly::field::JoltField;
+use crate::utils::instruction_utils::{chunk_and_concatenate_operands, concatenate_lookups};
+
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(AndnSubtable<F>)]
+pub struct ANDNInstruction(pub u64, pub u64);
+
+impl JoltInstruction for ANDNInstruction {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, self.1)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, M: usize) -> F {
+        concatenate_lookups(vals, C, log2(M) as usize / 2)
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        _: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        vec![(Box::new(AndnSubtable::new()), SubtableIndices::from(0..C))]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_and_concatenate_operands(self.0, self.1, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        self.0 & !self.1
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        Self(rng.next_u32() as u64, rng.next_u32() as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::ANDNInstruction;
+
+    #[test]
+    fn andn_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+
+        for _ in 0..256 {
+            let (x, y) = (rng.next_u32() as u64, rng.next_u32() as u64);
+            let instruction = ANDNInstruction(x, y);
+            jolt_instruction_test!(instruction);
+        }
+        let u32_max: u64 = u32::MAX as u64;
+        let instructions = vec![
+            ANDNInstruction(0, 0),
+            ANDNInstruction(u32_max, 0),
+            ANDNInstruction(0, u32_max),
+            ANDNInstruction(u32_max, u32_max),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
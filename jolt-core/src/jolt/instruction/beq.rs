@@ -2,7 +2,7 @@ use rand::prelude::StdRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use super::JoltInstruction;
+use super::{InstructionSubtables, JoltInstruction};
 use crate::{
     jolt::{
         instruction::SubtableIndices,
@@ -12,7 +12,8 @@ use crate::{
     utils::instruction_utils::chunk_and_concatenate_operands,
 };
 
-#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(EqSubtable<F>)]
 pub struct BEQInstruction(pub u64, pub u64);
 
 impl JoltInstruction for BEQInstruction {
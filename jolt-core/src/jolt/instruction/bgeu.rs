@@ -2,14 +2,15 @@ use rand::prelude::StdRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use super::{sltu::SLTUInstruction, JoltInstruction, SubtableIndices};
+use super::{sltu::SLTUInstruction, InstructionSubtables, JoltInstruction, SubtableIndices};
 use crate::{
     jolt::subtable::{eq::EqSubtable, ltu::LtuSubtable, LassoSubtable},
     poly::field::JoltField,
     utils::instruction_utils::chunk_and_concatenate_operands,
 };
 
-#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(LtuSubtable<F>, EqSubtable<F>)]
 pub struct BGEUInstruction(pub u64, pub u64);
 
 impl JoltInstruction for BGEUInstruction {
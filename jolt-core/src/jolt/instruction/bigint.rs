@@ -0,0 +1,510 @@
+use std::collections::HashMap;
+
+use common::constants::virtual_register_index;
+use tracer::{bigint_mulmod, ELFInstruction, RVTraceRow, RV32IM};
+
+use super::precompile::{execute_routine, Routine, RoutineBuilder, RoutineOp};
+use super::PrecompileRoutine;
+
+/// The 256-bit addition precompile, `bigint_add rd, rs1, rs2` (see
+/// `tracer::BIGINT_ADD_FUNCT3`).
+pub struct BIGINTADDInstruction<const WORD_SIZE: usize>;
+
+/// The 256-bit multiplication precompile, `bigint_mul rs1, rs2` (see
+/// `tracer::BIGINT_MUL_FUNCT3`).
+pub struct BIGINTMULInstruction<const WORD_SIZE: usize>;
+
+/// The 256-bit modular multiplication precompile, `bigint_mulmod rs1, rs2` (see
+/// `tracer::BIGINT_MULMOD_FUNCT3`).
+///
+/// The quotient `q` and remainder `r` of `a * b` by the modulus `p` are supplied as
+/// advice, and the routine checks that `a * b == q * p + r` and `r < p`. Both sides
+/// of the equation are computed column by column, and each column's low limb is
+/// asserted to match.
+pub struct BIGINTMULMODInstruction<const WORD_SIZE: usize>;
+
+const LIMBS: usize = 8;
+
+/// Holds limb `limb` of the first operand.
+const fn a_register(limb: usize) -> u64 {
+    virtual_register_index(limb as u64)
+}
+
+/// Holds limb `limb` of the second operand of `bigint_mul`, or of the quotient in
+/// `bigint_mulmod`.
+const fn b_register(limb: usize) -> u64 {
+    virtual_register_index((LIMBS + limb) as u64)
+}
+
+const X: [u64; 3] = [
+    virtual_register_index(16),
+    virtual_register_index(17),
+    virtual_register_index(18),
+];
+const Y: [u64; 3] = [
+    virtual_register_index(19),
+    virtual_register_index(20),
+    virtual_register_index(21),
+];
+const OPERAND: u64 = virtual_register_index(22);
+const LO: u64 = virtual_register_index(23);
+const HI: u64 = virtual_register_index(24);
+const CARRY: u64 = virtual_register_index(25);
+
+/// A 96-bit column accumulator, least significant limb first.
+struct Accumulator([u64; 3]);
+
+impl Accumulator {
+    /// Zeroes the accumulator.
+    fn new(routine: &mut RoutineBuilder, registers: [u64; 3]) -> Self {
+        for register in registers {
+            routine.r(RoutineOp::Add, register, 0, 0);
+        }
+        Self(registers)
+    }
+
+    /// The accumulator's low limb.
+    fn low(&self) -> u64 {
+        self.0[0]
+    }
+
+    /// acc += x * y
+    fn multiply_add(&self, routine: &mut RoutineBuilder, x: u64, y: u64) {
+        let [c0, c1, c2] = self.0;
+        routine.r(RoutineOp::Mulu, LO, x, y);
+        routine.r(RoutineOp::Mulhu, HI, x, y);
+        routine.r(RoutineOp::Add, c0, c0, LO);
+        routine.r(RoutineOp::Sltu, CARRY, c0, LO);
+        // HI is at most 2^32 - 2, so this cannot overflow
+        routine.r(RoutineOp::Add, HI, HI, CARRY);
+        routine.r(RoutineOp::Add, c1, c1, HI);
+        routine.r(RoutineOp::Sltu, CARRY, c1, HI);
+        routine.r(RoutineOp::Add, c2, c2, CARRY);
+    }
+
+    /// acc += x
+    fn add(&self, routine: &mut RoutineBuilder, x: u64) {
+        let [c0, c1, c2] = self.0;
+        routine.r(RoutineOp::Add, c0, c0, x);
+        routine.r(RoutineOp::Sltu, CARRY, c0, x);
+        routine.r(RoutineOp::Add, c1, c1, CARRY);
+        routine.r(RoutineOp::Sltu, CARRY, c1, CARRY);
+        routine.r(RoutineOp::Add, c2, c2, CARRY);
+    }
+
+    /// Shifts the accumulator right by one limb. Rather than moving values between
+    /// registers, the low limb's register is zeroed and becomes the high limb.
+    fn shift(&mut self, routine: &mut RoutineBuilder) {
+        let [c0, c1, c2] = self.0;
+        routine.r(RoutineOp::Add, c0, 0, 0);
+        self.0 = [c1, c2, c0];
+    }
+}
+
+/// The pairs of limb indices `(i, j)` with `i + j == column`.
+fn column_terms(column: usize) -> impl Iterator<Item = (usize, usize)> {
+    (column.saturating_sub(LIMBS - 1)..=column.min(LIMBS - 1)).map(move |i| (i, column - i))
+}
+
+const fn limb_offset(limb: usize) -> u32 {
+    4 * limb as u32
+}
+
+fn load_input(input: &[u32], addresses: &[u64]) -> HashMap<u64, u64> {
+    let mut memory = HashMap::new();
+    for (operand, address) in input.chunks_exact(LIMBS).zip(addresses) {
+        for (limb, value) in operand.iter().enumerate() {
+            memory.insert(address + limb_offset(limb) as u64, *value as u64);
+        }
+    }
+    memory
+}
+
+impl<const WORD_SIZE: usize> BIGINTADDInstruction<WORD_SIZE> {
+    /// The routine for the call site `instruction`, laid out at `routine_address`.
+    pub(crate) fn routine(instruction: &ELFInstruction, routine_address: u64) -> Routine {
+        assert_eq!(instruction.opcode, RV32IM::BIGINT_ADD);
+        assert_eq!(WORD_SIZE, 32);
+        let a = instruction.rs1.unwrap();
+        let b = instruction.rs2.unwrap();
+        let mut routine = RoutineBuilder::new(routine_address, Self::ROUTINE_LENGTH);
+
+        for limb in 0..LIMBS {
+            let offset = limb_offset(limb);
+            routine.load(LO, a, offset);
+            routine.load(HI, b, offset);
+            routine.r(RoutineOp::Add, LO, LO, HI);
+            if limb == 0 {
+                routine.r(RoutineOp::Sltu, CARRY, LO, HI);
+            } else {
+                // The two carries cannot both be set
+                routine.r(RoutineOp::Sltu, OPERAND, LO, HI);
+                routine.r(RoutineOp::Add, LO, LO, CARRY);
+                routine.r(RoutineOp::Sltu, HI, LO, CARRY);
+                routine.r(RoutineOp::Or, CARRY, OPERAND, HI);
+            }
+            routine.store(a, LO, offset);
+        }
+        routine.r(RoutineOp::Add, instruction.rd.unwrap_or(0), CARRY, 0);
+        routine.finish(instruction)
+    }
+}
+
+impl<const WORD_SIZE: usize> PrecompileRoutine for BIGINTADDInstruction<WORD_SIZE> {
+    // 5 for the first limb, 8 for each of the others, the carry out and the return
+    const ROUTINE_LENGTH: usize = 5 + 8 * (LIMBS - 1) + 1 + 1;
+
+    fn virtual_instructions(
+        instruction: &ELFInstruction,
+        routine_address: u64,
+    ) -> Vec<ELFInstruction> {
+        Self::routine(instruction, routine_address).into_instructions()
+    }
+
+    fn virtual_sequence(trace_row: RVTraceRow, routine_address: u64) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::BIGINT_ADD);
+        let input = trace_row.precompile_input.as_ref().unwrap();
+        assert_eq!(input.len(), 2 * LIMBS);
+        let a_address = trace_row.register_state.rs1_val.unwrap();
+        let b_address = trace_row.register_state.rs2_val.unwrap();
+
+        let mut registers = HashMap::new();
+        registers.insert(trace_row.instruction.rs1.unwrap(), a_address);
+        registers.insert(trace_row.instruction.rs2.unwrap(), b_address);
+        let memory = load_input(input, &[a_address, b_address]);
+
+        let routine = Self::routine(&trace_row.instruction, routine_address);
+        execute_routine(routine, registers, memory, &[])
+    }
+}
+
+impl<const WORD_SIZE: usize> BIGINTMULInstruction<WORD_SIZE> {
+    /// The routine for the call site `instruction`, laid out at `routine_address`.
+    pub(crate) fn routine(instruction: &ELFInstruction, routine_address: u64) -> Routine {
+        assert_eq!(instruction.opcode, RV32IM::BIGINT_MUL);
+        assert_eq!(WORD_SIZE, 32);
+        let a = instruction.rs1.unwrap();
+        let b = instruction.rs2.unwrap();
+        let mut routine = RoutineBuilder::new(routine_address, Self::ROUTINE_LENGTH);
+
+        for limb in 0..LIMBS {
+            routine.load(a_register(limb), a, limb_offset(limb));
+            routine.load(b_register(limb), b, limb_offset(limb));
+        }
+        let mut accumulator = Accumulator::new(&mut routine, X);
+        for column in 0..2 * LIMBS {
+            for (i, j) in column_terms(column) {
+                accumulator.multiply_add(&mut routine, a_register(i), b_register(j));
+            }
+            routine.store(a, accumulator.low(), limb_offset(column));
+            if column < 2 * LIMBS - 1 {
+                accumulator.shift(&mut routine);
+            }
+        }
+        routine.finish(instruction)
+    }
+}
+
+impl<const WORD_SIZE: usize> PrecompileRoutine for BIGINTMULInstruction<WORD_SIZE> {
+    // Loads, accumulator, 64 products, stores, shifts and the return
+    const ROUTINE_LENGTH: usize =
+        2 * LIMBS + 3 + 8 * LIMBS * LIMBS + 2 * LIMBS + (2 * LIMBS - 1) + 1;
+
+    fn virtual_instructions(
+        instruction: &ELFInstruction,
+        routine_address: u64,
+    ) -> Vec<ELFInstruction> {
+        Self::routine(instruction, routine_address).into_instructions()
+    }
+
+    fn virtual_sequence(trace_row: RVTraceRow, routine_address: u64) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::BIGINT_MUL);
+        let input = trace_row.precompile_input.as_ref().unwrap();
+        assert_eq!(input.len(), 2 * LIMBS);
+        let a_address = trace_row.register_state.rs1_val.unwrap();
+        let b_address = trace_row.register_state.rs2_val.unwrap();
+
+        let mut registers = HashMap::new();
+        registers.insert(trace_row.instruction.rs1.unwrap(), a_address);
+        registers.insert(trace_row.instruction.rs2.unwrap(), b_address);
+        let memory = load_input(input, &[a_address, b_address]);
+
+        let routine = Self::routine(&trace_row.instruction, routine_address);
+        execute_routine(routine, registers, memory, &[])
+    }
+}
+
+impl<const WORD_SIZE: usize> BIGINTMULMODInstruction<WORD_SIZE> {
+    /// The routine for the call site `instruction`, laid out at `routine_address`.
+    pub(crate) fn routine(instruction: &ELFInstruction, routine_address: u64) -> Routine {
+        assert_eq!(instruction.opcode, RV32IM::BIGINT_MULMOD);
+        assert_eq!(WORD_SIZE, 32);
+        let a = instruction.rs1.unwrap();
+        let b = instruction.rs2.unwrap();
+        let modulus_offset = |limb| limb_offset(LIMBS + limb);
+        let mut routine = RoutineBuilder::new(routine_address, Self::ROUTINE_LENGTH);
+
+        for limb in 0..LIMBS {
+            routine.load(a_register(limb), a, limb_offset(limb));
+        }
+        // The quotient
+        for limb in 0..LIMBS {
+            routine.advice(b_register(limb));
+        }
+
+        // x := a * b and y := q * p + r
+        let mut x = Accumulator::new(&mut routine, X);
+        let mut y = Accumulator::new(&mut routine, Y);
+        for column in 0..2 * LIMBS {
+            for (i, j) in column_terms(column) {
+                routine.load(OPERAND, b, limb_offset(j));
+                x.multiply_add(&mut routine, a_register(i), OPERAND);
+            }
+            for (i, j) in column_terms(column) {
+                routine.load(OPERAND, a, modulus_offset(j));
+                y.multiply_add(&mut routine, b_register(i), OPERAND);
+            }
+            if column < LIMBS {
+                // The remainder overwrites `a`, which has already been loaded
+                routine.advice(OPERAND);
+                y.add(&mut routine, OPERAND);
+                routine.store(a, OPERAND, limb_offset(column));
+            }
+            routine.assert_eq(x.low(), y.low());
+            if column < 2 * LIMBS - 1 {
+                x.shift(&mut routine);
+                y.shift(&mut routine);
+            }
+        }
+        // Both products are less than 2^512, so nothing is left in the accumulators
+
+        // r < p iff computing r - p borrows
+        let borrow = X[0];
+        for limb in 0..LIMBS {
+            routine.load(LO, a, limb_offset(limb));
+            routine.load(HI, a, modulus_offset(limb));
+            if limb == 0 {
+                routine.r(RoutineOp::Sltu, borrow, LO, HI);
+            } else {
+                routine.r(RoutineOp::Sltu, CARRY, LO, HI);
+                routine.r(RoutineOp::Sub, OPERAND, LO, HI);
+                routine.r(RoutineOp::Sltu, OPERAND, OPERAND, borrow);
+                routine.r(RoutineOp::Or, borrow, CARRY, OPERAND);
+            }
+        }
+        routine.assert_ltu(0, borrow);
+        routine.finish(instruction)
+    }
+}
+
+impl<const WORD_SIZE: usize> PrecompileRoutine for BIGINTMULMODInstruction<WORD_SIZE> {
+    // Loads and advice, accumulators, 2 * 64 loaded products, remainder, column
+    // assertions, shifts, the comparison of the remainder to the modulus, and the return
+    const ROUTINE_LENGTH: usize = 2 * LIMBS
+        + 6
+        + 2 * 9 * LIMBS * LIMBS
+        + 7 * LIMBS
+        + 2 * LIMBS
+        + 2 * (2 * LIMBS - 1)
+        + (3 + 6 * (LIMBS - 1) + 1)
+        + 1;
+
+    fn virtual_instructions(
+        instruction: &ELFInstruction,
+        routine_address: u64,
+    ) -> Vec<ELFInstruction> {
+        Self::routine(instruction, routine_address).into_instructions()
+    }
+
+    fn virtual_sequence(trace_row: RVTraceRow, routine_address: u64) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::BIGINT_MULMOD);
+        let input = trace_row.precompile_input.as_ref().unwrap();
+        assert_eq!(input.len(), 3 * LIMBS);
+        let a_address = trace_row.register_state.rs1_val.unwrap();
+        let b_address = trace_row.register_state.rs2_val.unwrap();
+
+        let mut registers = HashMap::new();
+        registers.insert(trace_row.instruction.rs1.unwrap(), a_address);
+        registers.insert(trace_row.instruction.rs2.unwrap(), b_address);
+        let modulus_address = a_address + limb_offset(LIMBS) as u64;
+        let memory = load_input(input, &[a_address, modulus_address, b_address]);
+
+        let operand = |index: usize| -> [u32; LIMBS] {
+            input[index * LIMBS..(index + 1) * LIMBS]
+                .try_into()
+                .unwrap()
+        };
+        let (quotient, remainder) = bigint_mulmod(&operand(0), &operand(2), &operand(1));
+        let advice: Vec<u32> = quotient.iter().chain(remainder.iter()).copied().collect();
+
+        let routine = Self::routine(&trace_row.instruction, routine_address);
+        execute_routine(routine, registers, memory, &advice)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+    use tracer::{bigint_add, bigint_mul, MemoryState, RegisterState};
+
+    use crate::jolt::{instruction::JoltInstruction, vm::rv32i_vm::RV32I};
+
+    use super::*;
+
+    const CALL_SITE: u64 = 0x8000_1000;
+    const ROUTINE_ADDRESS: u64 = 0x8000_4000;
+    const A_ADDRESS: u64 = 0x8010_0000;
+    const B_ADDRESS: u64 = 0x8010_0100;
+
+    fn trace_row(opcode: RV32IM, input: Vec<u32>) -> RVTraceRow {
+        RVTraceRow {
+            instruction: ELFInstruction {
+                address: CALL_SITE,
+                opcode,
+                rs1: Some(10),
+                rs2: Some(11),
+                rd: Some(12),
+                imm: None,
+                virtual_sequence_index: None,
+            },
+            register_state: RegisterState {
+                rs1_val: Some(A_ADDRESS),
+                rs2_val: Some(B_ADDRESS),
+                rd_post_val: None,
+            },
+            memory_state: None,
+            precompile_input: Some(input),
+        }
+    }
+
+    /// Checks each row's lookup, and returns the words the routine stored.
+    fn check_routine<P: PrecompileRoutine>(trace_row: RVTraceRow) -> HashMap<u64, u32> {
+        let bytecode = P::virtual_instructions(&trace_row.instruction, ROUTINE_ADDRESS);
+        let virtual_sequence = P::virtual_sequence(trace_row, ROUTINE_ADDRESS);
+        assert_eq!(virtual_sequence.len(), P::ROUTINE_LENGTH + 1);
+
+        let mut stored = HashMap::new();
+        for (row, instruction) in virtual_sequence.iter().zip(bytecode.iter()) {
+            assert_eq!(&row.instruction, instruction);
+            let lookup = RV32I::try_from(row).unwrap();
+            match row.instruction.opcode {
+                RV32IM::SW => {
+                    let Some(MemoryState::Write {
+                        address,
+                        post_value,
+                    }) = row.memory_state
+                    else {
+                        panic!("expected a write")
+                    };
+                    assert_eq!(lookup.lookup_entry(), post_value);
+                    stored.insert(address, post_value as u32);
+                }
+                // The R1CS constraints require assertions to output 1
+                RV32IM::VIRTUAL_ASSERT_EQ | RV32IM::VIRTUAL_ASSERT_LTU => {
+                    assert_eq!(lookup.lookup_entry(), 1)
+                }
+                RV32IM::JAL => assert_eq!(lookup.lookup_entry(), ROUTINE_ADDRESS),
+                RV32IM::JALR => assert_eq!(lookup.lookup_entry(), CALL_SITE + 4),
+                _ => assert_eq!(Some(lookup.lookup_entry()), row.register_state.rd_post_val),
+            }
+        }
+        stored
+    }
+
+    fn limbs(stored: &HashMap<u64, u32>, address: u64, count: usize) -> Vec<u32> {
+        (0..count)
+            .map(|limb| stored[&(address + 4 * limb as u64)])
+            .collect()
+    }
+
+    fn random_bigint(rng: &mut impl RngCore) -> [u32; LIMBS] {
+        std::array::from_fn(|_| rng.next_u32())
+    }
+
+    #[test]
+    fn bigint_add_virtual_sequence_32() {
+        let mut rng = test_rng();
+        for _ in 0..10 {
+            let a = random_bigint(&mut rng);
+            let b = random_bigint(&mut rng);
+            let row = trace_row(RV32IM::BIGINT_ADD, [a, b].concat());
+
+            let virtual_sequence =
+                BIGINTADDInstruction::<32>::virtual_sequence(row.clone(), ROUTINE_ADDRESS);
+            let stored = check_routine::<BIGINTADDInstruction<32>>(row);
+
+            let mut expected = a;
+            let carry = bigint_add(&mut expected, &b);
+            assert_eq!(limbs(&stored, A_ADDRESS, LIMBS), expected);
+            let carry_row = &virtual_sequence[virtual_sequence.len() - 2];
+            assert_eq!(carry_row.instruction.rd, Some(12));
+            assert_eq!(carry_row.register_state.rd_post_val, Some(carry as u64));
+        }
+    }
+
+    #[test]
+    fn bigint_mul_virtual_sequence_32() {
+        let mut rng = test_rng();
+        for _ in 0..10 {
+            let a = random_bigint(&mut rng);
+            let b = random_bigint(&mut rng);
+            let row = trace_row(RV32IM::BIGINT_MUL, [a, b].concat());
+
+            let stored = check_routine::<BIGINTMULInstruction<32>>(row);
+            assert_eq!(limbs(&stored, A_ADDRESS, 2 * LIMBS), bigint_mul(&a, &b));
+        }
+    }
+
+    #[test]
+    fn bigint_mulmod_virtual_sequence_32() {
+        let mut rng = test_rng();
+        // The BN254 base field modulus
+        let bn254 = [
+            0xd87cfd47, 0x3c208c16, 0x6871ca8d, 0x97816a91, 0x8181585d, 0xb85045b6, 0xe131a029,
+            0x30644e72,
+        ];
+        let mut moduli = vec![bn254, [u32::MAX; LIMBS], [7, 0, 0, 0, 0, 0, 0, 0]];
+        moduli.push(random_bigint(&mut rng));
+        for modulus in moduli {
+            for _ in 0..4 {
+                // Reduce `a` so that the quotient fits in 256 bits
+                let (_, a) = bigint_mulmod(
+                    &random_bigint(&mut rng),
+                    &[1, 0, 0, 0, 0, 0, 0, 0],
+                    &modulus,
+                );
+                let b = random_bigint(&mut rng);
+                let row = trace_row(RV32IM::BIGINT_MULMOD, [a, modulus, b].concat());
+
+                let stored = check_routine::<BIGINTMULMODInstruction<32>>(row);
+                let (_, expected) = bigint_mulmod(&a, &b, &modulus);
+                assert_eq!(limbs(&stored, A_ADDRESS, LIMBS), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed")]
+    fn bigint_mulmod_bad_advice() {
+        let a = [3, 0, 0, 0, 0, 0, 0, 0];
+        let b = [2, 0, 0, 0, 0, 0, 0, 0];
+        let modulus = [5, 0, 0, 0, 0, 0, 0, 0];
+        let instruction = trace_row(RV32IM::BIGINT_MULMOD, vec![]).instruction;
+
+        let mut registers = HashMap::new();
+        registers.insert(10, A_ADDRESS);
+        registers.insert(11, B_ADDRESS);
+        let modulus_address = A_ADDRESS + 4 * LIMBS as u64;
+        let memory = load_input(
+            &[a, modulus, b].concat(),
+            &[A_ADDRESS, modulus_address, B_ADDRESS],
+        );
+        // 6 = 0 * 5 + 6 satisfies the product check, but not r < p
+        let mut advice = vec![0; 2 * LIMBS];
+        advice[LIMBS] = 6;
+
+        let routine = BIGINTMULMODInstruction::<32>::routine(&instruction, ROUTINE_ADDRESS);
+        execute_routine(routine, registers, memory, &advice);
+    }
+}
@@ -0,0 +1,77 @@
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
+use crate::jolt::subtable::{popcount::PopcountSubtable, LassoSubtable};
+use crate::poly::field::JoltField;
+use crate::utils::instruction_utils::chunk_and_concatenate_operands;
+
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(PopcountSubtable<F>)]
+pub struct CPOPInstruction(pub u64);
+
+impl JoltInstruction for CPOPInstruction {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, 0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, _: usize) -> F {
+        assert_eq!(vals.len(), C);
+        vals.iter().sum()
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        _: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        vec![(
+            Box::new(PopcountSubtable::new()),
+            SubtableIndices::from(0..C),
+        )]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_and_concatenate_operands(self.0, 0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        self.0.count_ones() as u64
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        Self(rng.next_u32() as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::CPOPInstruction;
+
+    #[test]
+    fn cpop_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+
+        for _ in 0..256 {
+            let instruction = CPOPInstruction(rng.next_u32() as u64);
+            jolt_instruction_test!(instruction);
+        }
+        let instructions = vec![CPOPInstruction(0), CPOPInstruction(u32::MAX as u64)];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use ark_std::log2;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
+use crate::jolt::subtable::{eq::EqSubtable, trailing_zeros::TrailingZerosSubtable, LassoSubtable};
+use crate::poly::field::JoltField;
+use crate::utils::instruction_utils::{assert_valid_parameters, chunk_and_concatenate_operands};
+
+/// Counts the trailing zeros of a word. The second operand of each lookup is zero, so
+/// `EQ(x_i, 0)` indicates whether chunk i is zero.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(TrailingZerosSubtable<F>, EqSubtable<F>)]
+pub struct CTZInstruction<const WORD_SIZE: usize>(pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for CTZInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, 0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, M: usize) -> F {
+        let vals_by_subtable = self.slice_values(vals, C, M);
+        let trailing_zeros = vals_by_subtable[0];
+        let eq = vals_by_subtable[1];
+
+        // \sum_i TZ(x_i) * \prod_{j > i} EQ(x_j, 0)
+        let mut result = F::zero();
+        let mut eq_prod = F::one();
+        for i in (0..C).rev() {
+            result += trailing_zeros[i] * eq_prod;
+            eq_prod *= eq[i];
+        }
+
+        // If x is zero, the sum counts the chunks above the word as well
+        let excess_bits = C * log2(M) as usize / 2 - WORD_SIZE;
+        result - F::from_u64(excess_bits as u64).unwrap() * eq_prod
+    }
+
+    fn g_poly_degree(&self, C: usize) -> usize {
+        C
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        _: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        vec![
+            (
+                Box::new(TrailingZerosSubtable::new()),
+                SubtableIndices::from(0..C),
+            ),
+            (Box::new(EqSubtable::new()), SubtableIndices::from(0..C)),
+        ]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        assert_valid_parameters(WORD_SIZE, C, log_M);
+        chunk_and_concatenate_operands(self.0, 0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        self.0.trailing_zeros().min(WORD_SIZE as u32) as u64
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        Self((rng.next_u32() << (rng.next_u32() % 32)) as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::CTZInstruction;
+
+    #[test]
+    fn ctz_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        for _ in 0..256 {
+            let x = (rng.next_u32() << (rng.next_u32() % 32)) as u64;
+            let instruction = CTZInstruction::<WORD_SIZE>(x);
+            jolt_instruction_test!(instruction);
+        }
+        for shift in 0..32 {
+            let instruction = CTZInstruction::<WORD_SIZE>(1 << shift);
+            jolt_instruction_test!(instruction);
+        }
+        let instructions = vec![
+            CTZInstruction::<WORD_SIZE>(0),
+            CTZInstruction::<WORD_SIZE>(u32::MAX as u64),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+
+    #[test]
+    fn ctz_instruction_32_with_8_chunks_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        for _ in 0..256 {
+            let x = (rng.next_u32() << (rng.next_u32() % 32)) as u64;
+            let instruction = CTZInstruction::<WORD_SIZE>(x);
+            jolt_instruction_test!(instruction);
+        }
+        let instruction = CTZInstruction::<WORD_SIZE>(0);
+        jolt_instruction_test!(instruction);
+    }
+}
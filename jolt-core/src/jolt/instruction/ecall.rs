@@ -0,0 +1,93 @@
+use common::syscall::SYSCALL_RETURN_REGISTER;
+use tracer::{ELFInstruction, RVTraceRow, RegisterState, RV32IM};
+
+use super::VirtualInstructionSequence;
+
+/// Syscalls are serviced by the host (see `common::syscall`), so the only effect of
+/// an `ECALL` on guest-visible state is the value written to a0. We model it as a
+/// single write of untrusted advice to a0.
+pub struct ECALLInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> ECALLInstruction<WORD_SIZE> {
+    /// The instruction stored in the bytecode in place of an `ECALL`.
+    pub fn virtual_instruction(instruction: &ELFInstruction) -> ELFInstruction {
+        assert_eq!(instruction.opcode, RV32IM::ECALL);
+        ELFInstruction {
+            address: instruction.address,
+            opcode: RV32IM::VIRTUAL_ADVICE,
+            rs1: None,
+            rs2: None,
+            rd: Some(SYSCALL_RETURN_REGISTER),
+            imm: None,
+            virtual_sequence_index: Some(0),
+        }
+    }
+}
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for ECALLInstruction<WORD_SIZE> {
+    fn virtual_sequence(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::ECALL);
+        let result = trace_row.register_state.rd_post_val.unwrap();
+
+        vec![RVTraceRow {
+            instruction: Self::virtual_instruction(&trace_row.instruction),
+            register_state: RegisterState {
+                rs1_val: None,
+                rs2_val: None,
+                rd_post_val: Some(result),
+            },
+            memory_state: None,
+            precompile_input: None,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::test_rng;
+    use common::syscall::{Syscall, SYSCALL_NUMBER_REGISTER};
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::jolt::{instruction::JoltInstruction, vm::rv32i_vm::RV32I};
+
+    use super::*;
+
+    #[test]
+    fn ecall_virtual_sequence_32() {
+        let mut rng = test_rng();
+        let result = rng.next_u32() as u64;
+
+        let ecall_trace_row = RVTraceRow {
+            instruction: ELFInstruction {
+                address: rng.next_u64(),
+                opcode: RV32IM::ECALL,
+                rs1: Some(SYSCALL_NUMBER_REGISTER),
+                rs2: None,
+                rd: Some(SYSCALL_RETURN_REGISTER),
+                imm: None,
+                virtual_sequence_index: None,
+            },
+            register_state: RegisterState {
+                rs1_val: Some(Syscall::HintRead as u64),
+                rs2_val: None,
+                rd_post_val: Some(result),
+            },
+            memory_state: None,
+            precompile_input: None,
+        };
+
+        let virtual_sequence = ECALLInstruction::<32>::virtual_sequence(ecall_trace_row.clone());
+        assert_eq!(virtual_sequence.len(), 1);
+
+        let row = &virtual_sequence[0];
+        assert_eq!(
+            row.instruction,
+            ECALLInstruction::<32>::virtual_instruction(&ecall_trace_row.instruction)
+        );
+        assert_eq!(row.instruction.rd, Some(SYSCALL_RETURN_REGISTER));
+
+        let lookup = RV32I::try_from(row).unwrap();
+        assert_eq!(lookup.lookup_entry(), result);
+        assert_eq!(row.register_state.rd_post_val, Some(result));
+    }
+}
@@ -2,14 +2,15 @@ use rand::prelude::StdRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use super::{JoltInstruction, SubtableIndices};
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
 use crate::jolt::subtable::{
     sign_extend::SignExtendSubtable, truncate_overflow::TruncateOverflowSubtable, LassoSubtable,
 };
 use crate::poly::field::JoltField;
 use crate::utils::instruction_utils::chunk_operand_usize;
 
-#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(TruncateOverflowSubtable<F, 8>, SignExtendSubtable<F, 8>)]
 pub struct LBInstruction(pub u64);
 
 impl JoltInstruction for LBInstruction {
@@ -17,15 +18,16 @@ impl JoltInstruction for LBInstruction {
         (0, self.0)
     }
 
-    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, M: usize) -> F {
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], _: usize, M: usize) -> F {
         assert!(M >= 1 << 8);
         assert!(vals.len() == 2);
 
         let byte = vals[0];
         let sign_extension = vals[1];
 
+        // Fill the three upper bytes of the 32-bit result
         let mut result = byte;
-        for i in 1..C {
+        for i in 1..4 {
             result += F::from_u64(1 << (8 * i)).unwrap() * sign_extension;
         }
         result
@@ -105,4 +107,17 @@ mod test {
             jolt_instruction_test!(instruction);
         }
     }
+
+    #[test]
+    fn lb_instruction_with_8_chunks_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+
+        for _ in 0..256 {
+            let x = rng.next_u32() as u64;
+            let instruction = LBInstruction(x);
+            jolt_instruction_test!(instruction);
+        }
+    }
 }
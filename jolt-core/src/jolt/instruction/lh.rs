@@ -2,14 +2,15 @@ use rand::prelude::StdRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use super::{JoltInstruction, SubtableIndices};
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
 use crate::jolt::subtable::{
     identity::IdentitySubtable, sign_extend::SignExtendSubtable, LassoSubtable,
 };
 use crate::poly::field::JoltField;
 use crate::utils::instruction_utils::chunk_operand_usize;
 
-#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(IdentitySubtable<F>, SignExtendSubtable<F, 16>)]
 pub struct LHInstruction(pub u64);
 
 impl JoltInstruction for LHInstruction {
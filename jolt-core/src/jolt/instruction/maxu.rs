@@ -0,0 +1,112 @@
+use ark_std::log2;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{sltu::SLTUInstruction, InstructionSubtables, JoltInstruction, SubtableIndices};
+use crate::{
+    jolt::subtable::{
+        eq::EqSubtable, left_operand::LeftOperandSubtable, ltu::LtuSubtable,
+        right_operand::RightOperandSubtable, LassoSubtable,
+    },
+    poly::field::JoltField,
+    utils::instruction_utils::{chunk_and_concatenate_operands, concatenate_lookups},
+};
+
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(LtuSubtable<F>, EqSubtable<F>, LeftOperandSubtable<F>, RightOperandSubtable<F>)]
+pub struct MAXUInstruction(pub u64, pub u64);
+
+impl JoltInstruction for MAXUInstruction {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, self.1)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, M: usize) -> F {
+        let operand_bits = log2(M) as usize / 2;
+        let (lt_vals, operand_vals) = vals.split_at(vals.len() - 2 * C);
+        let lt = SLTUInstruction(self.0, self.1).combine_lookups(lt_vals, C, M);
+        let x = concatenate_lookups(&operand_vals[..C], C, operand_bits);
+        let y = concatenate_lookups(&operand_vals[C..], C, operand_bits);
+
+        // x + LTU(x, y) * (y - x)
+        x + lt * (y - x)
+    }
+
+    fn g_poly_degree(&self, C: usize) -> usize {
+        C + 1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        _: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        vec![
+            (Box::new(LtuSubtable::new()), SubtableIndices::from(0..C)),
+            (Box::new(EqSubtable::new()), SubtableIndices::from(0..C)),
+            (
+                Box::new(LeftOperandSubtable::new()),
+                SubtableIndices::from(0..C),
+            ),
+            (
+                Box::new(RightOperandSubtable::new()),
+                SubtableIndices::from(0..C),
+            ),
+        ]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_and_concatenate_operands(self.0, self.1, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        if self.0 < self.1 {
+            self.1
+        } else {
+            self.0
+        }
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        Self(rng.next_u32() as u64, rng.next_u32() as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::MAXUInstruction;
+
+    #[test]
+    fn maxu_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+
+        for _ in 0..256 {
+            let x = rng.next_u32() as u64;
+            let y = rng.next_u32() as u64;
+            let instruction = MAXUInstruction(x, y);
+            jolt_instruction_test!(instruction);
+        }
+        let u32_max: u64 = u32::MAX as u64;
+        let instructions = vec![
+            MAXUInstruction(100, 0),
+            MAXUInstruction(0, 100),
+            MAXUInstruction(0, u32_max),
+            MAXUInstruction(u32_max, 0),
+            MAXUInstruction(u32_max, u32_max),
+            MAXUInstruction(u32_max, 1 << 8),
+            MAXUInstruction(1 << 8, u32_max),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
@@ -1,19 +1,23 @@
-use enum_dispatch::enum_dispatch;
 use fixedbitset::*;
-use rand::prelude::StdRng;
+use rand::RngCore;
 use serde::Serialize;
 use std::marker::Sync;
 use std::ops::Range;
 use strum::{EnumCount, IntoEnumIterator};
 use tracer::RVTraceRow;
 
-use crate::jolt::subtable::LassoSubtable;
+use crate::jolt::subtable::{LassoSubtable, SubtableId};
 use crate::poly::field::JoltField;
 use crate::utils::instruction_utils::chunk_operand;
 use common::rv_trace::ELFInstruction;
+use std::any::TypeId;
 use std::fmt::Debug;
 
-#[enum_dispatch]
+#[doc(hidden)]
+pub use rand::prelude::StdRng;
+
+pub use jolt_core_macros::{InstructionSubtables, JoltInstructionSet};
+
 pub trait JoltInstruction: Clone + Debug + Send + Sync + Serialize {
     fn operands(&self) -> (u64, u64);
     /// Combines `vals` according to the instruction's "collation" polynomial `g`.
@@ -70,14 +74,42 @@ pub trait JoltInstruction: Clone + Debug + Send + Sync + Serialize {
     }
 }
 
+/// An enum of `JoltInstruction` types, usually implemented with `#[derive(JoltInstructionSet)]`.
 pub trait JoltInstructionSet:
-    JoltInstruction + IntoEnumIterator + EnumCount + for<'a> TryFrom<&'a ELFInstruction> + Send + Sync
+    'static
+    + JoltInstruction
+    + IntoEnumIterator
+    + EnumCount
+    + for<'a> TryFrom<&'a ELFInstruction>
+    + Send
+    + Sync
 {
-    fn enum_index(instruction: &Self) -> usize {
-        unsafe { *<*const _>::from(instruction).cast::<u8>() as usize }
+    /// Returns the index of the instruction's variant in the enum.
+    fn enum_index(instruction: &Self) -> usize;
+
+    /// Returns the index of the variant wrapping the instruction type with the given `TypeId`,
+    /// or `None` if this set does not contain that instruction type.
+    fn instruction_index(instruction_id: TypeId) -> Option<usize>;
+
+    /// Returns an instruction with random operands, of a random variant.
+    fn random_instruction(rng: &mut StdRng) -> Self {
+        let index = rng.next_u64() as usize % Self::COUNT;
+        Self::iter().nth(index).unwrap().random(rng)
     }
 }
 
+/// Implemented by an instruction for every subtable set that contains all of the subtables
+/// it may use, i.e. all of the types `JoltInstruction::subtables` returns for any `C` and `M`.
+/// Instructions list these types once, with `#[derive(InstructionSubtables)]`. The `Jolt` trait
+/// requires this of its instruction set (which `#[derive(JoltInstructionSet)]` implements by
+/// requiring it of every variant), so a VM whose subtable set is missing one of its
+/// instructions' subtables does not compile.
+pub trait InstructionSubtables<F: JoltField, Subtables> {
+    /// The index in `Subtables` of the subtable with the given id, which must be one of those
+    /// returned by `JoltInstruction::subtables`.
+    fn subtable_index(&self, subtable_id: SubtableId) -> usize;
+}
+
 #[derive(Clone)]
 pub struct SubtableIndices {
     bitset: FixedBitSet,
@@ -127,22 +159,62 @@ pub trait VirtualInstructionSequence {
     fn virtual_sequence(trace_row: RVTraceRow) -> Vec<RVTraceRow>;
 }
 
+/// A precompile instruction, checked by a routine of ordinary instructions. Each call
+/// site gets its own copy of the routine, laid out in the bytecode after the program
+/// text: the call site itself becomes a `JAL` into the routine, which returns with a
+/// `JALR`. Since every row of the routine has its own address, the existing PC
+/// constraints apply unchanged.
+pub trait PrecompileRoutine {
+    /// The number of instructions in the routine, excluding the call site's `JAL`.
+    const ROUTINE_LENGTH: usize;
+
+    /// The bytecode for a call site: a `JAL` at the call site's address, followed by
+    /// the routine at `routine_address`.
+    fn virtual_instructions(
+        instruction: &ELFInstruction,
+        routine_address: u64,
+    ) -> Vec<ELFInstruction>;
+
+    /// Expands a traced call into the rows executed by its routine.
+    fn virtual_sequence(trace_row: RVTraceRow, routine_address: u64) -> Vec<RVTraceRow>;
+}
+
 pub mod add;
+pub mod advice;
 pub mod and;
+pub mod andn;
 pub mod beq;
 pub mod bge;
 pub mod bgeu;
+pub mod bigint;
 pub mod bne;
+pub mod clz;
+pub mod cpop;
+pub mod ctz;
+pub mod ecall;
 pub mod lb;
 pub mod lh;
+pub mod max;
+pub mod maxu;
+pub mod min;
+pub mod minu;
 pub mod movsign;
 pub mod mul;
 pub mod mulh;
 pub mod mulhu;
 pub mod mulu;
 pub mod or;
+pub mod orcb;
+pub mod orn;
+pub mod precompile;
+pub mod rev8;
+pub mod rol;
+pub mod ror;
 pub mod sb;
+pub mod sextb;
+pub mod sexth;
 pub mod sh;
+pub mod shadd;
 pub mod sll;
 pub mod slt;
 pub mod sltu;
@@ -150,6 +222,7 @@ pub mod sra;
 pub mod srl;
 pub mod sub;
 pub mod sw;
+pub mod xnor;
 pub mod xor;
 
 #[cfg(test)]
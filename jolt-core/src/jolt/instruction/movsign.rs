@@ -2,7 +2,7 @@ use rand::prelude::StdRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use super::JoltInstruction;
+use super::{InstructionSubtables, JoltInstruction};
 use crate::{
     jolt::{
         instruction::SubtableIndices,
@@ -12,7 +12,8 @@ use crate::{
     utils::instruction_utils::chunk_operand_usize,
 };
 
-#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(SignExtendSubtable<F, 16>)]
 pub struct MOVSIGNInstruction<const WORD_SIZE: usize>(pub u64);
 
 // Constants for 32-bit and 64-bit word sizes
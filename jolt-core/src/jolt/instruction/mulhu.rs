@@ -3,14 +3,15 @@ use rand::prelude::StdRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use super::{JoltInstruction, SubtableIndices};
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
 use crate::jolt::subtable::{identity::IdentitySubtable, LassoSubtable};
 use crate::poly::field::JoltField;
 use crate::utils::instruction_utils::{
     assert_valid_parameters, concatenate_lookups, multiply_and_chunk_operands,
 };
 
-#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(IdentitySubtable<F>)]
 pub struct MULHUInstruction<const WORD_SIZE: usize>(pub u64, pub u64);
 
 impl<const WORD_SIZE: usize> JoltInstruction for MULHUInstruction<WORD_SIZE> {
@@ -31,10 +32,14 @@ impl<const WORD_SIZE: usize> JoltInstruction for MULHUInstruction<WORD_SIZE> {
         C: usize,
         M: usize,
     ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
-        assert_eq!(C * log2(M) as usize, 2 * WORD_SIZE);
+        // The upper word of the product is in whole chunks, after any chunks that are always zero
+        let log_M = log2(M) as usize;
+        assert!(C * log_M >= 2 * WORD_SIZE);
+        assert_eq!(WORD_SIZE % log_M, 0);
+        let upper_word_start = C - 2 * WORD_SIZE / log_M;
         vec![(
             Box::new(IdentitySubtable::new()),
-            SubtableIndices::from(0..C / 2),
+            SubtableIndices::from(upper_word_start..upper_word_start + WORD_SIZE / log_M),
         )]
     }
 
@@ -92,4 +97,18 @@ mod test {
             jolt_instruction_test!(instruction);
         }
     }
+
+    #[test]
+    fn mulhu_instruction_32_with_8_chunks_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+
+        for _ in 0..256 {
+            let x = rng.next_u32() as u64;
+            let y = rng.next_u32() as u64;
+            let instruction = MULHUInstruction::<32>(x, y);
+            jolt_instruction_test!(instruction);
+        }
+    }
 }
@@ -0,0 +1,93 @@
+use ark_std::log2;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
+use crate::jolt::subtable::{eq::EqSubtable, LassoSubtable};
+use crate::poly::field::JoltField;
+use crate::utils::instruction_utils::{chunk_and_concatenate_operands, concatenate_lookups};
+
+/// ORC.B: each byte of the result is 0xff if the corresponding byte of the operand is
+/// nonzero, and 0 otherwise. The second operand of each lookup is zero, so
+/// `EQ(x_i, 0)` indicates whether byte i is zero.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(EqSubtable<F>)]
+pub struct ORCBInstruction(pub u64);
+
+impl JoltInstruction for ORCBInstruction {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, 0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, _: usize) -> F {
+        let bytes: Vec<F> = vals
+            .iter()
+            .map(|eq| F::from_u64(0xff).unwrap() * (F::one() - eq))
+            .collect();
+        concatenate_lookups(&bytes, C, 8)
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        M: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        // Each chunk's first operand must be exactly one byte
+        assert_eq!(log2(M), 16);
+        vec![(Box::new(EqSubtable::new()), SubtableIndices::from(0..C))]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_and_concatenate_operands(self.0, 0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        (0..8)
+            .filter(|byte| (self.0 >> (8 * byte)) & 0xff != 0)
+            .fold(0, |result, byte| result | (0xff << (8 * byte)))
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        // Zero out some of the bytes
+        Self((rng.next_u32() & rng.next_u32() & rng.next_u32()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::ORCBInstruction;
+
+    #[test]
+    fn orcb_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+
+        for _ in 0..256 {
+            // Sparse enough that some bytes are zero
+            let x = rng.next_u32() & rng.next_u32() & rng.next_u32();
+            let instruction = ORCBInstruction(x as u64);
+            jolt_instruction_test!(instruction);
+        }
+        let instructions = vec![
+            ORCBInstruction(0),
+            ORCBInstruction(0x01000080),
+            ORCBInstruction(0x00100000),
+            ORCBInstruction(u32::MAX as u64),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
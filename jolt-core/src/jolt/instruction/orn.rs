@@ -0,0 +1,107 @@
+use ark_std::log2;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
+use crate::jolt::subtable::{orn::OrnSubtable, LassoSubtable};
+use crate::poly::field::JoltField;
+use crate::utils::instruction_utils::{
+    assert_valid_parameters, chunk_and_concatenate_operands, concatenate_lookups,
+};
+
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(OrnSubtable<F>)]
+pub struct ORNInstruction<const WORD_SIZE: usize>(pub u64, pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for ORNInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, self.1)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], _: usize, M: usize) -> F {
+        let operand_bits = log2(M) as usize / 2;
+        concatenate_lookups(vals, WORD_SIZE / operand_bits, operand_bits)
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        M: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        // Chunks above the word would be all ones, so only the word's own chunks are used
+        let operand_bits = log2(M) as usize / 2;
+        assert_eq!(WORD_SIZE % operand_bits, 0);
+        let num_word_chunks = WORD_SIZE / operand_bits;
+        vec![(
+            Box::new(OrnSubtable::new()),
+            SubtableIndices::from(C - num_word_chunks..C),
+        )]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        assert_valid_parameters(WORD_SIZE, C, log_M);
+        chunk_and_concatenate_operands(self.0, self.1, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        (self.0 | !self.1) & (u64::MAX >> (64 - WORD_SIZE))
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        Self(rng.next_u32() as u64, rng.next_u32() as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::ORNInstruction;
+
+    #[test]
+    fn orn_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        for _ in 0..256 {
+            let (x, y) = (rng.next_u32() as u64, rng.next_u32() as u64);
+            let instruction = ORNInstruction::<WORD_SIZE>(x, y);
+            jolt_instruction_test!(instruction);
+        }
+        let u32_max: u64 = u32::MAX as u64;
+        let instructions = vec![
+            ORNInstruction::<WORD_SIZE>(0, 0),
+            ORNInstruction::<WORD_SIZE>(u32_max, 0),
+            ORNInstruction::<WORD_SIZE>(0, u32_max),
+            ORNInstruction::<WORD_SIZE>(u32_max, u32_max),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+
+    #[test]
+    fn orn_instruction_32_with_8_chunks_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        for _ in 0..256 {
+            let (x, y) = (rng.next_u32() as u64, rng.next_u32() as u64);
+            let instruction = ORNInstruction::<WORD_SIZE>(x, y);
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
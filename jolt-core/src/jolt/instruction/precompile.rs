@@ -0,0 +1,289 @@
+//! Shared machinery for precompile routines (see `PrecompileRoutine`).
+
+use std::collections::HashMap;
+
+use common::constants::virtual_register_index;
+use tracer::{ELFInstruction, MemoryState, RVTraceRow, RegisterState, RV32IM};
+
+/// Holds the call site's return address for the duration of a routine. Routines may
+/// use every other virtual register.
+pub(crate) const RETURN_ADDRESS: u64 = virtual_register_index(31);
+
+/// The register-to-register instructions routines compute with.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum RoutineOp {
+    Add,
+    Sub,
+    Sltu,
+    Or,
+    Mulu,
+    Mulhu,
+}
+
+impl RoutineOp {
+    fn opcode(self) -> RV32IM {
+        match self {
+            RoutineOp::Add => RV32IM::ADD,
+            RoutineOp::Sub => RV32IM::SUB,
+            RoutineOp::Sltu => RV32IM::SLTU,
+            RoutineOp::Or => RV32IM::OR,
+            RoutineOp::Mulu => RV32IM::MULU,
+            RoutineOp::Mulhu => RV32IM::MULHU,
+        }
+    }
+
+    fn evaluate(self, x: u32, y: u32) -> u64 {
+        match self {
+            RoutineOp::Add => x.wrapping_add(y) as u64,
+            RoutineOp::Sub => x.wrapping_sub(y) as u64,
+            RoutineOp::Sltu => (x < y) as u64,
+            RoutineOp::Or => (x | y) as u64,
+            RoutineOp::Mulu => x.wrapping_mul(y) as u64,
+            RoutineOp::Mulhu => (x as u64 * y as u64) >> 32,
+        }
+    }
+}
+
+/// What a routine's instruction does, from which `execute_routine` computes its row.
+#[derive(Clone, Copy, Debug)]
+enum RoutineStep {
+    Call,
+    Load,
+    Store,
+    Op(RoutineOp),
+    Advice,
+    AssertEq,
+    AssertLtu,
+    Return,
+}
+
+impl RoutineStep {
+    fn opcode(self) -> RV32IM {
+        match self {
+            RoutineStep::Call => RV32IM::JAL,
+            RoutineStep::Load => RV32IM::LW,
+            RoutineStep::Store => RV32IM::SW,
+            RoutineStep::Op(op) => op.opcode(),
+            RoutineStep::Advice => RV32IM::VIRTUAL_ADVICE,
+            RoutineStep::AssertEq => RV32IM::VIRTUAL_ASSERT_EQ,
+            RoutineStep::AssertLtu => RV32IM::VIRTUAL_ASSERT_LTU,
+            RoutineStep::Return => RV32IM::JALR,
+        }
+    }
+}
+
+/// A call site's bytecode: the `JAL` that replaces it, followed by its routine.
+pub(crate) struct Routine {
+    steps: Vec<RoutineStep>,
+    instructions: Vec<ELFInstruction>,
+}
+
+impl Routine {
+    #[cfg(test)]
+    pub fn instructions(&self) -> &[ELFInstruction] {
+        &self.instructions
+    }
+
+    pub fn into_instructions(self) -> Vec<ELFInstruction> {
+        self.instructions
+    }
+}
+
+/// Lays out a routine's instructions at consecutive addresses.
+pub(crate) struct RoutineBuilder {
+    address: u64,
+    steps: Vec<RoutineStep>,
+    instructions: Vec<ELFInstruction>,
+}
+
+impl RoutineBuilder {
+    pub fn new(address: u64, capacity: usize) -> Self {
+        Self {
+            address,
+            steps: Vec::with_capacity(capacity),
+            instructions: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn push(
+        &mut self,
+        step: RoutineStep,
+        rs1: Option<u64>,
+        rs2: Option<u64>,
+        rd: Option<u64>,
+        imm: Option<u32>,
+    ) {
+        let index = self.instructions.len();
+        self.steps.push(step);
+        self.instructions.push(ELFInstruction {
+            address: self.address + 4 * index as u64,
+            opcode: step.opcode(),
+            rs1,
+            rs2,
+            rd,
+            imm,
+            // The call site's `JAL` is index 0
+            virtual_sequence_index: Some(index + 1),
+        });
+    }
+
+    pub fn r(&mut self, op: RoutineOp, rd: u64, rs1: u64, rs2: u64) {
+        self.push(RoutineStep::Op(op), Some(rs1), Some(rs2), Some(rd), None);
+    }
+
+    /// rd := the word at address rs1 + offset.
+    pub fn load(&mut self, rd: u64, rs1: u64, offset: u32) {
+        self.push(RoutineStep::Load, Some(rs1), None, Some(rd), Some(offset));
+    }
+
+    pub fn store(&mut self, rs1: u64, rs2: u64, offset: u32) {
+        self.push(RoutineStep::Store, Some(rs1), Some(rs2), None, Some(offset));
+    }
+
+    /// rd := the next prover-supplied advice word.
+    pub fn advice(&mut self, rd: u64) {
+        self.push(RoutineStep::Advice, None, None, Some(rd), None);
+    }
+
+    /// Asserts rs1 == rs2. The assertion is a `BEQ` lookup whose output the R1CS
+    /// constraints require to be 1.
+    pub fn assert_eq(&mut self, rs1: u64, rs2: u64) {
+        self.push(RoutineStep::AssertEq, Some(rs1), Some(rs2), None, None);
+    }
+
+    /// Asserts rs1 < rs2 as unsigned integers, through an `SLTU` lookup.
+    pub fn assert_ltu(&mut self, rs1: u64, rs2: u64) {
+        self.push(RoutineStep::AssertLtu, Some(rs1), Some(rs2), None, None);
+    }
+
+    /// Returns from the routine and prepends the `JAL` that replaces `call_site`.
+    pub fn finish(mut self, call_site: &ELFInstruction) -> Routine {
+        self.push(
+            RoutineStep::Return,
+            Some(RETURN_ADDRESS),
+            None,
+            Some(0),
+            Some(0),
+        );
+        assert!(self.address > call_site.address);
+        let call = ELFInstruction {
+            address: call_site.address,
+            opcode: RoutineStep::Call.opcode(),
+            rs1: None,
+            rs2: None,
+            rd: Some(RETURN_ADDRESS),
+            imm: Some((self.address - call_site.address) as u32),
+            virtual_sequence_index: Some(0),
+        };
+        Routine {
+            steps: std::iter::once(RoutineStep::Call)
+                .chain(self.steps)
+                .collect(),
+            instructions: std::iter::once(call).chain(self.instructions).collect(),
+        }
+    }
+}
+
+/// Runs a 32-bit routine from the given initial register and memory values, and
+/// returns its trace. Memory only needs to contain the words the routine loads
+/// before storing to them, and `advice` supplies the routine's advice words in order.
+///
+/// Panics if one of the routine's assertions fails.
+pub(crate) fn execute_routine(
+    routine: Routine,
+    registers: HashMap<u64, u64>,
+    memory: HashMap<u64, u64>,
+    advice: &[u32],
+) -> Vec<RVTraceRow> {
+    run_routine(routine, registers, memory, advice, true)
+}
+
+/// Same as `execute_routine`, but carries on past failed assertions, like a dishonest
+/// prover would.
+#[cfg(test)]
+pub(crate) fn execute_routine_unchecked(
+    routine: Routine,
+    registers: HashMap<u64, u64>,
+    memory: HashMap<u64, u64>,
+    advice: &[u32],
+) -> Vec<RVTraceRow> {
+    run_routine(routine, registers, memory, advice, false)
+}
+
+fn run_routine(
+    routine: Routine,
+    mut registers: HashMap<u64, u64>,
+    mut memory: HashMap<u64, u64>,
+    advice: &[u32],
+    check_assertions: bool,
+) -> Vec<RVTraceRow> {
+    let mut advice = advice.iter();
+    let mut trace = Vec::with_capacity(routine.instructions.len());
+    for (step, instruction) in routine.steps.into_iter().zip(routine.instructions) {
+        let read = |register: Option<u64>| match register {
+            Some(0) => Some(0),
+            Some(register) => Some(registers[&register]),
+            None => None,
+        };
+        let rs1_val = read(instruction.rs1);
+        let rs2_val = read(instruction.rs2);
+        let x = rs1_val.unwrap_or(0) as u32;
+        let y = rs2_val.unwrap_or(0) as u32;
+        let imm = instruction.imm.unwrap_or(0);
+
+        let mut memory_state = None;
+        let result = match step {
+            RoutineStep::Load => {
+                let address = x.wrapping_add(imm) as u64;
+                let value = memory[&address];
+                memory_state = Some(MemoryState::Read { address, value });
+                value
+            }
+            RoutineStep::Store => {
+                let address = x.wrapping_add(imm) as u64;
+                memory.insert(address, y as u64);
+                memory_state = Some(MemoryState::Write {
+                    address,
+                    post_value: y as u64,
+                });
+                0
+            }
+            RoutineStep::Op(op) => op.evaluate(x, y),
+            RoutineStep::AssertEq | RoutineStep::AssertLtu => {
+                let holds = match step {
+                    RoutineStep::AssertEq => x == y,
+                    _ => x < y,
+                };
+                assert!(
+                    holds || !check_assertions,
+                    "assertion failed at {:#x}",
+                    instruction.address
+                );
+                holds as u64
+            }
+            RoutineStep::Advice => *advice.next().expect("ran out of advice") as u64,
+            RoutineStep::Call => instruction.address + 4,
+            // Returns to the instruction after the call site
+            RoutineStep::Return => 0,
+        };
+        let rd_post_val = instruction.rd.map(|rd| match rd {
+            0 => 0,
+            _ => {
+                registers.insert(rd, result);
+                result
+            }
+        });
+
+        trace.push(RVTraceRow {
+            instruction,
+            register_state: RegisterState {
+                rs1_val,
+                rs2_val,
+                rd_post_val,
+            },
+            memory_state,
+            precompile_input: None,
+        });
+    }
+    trace
+}
@@ -0,0 +1,103 @@
+use ark_std::log2;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
+use crate::jolt::subtable::{left_operand::LeftOperandSubtable, LassoSubtable};
+use crate::poly::field::JoltField;
+use crate::utils::instruction_utils::{
+    assert_valid_parameters, chunk_and_concatenate_operands, concatenate_lookups,
+};
+
+/// REV8: reverses the bytes of a word.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(LeftOperandSubtable<F>)]
+pub struct REV8Instruction<const WORD_SIZE: usize>(pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for REV8Instruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, 0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], _: usize, _: usize) -> F {
+        assert_eq!(vals.len(), WORD_SIZE / 8);
+        let reversed: Vec<F> = vals.iter().rev().copied().collect();
+        concatenate_lookups(&reversed, WORD_SIZE / 8, 8)
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        M: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        // Each chunk's first operand must be exactly one byte
+        assert_eq!(log2(M), 16);
+        vec![(
+            Box::new(LeftOperandSubtable::new()),
+            SubtableIndices::from(C - WORD_SIZE / 8..C),
+        )]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        assert_valid_parameters(WORD_SIZE, C, log_M);
+        chunk_and_concatenate_operands(self.0, 0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        self.0.swap_bytes() >> (64 - WORD_SIZE)
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        Self(rng.next_u32() as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::REV8Instruction;
+
+    #[test]
+    fn rev8_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        for _ in 0..256 {
+            let instruction = REV8Instruction::<WORD_SIZE>(rng.next_u32() as u64);
+            jolt_instruction_test!(instruction);
+        }
+        let instructions = vec![
+            REV8Instruction::<WORD_SIZE>(0),
+            REV8Instruction::<WORD_SIZE>(0x12345678),
+            REV8Instruction::<WORD_SIZE>(u32::MAX as u64),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+
+    #[test]
+    fn rev8_instruction_64_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 64;
+
+        for _ in 0..256 {
+            let instruction = REV8Instruction::<WORD_SIZE>(rng.next_u64());
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
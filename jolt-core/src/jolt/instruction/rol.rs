@@ -0,0 +1,122 @@
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
+use crate::jolt::subtable::{rotl::RotlSubtable, LassoSubtable};
+use crate::poly::field::JoltField;
+use crate::utils::instruction_utils::{assert_valid_parameters, chunk_and_concatenate_for_shift};
+
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(
+    RotlSubtable<F, 0, WORD_SIZE>,
+    RotlSubtable<F, 1, WORD_SIZE>,
+    RotlSubtable<F, 2, WORD_SIZE>,
+    RotlSubtable<F, 3, WORD_SIZE>,
+    RotlSubtable<F, 4, WORD_SIZE>,
+    RotlSubtable<F, 5, WORD_SIZE>,
+    RotlSubtable<F, 6, WORD_SIZE>,
+    RotlSubtable<F, 7, WORD_SIZE>,
+    RotlSubtable<F, 8, WORD_SIZE>,
+    RotlSubtable<F, 9, WORD_SIZE>,
+)]
+pub struct ROLInstruction<const WORD_SIZE: usize>(pub u64, pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for ROLInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, self.1)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, _: usize) -> F {
+        // Each chunk's subtable rotates it into place within the word
+        assert!(C <= 10);
+        vals.iter().sum()
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        _: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        let mut subtables: Vec<Box<dyn LassoSubtable<F>>> = vec![
+            Box::new(RotlSubtable::<F, 0, WORD_SIZE>::new()),
+            Box::new(RotlSubtable::<F, 1, WORD_SIZE>::new()),
+            Box::new(RotlSubtable::<F, 2, WORD_SIZE>::new()),
+            Box::new(RotlSubtable::<F, 3, WORD_SIZE>::new()),
+            Box::new(RotlSubtable::<F, 4, WORD_SIZE>::new()),
+            Box::new(RotlSubtable::<F, 5, WORD_SIZE>::new()),
+            Box::new(RotlSubtable::<F, 6, WORD_SIZE>::new()),
+            Box::new(RotlSubtable::<F, 7, WORD_SIZE>::new()),
+            Box::new(RotlSubtable::<F, 8, WORD_SIZE>::new()),
+            Box::new(RotlSubtable::<F, 9, WORD_SIZE>::new()),
+        ];
+        subtables.truncate(C);
+        subtables.reverse();
+
+        let indices = (0..C).map(SubtableIndices::from);
+        subtables.into_iter().zip(indices).collect()
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        assert_valid_parameters(WORD_SIZE, C, log_M);
+        chunk_and_concatenate_for_shift(self.0, self.1, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        // Like SLL, ROL only uses the last log2(WORD_SIZE) bits of y
+        let k = self.1 as u32 % WORD_SIZE as u32;
+        if k == 0 {
+            return self.0;
+        }
+        let word_mask = u64::MAX >> (64 - WORD_SIZE);
+        ((self.0 << k) | (self.0 >> (WORD_SIZE as u32 - k))) & word_mask
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        Self(rng.next_u32() as u64, rng.next_u32() as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::ROLInstruction;
+
+    #[test]
+    fn rol_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        for _ in 0..256 {
+            let (x, y) = (rng.next_u32(), rng.next_u32());
+            let instruction = ROLInstruction::<WORD_SIZE>(x as u64, y as u64);
+            jolt_instruction_test!(instruction);
+        }
+
+        let u32_max: u64 = u32::MAX as u64;
+        let instructions = vec![
+            ROLInstruction::<32>(100, 0),
+            ROLInstruction::<32>(0, 100),
+            ROLInstruction::<32>(1, 31),
+            ROLInstruction::<32>(1 << 31, 1),
+            ROLInstruction::<32>(u32_max, 0),
+            ROLInstruction::<32>(u32_max, u32_max),
+            ROLInstruction::<32>(0x12345678, 8),
+            ROLInstruction::<32>(0x12345678, 1 << 8),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
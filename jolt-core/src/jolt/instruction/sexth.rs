@@ -0,0 +1,100 @@
+use ark_std::log2;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
+use crate::jolt::subtable::{
+    left_msb::LeftMSBSubtable, left_operand::LeftOperandSubtable, LassoSubtable,
+};
+use crate::poly::field::JoltField;
+use crate::utils::instruction_utils::{assert_valid_parameters, chunk_and_concatenate_operands};
+
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(LeftOperandSubtable<F>, LeftMSBSubtable<F>)]
+pub struct SEXTHInstruction<const WORD_SIZE: usize>(pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for SEXTHInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, 0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], _: usize, _: usize) -> F {
+        assert!(vals.len() == 3);
+        let high_byte = vals[0];
+        let low_byte = vals[1];
+        let sign_bit = vals[2];
+
+        let sign_extension = F::from_u64((u64::MAX >> (64 - WORD_SIZE)) ^ 0xffff).unwrap();
+        F::from_u64(1 << 8).unwrap() * high_byte + low_byte + sign_bit * sign_extension
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        M: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        // The last two chunks' first operands must be exactly the lowest two bytes
+        assert_eq!(log2(M), 16);
+        vec![
+            (
+                Box::new(LeftOperandSubtable::new()),
+                SubtableIndices::from(C - 2..C),
+            ),
+            (
+                Box::new(LeftMSBSubtable::new()),
+                SubtableIndices::from(C - 2),
+            ),
+        ]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        assert_valid_parameters(WORD_SIZE, C, log_M);
+        chunk_and_concatenate_operands(self.0, 0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        (self.0 as i16 as u64) & (u64::MAX >> (64 - WORD_SIZE))
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        Self(rng.next_u32() as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::SEXTHInstruction;
+
+    #[test]
+    fn sexth_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        for _ in 0..256 {
+            let instruction = SEXTHInstruction::<WORD_SIZE>(rng.next_u32() as u64);
+            jolt_instruction_test!(instruction);
+        }
+        let instructions = vec![
+            SEXTHInstruction::<WORD_SIZE>(0),
+            SEXTHInstruction::<WORD_SIZE>(0x7fff),
+            SEXTHInstruction::<WORD_SIZE>(0x8000),
+            SEXTHInstruction::<WORD_SIZE>(u32::MAX as u64),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use ark_std::log2;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
+use crate::jolt::subtable::{
+    identity::IdentitySubtable, truncate_overflow::TruncateOverflowSubtable, LassoSubtable,
+};
+use crate::poly::field::JoltField;
+use crate::utils::instruction_utils::{
+    add_and_chunk_operands, assert_valid_parameters, concatenate_lookups,
+};
+
+/// SH1ADD, SH2ADD and SH3ADD: (x << SHIFT) + y. Like ADD, the lookup query is the
+/// untruncated sum, and the lookup truncates it to a word.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(TruncateOverflowSubtable<F, WORD_SIZE>, IdentitySubtable<F>)]
+pub struct SHADDInstruction<const WORD_SIZE: usize, const SHIFT: usize>(pub u64, pub u64);
+
+impl<const WORD_SIZE: usize, const SHIFT: usize> JoltInstruction
+    for SHADDInstruction<WORD_SIZE, SHIFT>
+{
+    fn operands(&self) -> (u64, u64) {
+        (self.0, self.1)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, M: usize) -> F {
+        assert!(vals.len() == C);
+        // The output is the TruncateOverflow(most significant chunk) || identity of other chunks
+        concatenate_lookups(vals, C, log2(M) as usize)
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        M: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        let msb_chunk_index = C - (WORD_SIZE / log2(M) as usize) - 1;
+        vec![
+            (
+                Box::new(TruncateOverflowSubtable::<F, WORD_SIZE>::new()),
+                SubtableIndices::from(0..msb_chunk_index + 1),
+            ),
+            (
+                Box::new(IdentitySubtable::new()),
+                SubtableIndices::from(msb_chunk_index + 1..C),
+            ),
+        ]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        assert_valid_parameters(WORD_SIZE, C, log_M);
+        add_and_chunk_operands((self.0 as u128) << SHIFT, self.1 as u128, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        ((self.0 << SHIFT).wrapping_add(self.1)) & (u64::MAX >> (64 - WORD_SIZE))
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        Self(rng.next_u32() as u64, rng.next_u32() as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::SHADDInstruction;
+
+    #[test]
+    fn shadd_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        for _ in 0..256 {
+            let (x, y) = (rng.next_u32() as u64, rng.next_u32() as u64);
+            let instruction = SHADDInstruction::<WORD_SIZE, 1>(x, y);
+            jolt_instruction_test!(instruction);
+        }
+        for _ in 0..256 {
+            let (x, y) = (rng.next_u32() as u64, rng.next_u32() as u64);
+            let instruction = SHADDInstruction::<WORD_SIZE, 2>(x, y);
+            jolt_instruction_test!(instruction);
+        }
+        for _ in 0..256 {
+            let (x, y) = (rng.next_u32() as u64, rng.next_u32() as u64);
+            let instruction = SHADDInstruction::<WORD_SIZE, 3>(x, y);
+            jolt_instruction_test!(instruction);
+        }
+        let u32_max: u64 = u32::MAX as u64;
+        let instructions = vec![
+            SHADDInstruction::<WORD_SIZE, 3>(0, 0),
+            SHADDInstruction::<WORD_SIZE, 3>(u32_max, 0),
+            SHADDInstruction::<WORD_SIZE, 3>(0, u32_max),
+            SHADDInstruction::<WORD_SIZE, 3>(u32_max, u32_max),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
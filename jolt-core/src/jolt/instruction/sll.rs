@@ -3,14 +3,26 @@ use rand::prelude::StdRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use super::{JoltInstruction, SubtableIndices};
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
 use crate::jolt::subtable::{sll::SllSubtable, LassoSubtable};
 use crate::poly::field::JoltField;
 use crate::utils::instruction_utils::{
     assert_valid_parameters, chunk_and_concatenate_for_shift, concatenate_lookups,
 };
 
-#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(
+    SllSubtable<F, 0, WORD_SIZE>,
+    SllSubtable<F, 1, WORD_SIZE>,
+    SllSubtable<F, 2, WORD_SIZE>,
+    SllSubtable<F, 3, WORD_SIZE>,
+    SllSubtable<F, 4, WORD_SIZE>,
+    SllSubtable<F, 5, WORD_SIZE>,
+    SllSubtable<F, 6, WORD_SIZE>,
+    SllSubtable<F, 7, WORD_SIZE>,
+    SllSubtable<F, 8, WORD_SIZE>,
+    SllSubtable<F, 9, WORD_SIZE>,
+)]
 pub struct SLLInstruction<const WORD_SIZE: usize>(pub u64, pub u64);
 
 impl<const WORD_SIZE: usize> JoltInstruction for SLLInstruction<WORD_SIZE> {
@@ -1,18 +1,27 @@
 use crate::poly::field::JoltField;
+use ark_std::log2;
 use rand::prelude::StdRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use super::{JoltInstruction, SubtableIndices};
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
 use crate::{
     jolt::subtable::{
         eq::EqSubtable, eq_abs::EqAbsSubtable, eq_msb::EqMSBSubtable, gt_msb::GtMSBSubtable,
         lt_abs::LtAbsSubtable, ltu::LtuSubtable, LassoSubtable,
     },
-    utils::instruction_utils::chunk_and_concatenate_operands,
+    utils::instruction_utils::{chunk_and_concatenate_operands, operand_msb_chunk_index},
 };
 
-#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(
+    GtMSBSubtable<F>,
+    EqMSBSubtable<F>,
+    LtuSubtable<F>,
+    EqSubtable<F>,
+    LtAbsSubtable<F>,
+    EqAbsSubtable<F>,
+)]
 pub struct SLTInstruction(pub u64, pub u64);
 
 impl JoltInstruction for SLTInstruction {
@@ -51,15 +60,34 @@ impl JoltInstruction for SLTInstruction {
     fn subtables<F: JoltField>(
         &self,
         C: usize,
-        _: usize,
+        M: usize,
     ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        let msb_chunk_index = operand_msb_chunk_index(32, C, log2(M) as usize);
         vec![
-            (Box::new(GtMSBSubtable::new()), SubtableIndices::from(0)),
-            (Box::new(EqMSBSubtable::new()), SubtableIndices::from(0)),
-            (Box::new(LtuSubtable::new()), SubtableIndices::from(1..C)),
-            (Box::new(EqSubtable::new()), SubtableIndices::from(1..C)),
-            (Box::new(LtAbsSubtable::new()), SubtableIndices::from(0)),
-            (Box::new(EqAbsSubtable::new()), SubtableIndices::from(0)),
+            (
+                Box::new(GtMSBSubtable::new()),
+                SubtableIndices::from(msb_chunk_index),
+            ),
+            (
+                Box::new(EqMSBSubtable::new()),
+                SubtableIndices::from(msb_chunk_index),
+            ),
+            (
+                Box::new(LtuSubtable::new()),
+                SubtableIndices::from(msb_chunk_index + 1..C),
+            ),
+            (
+                Box::new(EqSubtable::new()),
+                SubtableIndices::from(msb_chunk_index + 1..C),
+            ),
+            (
+                Box::new(LtAbsSubtable::new()),
+                SubtableIndices::from(msb_chunk_index),
+            ),
+            (
+                Box::new(EqAbsSubtable::new()),
+                SubtableIndices::from(msb_chunk_index),
+            ),
         ]
     }
 
@@ -113,4 +141,18 @@ mod test {
             jolt_instruction_test!(instruction);
         }
     }
+
+    #[test]
+    fn slt_instruction_32_with_8_chunks_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+
+        for _ in 0..256 {
+            let x = rng.next_u32() as u64;
+            let y = rng.next_u32() as u64;
+            let instruction = SLTInstruction(x, y);
+            jolt_instruction_test!(instruction);
+        }
+    }
 }
@@ -1,13 +1,29 @@
 use crate::poly::field::JoltField;
+use ark_std::log2;
 use rand::prelude::StdRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use super::{JoltInstruction, SubtableIndices};
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
 use crate::jolt::subtable::{sra_sign::SraSignSubtable, srl::SrlSubtable, LassoSubtable};
-use crate::utils::instruction_utils::{assert_valid_parameters, chunk_and_concatenate_for_shift};
-
-#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+use crate::utils::instruction_utils::{
+    assert_valid_parameters, chunk_and_concatenate_for_shift, operand_msb_chunk_index,
+};
+
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(
+    SrlSubtable<F, 0, WORD_SIZE>,
+    SrlSubtable<F, 1, WORD_SIZE>,
+    SrlSubtable<F, 2, WORD_SIZE>,
+    SrlSubtable<F, 3, WORD_SIZE>,
+    SrlSubtable<F, 4, WORD_SIZE>,
+    SrlSubtable<F, 5, WORD_SIZE>,
+    SrlSubtable<F, 6, WORD_SIZE>,
+    SrlSubtable<F, 7, WORD_SIZE>,
+    SrlSubtable<F, 8, WORD_SIZE>,
+    SrlSubtable<F, 9, WORD_SIZE>,
+    SraSignSubtable<F, WORD_SIZE>,
+)]
 pub struct SRAInstruction<const WORD_SIZE: usize>(pub u64, pub u64);
 
 impl<const WORD_SIZE: usize> JoltInstruction for SRAInstruction<WORD_SIZE> {
@@ -28,7 +44,7 @@ impl<const WORD_SIZE: usize> JoltInstruction for SRAInstruction<WORD_SIZE> {
     fn subtables<F: JoltField>(
         &self,
         C: usize,
-        _: usize,
+        M: usize,
     ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
         let mut subtables: Vec<Box<dyn LassoSubtable<F>>> = vec![
             Box::new(SrlSubtable::<F, 0, WORD_SIZE>::new()),
@@ -48,9 +64,10 @@ impl<const WORD_SIZE: usize> JoltInstruction for SRAInstruction<WORD_SIZE> {
         let mut subtables_and_indices: Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> =
             subtables.into_iter().zip(indices).collect();
 
+        // The sign bit is in the first chunk that isn't always zero
         subtables_and_indices.push((
             Box::new(SraSignSubtable::<F, WORD_SIZE>::new()),
-            SubtableIndices::from(0),
+            SubtableIndices::from(operand_msb_chunk_index(WORD_SIZE, C, log2(M) as usize)),
         ));
 
         subtables_and_indices
@@ -109,4 +126,19 @@ mod test {
             jolt_instruction_test!(instruction);
         }
     }
+
+    #[test]
+    fn sra_instruction_32_with_8_chunks_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        for _ in 0..256 {
+            let x = rng.next_u32() as u64;
+            let y = rng.next_u32() as u64;
+            let instruction = SRAInstruction::<WORD_SIZE>(x, y);
+            jolt_instruction_test!(instruction);
+        }
+    }
 }
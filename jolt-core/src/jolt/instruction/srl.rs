@@ -3,11 +3,23 @@ use rand::prelude::StdRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use super::{JoltInstruction, SubtableIndices};
+use super::{InstructionSubtables, JoltInstruction, SubtableIndices};
 use crate::jolt::subtable::{srl::SrlSubtable, LassoSubtable};
 use crate::utils::instruction_utils::{assert_valid_parameters, chunk_and_concatenate_for_shift};
 
-#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, InstructionSubtables)]
+#[subtables(
+    SrlSubtable<F, 0, WORD_SIZE>,
+    SrlSubtable<F, 1, WORD_SIZE>,
+    SrlSubtable<F, 2, WORD_SIZE>,
+    SrlSubtable<F, 3, WORD_SIZE>,
+    SrlSubtable<F, 4, WORD_SIZE>,
+    SrlSubtable<F, 5, WORD_SIZE>,
+    SrlSubtable<F, 6, WORD_SIZE>,
+    SrlSubtable<F, 7, WORD_SIZE>,
+    SrlSubtable<F, 8, WORD_SIZE>,
+    SrlSubtable<F, 9, WORD_SIZE>,
+)]
 pub struct SRLInstruction<const WORD_SIZE: usize>(pub u64, pub u64);
 
 impl<const WORD_SIZE: usize> JoltInstruction for SRLInstruction<WORD_SIZE> {
@@ -0,0 +1,62 @@
+use crate::poly::field::JoltField;
+use ark_std::log2;
+use std::marker::PhantomData;
+
+use super::LassoSubtable;
+use crate::utils::split_bits;
+
+/// The most significant bit of the first operand. The second operand is ignored.
+#[derive(Default)]
+pub struct LeftMSBSubtable<F: JoltField> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField> LeftMSBSubtable<F> {
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField> LassoSubtable<F> for LeftMSBSubtable<F> {
+    fn materialize(&self, M: usize) -> Vec<F> {
+        let mut entries: Vec<F> = Vec::with_capacity(M);
+        let bits_per_operand = (log2(M) / 2) as usize;
+        let high_bit = 1usize << (bits_per_operand - 1);
+
+        // Materialize table entries in order from 0..M
+        for idx in 0..M {
+            let (x, _) = split_bits(idx, bits_per_operand);
+            entries.push(if x & high_bit != 0 {
+                F::one()
+            } else {
+                F::zero()
+            });
+        }
+        entries
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // x_0
+        debug_assert!(point.len() % 2 == 0);
+        point[0]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+
+    use crate::{
+        jolt::subtable::{left_msb::LeftMSBSubtable, LassoSubtable},
+        subtable_materialize_mle_parity_test,
+    };
+
+    subtable_materialize_mle_parity_test!(
+        left_msb_materialize_mle_parity,
+        LeftMSBSubtable<Fr>,
+        Fr,
+        256
+    );
+}
@@ -0,0 +1,63 @@
+use crate::poly::field::JoltField;
+use ark_std::log2;
+use std::marker::PhantomData;
+
+use super::LassoSubtable;
+use crate::utils::split_bits;
+
+#[derive(Default)]
+pub struct LeftOperandSubtable<F: JoltField> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField> LeftOperandSubtable<F> {
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField> LassoSubtable<F> for LeftOperandSubtable<F> {
+    fn materialize(&self, M: usize) -> Vec<F> {
+        let mut entries: Vec<F> = Vec::with_capacity(M);
+        let bits_per_operand = (log2(M) / 2) as usize;
+
+        // Materialize table entries in order where (x | y) ranges 0..M
+        for idx in 0..M {
+            let (x, _) = split_bits(idx, bits_per_operand);
+            entries.push(F::from_u64(x as u64).unwrap());
+        }
+        entries
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // x
+        debug_assert!(point.len() % 2 == 0);
+        let b = point.len() / 2;
+        let x = &point[..b];
+
+        let mut result = F::zero();
+        for i in 0..b {
+            result += F::from_u64(1u64 << i).unwrap() * x[b - i - 1];
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+
+    use crate::{
+        jolt::subtable::{left_operand::LeftOperandSubtable, LassoSubtable},
+        subtable_materialize_mle_parity_test,
+    };
+
+    subtable_materialize_mle_parity_test!(
+        left_operand_materialize_mle_parity,
+        LeftOperandSubtable<Fr>,
+        Fr,
+        256
+    );
+}
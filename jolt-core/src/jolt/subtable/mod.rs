@@ -1,10 +1,10 @@
 use crate::poly::field::JoltField;
-use enum_dispatch::enum_dispatch;
 use std::any::TypeId;
 use std::marker::Sync;
 use strum::{EnumCount, IntoEnumIterator};
 
-#[enum_dispatch]
+pub use jolt_core_macros::JoltSubtableSet;
+
 pub trait LassoSubtable<F: JoltField>: 'static + Sync {
     /// Returns the TypeId of this subtable.
     /// The `Jolt` trait has associated enum types `InstructionSet` and `Subtables`.
@@ -21,27 +21,43 @@ pub trait LassoSubtable<F: JoltField>: 'static + Sync {
 }
 
 pub type SubtableId = TypeId;
+
+/// An enum of `LassoSubtable` types, usually implemented with `#[derive(JoltSubtableSet)]`.
 pub trait JoltSubtableSet<F: JoltField>:
-    LassoSubtable<F> + IntoEnumIterator + EnumCount + From<SubtableId> + Into<usize> + Send + Sync
+    LassoSubtable<F> + IntoEnumIterator + EnumCount + Send + Sync
 {
-    fn enum_index(subtable: Box<dyn LassoSubtable<F>>) -> usize {
-        Self::from(subtable.subtable_id()).into()
-    }
+}
+
+/// Implemented by a subtable set for each subtable type it contains; `#[derive(JoltSubtableSet)]`
+/// generates one impl per variant.
+pub trait ContainsSubtable<Subtable> {
+    /// The index of the variant wrapping `Subtable`.
+    const INDEX: usize;
 }
 
 pub mod and;
+pub mod andn;
 pub mod eq;
 pub mod eq_abs;
 pub mod eq_msb;
 pub mod gt_msb;
 pub mod identity;
+pub mod leading_zeros;
+pub mod left_msb;
+pub mod left_operand;
 pub mod lt_abs;
 pub mod ltu;
 pub mod or;
+pub mod orn;
+pub mod popcount;
+pub mod right_operand;
+pub mod rotl;
+pub mod rotr;
 pub mod sign_extend;
 pub mod sll;
 pub mod sra_sign;
 pub mod srl;
+pub mod trailing_zeros;
 pub mod truncate_overflow;
 pub mod xor;
 pub mod zero_lsb;
@@ -0,0 +1,58 @@
+use crate::poly::field::JoltField;
+use ark_std::log2;
+use std::marker::PhantomData;
+
+use super::LassoSubtable;
+use crate::utils::split_bits;
+
+/// The number of set bits in the first operand. The second operand is ignored.
+#[derive(Default)]
+pub struct PopcountSubtable<F: JoltField> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField> PopcountSubtable<F> {
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField> LassoSubtable<F> for PopcountSubtable<F> {
+    fn materialize(&self, M: usize) -> Vec<F> {
+        let mut entries: Vec<F> = Vec::with_capacity(M);
+        let bits_per_operand = (log2(M) / 2) as usize;
+
+        // Materialize table entries in order where (x | y) ranges 0..M
+        for idx in 0..M {
+            let (x, _) = split_bits(idx, bits_per_operand);
+            entries.push(F::from_u64(x.count_ones() as u64).unwrap());
+        }
+        entries
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // \sum_i x_i
+        debug_assert!(point.len() % 2 == 0);
+        let b = point.len() / 2;
+        point[..b].iter().sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+
+    use crate::{
+        jolt::subtable::{popcount::PopcountSubtable, LassoSubtable},
+        subtable_materialize_mle_parity_test,
+    };
+
+    subtable_materialize_mle_parity_test!(
+        popcount_materialize_mle_parity,
+        PopcountSubtable<Fr>,
+        Fr,
+        256
+    );
+}
@@ -0,0 +1,63 @@
+use crate::poly::field::JoltField;
+use ark_std::log2;
+use std::marker::PhantomData;
+
+use super::LassoSubtable;
+use crate::utils::split_bits;
+
+#[derive(Default)]
+pub struct RightOperandSubtable<F: JoltField> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField> RightOperandSubtable<F> {
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField> LassoSubtable<F> for RightOperandSubtable<F> {
+    fn materialize(&self, M: usize) -> Vec<F> {
+        let mut entries: Vec<F> = Vec::with_capacity(M);
+        let bits_per_operand = (log2(M) / 2) as usize;
+
+        // Materialize table entries in order where (x | y) ranges 0..M
+        for idx in 0..M {
+            let (_, y) = split_bits(idx, bits_per_operand);
+            entries.push(F::from_u64(y as u64).unwrap());
+        }
+        entries
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // y
+        debug_assert!(point.len() % 2 == 0);
+        let b = point.len() / 2;
+        let y = &point[b..];
+
+        let mut result = F::zero();
+        for i in 0..b {
+            result += F::from_u64(1u64 << i).unwrap() * y[b - i - 1];
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+
+    use crate::{
+        jolt::subtable::{right_operand::RightOperandSubtable, LassoSubtable},
+        subtable_materialize_mle_parity_test,
+    };
+
+    subtable_materialize_mle_parity_test!(
+        right_operand_materialize_mle_parity,
+        RightOperandSubtable<Fr>,
+        Fr,
+        256
+    );
+}
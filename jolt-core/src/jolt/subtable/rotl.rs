@@ -0,0 +1,110 @@
+use crate::poly::field::JoltField;
+use ark_std::log2;
+use std::cmp::min;
+use std::marker::PhantomData;
+
+use super::LassoSubtable;
+use crate::utils::math::Math;
+use crate::utils::split_bits;
+
+/// Rotates the `CHUNK_INDEX`-th chunk of a `WORD_SIZE`-bit word left by the second
+/// operand. Unlike `SllSubtable`, entries are not scaled down by the chunk's position,
+/// since the rotated bits may wrap around: an instruction sums the entries of all chunks.
+#[derive(Default)]
+pub struct RotlSubtable<F: JoltField, const CHUNK_INDEX: usize, const WORD_SIZE: usize> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField, const CHUNK_INDEX: usize, const WORD_SIZE: usize>
+    RotlSubtable<F, CHUNK_INDEX, WORD_SIZE>
+{
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField, const CHUNK_INDEX: usize, const WORD_SIZE: usize> LassoSubtable<F>
+    for RotlSubtable<F, CHUNK_INDEX, WORD_SIZE>
+{
+    fn materialize(&self, M: usize) -> Vec<F> {
+        let mut entries: Vec<F> = Vec::with_capacity(M);
+
+        let operand_chunk_width: usize = (log2(M) / 2) as usize;
+        let suffix_length = operand_chunk_width * CHUNK_INDEX;
+        let word_mask: u64 = u64::MAX >> (64 - WORD_SIZE);
+
+        for idx in 0..M {
+            let (x, y) = split_bits(idx, operand_chunk_width);
+            let k = (y % WORD_SIZE) as u32;
+
+            let x = (x as u64).checked_shl(suffix_length as u32).unwrap_or(0) & word_mask;
+            let row = (x.checked_shl(k).unwrap_or(0)
+                | x.checked_shr(WORD_SIZE as u32 - k).unwrap_or(0))
+                & word_mask;
+
+            entries.push(F::from_u64(row).unwrap());
+        }
+        entries
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // first half is chunk X_i
+        // and second half is always chunk Y_0
+        debug_assert!(point.len() % 2 == 0);
+
+        let log_WORD_SIZE = log2(WORD_SIZE) as usize;
+
+        let b = point.len() / 2;
+        let (x, y) = point.split_at(b);
+
+        let mut result = F::zero();
+
+        // min with 1 << b is included for test cases with subtables of bit-length smaller than 6
+        for k in 0..min(WORD_SIZE, 1 << b) {
+            let k_bits = k
+                .get_bits(log_WORD_SIZE)
+                .iter()
+                .map(|bit| F::from_u64(*bit as u64).unwrap())
+                .collect::<Vec<F>>(); // big-endian
+
+            let mut eq_term = F::one();
+            // again, min with b is included when subtables of bit-length less than 6 are used
+            for i in 0..min(log_WORD_SIZE, b) {
+                eq_term *= k_bits[log_WORD_SIZE - 1 - i] * y[b - 1 - i]
+                    + (F::one() - k_bits[log_WORD_SIZE - 1 - i]) * (F::one() - y[b - 1 - i]);
+            }
+
+            // Bit j of the chunk is bit `position` of the word, which moves to bit `destination`
+            let mut rotate_x_by_k = F::zero();
+            for j in 0..b {
+                let position = b * CHUNK_INDEX + j;
+                if position >= WORD_SIZE {
+                    break;
+                }
+                let destination = (position + k) % WORD_SIZE;
+                rotate_x_by_k += F::from_u64(1 << destination).unwrap() * x[b - 1 - j];
+            }
+
+            result += eq_term * rotate_x_by_k;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+
+    use crate::{
+        jolt::subtable::{rotl::RotlSubtable, LassoSubtable},
+        subtable_materialize_mle_parity_test,
+    };
+
+    subtable_materialize_mle_parity_test!(rotl_materialize_mle_parity0, RotlSubtable<Fr, 0, 32>, Fr, 1 << 10);
+    subtable_materialize_mle_parity_test!(rotl_materialize_mle_parity1, RotlSubtable<Fr, 1, 32>, Fr, 1 << 10);
+    subtable_materialize_mle_parity_test!(rotl_materialize_mle_parity2, RotlSubtable<Fr, 2, 32>, Fr, 1 << 10);
+    subtable_materialize_mle_parity_test!(rotl_materialize_mle_parity3, RotlSubtable<Fr, 3, 32>, Fr, 1 << 10);
+    subtable_materialize_mle_parity_test!(rotl_materialize_mle_parity7, RotlSubtable<Fr, 7, 32>, Fr, 1 << 10);
+}
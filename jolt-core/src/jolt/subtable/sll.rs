@@ -105,4 +105,5 @@ mod test {
     subtable_materialize_mle_parity_test!(sll_materialize_mle_parity1, SllSubtable<Fr, 1, 32>, Fr, 1 << 10);
     subtable_materialize_mle_parity_test!(sll_materialize_mle_parity2, SllSubtable<Fr, 2, 32>, Fr, 1 << 10);
     subtable_materialize_mle_parity_test!(sll_materialize_mle_parity3, SllSubtable<Fr, 3, 32>, Fr, 1 << 10);
+    subtable_materialize_mle_parity_test!(sll_materialize_mle_parity7, SllSubtable<Fr, 7, 32>, Fr, 1 << 10);
 }
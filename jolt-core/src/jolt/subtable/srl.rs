@@ -29,13 +29,12 @@ impl<F: JoltField, const CHUNK_INDEX: usize, const WORD_SIZE: usize> LassoSubtab
 
         let operand_chunk_width: usize = (log2(M) / 2) as usize;
         let suffix_length = operand_chunk_width * CHUNK_INDEX;
+        let word_mask: u64 = u64::MAX >> (64 - WORD_SIZE);
 
         for idx in 0..M {
             let (x, y) = split_bits(idx, operand_chunk_width);
 
-            let row = x
-                .checked_shl(suffix_length as u32)
-                .unwrap_or(0)
+            let row = ((x as u64).checked_shl(suffix_length as u32).unwrap_or(0) & word_mask)
                 .checked_shr((y % WORD_SIZE) as u32)
                 .unwrap_or(0);
 
@@ -77,12 +76,8 @@ impl<F: JoltField, const CHUNK_INDEX: usize, const WORD_SIZE: usize> LassoSubtab
                 0
             };
 
-            // the most significant chunk might be shorter
-            let chunk_length = if (b * (CHUNK_INDEX + 1)) > WORD_SIZE {
-                b - ((b * (CHUNK_INDEX + 1)) - WORD_SIZE)
-            } else {
-                b
-            };
+            // the most significant chunk might be shorter, and chunks past the word are empty
+            let chunk_length = std::cmp::min(b, WORD_SIZE.saturating_sub(b * CHUNK_INDEX));
 
             let shift_x_by_k = (m..chunk_length)
                 .enumerate()
@@ -110,4 +105,5 @@ mod test {
     subtable_materialize_mle_parity_test!(srl_materialize_mle_parity1, SrlSubtable<Fr, 1, 32>, Fr, 1 << 10);
     subtable_materialize_mle_parity_test!(srl_materialize_mle_parity2, SrlSubtable<Fr, 2, 32>, Fr, 1 << 10);
     subtable_materialize_mle_parity_test!(srl_materialize_mle_parity3, SrlSubtable<Fr, 3, 32>, Fr, 1 << 10);
+    subtable_materialize_mle_parity_test!(srl_materialize_mle_parity7, SrlSubtable<Fr, 7, 32>, Fr, 1 << 10);
 }
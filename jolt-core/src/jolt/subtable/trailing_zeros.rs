@@ -0,0 +1,69 @@
+use crate::poly::field::JoltField;
+use ark_std::log2;
+use std::marker::PhantomData;
+
+use super::LassoSubtable;
+use crate::utils::split_bits;
+
+/// The number of trailing zeros of the first operand, as a `bits_per_operand`-bit value.
+/// The second operand is ignored.
+#[derive(Default)]
+pub struct TrailingZerosSubtable<F: JoltField> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField> TrailingZerosSubtable<F> {
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField> LassoSubtable<F> for TrailingZerosSubtable<F> {
+    fn materialize(&self, M: usize) -> Vec<F> {
+        let mut entries: Vec<F> = Vec::with_capacity(M);
+        let bits_per_operand = (log2(M) / 2) as usize;
+
+        // Materialize table entries in order where (x | y) ranges 0..M
+        for idx in 0..M {
+            let (x, _) = split_bits(idx, bits_per_operand);
+            let row = (x as u64).trailing_zeros().min(bits_per_operand as u32);
+            entries.push(F::from_u64(row as u64).unwrap());
+        }
+        entries
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // The number of trailing zeros is the number of all-zero suffixes of x:
+        // \sum_{k=1}^{b} \prod_{j < k} (1 - x_{b - 1 - j})
+        debug_assert!(point.len() % 2 == 0);
+        let b = point.len() / 2;
+        let x = &point[..b];
+
+        let mut result = F::zero();
+        let mut suffix_is_zero = F::one();
+        for x_j in x.iter().rev() {
+            suffix_is_zero *= F::one() - x_j;
+            result += suffix_is_zero;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+
+    use crate::{
+        jolt::subtable::{trailing_zeros::TrailingZerosSubtable, LassoSubtable},
+        subtable_materialize_mle_parity_test,
+    };
+
+    subtable_materialize_mle_parity_test!(
+        trailing_zeros_materialize_mle_parity,
+        TrailingZerosSubtable<Fr>,
+        Fr,
+        256
+    );
+}
@@ -1,16 +1,33 @@
+use crate::jolt::instruction::advice::ADVICEInstruction;
 use crate::jolt::instruction::and::ANDInstruction;
+use crate::jolt::instruction::andn::ANDNInstruction;
 use crate::jolt::instruction::beq::BEQInstruction;
 use crate::jolt::instruction::bge::BGEInstruction;
 use crate::jolt::instruction::bgeu::BGEUInstruction;
 use crate::jolt::instruction::bne::BNEInstruction;
+use crate::jolt::instruction::clz::CLZInstruction;
+use crate::jolt::instruction::cpop::CPOPInstruction;
+use crate::jolt::instruction::ctz::CTZInstruction;
 use crate::jolt::instruction::lb::LBInstruction;
 use crate::jolt::instruction::lh::LHInstruction;
+use crate::jolt::instruction::max::MAXInstruction;
+use crate::jolt::instruction::maxu::MAXUInstruction;
+use crate::jolt::instruction::min::MINInstruction;
+use crate::jolt::instruction::minu::MINUInstruction;
 use crate::jolt::instruction::mul::MULInstruction;
 use crate::jolt::instruction::mulhu::MULHUInstruction;
 use crate::jolt::instruction::mulu::MULUInstruction;
 use crate::jolt::instruction::or::ORInstruction;
+use crate::jolt::instruction::orcb::ORCBInstruction;
+use crate::jolt::instruction::orn::ORNInstruction;
+use crate::jolt::instruction::rev8::REV8Instruction;
+use crate::jolt::instruction::rol::ROLInstruction;
+use crate::jolt::instruction::ror::RORInstruction;
 use crate::jolt::instruction::sb::SBInstruction;
+use crate::jolt::instruction::sextb::SEXTBInstruction;
+use crate::jolt::instruction::sexth::SEXTHInstruction;
 use crate::jolt::instruction::sh::SHInstruction;
+use crate::jolt::instruction::shadd::SHADDInstruction;
 use crate::jolt::instruction::sll::SLLInstruction;
 use crate::jolt::instruction::slt::SLTInstruction;
 use crate::jolt::instruction::sltu::SLTUInstruction;
@@ -18,6 +35,7 @@ use crate::jolt::instruction::sra::SRAInstruction;
 use crate::jolt::instruction::srl::SRLInstruction;
 use crate::jolt::instruction::sub::SUBInstruction;
 use crate::jolt::instruction::sw::SWInstruction;
+use crate::jolt::instruction::xnor::XNORInstruction;
 use crate::jolt::instruction::xor::XORInstruction;
 use crate::jolt::instruction::{add::ADDInstruction, movsign::MOVSIGNInstruction};
 use crate::jolt::vm::rv32i_vm::RV32I;
@@ -75,11 +93,33 @@ impl TryFrom<&ELFInstruction> for RV32I {
             RV32IM::MULU => Ok(MULUInstruction::default().into()),
             RV32IM::MULHU => Ok(MULHUInstruction::default().into()),
 
-            RV32IM::VIRTUAL_ADVICE => todo!(),
+            RV32IM::ANDN   => Ok(ANDNInstruction::default().into()),
+            RV32IM::ORN    => Ok(ORNInstruction::default().into()),
+            RV32IM::XNOR   => Ok(XNORInstruction::default().into()),
+            RV32IM::CLZ    => Ok(CLZInstruction::default().into()),
+            RV32IM::CTZ    => Ok(CTZInstruction::default().into()),
+            RV32IM::CPOP   => Ok(CPOPInstruction::default().into()),
+            RV32IM::SEXT_B => Ok(SEXTBInstruction::default().into()),
+            RV32IM::SEXT_H => Ok(SEXTHInstruction::default().into()),
+            RV32IM::ZEXT_H => Ok(ANDInstruction::default().into()),
+            RV32IM::MAX    => Ok(MAXInstruction::default().into()),
+            RV32IM::MAXU   => Ok(MAXUInstruction::default().into()),
+            RV32IM::MIN    => Ok(MINInstruction::default().into()),
+            RV32IM::MINU   => Ok(MINUInstruction::default().into()),
+            RV32IM::ROL    => Ok(ROLInstruction::default().into()),
+            RV32IM::ROR    => Ok(RORInstruction::default().into()),
+            RV32IM::RORI   => Ok(RORInstruction::default().into()),
+            RV32IM::ORC_B  => Ok(ORCBInstruction::default().into()),
+            RV32IM::REV8   => Ok(REV8Instruction::default().into()),
+            RV32IM::SH1ADD => Ok(SHADDInstruction::<32, 1>::default().into()),
+            RV32IM::SH2ADD => Ok(SHADDInstruction::<32, 2>::default().into()),
+            RV32IM::SH3ADD => Ok(SHADDInstruction::<32, 3>::default().into()),
+
+            RV32IM::VIRTUAL_ADVICE => Ok(ADVICEInstruction::default().into()),
             RV32IM::VIRTUAL_MOVSIGN => Ok(MOVSIGNInstruction::default().into()),
-            RV32IM::VIRTUAL_ASSERT_EQ => todo!(),
+            RV32IM::VIRTUAL_ASSERT_EQ => Ok(BEQInstruction::default().into()),
             RV32IM::VIRTUAL_ASSERT_LTE => todo!(),
-            RV32IM::VIRTUAL_ASSERT_LTU => todo!(),
+            RV32IM::VIRTUAL_ASSERT_LTU => Ok(SLTUInstruction::default().into()),
             RV32IM::VIRTUAL_ASSERT_LT_ABS => todo!(),
             RV32IM::VIRTUAL_ASSERT_EQ_SIGNS => todo!(),
 
@@ -140,11 +180,33 @@ impl TryFrom<&RVTraceRow> for RV32I {
             RV32IM::MULU => Ok(MULUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
             RV32IM::MULHU => Ok(MULHUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
 
-            RV32IM::VIRTUAL_ADVICE => todo!(),
+            RV32IM::ANDN   => Ok(ANDNInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+            RV32IM::ORN    => Ok(ORNInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+            RV32IM::XNOR   => Ok(XNORInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+            RV32IM::CLZ    => Ok(CLZInstruction(row.register_state.rs1_val.unwrap()).into()),
+            RV32IM::CTZ    => Ok(CTZInstruction(row.register_state.rs1_val.unwrap()).into()),
+            RV32IM::CPOP   => Ok(CPOPInstruction(row.register_state.rs1_val.unwrap()).into()),
+            RV32IM::SEXT_B => Ok(SEXTBInstruction(row.register_state.rs1_val.unwrap()).into()),
+            RV32IM::SEXT_H => Ok(SEXTHInstruction(row.register_state.rs1_val.unwrap()).into()),
+            RV32IM::ZEXT_H => Ok(ANDInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
+            RV32IM::MAX    => Ok(MAXInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+            RV32IM::MAXU   => Ok(MAXUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+            RV32IM::MIN    => Ok(MINInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+            RV32IM::MINU   => Ok(MINUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+            RV32IM::ROL    => Ok(ROLInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+            RV32IM::ROR    => Ok(RORInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+            RV32IM::RORI   => Ok(RORInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
+            RV32IM::ORC_B  => Ok(ORCBInstruction(row.register_state.rs1_val.unwrap()).into()),
+            RV32IM::REV8   => Ok(REV8Instruction(row.register_state.rs1_val.unwrap()).into()),
+            RV32IM::SH1ADD => Ok(SHADDInstruction::<32, 1>(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+            RV32IM::SH2ADD => Ok(SHADDInstruction::<32, 2>(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+            RV32IM::SH3ADD => Ok(SHADDInstruction::<32, 3>(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+
+            RV32IM::VIRTUAL_ADVICE => Ok(ADVICEInstruction(row.register_state.rd_post_val.unwrap()).into()),
             RV32IM::VIRTUAL_MOVSIGN => Ok(MOVSIGNInstruction(row.register_state.rs1_val.unwrap()).into()),
-            RV32IM::VIRTUAL_ASSERT_EQ => todo!(),
+            RV32IM::VIRTUAL_ASSERT_EQ => Ok(BEQInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
             RV32IM::VIRTUAL_ASSERT_LTE => todo!(),
-            RV32IM::VIRTUAL_ASSERT_LTU => todo!(),
+            RV32IM::VIRTUAL_ASSERT_LTU => Ok(SLTUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
             RV32IM::VIRTUAL_ASSERT_LT_ABS => todo!(),
             RV32IM::VIRTUAL_ASSERT_EQ_SIGNS => todo!(),
 
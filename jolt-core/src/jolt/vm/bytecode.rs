@@ -19,11 +19,15 @@ use rayon::prelude::*;
 use crate::{
     lasso::memory_checking::{MemoryCheckingProof, MemoryCheckingProver, MemoryCheckingVerifier},
     poly::{
+        compact_polynomial::{CompactPolynomial, Padding},
         dense_mlpoly::DensePolynomial,
         identity_poly::IdentityPolynomial,
+        multilinear_polynomial::MultilinearPolynomial,
+        opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator},
         structured_poly::{StructuredCommitment, StructuredOpeningProof},
     },
-    utils::errors::ProofVerifyError,
+    subprotocols::grand_product_quarks::{QuarkGrandProduct, DEFAULT_NUM_GKR_LAYERS},
+    utils::{errors::ProofVerifyError, math::Math},
 };
 
 use super::JoltTraceStep;
@@ -36,11 +40,18 @@ pub type BytecodeProof<F, C> = MemoryCheckingProof<
     BytecodeInitFinalOpenings<F>,
 >;
 
+/// The number of bits used to encode an instruction's opcode in `BytecodeRow::bitflags`.
+/// Opcodes range over `0..=instruction_count`, since opcode 0 is reserved for instructions
+/// that do not perform a lookup.
+pub const fn opcode_bits(instruction_count: usize) -> usize {
+    (usize::BITS - instruction_count.leading_zeros()) as usize
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BytecodeRow {
     /// Memory address as read from the ELF.
     address: usize,
-    /// Packed instruction/circuit flags, used for r1cs
+    /// Packed circuit flags and opcode, used for r1cs. See `BytecodeRow::bitflags`.
     pub bitflags: u64,
     /// Index of the destination register for this instruction (0 if register is unused).
     rd: u64,
@@ -64,6 +75,16 @@ impl BytecodeRow {
         }
     }
 
+    /// Returns the ELF address of this row once `BytecodePolynomialsBuilder::push_steps` has
+    /// compressed `address`, or 0 for no-op padding.
+    pub fn elf_address(&self) -> u64 {
+        if self.address == 0 {
+            0
+        } else {
+            RAM_START_ADDRESS + ((self.address - 1) * BYTES_PER_INSTRUCTION) as u64
+        }
+    }
+
     pub fn no_op(address: usize) -> Self {
         Self {
             address,
@@ -78,7 +99,7 @@ impl BytecodeRow {
     pub fn random(index: usize, rng: &mut StdRng) -> Self {
         Self {
             address: to_ram_address(index),
-            bitflags: rng.next_u32() as u64, // Roughly how many flags there are
+            bitflags: rng.next_u32() as u64, // Roughly how many bits the packed flags use
             rd: rng.next_u64() % REGISTER_COUNT,
             rs1: rng.next_u64() % REGISTER_COUNT,
             rs2: rng.next_u64() % REGISTER_COUNT,
@@ -86,32 +107,30 @@ impl BytecodeRow {
         }
     }
 
-    /// Packs the instruction's circuit flags and instruction flags into a single u64 bitvector.
+    /// Packs the instruction's circuit flags and opcode into a single u64 bitvector.
     /// The layout is:
-    ///     circuit flags || instruction flags
-    /// where instruction flags is a one-hot bitvector corresponding to the instruction's
-    /// index in the `InstructionSet` enum.
+    ///     circuit flags || opcode
+    /// where the opcode is one plus the instruction's index in the `InstructionSet` enum,
+    /// or zero if the instruction does not perform a lookup. The opcode occupies
+    /// `opcode_bits(InstructionSet::COUNT)` bits, so the packed value grows only
+    /// logarithmically with the size of the instruction set. The R1CS constraints expand
+    /// the opcode into one-hot instruction flags.
     pub fn bitflags<InstructionSet>(instruction: &ELFInstruction) -> u64
     where
         InstructionSet: JoltInstructionSet,
     {
         let mut bitvector = 0;
         for flag in instruction.to_circuit_flags() {
-            bitvector |= flag as u64;
             bitvector <<= 1;
+            bitvector |= flag as u64;
         }
 
-        // instruction flag
-        if let Ok(jolt_instruction) = InstructionSet::try_from(instruction) {
-            let instruction_index = InstructionSet::enum_index(&jolt_instruction);
-            bitvector <<= instruction_index;
-            bitvector |= 1;
-            bitvector <<= InstructionSet::COUNT - instruction_index - 1;
-        } else {
-            bitvector <<= InstructionSet::COUNT - 1;
-        }
+        let opcode = match InstructionSet::try_from(instruction) {
+            Ok(jolt_instruction) => InstructionSet::enum_index(&jolt_instruction) as u64 + 1,
+            Err(_) => 0,
+        };
 
-        bitvector
+        (bitvector << opcode_bits(InstructionSet::COUNT)) | opcode
     }
 
     pub fn from_instruction<InstructionSet>(instruction: &ELFInstruction) -> Self
@@ -141,19 +160,20 @@ pub fn random_bytecode_trace(
     trace
 }
 
+#[derive(CanonicalSerialize, CanonicalDeserialize)]
 pub struct BytecodePolynomials<F: JoltField, C: CommitmentScheme<Field = F>> {
     _group: PhantomData<C>,
     /// MLE of read/write addresses. For offline memory checking, each read is paired with a "virtual" write,
     /// so the read addresses and write addresses are the same.
-    pub(super) a_read_write: DensePolynomial<F>,
+    pub(super) a_read_write: CompactPolynomial<u32, F>,
     /// MLE of read/write values. For offline memory checking, each read is paired with a "virtual" write,
     /// so the read values and write values are the same. There are six values (address, bitflags, rd, rs1, rs2, imm)
     /// associated with each memory address, so `v_read_write` comprises five polynomials.
-    pub(super) v_read_write: [DensePolynomial<F>; 6],
+    pub(super) v_read_write: [CompactPolynomial<u64, F>; 6],
     /// MLE of the read timestamps.
-    pub(super) t_read: DensePolynomial<F>,
+    pub(super) t_read: CompactPolynomial<u32, F>,
     /// MLE of the final timestamps.
-    pub(super) t_final: DensePolynomial<F>,
+    pub(super) t_final: CompactPolynomial<u32, F>,
 }
 
 #[derive(Clone)]
@@ -229,19 +249,38 @@ impl<F: JoltField> BytecodePreprocessing<F> {
     }
 }
 
-impl<F: JoltField, C: CommitmentScheme<Field = F>> BytecodePolynomials<F, C> {
-    #[tracing::instrument(skip_all, name = "BytecodePolynomials::new")]
-    pub fn new<InstructionSet: JoltInstructionSet>(
-        preprocessing: &BytecodePreprocessing<F>,
-        trace: &mut Vec<JoltTraceStep<InstructionSet>>,
-    ) -> Self {
-        let num_ops = trace.len();
+/// Builds the `BytecodePolynomials` of a trace from chunks of its steps, as they are
+/// produced. See `BytecodePolynomials::new`.
+pub struct BytecodePolynomialsBuilder<'a, F: JoltField, C: CommitmentScheme<Field = F>> {
+    _group: PhantomData<C>,
+    preprocessing: &'a BytecodePreprocessing<F>,
+    a_read_write: Vec<u32>,
+    read_cts: Vec<u32>,
+    final_cts: Vec<usize>,
+    /// The (address, bitflags, rd, rs1, rs2, imm) read at each step.
+    v_read_write: [Vec<u64>; 6],
+}
 
-        let mut a_read_write_usize: Vec<usize> = vec![0; num_ops];
-        let mut read_cts: Vec<usize> = vec![0; num_ops];
-        let mut final_cts: Vec<usize> = vec![0; preprocessing.code_size];
+impl<'a, F: JoltField, C: CommitmentScheme<Field = F>> BytecodePolynomialsBuilder<'a, F, C> {
+    pub fn new(preprocessing: &'a BytecodePreprocessing<F>) -> Self {
+        Self {
+            _group: PhantomData,
+            preprocessing,
+            a_read_write: Vec::new(),
+            read_cts: Vec::new(),
+            final_cts: vec![0; preprocessing.code_size],
+            v_read_write: std::array::from_fn(|_| Vec::new()),
+        }
+    }
 
-        for (step_index, step) in trace.iter_mut().enumerate() {
+    /// Reads the bytecode rows of the next `steps` of the trace, compressing their addresses
+    /// as in `BytecodePreprocessing::preprocess`.
+    #[tracing::instrument(skip_all, name = "BytecodePolynomialsBuilder::push_steps")]
+    pub fn push_steps<InstructionSet: JoltInstructionSet>(
+        &mut self,
+        steps: &mut [JoltTraceStep<InstructionSet>],
+    ) {
+        for step in steps.iter_mut() {
             if !step.bytecode_row.address.is_zero() {
                 assert!(step.bytecode_row.address >= RAM_START_ADDRESS as usize);
                 assert!(step.bytecode_row.address % BYTES_PER_INSTRUCTION == 0);
@@ -250,47 +289,67 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> BytecodePolynomials<F, C> {
                     + (step.bytecode_row.address - RAM_START_ADDRESS as usize)
                         / BYTES_PER_INSTRUCTION;
             }
+        }
 
-            let virtual_address = preprocessing
+        for step in steps.iter() {
+            let virtual_address = *self
+                .preprocessing
                 .virtual_address_map
                 .get(&step.bytecode_row.address)
                 .unwrap();
-            a_read_write_usize[step_index] = *virtual_address;
-            let counter = final_cts[*virtual_address];
-            read_cts[step_index] = counter;
-            final_cts[*virtual_address] = counter + 1;
-        }
-
-        let a_read_write = DensePolynomial::from_usize(&a_read_write_usize);
-
-        let mut address = vec![];
-        let mut bitflags = vec![];
-        let mut rd = vec![];
-        let mut rs1 = vec![];
-        let mut rs2 = vec![];
-        let mut imm = vec![];
-
-        for step in trace {
-            address.push(F::from_u64(step.bytecode_row.address as u64).unwrap());
-            bitflags.push(F::from_u64(step.bytecode_row.bitflags).unwrap());
-            rd.push(F::from_u64(step.bytecode_row.rd).unwrap());
-            rs1.push(F::from_u64(step.bytecode_row.rs1).unwrap());
-            rs2.push(F::from_u64(step.bytecode_row.rs2).unwrap());
-            imm.push(F::from_u64(step.bytecode_row.imm).unwrap());
+            self.a_read_write.push(virtual_address as u32);
+            self.read_cts.push(self.final_cts[virtual_address] as u32);
+            self.final_cts[virtual_address] += 1;
+
+            let bytecode_row = &step.bytecode_row;
+            let [address, bitflags, rd, rs1, rs2, imm] = &mut self.v_read_write;
+            address.push(bytecode_row.address as u64);
+            bitflags.push(bytecode_row.bitflags);
+            rd.push(bytecode_row.rd);
+            rs1.push(bytecode_row.rs1);
+            rs2.push(bytecode_row.rs2);
+            imm.push(bytecode_row.imm);
         }
+    }
 
+    /// Builds the polynomials once the whole trace has been pushed.
+    pub fn finish(self) -> BytecodePolynomials<F, C> {
+        let Self {
+            preprocessing,
+            a_read_write,
+            read_cts,
+            mut final_cts,
+            v_read_write: [address, bitflags, rd, rs1, rs2, imm],
+            ..
+        } = self;
+        let num_ops = a_read_write.len();
+        let padded_num_ops = num_ops.next_power_of_two();
+
+        // The trace is implicitly padded to a power of two with no-op rows. Their values are
+        // the padding of each polynomial, so they are never stored: every no-op row reads the
+        // no-op's virtual address, whose counter keeps counting up.
+        let no_op = BytecodeRow::no_op(0);
+        let no_op_address = *preprocessing
+            .virtual_address_map
+            .get(&no_op.address)
+            .unwrap();
+        let no_op_read_cts = Padding::Counter(final_cts[no_op_address] as u32);
+        final_cts[no_op_address] += padded_num_ops - num_ops;
+
+        let a_read_write =
+            CompactPolynomial::padded(a_read_write, Padding::Constant(no_op_address as u32));
         let v_read_write = [
-            DensePolynomial::new(address),
-            DensePolynomial::new(bitflags),
-            DensePolynomial::new(rd),
-            DensePolynomial::new(rs1),
-            DensePolynomial::new(rs2),
-            DensePolynomial::new(imm),
+            CompactPolynomial::padded(address, Padding::Constant(no_op.address as u64)),
+            CompactPolynomial::padded(bitflags, Padding::Constant(no_op.bitflags)),
+            CompactPolynomial::padded(rd, Padding::Constant(no_op.rd)),
+            CompactPolynomial::padded(rs1, Padding::Constant(no_op.rs1)),
+            CompactPolynomial::padded(rs2, Padding::Constant(no_op.rs2)),
+            CompactPolynomial::padded(imm, Padding::Constant(no_op.imm)),
         ];
-        let t_read = DensePolynomial::from_usize(&read_cts);
-        let t_final = DensePolynomial::from_usize(&final_cts);
+        let t_read = CompactPolynomial::padded(read_cts, no_op_read_cts);
+        let t_final = CompactPolynomial::from_usize(&final_cts);
 
-        Self {
+        BytecodePolynomials {
             _group: PhantomData,
             a_read_write,
             v_read_write,
@@ -298,12 +357,26 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> BytecodePolynomials<F, C> {
             t_final,
         }
     }
+}
 
+impl<F: JoltField, C: CommitmentScheme<Field = F>> BytecodePolynomials<F, C> {
+    #[tracing::instrument(skip_all, name = "BytecodePolynomials::new")]
+    pub fn new<InstructionSet: JoltInstructionSet>(
+        preprocessing: &BytecodePreprocessing<F>,
+        trace: &mut [JoltTraceStep<InstructionSet>],
+    ) -> Self {
+        let mut builder = BytecodePolynomialsBuilder::new(preprocessing);
+        builder.push_steps(trace);
+        builder.finish()
+    }
+
+    /// Returns the first `num_steps` rows of the polynomials that are inputs to the R1CS,
+    /// along with their values at later (padding) steps. See `CompactPolynomial::flatten`.
     #[tracing::instrument(skip_all, name = "BytecodePolynomials::get_polys_r1cs")]
-    pub fn get_polys_r1cs(&self) -> (Vec<F>, Vec<F>) {
+    pub fn get_polys_r1cs(&self, num_steps: usize) -> ((Vec<u64>, Vec<u64>), (Vec<u64>, Vec<u64>)) {
         let (a_read_write, v_read_write) = rayon::join(
-            || self.a_read_write.evals(),
-            || DensePolynomial::flatten(&self.v_read_write),
+            || CompactPolynomial::flatten([&self.a_read_write], num_steps),
+            || CompactPolynomial::flatten(&self.v_read_write, num_steps),
         );
 
         (a_read_write, v_read_write)
@@ -339,7 +412,13 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> BytecodePolynomials<F, C> {
         // t_final
         let init_final_gen_shape = CommitShape::new(max_bytecode_size, BatchType::Small);
 
-        vec![read_write_gen_shape, init_final_gen_shape]
+        // Partial products of the read/write Quarks grand product
+        let quark_gen_shape = CommitShape::new(
+            std::cmp::max(max_trace_length >> DEFAULT_NUM_GKR_LAYERS, 2),
+            BatchType::Small,
+        );
+
+        vec![read_write_gen_shape, init_final_gen_shape, quark_gen_shape]
     }
 }
 
@@ -371,19 +450,24 @@ where
 
     #[tracing::instrument(skip_all, name = "BytecodePolynomials::commit")]
     fn commit(&self, generators: &C::Setup) -> Self::Commitment {
-        let trace_polys = vec![
-            &self.a_read_write,
-            &self.t_read, // t_read isn't used in r1cs, but it's cleaner to commit to it as a rectangular matrix alongside everything else
-            &self.v_read_write[0],
-            &self.v_read_write[1],
-            &self.v_read_write[2],
-            &self.v_read_write[3],
-            &self.v_read_write[4],
-            &self.v_read_write[5],
-        ];
-        let trace_commitments = C::batch_commit_polys_ref(&trace_polys, generators, BatchType::Big);
+        let mut trace_commitments = C::batch_commit_compact(
+            &[
+                &self.a_read_write,
+                &self.t_read, // t_read isn't used in r1cs, but it's cleaner to commit to it as a rectangular matrix alongside everything else
+            ],
+            generators,
+            BatchType::Big,
+        );
+        trace_commitments.extend(C::batch_commit_compact(
+            &self.v_read_write.iter().collect::<Vec<_>>(),
+            generators,
+            BatchType::Big,
+        ));
 
-        let t_final_commitment = C::commit(&self.t_final, generators);
+        let t_final_commitment =
+            C::batch_commit_compact(&[&self.t_final], generators, BatchType::Small)
+                .pop()
+                .unwrap();
 
         Self::Commitment {
             trace_commitments,
@@ -397,6 +481,9 @@ where
     F: JoltField,
     C: CommitmentScheme<Field = F>,
 {
+    /// The read/write circuits are as deep as the trace is long, so proving their top layers
+    /// with Quarks saves the verifier most of the GKR rounds.
+    type ReadWriteGrandProduct = QuarkGrandProduct<F, C>;
     type Preprocessing = BytecodePreprocessing<F>;
     type ReadWriteOpenings = BytecodeReadWriteOpenings<F>;
     type InitFinalOpenings = BytecodeInitFinalOpenings<F>;
@@ -422,27 +509,43 @@ where
         tau: &F,
     ) -> (Vec<Vec<F>>, Vec<Vec<F>>) {
         let num_ops = polynomials.a_read_write.len();
+        let num_stored_ops = polynomials.t_read.coeffs().len();
         let bytecode_size = preprocessing.v_init_final[0].len();
 
-        let read_leaves = (0..num_ops)
+        let read_fingerprint = |i: usize| {
+            Self::fingerprint(
+                &[
+                    polynomials.a_read_write.get_coeff(i),
+                    polynomials.v_read_write[0].get_coeff(i),
+                    polynomials.v_read_write[1].get_coeff(i),
+                    polynomials.v_read_write[2].get_coeff(i),
+                    polynomials.v_read_write[3].get_coeff(i),
+                    polynomials.v_read_write[4].get_coeff(i),
+                    polynomials.v_read_write[5].get_coeff(i),
+                    polynomials.t_read.get_coeff(i),
+                ],
+                gamma,
+                tau,
+            )
+        };
+        // The timestamp is the last entry of the tuple, so incrementing it adds gamma^7 to the
+        // fingerprint.
+        let gamma_t = (0..7).fold(F::one(), |gamma_power, _| gamma_power * gamma);
+
+        let mut read_leaves: Vec<F> = (0..num_stored_ops)
             .into_par_iter()
-            .map(|i| {
-                Self::fingerprint(
-                    &[
-                        polynomials.a_read_write[i],
-                        polynomials.v_read_write[0][i],
-                        polynomials.v_read_write[1][i],
-                        polynomials.v_read_write[2][i],
-                        polynomials.v_read_write[3][i],
-                        polynomials.v_read_write[4][i],
-                        polynomials.v_read_write[5][i],
-                        polynomials.t_read[i],
-                    ],
-                    gamma,
-                    tau,
-                )
-            })
+            .map(read_fingerprint)
             .collect();
+        // The padding rows all read the no-op's bytecode row, with a counter that counts up
+        // (see `BytecodePolynomials::new`), so their fingerprints follow from the first one.
+        if num_stored_ops < num_ops {
+            let padding_leaf = read_fingerprint(num_stored_ops);
+            read_leaves.par_extend(
+                (0..(num_ops - num_stored_ops) as u64)
+                    .into_par_iter()
+                    .map(|offset| padding_leaf + gamma_t * F::from_u64(offset).unwrap()),
+            );
+        }
 
         let init_leaves = (0..bytecode_size)
             .into_par_iter()
@@ -464,25 +567,9 @@ where
             })
             .collect();
 
-        // TODO(moodlezoup): Compute write_leaves from read_leaves
-        let write_leaves = (0..num_ops)
-            .into_par_iter()
-            .map(|i| {
-                Self::fingerprint(
-                    &[
-                        polynomials.a_read_write[i],
-                        polynomials.v_read_write[0][i],
-                        polynomials.v_read_write[1][i],
-                        polynomials.v_read_write[2][i],
-                        polynomials.v_read_write[3][i],
-                        polynomials.v_read_write[4][i],
-                        polynomials.v_read_write[5][i],
-                        polynomials.t_read[i] + F::one(),
-                    ],
-                    gamma,
-                    tau,
-                )
-            })
+        let write_leaves = read_leaves
+            .par_iter()
+            .map(|read_leaf| *read_leaf + gamma_t)
             .collect();
 
         // TODO(moodlezoup): Compute final_leaves from init_leaves
@@ -498,7 +585,7 @@ where
                         preprocessing.v_init_final[3][i],
                         preprocessing.v_init_final[4][i],
                         preprocessing.v_init_final[5][i],
-                        polynomials.t_final[i],
+                        polynomials.t_final.get_coeff(i),
                     ],
                     gamma,
                     tau,
@@ -604,8 +691,6 @@ where
     F: JoltField,
     C: CommitmentScheme<Field = F>,
 {
-    type Proof = C::BatchedProof;
-
     #[tracing::instrument(skip_all, name = "BytecodeReadWriteOpenings::open")]
     fn open(polynomials: &BytecodePolynomials<F, C>, opening_point: &[F]) -> Self {
         let chis = EqPolynomial::evals(opening_point);
@@ -627,47 +712,40 @@ where
         polynomials: &BytecodePolynomials<F, C>,
         opening_point: &[F],
         openings: &Self,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
-    ) -> Self::Proof {
+    ) {
         let mut combined_openings: Vec<F> =
             vec![openings.a_read_write_opening, openings.t_read_opening];
         combined_openings.extend(openings.v_read_write_openings.iter());
 
-        C::batch_prove(
-            &[
-                &polynomials.a_read_write,
-                &polynomials.t_read,
-                &polynomials.v_read_write[0],
-                &polynomials.v_read_write[1],
-                &polynomials.v_read_write[2],
-                &polynomials.v_read_write[3],
-                &polynomials.v_read_write[4],
-                &polynomials.v_read_write[5],
-            ],
-            opening_point,
-            &combined_openings,
-            BatchType::Big,
-            transcript,
-        )
+        let polys: [&dyn MultilinearPolynomial<F>; 8] = [
+            &polynomials.a_read_write,
+            &polynomials.t_read,
+            &polynomials.v_read_write[0],
+            &polynomials.v_read_write[1],
+            &polynomials.v_read_write[2],
+            &polynomials.v_read_write[3],
+            &polynomials.v_read_write[4],
+            &polynomials.v_read_write[5],
+        ];
+        opening_accumulator.append(&polys, opening_point, &combined_openings, transcript);
     }
 
     fn verify_openings(
         &self,
-        generators: &C::Setup,
-        opening_proof: &Self::Proof,
         commitment: &BytecodeCommitment<C>,
         opening_point: &[F],
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         let mut combined_openings: Vec<F> = vec![self.a_read_write_opening, self.t_read_opening];
         combined_openings.extend(self.v_read_write_openings.iter());
 
-        C::batch_verify(
-            opening_proof,
-            generators,
+        opening_accumulator.append(
+            &commitment.trace_commitments.iter().collect::<Vec<_>>(),
             opening_point,
             &combined_openings,
-            &commitment.trace_commitments.iter().collect::<Vec<_>>(),
             transcript,
         )
     }
@@ -692,7 +770,6 @@ where
     C: CommitmentScheme<Field = F>,
 {
     type Preprocessing = BytecodePreprocessing<F>;
-    type Proof = C::Proof;
 
     #[tracing::instrument(skip_all, name = "BytecodeInitFinalOpenings::open")]
     fn open(polynomials: &BytecodePolynomials<F, C>, opening_point: &[F]) -> Self {
@@ -707,17 +784,30 @@ where
     fn prove_openings(
         polynomials: &BytecodePolynomials<F, C>,
         opening_point: &[F],
-        _openings: &Self,
+        openings: &Self,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
-    ) -> Self::Proof {
-        C::prove(&polynomials.t_final, opening_point, transcript)
+    ) {
+        opening_accumulator.append(
+            &[&polynomials.t_final],
+            opening_point,
+            &[openings.t_final],
+            transcript,
+        );
     }
 
     fn compute_verifier_openings(
         &mut self,
         preprocessing: &BytecodePreprocessing<F>,
         opening_point: &[F],
-    ) {
+    ) -> Result<(), ProofVerifyError> {
+        if opening_point.len() != preprocessing.code_size.log_2() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                preprocessing.code_size.log_2(),
+                opening_point.len(),
+            ));
+        }
+
         self.a_init_final =
             Some(IdentityPolynomial::new(opening_point.len()).evaluate(opening_point));
 
@@ -731,23 +821,21 @@ where
                 .try_into()
                 .unwrap(),
         );
+        Ok(())
     }
 
     fn verify_openings(
         &self,
-        generators: &C::Setup,
-        opening_proof: &Self::Proof,
         commitment: &BytecodeCommitment<C>,
         opening_point: &[F],
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        C::verify(
-            opening_proof,
-            generators,
-            transcript,
+        opening_accumulator.append(
+            &[&commitment.t_final_commitment],
             opening_point,
-            &self.t_final,
-            &commitment.t_final_commitment,
+            &[self.t_final],
+            transcript,
         )
     }
 }
@@ -859,17 +947,83 @@ mod tests {
 
         let generators = HyraxScheme::<G1Projective>::setup(&commitment_shapes);
         let commitments = polys.commit(&generators);
-        let proof = BytecodeProof::prove_memory_checking(&preprocessing, &polys, &mut transcript);
+        let mut prover_accumulator = ProverOpeningAccumulator::new();
+        let proof = BytecodeProof::prove_memory_checking(
+            &preprocessing,
+            &generators,
+            &polys,
+            &mut prover_accumulator,
+            &mut transcript,
+        );
+        let opening_proof = prover_accumulator
+            .reduce_and_prove::<HyraxScheme<G1Projective>>(&generators, &mut transcript);
 
         let mut transcript = ProofTranscript::new(b"test_transcript");
+        let mut verifier_accumulator = VerifierOpeningAccumulator::new();
         BytecodeProof::verify_memory_checking(
             &preprocessing,
             &generators,
             proof,
             &commitments,
+            &mut verifier_accumulator,
             &mut transcript,
         )
         .expect("proof should verify");
+        verifier_accumulator
+            .reduce_and_verify(&generators, &opening_proof, &mut transcript)
+            .expect("proof should verify");
+    }
+
+    #[test]
+    fn e2e_memchecking_with_quarks() {
+        let program: Vec<BytecodeRow> = (0..8u64)
+            .map(|i| BytecodeRow::new(to_ram_address(i as usize), i, i, i, i, i))
+            .collect();
+        // Deep enough that the read/write grand product has layers above the GKR ones
+        let mut trace: Vec<JoltTraceStep<RV32I>> = (0..64)
+            .map(|i| trace_step(program[(i * 3) % program.len()].clone()))
+            .collect();
+        let commit_shapes = BytecodePolynomials::<Fr, HyraxScheme<G1Projective>>::commit_shapes(
+            program.len(),
+            trace.len(),
+        );
+        let preprocessing = BytecodePreprocessing::preprocess(program);
+        let polys: BytecodePolynomials<Fr, HyraxScheme<G1Projective>> =
+            BytecodePolynomials::new(&preprocessing, &mut trace);
+        let generators = HyraxScheme::<G1Projective>::setup(&commit_shapes);
+        let commitments = polys.commit(&generators);
+
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        let mut prover_accumulator = ProverOpeningAccumulator::new();
+        let proof = BytecodeProof::prove_memory_checking(
+            &preprocessing,
+            &generators,
+            &polys,
+            &mut prover_accumulator,
+            &mut transcript,
+        );
+        let opening_proof = prover_accumulator
+            .reduce_and_prove::<HyraxScheme<G1Projective>>(&generators, &mut transcript);
+        assert!(proof.read_write_grand_product.quark_proof.is_some());
+        assert_eq!(
+            proof.read_write_grand_product.layers.len(),
+            DEFAULT_NUM_GKR_LAYERS
+        );
+
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        let mut verifier_accumulator = VerifierOpeningAccumulator::new();
+        BytecodeProof::verify_memory_checking(
+            &preprocessing,
+            &generators,
+            proof,
+            &commitments,
+            &mut verifier_accumulator,
+            &mut transcript,
+        )
+        .expect("should verify");
+        verifier_accumulator
+            .reduce_and_verify(&generators, &opening_proof, &mut transcript)
+            .expect("should verify");
     }
 
     #[test]
@@ -907,7 +1061,6 @@ mod tests {
                 32u64,
             )),
         ];
-        JoltTraceStep::pad(&mut trace);
 
         let commit_shapes = BytecodePolynomials::<Fr, HyraxScheme<G1Projective>>::commit_shapes(
             program.len(),
@@ -921,17 +1074,31 @@ mod tests {
 
         let mut transcript = ProofTranscript::new(b"test_transcript");
 
-        let proof = BytecodeProof::prove_memory_checking(&preprocessing, &polys, &mut transcript);
+        let mut prover_accumulator = ProverOpeningAccumulator::new();
+        let proof = BytecodeProof::prove_memory_checking(
+            &preprocessing,
+            &generators,
+            &polys,
+            &mut prover_accumulator,
+            &mut transcript,
+        );
+        let opening_proof = prover_accumulator
+            .reduce_and_prove::<HyraxScheme<G1Projective>>(&generators, &mut transcript);
 
         let mut transcript = ProofTranscript::new(b"test_transcript");
+        let mut verifier_accumulator = VerifierOpeningAccumulator::new();
         BytecodeProof::verify_memory_checking(
             &preprocessing,
             &generators,
             proof,
             &commitments,
+            &mut verifier_accumulator,
             &mut transcript,
         )
         .expect("should verify");
+        verifier_accumulator
+            .reduce_and_verify(&generators, &opening_proof, &mut transcript)
+            .expect("should verify");
     }
 
     #[test]
@@ -967,4 +1134,40 @@ mod tests {
         ];
         BytecodePolynomials::<Fr, HyraxScheme<G1Projective>>::validate_bytecode(&program, &trace);
     }
+
+    #[test]
+    fn bitflags_packing() {
+        use crate::jolt::instruction::add::ADDInstruction;
+        use common::rv_trace::{NUM_CIRCUIT_FLAGS, RV32IM};
+        use strum::EnumCount;
+
+        let instruction = |opcode| ELFInstruction {
+            address: RAM_START_ADDRESS,
+            opcode,
+            rs1: Some(1),
+            rs2: None,
+            rd: Some(2),
+            imm: Some(0x800),
+            virtual_sequence_index: None,
+        };
+        let opcode_bits = opcode_bits(RV32I::COUNT);
+        assert!(NUM_CIRCUIT_FLAGS + opcode_bits <= 64);
+
+        let addi = instruction(RV32IM::ADDI);
+        let bitflags = BytecodeRow::bitflags::<RV32I>(&addi);
+        let add_index = RV32I::enum_index(&RV32I::ADD(ADDInstruction::default()));
+        assert_eq!(bitflags & ((1 << opcode_bits) - 1), add_index as u64 + 1);
+        let circuit_flags = addi.to_circuit_flags();
+        for (i, flag) in circuit_flags.iter().enumerate() {
+            let packed_flag = (bitflags >> (opcode_bits + NUM_CIRCUIT_FLAGS - 1 - i)) & 1;
+            assert_eq!(packed_flag == 1, *flag);
+        }
+
+        // LUI does not perform a lookup
+        let lui = instruction(RV32IM::LUI);
+        assert_eq!(
+            BytecodeRow::bitflags::<RV32I>(&lui) & ((1 << opcode_bits) - 1),
+            0
+        );
+    }
 }
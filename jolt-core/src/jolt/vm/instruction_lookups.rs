@@ -2,12 +2,12 @@ use crate::poly::field::JoltField;
 use crate::subprotocols::grand_product::{BatchedGrandProduct, ToggledBatchedGrandProduct};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use itertools::{interleave, Itertools};
-use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use rayon::prelude::*;
 use std::marker::PhantomData;
 use tracing::trace_span;
 
-use crate::jolt::instruction::{JoltInstructionSet, SubtableIndices};
+use crate::jolt::instruction::{InstructionSubtables, JoltInstructionSet, SubtableIndices};
 use crate::jolt::subtable::JoltSubtableSet;
 use crate::lasso::memory_checking::MultisetHashes;
 use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
@@ -15,9 +15,13 @@ use crate::utils::mul_0_1_optimized;
 use crate::{
     lasso::memory_checking::{MemoryCheckingProof, MemoryCheckingProver, MemoryCheckingVerifier},
     poly::{
+        compact_polynomial::{CompactPolynomial, Padding, SmallInt},
         dense_mlpoly::DensePolynomial,
         eq_poly::EqPolynomial,
         identity_poly::IdentityPolynomial,
+        multilinear_polynomial::MultilinearPolynomial,
+        opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator},
+        sparse_polynomial::SparsePolynomial,
         structured_poly::{StructuredCommitment, StructuredOpeningProof},
         unipoly::{CompressedUniPoly, UniPoly},
     },
@@ -32,42 +36,43 @@ use crate::{
 use super::JoltTraceStep;
 
 /// All polynomials associated with Jolt instruction lookups.
+#[derive(CanonicalSerialize, CanonicalDeserialize)]
 pub struct InstructionPolynomials<F, C>
 where
     F: JoltField,
     C: CommitmentScheme<Field = F>,
 {
     _marker: PhantomData<C>,
-    /// `C` sized vector of `DensePolynomials` whose evaluations correspond to
-    /// indices at which the memories will be evaluated. Each `DensePolynomial` has size
+    /// `C` sized vector of `CompactPolynomials` whose evaluations correspond to
+    /// indices at which the memories will be evaluated. Each `CompactPolynomial` has size
     /// `m` (# lookups).
-    pub dim: Vec<DensePolynomial<F>>,
+    ///
+    /// Like `read_cts`, `E_polys` and `lookup_outputs`, these only store the lookups of the
+    /// trace itself: the no-op steps padding it perform no lookups, so they are zero.
+    pub dim: Vec<CompactPolynomial<u32, F>>,
 
-    /// `NUM_MEMORIES` sized vector of `DensePolynomials` whose evaluations correspond to
-    /// read access counts to the memory. Each `DensePolynomial` has size `m` (# lookups).
-    pub read_cts: Vec<DensePolynomial<F>>,
+    /// `NUM_MEMORIES` sized vector of `CompactPolynomials` whose evaluations correspond to
+    /// read access counts to the memory. Each `CompactPolynomial` has size `m` (# lookups).
+    pub read_cts: Vec<CompactPolynomial<u32, F>>,
 
-    /// `NUM_MEMORIES` sized vector of `DensePolynomials` whose evaluations correspond to
-    /// final access counts to the memory. Each `DensePolynomial` has size M, AKA subtable size.
-    pub final_cts: Vec<DensePolynomial<F>>,
+    /// `NUM_MEMORIES` sized vector of `CompactPolynomials` whose evaluations correspond to
+    /// final access counts to the memory. Each `CompactPolynomial` has size M, AKA subtable size.
+    pub final_cts: Vec<CompactPolynomial<u32, F>>,
 
-    /// `NUM_MEMORIES` sized vector of `DensePolynomials` whose evaluations correspond to
-    /// the evaluation of memory accessed at each step of the CPU. Each `DensePolynomial` has
+    /// `NUM_MEMORIES` sized vector of `CompactPolynomials` whose evaluations correspond to
+    /// the evaluation of memory accessed at each step of the CPU. Each `CompactPolynomial` has
     /// size `m` (# lookups).
-    pub E_polys: Vec<DensePolynomial<F>>,
+    pub E_polys: Vec<CompactPolynomial<u32, F>>,
 
     /// Polynomial encodings for flag polynomials for each instruction.
     /// If using a single instruction this will be empty.
     /// NUM_INSTRUCTIONS sized, each polynomial of length 'm' (# lookups).
     ///
-    /// Stored independently for use in sumcheck, combined into single DensePolynomial for commitment.
-    pub instruction_flag_polys: Vec<DensePolynomial<F>>,
-
-    /// Instruction flag polynomials as bitvectors, kept in this struct for more efficient
-    /// construction of the memory flag polynomials in `read_write_grand_product`.
-    instruction_flag_bitvectors: Vec<Vec<u64>>,
+    /// The flags are one-hot per step, so each polynomial is stored sparsely: it is only
+    /// non-zero at the steps executing its instruction.
+    pub instruction_flag_polys: Vec<SparsePolynomial<F>>,
     /// The lookup output for each instruction of the execution trace.
-    pub lookup_outputs: DensePolynomial<F>,
+    pub lookup_outputs: CompactPolynomial<u32, F>,
 }
 
 /// Commitments to BatchedInstructionPolynomials.
@@ -91,6 +96,26 @@ impl<C: CommitmentScheme> AppendToTranscript for InstructionCommitment<C> {
     }
 }
 
+impl<F, C> Default for InstructionPolynomials<F, C>
+where
+    F: JoltField,
+    C: CommitmentScheme<Field = F>,
+{
+    /// An empty set of polynomials, standing in for the instruction lookup polynomials in a
+    /// `JoltPolynomials` whose consumer doesn't read them.
+    fn default() -> Self {
+        Self {
+            _marker: PhantomData,
+            dim: vec![],
+            read_cts: vec![],
+            final_cts: vec![],
+            E_polys: vec![],
+            instruction_flag_polys: vec![],
+            lookup_outputs: CompactPolynomial::from_coeffs(vec![0]),
+        }
+    }
+}
+
 impl<F, C> StructuredCommitment<C> for InstructionPolynomials<F, C>
 where
     F: JoltField,
@@ -100,17 +125,30 @@ where
 
     #[tracing::instrument(skip_all, name = "InstructionPolynomials::commit")]
     fn commit(&self, generators: &C::Setup) -> Self::Commitment {
-        let trace_polys: Vec<&DensePolynomial<F>> = self
+        let dim_read_E_polys: Vec<&CompactPolynomial<u32, F>> = self
             .dim
             .iter()
             .chain(self.read_cts.iter())
             .chain(self.E_polys.iter())
-            .chain(self.instruction_flag_polys.iter())
-            .chain([&self.lookup_outputs].into_iter())
             .collect();
-        let trace_commitment = C::batch_commit_polys_ref(&trace_polys, generators, BatchType::Big);
+        let mut trace_commitment =
+            C::batch_commit_compact(&dim_read_E_polys, generators, BatchType::Big);
+        trace_commitment.extend(C::batch_commit_sparse(
+            &self.instruction_flag_polys.iter().collect::<Vec<_>>(),
+            generators,
+            BatchType::Big,
+        ));
+        trace_commitment.extend(C::batch_commit_compact(
+            &[&self.lookup_outputs],
+            generators,
+            BatchType::Big,
+        ));
 
-        let final_commitment = C::batch_commit_polys(&self.final_cts, generators, BatchType::Big);
+        let final_commitment = C::batch_commit_compact(
+            &self.final_cts.iter().collect::<Vec<_>>(),
+            generators,
+            BatchType::Big,
+        );
 
         Self::Commitment {
             trace_commitment,
@@ -138,8 +176,6 @@ where
     F: JoltField,
     C: CommitmentScheme<Field = F>,
 {
-    type Proof = C::BatchedProof;
-
     fn open(_polynomials: &InstructionPolynomials<F, C>, _opening_point: &[F]) -> Self {
         unimplemented!("Openings are output by sumcheck protocol");
     }
@@ -149,13 +185,20 @@ where
         polynomials: &InstructionPolynomials<F, C>,
         opening_point: &[F],
         openings: &Self,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
-    ) -> Self::Proof {
+    ) {
         let primary_sumcheck_polys = polynomials
             .E_polys
             .iter()
-            .chain(polynomials.instruction_flag_polys.iter())
-            .chain([&polynomials.lookup_outputs].into_iter())
+            .map(|poly| poly as &dyn MultilinearPolynomial<F>)
+            .chain(
+                polynomials
+                    .instruction_flag_polys
+                    .iter()
+                    .map(|poly| poly as &dyn MultilinearPolynomial<F>),
+            )
+            .chain([&polynomials.lookup_outputs as &dyn MultilinearPolynomial<F>])
             .collect::<Vec<_>>();
         let mut primary_sumcheck_openings: Vec<F> = [
             openings.E_poly_openings.as_slice(),
@@ -164,21 +207,19 @@ where
         .concat();
         primary_sumcheck_openings.push(openings.lookup_outputs_opening);
 
-        C::batch_prove(
+        opening_accumulator.append(
             &primary_sumcheck_polys,
             opening_point,
             &primary_sumcheck_openings,
-            BatchType::Big,
             transcript,
-        )
+        );
     }
 
     fn verify_openings(
         &self,
-        generators: &C::Setup,
-        opening_proof: &Self::Proof,
         commitment: &InstructionCommitment<C>,
         opening_point: &[F],
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         let mut primary_sumcheck_openings: Vec<F> = [
@@ -187,17 +228,24 @@ where
         ]
         .concat();
         primary_sumcheck_openings.push(self.lookup_outputs_opening);
-        let primary_sumcheck_commitments = commitment.trace_commitment
-            [commitment.trace_commitment.len() - primary_sumcheck_openings.len()..]
-            .iter()
-            .collect::<Vec<_>>();
+        let primary_sumcheck_commitments = commitment
+            .trace_commitment
+            .len()
+            .checked_sub(primary_sumcheck_openings.len())
+            .map(|start| {
+                commitment.trace_commitment[start..]
+                    .iter()
+                    .collect::<Vec<_>>()
+            })
+            .ok_or(ProofVerifyError::InvalidInputLength(
+                primary_sumcheck_openings.len(),
+                commitment.trace_commitment.len(),
+            ))?;
 
-        C::batch_verify(
-            opening_proof,
-            generators,
+        opening_accumulator.append(
+            &primary_sumcheck_commitments,
             opening_point,
             &primary_sumcheck_openings,
-            &primary_sumcheck_commitments,
             transcript,
         )
     }
@@ -224,8 +272,6 @@ where
     F: JoltField,
     C: CommitmentScheme<Field = F>,
 {
-    type Proof = C::BatchedProof;
-
     #[tracing::instrument(skip_all, name = "InstructionReadWriteOpenings::open")]
     fn open(polynomials: &InstructionPolynomials<F, C>, opening_point: &[F]) -> Self {
         // All of these evaluations share the lagrange basis polynomials.
@@ -234,22 +280,22 @@ where
         let dim_openings = polynomials
             .dim
             .par_iter()
-            .map(|poly| poly.evaluate_at_chi_low_optimized(&chis))
+            .map(|poly| poly.evaluate_at_chi(&chis))
             .collect();
         let read_openings = polynomials
             .read_cts
             .par_iter()
-            .map(|poly| poly.evaluate_at_chi_low_optimized(&chis))
+            .map(|poly| poly.evaluate_at_chi(&chis))
             .collect();
         let E_poly_openings = polynomials
             .E_polys
             .par_iter()
-            .map(|poly| poly.evaluate_at_chi_low_optimized(&chis))
+            .map(|poly| poly.evaluate_at_chi(&chis))
             .collect();
         let flag_openings = polynomials
             .instruction_flag_polys
             .par_iter()
-            .map(|poly| poly.evaluate_at_chi_low_optimized(&chis))
+            .map(|poly| poly.evaluate_at_chi(&chis))
             .collect();
 
         Self {
@@ -265,14 +311,26 @@ where
         polynomials: &InstructionPolynomials<F, C>,
         opening_point: &[F],
         openings: &Self,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
-    ) -> Self::Proof {
+    ) {
         let read_write_polys = polynomials
             .dim
             .iter()
             .chain(polynomials.read_cts.iter())
-            .chain(polynomials.E_polys.iter())
-            .chain(polynomials.instruction_flag_polys.iter())
+            .map(|poly| poly as &dyn MultilinearPolynomial<F>)
+            .chain(
+                polynomials
+                    .E_polys
+                    .iter()
+                    .map(|poly| poly as &dyn MultilinearPolynomial<F>),
+            )
+            .chain(
+                polynomials
+                    .instruction_flag_polys
+                    .iter()
+                    .map(|poly| poly as &dyn MultilinearPolynomial<F>),
+            )
             .collect::<Vec<_>>();
 
         let read_write_openings: Vec<F> = [
@@ -283,21 +341,19 @@ where
         ]
         .concat();
 
-        C::batch_prove(
+        opening_accumulator.append(
             &read_write_polys,
             opening_point,
             &read_write_openings,
-            BatchType::Big,
             transcript,
-        )
+        );
     }
 
     fn verify_openings(
         &self,
-        generators: &C::Setup,
-        opening_proof: &Self::Proof,
         commitment: &InstructionCommitment<C>,
         opening_point: &[F],
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         let read_write_openings: Vec<F> = [
@@ -307,14 +363,17 @@ where
             self.flag_openings.as_slice(),
         ]
         .concat();
-        C::batch_verify(
-            opening_proof,
-            generators,
+        let read_write_commitments = commitment
+            .trace_commitment
+            .get(..read_write_openings.len())
+            .ok_or(ProofVerifyError::InvalidInputLength(
+                read_write_openings.len(),
+                commitment.trace_commitment.len(),
+            ))?;
+        opening_accumulator.append(
+            &read_write_commitments.iter().collect::<Vec<_>>(),
             opening_point,
             &read_write_openings,
-            &commitment.trace_commitment[..read_write_openings.len()]
-                .iter()
-                .collect::<Vec<_>>(),
             transcript,
         )
     }
@@ -343,7 +402,6 @@ where
     Subtables: JoltSubtableSet<F>,
 {
     type Preprocessing = InstructionLookupsPreprocessing<F>;
-    type Proof = C::BatchedProof;
 
     #[tracing::instrument(skip_all, name = "InstructionFinalOpenings::open")]
     fn open(polynomials: &InstructionPolynomials<F, C>, opening_point: &[F]) -> Self {
@@ -352,7 +410,7 @@ where
         let final_openings = polynomials
             .final_cts
             .par_iter()
-            .map(|final_cts_i| final_cts_i.evaluate_at_chi_low_optimized(&chis))
+            .map(|final_cts_i| final_cts_i.evaluate_at_chi(&chis))
             .collect();
         Self {
             _subtables: PhantomData,
@@ -367,45 +425,60 @@ where
         polynomials: &InstructionPolynomials<F, C>,
         opening_point: &[F],
         openings: &Self,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
-    ) -> Self::Proof {
-        C::batch_prove(
+    ) {
+        opening_accumulator.append(
             &polynomials.final_cts.iter().collect::<Vec<_>>(),
             opening_point,
             &openings.final_openings,
-            BatchType::Big,
             transcript,
-        )
+        );
     }
 
     fn compute_verifier_openings(
         &mut self,
-        _preprocessing: &Self::Preprocessing,
+        preprocessing: &Self::Preprocessing,
         opening_point: &[F],
-    ) {
+    ) -> Result<(), ProofVerifyError> {
+        let log_M = preprocessing.materialized_subtables[preprocessing.used_subtables[0]]
+            .len()
+            .log_2();
+        if opening_point.len() != log_M {
+            return Err(ProofVerifyError::InvalidInputLength(
+                log_M,
+                opening_point.len(),
+            ));
+        }
+
         self.a_init_final =
             Some(IdentityPolynomial::new(opening_point.len()).evaluate(opening_point));
         self.v_init_final = Some(
             Subtables::iter()
-                .map(|subtable| subtable.evaluate_mle(opening_point))
+                .enumerate()
+                .map(|(subtable_index, subtable)| {
+                    if preprocessing.used_subtables.contains(&subtable_index) {
+                        subtable.evaluate_mle(opening_point)
+                    } else {
+                        F::zero()
+                    }
+                })
                 .collect(),
         );
+        Ok(())
     }
 
     fn verify_openings(
         &self,
-        generators: &C::Setup,
-        opening_proof: &Self::Proof,
         commitment: &InstructionCommitment<C>,
         opening_point: &[F],
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        C::batch_verify(
-            opening_proof,
-            generators,
+        opening_accumulator.append(
+            &commitment.final_commitment.iter().collect::<Vec<_>>(),
             opening_point,
             &self.final_openings,
-            &commitment.final_commitment.iter().collect::<Vec<_>>(),
             transcript,
         )
     }
@@ -442,8 +515,8 @@ where
         gamma: &F,
         tau: &F,
     ) -> (
-        <Self::ReadWriteGrandProduct as BatchedGrandProduct<F>>::Leaves,
-        <Self::InitFinalGrandProduct as BatchedGrandProduct<F>>::Leaves,
+        <Self::ReadWriteGrandProduct as BatchedGrandProduct<F, CS>>::Leaves,
+        <Self::InitFinalGrandProduct as BatchedGrandProduct<F, CS>>::Leaves,
     ) {
         let gamma_squared = gamma.square();
         let num_lookups = polynomials.dim[0].len();
@@ -455,9 +528,9 @@ where
 
                 let read_fingerprints: Vec<F> = (0..num_lookups)
                     .map(|i| {
-                        let a = &polynomials.dim[dim_index][i];
-                        let v = &polynomials.E_polys[memory_index][i];
-                        let t = &polynomials.read_cts[memory_index][i];
+                        let a = &polynomials.dim[dim_index].get_coeff(i);
+                        let v = &polynomials.E_polys[memory_index].get_coeff(i);
+                        let t = &polynomials.read_cts[memory_index].get_coeff(i);
                         mul_0_1_optimized(t, &gamma_squared) + mul_0_1_optimized(v, gamma) + a - tau
                     })
                     .collect();
@@ -470,10 +543,10 @@ where
             .collect();
 
         let init_final_leaves: Vec<Vec<F>> = preprocessing
-            .materialized_subtables
+            .used_subtables
             .par_iter()
-            .enumerate()
-            .flat_map_iter(|(subtable_index, subtable)| {
+            .flat_map_iter(|&subtable_index| {
+                let subtable = &preprocessing.materialized_subtables[subtable_index];
                 let init_leaves: Vec<F> = (0..M)
                     .map(|i| {
                         let a = &F::from_u64(i as u64).unwrap();
@@ -491,7 +564,8 @@ where
                         let final_cts = &polynomials.final_cts[*memory_index];
                         (0..M)
                             .map(|i| {
-                                init_leaves[i] + mul_0_1_optimized(&final_cts[i], &gamma_squared)
+                                init_leaves[i]
+                                    + mul_0_1_optimized(&final_cts.get_coeff(i), &gamma_squared)
                             })
                             .collect()
                     })
@@ -505,7 +579,7 @@ where
             .collect();
 
         let memory_flags =
-            Self::memory_flag_indices(preprocessing, &polynomials.instruction_flag_bitvectors);
+            Self::memory_flag_indices(preprocessing, &polynomials.instruction_flag_polys);
 
         ((memory_flags, read_write_leaves), init_final_leaves)
     }
@@ -525,8 +599,8 @@ where
         let mut init_final_hashes = Vec::with_capacity(
             multiset_hashes.init_hashes.len() + multiset_hashes.final_hashes.len(),
         );
-        for subtable_index in 0..Self::NUM_SUBTABLES {
-            init_final_hashes.push(multiset_hashes.init_hashes[subtable_index]);
+        for (i, &subtable_index) in preprocessing.used_subtables.iter().enumerate() {
+            init_final_hashes.push(multiset_hashes.init_hashes[i]);
             let memory_indices = &preprocessing.subtable_to_memory_indices[subtable_index];
             memory_indices
                 .iter()
@@ -544,7 +618,7 @@ where
         assert_eq!(read_write_hashes.len(), 2 * preprocessing.num_memories);
         assert_eq!(
             init_final_hashes.len(),
-            Self::NUM_SUBTABLES + preprocessing.num_memories
+            preprocessing.used_subtables.len() + preprocessing.num_memories
         );
 
         let mut read_hashes = Vec::with_capacity(preprocessing.num_memories);
@@ -554,10 +628,10 @@ where
             write_hashes.push(read_write_hashes[2 * i + 1]);
         }
 
-        let mut init_hashes = Vec::with_capacity(Self::NUM_SUBTABLES);
+        let mut init_hashes = Vec::with_capacity(preprocessing.used_subtables.len());
         let mut final_hashes = Vec::with_capacity(preprocessing.num_memories);
         let mut init_final_hashes = init_final_hashes.iter();
-        for subtable_index in 0..Self::NUM_SUBTABLES {
+        for &subtable_index in preprocessing.used_subtables.iter() {
             // I
             init_hashes.push(*init_final_hashes.next().unwrap());
             // F F F F
@@ -578,35 +652,29 @@ where
     fn check_multiset_equality(
         preprocessing: &InstructionLookupsPreprocessing<F>,
         multiset_hashes: &MultisetHashes<F>,
-    ) {
-        assert_eq!(multiset_hashes.init_hashes.len(), Self::NUM_SUBTABLES);
-        assert_eq!(
-            multiset_hashes.read_hashes.len(),
-            preprocessing.num_memories
-        );
-        assert_eq!(
-            multiset_hashes.write_hashes.len(),
-            preprocessing.num_memories
-        );
-        assert_eq!(
-            multiset_hashes.final_hashes.len(),
-            preprocessing.num_memories
-        );
+    ) -> Result<(), ProofVerifyError> {
+        multiset_hashes.check_lengths(
+            preprocessing.num_memories,
+            preprocessing.used_subtables.len(),
+        )?;
 
         (0..preprocessing.num_memories)
             .into_par_iter()
-            .for_each(|i| {
+            .try_for_each(|i| {
                 let read_hash = multiset_hashes.read_hashes[i];
                 let write_hash = multiset_hashes.write_hashes[i];
-                let init_hash =
-                    multiset_hashes.init_hashes[preprocessing.memory_to_subtable_index[i]];
+                let subtable_index = preprocessing.memory_to_subtable_index[i];
+                let init_index = preprocessing
+                    .used_subtables
+                    .binary_search(&subtable_index)
+                    .unwrap();
+                let init_hash = multiset_hashes.init_hashes[init_index];
                 let final_hash = multiset_hashes.final_hashes[i];
-                assert_eq!(
-                    init_hash * write_hash,
-                    final_hash * read_hash,
-                    "Multiset hashes don't match"
-                );
-            });
+                if init_hash * write_hash != final_hash * read_hash {
+                    return Err(ProofVerifyError::MultisetHashMismatch(i));
+                }
+                Ok(())
+            })
     }
 
     fn protocol_name() -> &'static [u8] {
@@ -650,14 +718,16 @@ where
             .collect()
     }
     fn init_tuples(
-        _preprocessing: &InstructionLookupsPreprocessing<F>,
+        preprocessing: &InstructionLookupsPreprocessing<F>,
         openings: &Self::InitFinalOpenings,
     ) -> Vec<Self::MemoryTuple> {
         let a_init = openings.a_init_final.unwrap();
         let v_init = openings.v_init_final.as_ref().unwrap();
 
-        (0..Self::NUM_SUBTABLES)
-            .map(|subtable_index| (a_init, v_init[subtable_index], F::zero(), None))
+        preprocessing
+            .used_subtables
+            .iter()
+            .map(|subtable_index| (a_init, v_init[*subtable_index], F::zero(), None))
             .collect()
     }
     fn final_tuples(
@@ -690,7 +760,7 @@ where
     InstructionSet: JoltInstructionSet,
 {
     _instructions: PhantomData<InstructionSet>,
-    primary_sumcheck: PrimarySumcheck<F, CS>,
+    primary_sumcheck: PrimarySumcheck<F>,
     memory_checking: MemoryCheckingProof<
         F,
         CS,
@@ -701,11 +771,10 @@ where
 }
 
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
-pub struct PrimarySumcheck<F: JoltField, CS: CommitmentScheme<Field = F>> {
+pub struct PrimarySumcheck<F: JoltField> {
     sumcheck_proof: SumcheckInstanceProof<F>,
     num_rounds: usize,
     openings: PrimarySumcheckOpenings<F>,
-    opening_proof: CS::BatchedProof,
 }
 
 #[derive(Clone)]
@@ -714,6 +783,9 @@ pub struct InstructionLookupsPreprocessing<F: JoltField> {
     instruction_to_memory_indices: Vec<Vec<usize>>,
     memory_to_subtable_index: Vec<usize>,
     memory_to_dimension_index: Vec<usize>,
+    /// The subtables that some chunk accesses, in order. Only these are materialized and
+    /// memory-checked.
+    used_subtables: Vec<usize>,
     materialized_subtables: Vec<Vec<F>>,
     num_memories: usize,
 }
@@ -722,20 +794,24 @@ impl<F: JoltField> InstructionLookupsPreprocessing<F> {
     #[tracing::instrument(skip_all, name = "InstructionLookups::preprocess")]
     pub fn preprocess<const C: usize, const M: usize, InstructionSet, Subtables>() -> Self
     where
-        InstructionSet: JoltInstructionSet,
+        InstructionSet: JoltInstructionSet + InstructionSubtables<F, Subtables>,
         Subtables: JoltSubtableSet<F>,
     {
-        let materialized_subtables = Self::materialize_subtables::<M, Subtables>();
-
         // Build a mapping from subtable type => chunk indices that access that subtable type
         let mut subtable_indices: Vec<SubtableIndices> =
             vec![SubtableIndices::with_capacity(C); Subtables::COUNT];
         for instruction in InstructionSet::iter() {
             for (subtable, indices) in instruction.subtables::<F>(C, M) {
-                subtable_indices[Subtables::enum_index(subtable)].union_with(&indices);
+                subtable_indices[instruction.subtable_index(subtable.subtable_id())]
+                    .union_with(&indices);
             }
         }
 
+        let used_subtables: Vec<usize> = (0..Subtables::COUNT)
+            .filter(|subtable_index| subtable_indices[*subtable_index].len() != 0)
+            .collect();
+        let materialized_subtables = Self::materialize_subtables::<M, Subtables>(&used_subtables);
+
         let mut subtable_to_memory_indices = Vec::with_capacity(Subtables::COUNT);
         let mut memory_to_subtable_index = vec![];
         let mut memory_to_dimension_index = vec![];
@@ -754,7 +830,7 @@ impl<F: JoltField> InstructionLookupsPreprocessing<F> {
         for instruction in InstructionSet::iter() {
             for (subtable, dimension_indices) in instruction.subtables::<F>(C, M) {
                 let memory_indices: Vec<_> = subtable_to_memory_indices
-                    [Subtables::enum_index(subtable)]
+                    [instruction.subtable_index(subtable.subtable_id())]
                 .iter()
                 .filter(|memory_index| {
                     dimension_indices.contains(memory_to_dimension_index[**memory_index])
@@ -767,6 +843,7 @@ impl<F: JoltField> InstructionLookupsPreprocessing<F> {
 
         Self {
             num_memories,
+            used_subtables,
             materialized_subtables,
             subtable_to_memory_indices,
             memory_to_subtable_index,
@@ -775,20 +852,177 @@ impl<F: JoltField> InstructionLookupsPreprocessing<F> {
         }
     }
 
-    /// Materializes all subtables used by this Jolt instance.
+    /// Materializes all subtables used by this Jolt instance. A subtable set may contain
+    /// subtables that no chunk accesses for this `C` (e.g. shift subtables for higher
+    /// dimensions), which are left empty.
     #[tracing::instrument(skip_all)]
-    fn materialize_subtables<const M: usize, Subtables>() -> Vec<Vec<F>>
+    fn materialize_subtables<const M: usize, Subtables>(used_subtables: &[usize]) -> Vec<Vec<F>>
     where
         Subtables: JoltSubtableSet<F>,
     {
         let mut subtables = Vec::with_capacity(Subtables::COUNT);
-        for subtable in Subtables::iter() {
-            subtables.push(subtable.materialize(M));
+        for (subtable_index, subtable) in Subtables::iter().enumerate() {
+            if used_subtables.contains(&subtable_index) {
+                subtables.push(subtable.materialize(M));
+            } else {
+                subtables.push(vec![]);
+            }
         }
         subtables
     }
 }
 
+/// Builds the `InstructionPolynomials` of a trace from chunks of its steps, as they are
+/// produced. See `InstructionLookupsProof::polynomialize`.
+pub struct InstructionPolynomialsBuilder<'a, const C: usize, const M: usize, F, CS, InstructionSet>
+where
+    F: JoltField,
+    CS: CommitmentScheme<Field = F>,
+    InstructionSet: JoltInstructionSet,
+{
+    _marker: PhantomData<(CS, InstructionSet)>,
+    preprocessing: &'a InstructionLookupsPreprocessing<F>,
+    /// The subtable entries are the values of 32-bit words, so the E polynomials are
+    /// stored as such.
+    materialized_subtables: Vec<Vec<u32>>,
+    dim: Vec<Vec<u32>>,
+    read_cts: Vec<Vec<u32>>,
+    final_cts: Vec<Vec<usize>>,
+    E_polys: Vec<Vec<u32>>,
+    instruction_flag_indices: Vec<Vec<usize>>,
+    lookup_outputs: Vec<u32>,
+}
+
+impl<'a, const C: usize, const M: usize, F, CS, InstructionSet>
+    InstructionPolynomialsBuilder<'a, C, M, F, CS, InstructionSet>
+where
+    F: JoltField,
+    CS: CommitmentScheme<Field = F>,
+    InstructionSet: JoltInstructionSet,
+{
+    pub fn new(preprocessing: &'a InstructionLookupsPreprocessing<F>) -> Self {
+        let materialized_subtables = preprocessing
+            .materialized_subtables
+            .par_iter()
+            .map(|subtable| {
+                subtable
+                    .iter()
+                    .map(|value| u32::from_u64(value.to_i128().unwrap() as u64))
+                    .collect()
+            })
+            .collect();
+        let num_memories = preprocessing.num_memories;
+        Self {
+            _marker: PhantomData,
+            preprocessing,
+            materialized_subtables,
+            dim: vec![vec![]; C],
+            read_cts: vec![vec![]; num_memories],
+            final_cts: vec![vec![0; M]; num_memories],
+            E_polys: vec![vec![]; num_memories],
+            instruction_flag_indices: vec![vec![]; InstructionSet::COUNT],
+            lookup_outputs: vec![],
+        }
+    }
+
+    /// Performs the lookups of the next `ops` of the trace.
+    #[tracing::instrument(skip_all, name = "InstructionPolynomialsBuilder::push_steps")]
+    pub fn push_steps(&mut self, ops: &[JoltTraceStep<InstructionSet>]) {
+        let offset = self.lookup_outputs.len();
+        let log_M = M.log_2();
+        let chunked_indices: Vec<Vec<usize>> = ops
+            .par_iter()
+            .map(|op| {
+                if let Some(instr) = &op.instruction_lookup {
+                    instr.to_indices(C, log_M)
+                } else {
+                    vec![0; C]
+                }
+            })
+            .collect();
+
+        let preprocessing = self.preprocessing;
+        let materialized_subtables = &self.materialized_subtables;
+        self.read_cts
+            .par_iter_mut()
+            .zip(self.final_cts.par_iter_mut())
+            .zip(self.E_polys.par_iter_mut())
+            .enumerate()
+            .for_each(
+                |(memory_index, ((read_cts_i, final_cts_i), subtable_lookups))| {
+                    let dim_index = preprocessing.memory_to_dimension_index[memory_index];
+                    let subtable_index = preprocessing.memory_to_subtable_index[memory_index];
+
+                    for (op, indices) in ops.iter().zip(chunked_indices.iter()) {
+                        let (read_ct, subtable_lookup) = match &op.instruction_lookup {
+                            Some(instr)
+                                if preprocessing.instruction_to_memory_indices
+                                    [InstructionSet::enum_index(instr)]
+                                .contains(&memory_index) =>
+                            {
+                                let memory_address = indices[dim_index];
+                                debug_assert!(memory_address < M);
+
+                                let counter = final_cts_i[memory_address];
+                                final_cts_i[memory_address] = counter + 1;
+                                (
+                                    counter as u32,
+                                    materialized_subtables[subtable_index][memory_address],
+                                )
+                            }
+                            _ => (0, 0),
+                        };
+                        read_cts_i.push(read_ct);
+                        subtable_lookups.push(subtable_lookup);
+                    }
+                },
+            );
+
+        for (i, access_sequence) in self.dim.iter_mut().enumerate() {
+            access_sequence.extend(chunked_indices.iter().map(|indices| indices[i] as u32));
+        }
+
+        for (j, op) in ops.iter().enumerate() {
+            if let Some(instr) = &op.instruction_lookup {
+                self.instruction_flag_indices[InstructionSet::enum_index(instr)].push(offset + j);
+            }
+        }
+
+        self.lookup_outputs.par_extend(ops.par_iter().map(|op| {
+            if let Some(instr) = &op.instruction_lookup {
+                u32::from_u64(instr.lookup_entry())
+            } else {
+                0
+            }
+        }));
+    }
+
+    /// Builds the polynomials once the whole trace has been pushed.
+    #[tracing::instrument(skip_all, name = "InstructionPolynomialsBuilder::finish")]
+    pub fn finish(self) -> InstructionPolynomials<F, CS> {
+        let padded = |values: Vec<u32>| CompactPolynomial::padded(values, Padding::Constant(0));
+        let num_vars = self.lookup_outputs.len().next_power_of_two().log_2();
+
+        InstructionPolynomials {
+            _marker: PhantomData,
+            dim: self.dim.into_par_iter().map(padded).collect(),
+            read_cts: self.read_cts.into_par_iter().map(padded).collect(),
+            final_cts: self
+                .final_cts
+                .par_iter()
+                .map(|final_cts_i| CompactPolynomial::from_usize(final_cts_i))
+                .collect(),
+            instruction_flag_polys: self
+                .instruction_flag_indices
+                .into_par_iter()
+                .map(|flag_indices| SparsePolynomial::from_indices(num_vars, flag_indices))
+                .collect(),
+            E_polys: self.E_polys.into_par_iter().map(padded).collect(),
+            lookup_outputs: padded(self.lookup_outputs),
+        }
+    }
+}
+
 impl<F, CS, InstructionSet, Subtables, const C: usize, const M: usize>
     InstructionLookupsProof<C, M, F, CS, InstructionSet, Subtables>
 where
@@ -797,13 +1031,14 @@ where
     InstructionSet: JoltInstructionSet,
     Subtables: JoltSubtableSet<F>,
 {
-    const NUM_SUBTABLES: usize = Subtables::COUNT;
     const NUM_INSTRUCTIONS: usize = InstructionSet::COUNT;
 
     #[tracing::instrument(skip_all, name = "InstructionLookups::prove")]
     pub fn prove(
         polynomials: &InstructionPolynomials<F, CS>,
         preprocessing: &InstructionLookupsPreprocessing<F>,
+        generators: &CS::Setup,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
     ) -> InstructionLookupsProof<C, M, F, CS, InstructionSet, Subtables> {
         transcript.append_protocol_name(Self::protocol_name());
@@ -823,21 +1058,21 @@ where
                 &mut eq_poly,
                 &polynomials.E_polys,
                 &polynomials.instruction_flag_polys,
-                &mut polynomials.lookup_outputs.clone(),
+                &polynomials.lookup_outputs,
                 Self::sumcheck_poly_degree(),
                 transcript,
             );
 
-        // Create a single opening proof for the flag_evals and memory_evals
         let sumcheck_openings = PrimarySumcheckOpenings {
             E_poly_openings: E_evals,
             flag_openings: flag_evals,
             lookup_outputs_opening: outputs_eval,
         };
-        let sumcheck_opening_proof = PrimarySumcheckOpenings::prove_openings(
+        PrimarySumcheckOpenings::prove_openings(
             polynomials,
             &r_primary_sumcheck,
             &sumcheck_openings,
+            opening_accumulator,
             transcript,
         );
 
@@ -845,10 +1080,15 @@ where
             sumcheck_proof: primary_sumcheck_proof,
             num_rounds,
             openings: sumcheck_openings,
-            opening_proof: sumcheck_opening_proof,
         };
 
-        let memory_checking = Self::prove_memory_checking(preprocessing, polynomials, transcript);
+        let memory_checking = Self::prove_memory_checking(
+            preprocessing,
+            generators,
+            polynomials,
+            opening_accumulator,
+            transcript,
+        );
 
         InstructionLookupsProof {
             _instructions: PhantomData,
@@ -862,152 +1102,121 @@ where
         generators: &CS::Setup,
         proof: InstructionLookupsProof<C, M, F, CS, InstructionSet, Subtables>,
         commitment: &InstructionCommitment<CS>,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, CS>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         transcript.append_protocol_name(Self::protocol_name());
 
+        Self::check_opening_lengths(preprocessing, &proof)?;
+
         let r_eq = transcript.challenge_vector(
             b"Jolt instruction lookups",
             proof.primary_sumcheck.num_rounds,
         );
 
         // TODO: compartmentalize all primary sumcheck logic
-        let (claim_last, r_primary_sumcheck) = proof.primary_sumcheck.sumcheck_proof.verify(
-            F::zero(),
-            proof.primary_sumcheck.num_rounds,
-            Self::sumcheck_poly_degree(),
-            transcript,
-        )?;
+        (|| {
+            let (claim_last, r_primary_sumcheck) = proof.primary_sumcheck.sumcheck_proof.verify(
+                F::zero(),
+                proof.primary_sumcheck.num_rounds,
+                Self::sumcheck_poly_degree(),
+                transcript,
+            )?;
 
-        // Verify that eq(r, r_z) * [f_1(r_z) * g(E_1(r_z)) + ... + f_F(r_z) * E_F(r_z))] = claim_last
-        let eq_eval = EqPolynomial::new(r_eq.to_vec()).evaluate(&r_primary_sumcheck);
-        assert_eq!(
-            eq_eval
+            // Verify that eq(r, r_z) * [f_1(r_z) * g(E_1(r_z)) + ... + f_F(r_z) * E_F(r_z))] = claim_last
+            let eq_eval = EqPolynomial::new(r_eq.to_vec()).evaluate(&r_primary_sumcheck);
+            if eq_eval
                 * (Self::combine_lookups(
                     preprocessing,
                     &proof.primary_sumcheck.openings.E_poly_openings,
                     &proof.primary_sumcheck.openings.flag_openings,
-                ) - proof.primary_sumcheck.openings.lookup_outputs_opening),
-            claim_last,
-            "Primary sumcheck check failed."
-        );
+                ) - proof.primary_sumcheck.openings.lookup_outputs_opening)
+                != claim_last
+            {
+                return Err(ProofVerifyError::SumcheckClaimError);
+            }
 
-        proof.primary_sumcheck.openings.verify_openings(
-            generators,
-            &proof.primary_sumcheck.opening_proof,
-            commitment,
-            &r_primary_sumcheck,
-            transcript,
-        )?;
+            proof.primary_sumcheck.openings.verify_openings(
+                commitment,
+                &r_primary_sumcheck,
+                opening_accumulator,
+                transcript,
+            )
+        })()
+        .map_err(ProofVerifyError::wrap(
+            ProofVerifyError::PrimarySumcheckError,
+        ))?;
 
         Self::verify_memory_checking(
             preprocessing,
             generators,
             proof.memory_checking,
             commitment,
+            opening_accumulator,
             transcript,
-        )?;
+        )
+        .map_err(ProofVerifyError::wrap(
+            ProofVerifyError::InstructionLookupsError,
+        ))
+    }
 
+    /// Checks that every vector of openings in `proof` has one entry per dimension, memory, or
+    /// instruction (as appropriate), so that the rest of the verifier can index into them.
+    fn check_opening_lengths(
+        preprocessing: &InstructionLookupsPreprocessing<F>,
+        proof: &InstructionLookupsProof<C, M, F, CS, InstructionSet, Subtables>,
+    ) -> Result<(), ProofVerifyError> {
+        let read_write_openings = &proof.memory_checking.read_write_openings;
+        for (expected, actual) in [
+            (
+                preprocessing.num_memories,
+                proof.primary_sumcheck.openings.E_poly_openings.len(),
+            ),
+            (
+                Self::NUM_INSTRUCTIONS,
+                proof.primary_sumcheck.openings.flag_openings.len(),
+            ),
+            (C, read_write_openings.dim_openings.len()),
+            (
+                preprocessing.num_memories,
+                read_write_openings.read_openings.len(),
+            ),
+            (
+                preprocessing.num_memories,
+                read_write_openings.E_poly_openings.len(),
+            ),
+            (
+                Self::NUM_INSTRUCTIONS,
+                read_write_openings.flag_openings.len(),
+            ),
+            (
+                preprocessing.num_memories,
+                proof
+                    .memory_checking
+                    .init_final_openings
+                    .final_openings
+                    .len(),
+            ),
+        ] {
+            if expected != actual {
+                return Err(ProofVerifyError::InvalidInputLength(expected, actual));
+            }
+        }
         Ok(())
     }
 
-    /// Constructs the polynomials used in the primary sumcheck and memory checking.
+    /// Constructs the polynomials used in the primary sumcheck and memory checking. The
+    /// polynomials only store the `ops.len()` steps of the trace; the no-op steps padding it
+    /// to a power of two are implicit.
     #[tracing::instrument(skip_all, name = "InstructionLookups::polynomialize")]
     pub fn polynomialize(
         preprocessing: &InstructionLookupsPreprocessing<F>,
-        ops: &Vec<JoltTraceStep<InstructionSet>>,
+        ops: &[JoltTraceStep<InstructionSet>],
     ) -> InstructionPolynomials<F, CS> {
-        let m: usize = ops.len().next_power_of_two();
-
-        let subtable_lookup_indices: Vec<Vec<usize>> = Self::subtable_lookup_indices(ops);
-
-        let polys: Vec<(DensePolynomial<F>, DensePolynomial<F>, DensePolynomial<F>)> = (0
-            ..preprocessing.num_memories)
-            .into_par_iter()
-            .map(|memory_index| {
-                let dim_index = preprocessing.memory_to_dimension_index[memory_index];
-                let subtable_index = preprocessing.memory_to_subtable_index[memory_index];
-                let access_sequence: &Vec<usize> = &subtable_lookup_indices[dim_index];
-
-                let mut final_cts_i = vec![0usize; M];
-                let mut read_cts_i = vec![0usize; m];
-                let mut subtable_lookups = vec![F::zero(); m];
-
-                for (j, op) in ops.iter().enumerate() {
-                    if let Some(instr) = &op.instruction_lookup {
-                        let memories_used = &preprocessing.instruction_to_memory_indices
-                            [InstructionSet::enum_index(instr)];
-                        if memories_used.contains(&memory_index) {
-                            let memory_address = access_sequence[j];
-                            debug_assert!(memory_address < M);
-
-                            let counter = final_cts_i[memory_address];
-                            read_cts_i[j] = counter;
-                            final_cts_i[memory_address] = counter + 1;
-                            subtable_lookups[j] = preprocessing.materialized_subtables
-                                [subtable_index][memory_address];
-                        }
-                    }
-                }
-
-                (
-                    DensePolynomial::from_usize(&read_cts_i),
-                    DensePolynomial::from_usize(&final_cts_i),
-                    DensePolynomial::new(subtable_lookups),
-                )
-            })
-            .collect();
-
-        // Vec<(DensePolynomial<F>, DensePolynomial<F>, DensePolynomial<F>)> -> (Vec<DensePolynomial<F>>, Vec<DensePolynomial<F>>, Vec<DensePolynomial<F>>)
-        let (read_cts, final_cts, E_polys): (
-            Vec<DensePolynomial<F>>,
-            Vec<DensePolynomial<F>>,
-            Vec<DensePolynomial<F>>,
-        ) = polys.into_iter().fold(
-            (Vec::new(), Vec::new(), Vec::new()),
-            |(mut read_acc, mut final_acc, mut E_acc), (read, f, E)| {
-                read_acc.push(read);
-                final_acc.push(f);
-                E_acc.push(E);
-                (read_acc, final_acc, E_acc)
-            },
-        );
-
-        let dim: Vec<DensePolynomial<F>> = (0..C)
-            .into_par_iter()
-            .map(|i| {
-                let access_sequence: &Vec<usize> = &subtable_lookup_indices[i];
-                DensePolynomial::from_usize(access_sequence)
-            })
-            .collect();
-
-        let mut instruction_flag_bitvectors: Vec<Vec<u64>> =
-            vec![vec![0u64; m]; Self::NUM_INSTRUCTIONS];
-        for (j, op) in ops.iter().enumerate() {
-            if let Some(instr) = &op.instruction_lookup {
-                instruction_flag_bitvectors[InstructionSet::enum_index(instr)][j] = 1;
-            }
-        }
-
-        let instruction_flag_polys: Vec<DensePolynomial<F>> = instruction_flag_bitvectors
-            .par_iter()
-            .map(|flag_bitvector| DensePolynomial::from_u64(flag_bitvector))
-            .collect();
-
-        let mut lookup_outputs = Self::compute_lookup_outputs(ops);
-        lookup_outputs.resize(m, F::zero());
-        let lookup_outputs = DensePolynomial::new(lookup_outputs);
-
-        InstructionPolynomials {
-            _marker: PhantomData,
-            dim,
-            read_cts,
-            final_cts,
-            instruction_flag_polys,
-            instruction_flag_bitvectors,
-            E_polys,
-            lookup_outputs,
-        }
+        let mut builder =
+            InstructionPolynomialsBuilder::<C, M, F, CS, InstructionSet>::new(preprocessing);
+        builder.push_steps(ops);
+        builder.finish()
     }
 
     /// Prove Jolt primary sumcheck including instruction collation.
@@ -1031,9 +1240,9 @@ where
         preprocessing: &InstructionLookupsPreprocessing<F>,
         num_rounds: usize,
         eq_poly: &mut DensePolynomial<F>,
-        memory_polys: &Vec<DensePolynomial<F>>,
-        flag_polys: &Vec<DensePolynomial<F>>,
-        lookup_outputs_poly: &mut DensePolynomial<F>,
+        memory_polys: &[CompactPolynomial<u32, F>],
+        flag_polys: &[SparsePolynomial<F>],
+        lookup_outputs_poly: &CompactPolynomial<u32, F>,
         degree: usize,
         transcript: &mut ProofTranscript,
     ) -> (SumcheckInstanceProof<F>, Vec<F>, Vec<F>, Vec<F>, F) {
@@ -1063,15 +1272,16 @@ where
         let r_j = Self::update_primary_sumcheck_transcript(round_uni_poly, transcript);
         random_vars.push(r_j);
 
+        // Binding the first variable promotes the compact polynomials to (half-size) dense ones
         let _bind_span = trace_span!("BindPolys");
         let _bind_enter = _bind_span.enter();
-        rayon::join(
+        let (_, mut lookup_outputs_poly) = rayon::join(
             || eq_poly.bound_poly_var_top(&r_j),
-            || lookup_outputs_poly.bound_poly_var_top_many_ones(&r_j),
+            || lookup_outputs_poly.new_poly_from_bound_poly_var_top(&r_j),
         );
-        let mut flag_polys_updated: Vec<DensePolynomial<F>> = flag_polys
+        let mut flag_polys_updated: Vec<SparsePolynomial<F>> = flag_polys
             .par_iter()
-            .map(|poly| poly.new_poly_from_bound_poly_var_top_flags(&r_j))
+            .map(|poly| poly.new_poly_from_bound_poly_var_top(&r_j))
             .collect();
         let mut memory_polys_updated: Vec<DensePolynomial<F>> = memory_polys
             .par_iter()
@@ -1086,7 +1296,7 @@ where
                 eq_poly,
                 &flag_polys_updated,
                 &memory_polys_updated,
-                lookup_outputs_poly,
+                &lookup_outputs_poly,
                 num_eval_points,
             );
             compressed_polys.push(round_uni_poly.compress());
@@ -1102,7 +1312,7 @@ where
             );
             flag_polys_updated
                 .par_iter_mut()
-                .for_each(|poly| poly.bound_poly_var_top_many_ones(&r_j));
+                .for_each(|poly| poly.bound_poly_var_top(&r_j));
             memory_polys_updated
                 .par_iter_mut()
                 .for_each(|poly| poly.bound_poly_var_top_many_ones(&r_j));
@@ -1116,8 +1326,10 @@ where
         // - E(r) * NUM_SUBTABLES
 
         // Polys are fully defined so we can just take the first (and only) evaluation
-        // let flag_evals = (0..flag_polys.len()).map(|i| flag_polys[i][0]).collect();
-        let flag_evals = flag_polys_updated.iter().map(|poly| poly[0]).collect();
+        let flag_evals = flag_polys_updated
+            .iter()
+            .map(|poly| poly.get_coeff(0))
+            .collect();
         let memory_evals = memory_polys_updated.iter().map(|poly| poly[0]).collect();
         let outputs_eval = lookup_outputs_poly[0];
 
@@ -1131,121 +1343,102 @@ where
     }
 
     #[tracing::instrument(skip_all, name = "InstructionLookups::primary_sumcheck_inner_loop")]
-    fn primary_sumcheck_inner_loop(
+    fn primary_sumcheck_inner_loop<P: MultilinearPolynomial<F>>(
         preprocessing: &InstructionLookupsPreprocessing<F>,
         eq_poly: &DensePolynomial<F>,
-        flag_polys: &[DensePolynomial<F>],
-        memory_polys: &[DensePolynomial<F>],
-        lookup_outputs_poly: &DensePolynomial<F>,
+        flag_polys: &[SparsePolynomial<F>],
+        memory_polys: &[P],
+        lookup_outputs_poly: &P,
         num_eval_points: usize,
     ) -> UniPoly<F> {
         let mle_len = eq_poly.len();
         let mle_half = mle_len / 2;
 
-        // Loop over half MLE size (size of MLE next round)
-        //   - Compute evaluations of eq, flags, E, at p {0, 1, ..., degree}:
-        //       eq(p, _boolean_hypercube_), flags(p, _boolean_hypercube_), E(p, _boolean_hypercube_)
-        // After: Sum over MLE elements (with combine)
-        let evaluations: Vec<F> = (0..mle_half)
+        // Evaluations at p {0, 1, ..., degree} of the linear polynomial through `low` and `high`
+        let evals_at = |low: F, high: F| -> Vec<F> {
+            let m = high - low;
+            let mut evals = Vec::with_capacity(num_eval_points);
+            evals.push(low);
+            for eval_index in 1..num_eval_points {
+                evals.push(evals[eval_index - 1] + m);
+            }
+            evals
+        };
+        let sum_evals = |running: Vec<F>, new: Vec<F>| -> Vec<F> {
+            debug_assert_eq!(running.len(), new.len());
+            running
+                .iter()
+                .zip(new.iter())
+                .map(|(r, n)| *r + n)
+                .collect()
+        };
+
+        // S({0,1,... num_eval_points}) = eq * [ INNER TERMS ] splits into
+        //            \sum_x eq(x) * [ flags_0(x) * g_0(E(x)) + flags_1(x) * g_1(E(x)) + ... ]
+        //            - \sum_x eq(x) * lookup_outputs(x)
+        // Loop over half MLE size (size of MLE next round) for the lookup outputs term.
+        let outputs_evals: Vec<F> = (0..mle_half)
             .into_par_iter()
             .map(|low_index| {
                 let high_index = mle_half + low_index;
+                let eq_evals = evals_at(eq_poly[low_index], eq_poly[high_index]);
+                let outputs_evals = evals_at(
+                    lookup_outputs_poly.get_coeff(low_index),
+                    lookup_outputs_poly.get_coeff(high_index),
+                );
+                eq_evals
+                    .iter()
+                    .zip(outputs_evals.iter())
+                    .map(|(eq_eval, outputs_eval)| *eq_eval * outputs_eval)
+                    .collect()
+            })
+            .reduce(|| vec![F::zero(); num_eval_points], sum_evals);
 
-                let mut eq_evals: Vec<F> = vec![F::zero(); num_eval_points];
-                let mut outputs_evals: Vec<F> = vec![F::zero(); num_eval_points];
-                let mut multi_flag_evals: Vec<Vec<F>> =
-                    vec![vec![F::zero(); Self::NUM_INSTRUCTIONS]; num_eval_points];
-                let mut multi_memory_evals: Vec<Vec<F>> =
-                    vec![vec![F::zero(); preprocessing.num_memories]; num_eval_points];
-
-                eq_evals[0] = eq_poly[low_index];
-                eq_evals[1] = eq_poly[high_index];
-                let eq_m = eq_poly[high_index] - eq_poly[low_index];
-                for eval_index in 2..num_eval_points {
-                    eq_evals[eval_index] = eq_evals[eval_index - 1] + eq_m;
-                }
-
-                outputs_evals[0] = lookup_outputs_poly[low_index];
-                outputs_evals[1] = lookup_outputs_poly[high_index];
-                let outputs_m = lookup_outputs_poly[high_index] - lookup_outputs_poly[low_index];
-                for eval_index in 2..num_eval_points {
-                    outputs_evals[eval_index] = outputs_evals[eval_index - 1] + outputs_m;
-                }
-
-                // TODO: Exactly one flag across NUM_INSTRUCTIONS is non-zero
-                for flag_instruction_index in 0..Self::NUM_INSTRUCTIONS {
-                    multi_flag_evals[0][flag_instruction_index] =
-                        flag_polys[flag_instruction_index][low_index];
-                    multi_flag_evals[1][flag_instruction_index] =
-                        flag_polys[flag_instruction_index][high_index];
-                    let flag_m = flag_polys[flag_instruction_index][high_index]
-                        - flag_polys[flag_instruction_index][low_index];
-                    for eval_index in 2..num_eval_points {
-                        let flag_eval =
-                            multi_flag_evals[eval_index - 1][flag_instruction_index] + flag_m;
-                        multi_flag_evals[eval_index][flag_instruction_index] = flag_eval;
-                    }
-                }
-
-                // TODO: Some of these intermediates need not be computed if flags is computed
-                for memory_index in 0..preprocessing.num_memories {
-                    multi_memory_evals[0][memory_index] = memory_polys[memory_index][low_index];
-
-                    multi_memory_evals[1][memory_index] = memory_polys[memory_index][high_index];
-                    let memory_m = memory_polys[memory_index][high_index]
-                        - memory_polys[memory_index][low_index];
-                    for eval_index in 2..num_eval_points {
-                        multi_memory_evals[eval_index][memory_index] =
-                            multi_memory_evals[eval_index - 1][memory_index] + memory_m;
-                    }
-                }
-
-                // Accumulate inner terms.
-                // S({0,1,... num_eval_points}) = eq * [ INNER TERMS ]
-                //            = eq[000] * [ flags_0[000] * g_0(E_0)[000] + flags_1[000] * g_1(E_1)[000]]
-                //            + eq[001] * [ flags_0[001] * g_0(E_0)[001] + flags_1[001] * g_1(E_1)[001]]
-                //            + ...
-                //            + eq[111] * [ flags_0[111] * g_0(E_0)[111] + flags_1[111] * g_1(E_1)[111]]
-                let mut inner_sum = vec![F::zero(); num_eval_points];
-                for instruction in InstructionSet::iter() {
-                    let instruction_index = InstructionSet::enum_index(&instruction);
-                    let memory_indices =
-                        &preprocessing.instruction_to_memory_indices[instruction_index];
-
-                    for eval_index in 0..num_eval_points {
-                        let flag_eval = multi_flag_evals[eval_index][instruction_index];
-                        if flag_eval.is_zero() {
-                            continue;
-                        }; // Early exit if no contribution.
-
-                        let terms: Vec<F> = memory_indices
-                            .iter()
-                            .map(|memory_index| multi_memory_evals[eval_index][*memory_index])
-                            .collect();
-                        let instruction_collation_eval = instruction.combine_lookups(&terms, C, M);
+        // The flag polynomials are sparse, so each instruction's term only needs to be computed
+        // at the indices where its flag is non-zero in either half.
+        let mut evaluations: Vec<F> = outputs_evals.into_iter().map(|eval| -eval).collect();
+        for instruction in InstructionSet::iter() {
+            let instruction_index = InstructionSet::enum_index(&instruction);
+            let memory_indices = &preprocessing.instruction_to_memory_indices[instruction_index];
 
-                        // TODO(sragss): Could sum all shared inner terms before multiplying by the flag eval
-                        inner_sum[eval_index] += flag_eval * instruction_collation_eval;
-                    }
-                }
-                let evaluations: Vec<F> = (0..num_eval_points)
-                    .map(|eval_index| {
-                        eq_evals[eval_index] * (inner_sum[eval_index] - outputs_evals[eval_index])
-                    })
-                    .collect();
-                evaluations
-            })
-            .reduce(
-                || vec![F::zero(); num_eval_points],
-                |running, new| {
-                    debug_assert_eq!(running.len(), new.len());
-                    running
+            let instruction_evals: Vec<F> = flag_polys[instruction_index]
+                .low_high_pairs()
+                .into_par_iter()
+                .map(|(low_index, flag_low, flag_high)| {
+                    let high_index = mle_half + low_index;
+                    let eq_evals = evals_at(eq_poly[low_index], eq_poly[high_index]);
+                    let flag_evals = evals_at(flag_low, flag_high);
+                    let memory_evals: Vec<Vec<F>> = memory_indices
                         .iter()
-                        .zip(new.iter())
-                        .map(|(r, n)| *r + n)
+                        .map(|memory_index| {
+                            let memory_poly = &memory_polys[*memory_index];
+                            evals_at(
+                                memory_poly.get_coeff(low_index),
+                                memory_poly.get_coeff(high_index),
+                            )
+                        })
+                        .collect();
+
+                    (0..num_eval_points)
+                        .map(|eval_index| {
+                            let flag_eval = flag_evals[eval_index];
+                            // Early exit if no contribution.
+                            if flag_eval.is_zero() {
+                                return F::zero();
+                            }
+
+                            let terms: Vec<F> =
+                                memory_evals.iter().map(|evals| evals[eval_index]).collect();
+                            let instruction_collation_eval =
+                                instruction.combine_lookups(&terms, C, M);
+                            eq_evals[eval_index] * flag_eval * instruction_collation_eval
+                        })
                         .collect()
-                },
-            );
+                })
+                .reduce(|| vec![F::zero(); num_eval_points], sum_evals);
+
+            evaluations = sum_evals(evaluations, instruction_evals);
+        }
 
         UniPoly::from_evals(&evaluations)
     }
@@ -1299,33 +1492,30 @@ where
         memory_flags
     }
 
-    /// Converts instruction flag bitvectors into a sparse representation of the corresponding memory flags.
+    /// Converts instruction flag polynomials into a sparse representation of the corresponding memory flags.
     /// A memory flag polynomial can be computed by summing over the instructions that use that memory: if a
     /// given execution step accesses the memory, it must be executing exactly one of those instructions.
     fn memory_flag_indices(
         preprocessing: &InstructionLookupsPreprocessing<F>,
-        instruction_flag_bitvectors: &[Vec<u64>],
+        instruction_flag_polys: &[SparsePolynomial<F>],
     ) -> Vec<Vec<usize>> {
-        let m = instruction_flag_bitvectors[0].len();
-
         (0..preprocessing.num_memories)
             .into_par_iter()
             .map(|memory_index| {
-                let instruction_indices: Vec<_> = (0..Self::NUM_INSTRUCTIONS)
+                // The instruction flags are one-hot, so these index sets are disjoint.
+                let mut memory_flag_indices: Vec<usize> = (0..Self::NUM_INSTRUCTIONS)
                     .filter(|instruction_index| {
                         preprocessing.instruction_to_memory_indices[*instruction_index]
                             .contains(&memory_index)
                     })
+                    .flat_map(|instruction_index| {
+                        instruction_flag_polys[instruction_index]
+                            .indices()
+                            .iter()
+                            .copied()
+                    })
                     .collect();
-                let mut memory_flag_indices = vec![];
-                for i in 0..m {
-                    for instruction_index in instruction_indices.iter() {
-                        if instruction_flag_bitvectors[*instruction_index][i] != 0 {
-                            memory_flag_indices.push(i);
-                            break;
-                        }
-                    }
-                }
+                memory_flag_indices.sort_unstable();
                 memory_flag_indices
             })
             .collect()
@@ -1343,31 +1533,7 @@ where
     }
 
     /// Converts each instruction in `ops` into its corresponding subtable lookup indices.
-    /// The output is `C` vectors, each of length `m`.
-    fn subtable_lookup_indices(ops: &Vec<JoltTraceStep<InstructionSet>>) -> Vec<Vec<usize>> {
-        let m = ops.len().next_power_of_two();
-        let log_M = M.log_2();
-        let chunked_indices: Vec<Vec<usize>> = ops
-            .iter()
-            .map(|op| {
-                if let Some(instr) = &op.instruction_lookup {
-                    instr.to_indices(C, log_M)
-                } else {
-                    vec![0; C]
-                }
-            })
-            .collect();
-
-        let mut subtable_lookup_indices: Vec<Vec<usize>> = Vec::with_capacity(C);
-        for i in 0..C {
-            let mut access_sequence: Vec<usize> =
-                chunked_indices.iter().map(|chunks| chunks[i]).collect();
-            access_sequence.resize(m, 0);
-            subtable_lookup_indices.push(access_sequence);
-        }
-        subtable_lookup_indices
-    }
-
+    /// The output is `C` vectors, each of length `ops.len()`.
     /// Computes the shape of all commitments.
     pub fn commitment_shapes(
         preprocessing: &InstructionLookupsPreprocessing<F>,
@@ -1384,20 +1550,6 @@ where
         vec![read_write_generator_shape, init_final_generator_shape]
     }
 
-    #[tracing::instrument(skip_all, name = "InstructionLookupsProof::compute_lookup_outputs")]
-    fn compute_lookup_outputs(instructions: &Vec<JoltTraceStep<InstructionSet>>) -> Vec<F> {
-        instructions
-            .par_iter()
-            .map(|op| {
-                if let Some(instr) = &op.instruction_lookup {
-                    F::from_u64(instr.lookup_entry()).unwrap()
-                } else {
-                    F::zero()
-                }
-            })
-            .collect()
-    }
-
     fn protocol_name() -> &'static [u8] {
         b"Jolt instruction lookups"
     }
@@ -6,6 +6,7 @@ use ark_std::log2;
 use common::constants::RAM_START_ADDRESS;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::io;
 use strum::EnumCount;
 
 use crate::jolt::vm::timestamp_range_check::RangeCheckPolynomials;
@@ -14,25 +15,32 @@ use crate::jolt::{
     vm::timestamp_range_check::TimestampValidityProof,
 };
 use crate::lasso::memory_checking::{MemoryCheckingProver, MemoryCheckingVerifier};
-use crate::poly::commitment::commitment_scheme::{BatchType, CommitmentScheme};
-use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::poly::compact_polynomial::CompactPolynomial;
+use crate::poly::opening_proof::{
+    ProverOpeningAccumulator, ReducedOpeningProof, VerifierOpeningAccumulator,
+};
+use crate::poly::padded_polynomial::PaddedPolynomial;
 use crate::poly::structured_poly::StructuredCommitment;
 use crate::r1cs::snark::{R1CSCommitment, R1CSInputs, R1CSProof};
 use crate::r1cs::spartan::UniformSpartanKey;
 use crate::utils::errors::ProofVerifyError;
-use crate::utils::thread::{drop_in_background_thread, unsafe_allocate_zero_vec};
+use crate::utils::memory_budget::{peak_rss_bytes, MemoryBudget, Spillable};
+use crate::utils::thread::drop_in_background_thread;
 use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
 use common::{
-    constants::MEMORY_OPS_PER_INSTRUCTION,
-    rv_trace::{ELFInstruction, JoltDevice, MemoryOp},
+    constants::{MEMORY_OPS_PER_INSTRUCTION, REGISTER_COUNT},
+    rv_trace::{ELFInstruction, JoltDevice, MemoryLayout, MemoryOp, NUM_CIRCUIT_FLAGS},
 };
 
-use self::bytecode::BytecodePreprocessing;
+use self::bytecode::{BytecodePolynomialsBuilder, BytecodePreprocessing};
 use self::instruction_lookups::{
     InstructionCommitment, InstructionLookupsPreprocessing, InstructionLookupsProof,
+    InstructionPolynomialsBuilder,
 };
 use self::read_write_memory::{
-    MemoryCommitment, ReadWriteMemory, ReadWriteMemoryPreprocessing, ReadWriteMemoryProof,
+    MemoryCommitment, ReadWriteMemory, ReadWriteMemoryBuilder, ReadWriteMemoryPreprocessing,
+    ReadWriteMemoryProof,
 };
 use self::timestamp_range_check::RangeCheckCommitment;
 use self::{
@@ -40,7 +48,7 @@ use self::{
     instruction_lookups::InstructionPolynomials,
 };
 
-use super::instruction::JoltInstructionSet;
+use super::instruction::{InstructionSubtables, JoltInstructionSet};
 
 #[derive(Clone)]
 pub struct JoltPreprocessing<F, PCS>
@@ -52,6 +60,9 @@ where
     pub instruction_lookups: InstructionLookupsPreprocessing<F>,
     pub bytecode: BytecodePreprocessing<F>,
     pub read_write_memory: ReadWriteMemoryPreprocessing,
+    /// The program's instructions, to report the one behind an unsatisfied R1CS step.
+    #[cfg(feature = "r1cs-debug")]
+    pub elf_bytecode: Vec<ELFInstruction>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -62,6 +73,9 @@ pub struct JoltTraceStep<InstructionSet: JoltInstructionSet> {
 }
 
 impl<InstructionSet: JoltInstructionSet> JoltTraceStep<InstructionSet> {
+    /// The step that the trace is implicitly padded with, up to the next power of two.
+    /// Padding steps are never materialized: each set of polynomials fills in the rows
+    /// past the end of the trace with the values of this step.
     fn no_op() -> Self {
         JoltTraceStep {
             instruction_lookup: None,
@@ -77,12 +91,6 @@ impl<InstructionSet: JoltInstructionSet> JoltTraceStep<InstructionSet> {
             ],
         }
     }
-
-    fn pad(trace: &mut Vec<Self>) {
-        let unpadded_length = trace.len();
-        let padded_length = unpadded_length.next_power_of_two();
-        trace.resize(padded_length, Self::no_op());
-    }
 }
 
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
@@ -99,6 +107,7 @@ where
     pub read_write_memory: ReadWriteMemoryProof<F, PCS>,
     pub instruction_lookups: InstructionLookupsProof<C, M, F, PCS, InstructionSet, Subtables>,
     pub r1cs: R1CSProof<F, PCS>,
+    pub opening_proof: ReducedOpeningProof<F, PCS>,
 }
 
 pub struct JoltPolynomials<F, PCS>
@@ -146,109 +155,114 @@ where
 
     #[tracing::instrument(skip_all, name = "JoltPolynomials::commit")]
     fn commit(&self, generators: &PCS::Setup) -> Self::Commitment {
-        let bytecode_trace_polys = vec![
-            &self.bytecode.a_read_write,
-            &self.bytecode.t_read,
-            &self.bytecode.v_read_write[0],
-            &self.bytecode.v_read_write[1],
-            &self.bytecode.v_read_write[2],
-            &self.bytecode.v_read_write[3],
-            &self.bytecode.v_read_write[4],
-            &self.bytecode.v_read_write[5],
-        ];
-        let num_bytecode_trace_polys = bytecode_trace_polys.len();
-
-        let memory_trace_polys: Vec<&DensePolynomial<F>> = [&self.read_write_memory.a_ram]
-            .into_iter()
-            .chain(self.read_write_memory.v_read.iter())
-            .chain([&self.read_write_memory.v_write_rd].into_iter())
-            .chain(self.read_write_memory.v_write_ram.iter())
-            .chain(self.read_write_memory.t_read.iter())
-            .chain(self.read_write_memory.t_write_ram.iter())
-            .collect();
-        let num_memory_trace_polys = memory_trace_polys.len();
+        // Each set of polynomials commits to its small-valued polynomials with the compact
+        // (small-scalar) path, so the commitments are computed per component.
+        let ((bytecode, read_write_memory), (timestamp_range_check, instruction_lookups)) =
+            rayon::join(
+                || {
+                    rayon::join(
+                        || self.bytecode.commit(generators),
+                        || self.read_write_memory.commit(generators),
+                    )
+                },
+                || {
+                    rayon::join(
+                        || self.timestamp_range_check.commit(generators),
+                        || self.instruction_lookups.commit(generators),
+                    )
+                },
+            );
 
-        let range_check_polys: Vec<&DensePolynomial<F>> = self
-            .timestamp_range_check
-            .read_cts_read_timestamp
-            .iter()
-            .chain(self.timestamp_range_check.read_cts_global_minus_read.iter())
-            .chain(self.timestamp_range_check.final_cts_read_timestamp.iter())
-            .chain(
-                self.timestamp_range_check
-                    .final_cts_global_minus_read
-                    .iter(),
-            )
-            .collect();
-        let num_range_check_polys = range_check_polys.len();
+        JoltCommitments {
+            bytecode,
+            read_write_memory,
+            timestamp_range_check,
+            instruction_lookups,
+            r1cs: None,
+        }
+    }
+}
 
-        let instruction_trace_polys: Vec<&DensePolynomial<F>> = self
-            .instruction_lookups
-            .dim
-            .iter()
-            .chain(self.instruction_lookups.read_cts.iter())
-            .chain(self.instruction_lookups.E_polys.iter())
-            .chain(self.instruction_lookups.instruction_flag_polys.iter())
-            .chain([&self.instruction_lookups.lookup_outputs].into_iter())
-            .collect();
+/// Builds the `JoltPolynomials` of a trace from chunks of its steps, as they are produced,
+/// so that filling in the polynomials overlaps with running the program. Only the timestamp
+/// range-check polynomials wait for the whole trace, as they are derived from the memory
+/// read timestamps.
+pub struct JoltPolynomialsBuilder<'a, F, PCS, const C: usize, const M: usize, InstructionSet>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+    InstructionSet: JoltInstructionSet,
+{
+    bytecode: BytecodePolynomialsBuilder<'a, F, PCS>,
+    read_write_memory: ReadWriteMemoryBuilder<F, PCS, InstructionSet>,
+    instruction_lookups: InstructionPolynomialsBuilder<'a, C, M, F, PCS, InstructionSet>,
+}
 
-        let all_trace_polys = bytecode_trace_polys
-            .into_iter()
-            .chain(memory_trace_polys.into_iter())
-            .chain(range_check_polys.into_iter())
-            .chain(instruction_trace_polys.into_iter())
-            .collect::<Vec<_>>();
-        let mut trace_comitments =
-            PCS::batch_commit_polys_ref(&all_trace_polys, generators, BatchType::Big);
-
-        let bytecode_trace_commitment = trace_comitments
-            .drain(..num_bytecode_trace_polys)
-            .collect::<Vec<_>>();
-        let memory_trace_commitment = trace_comitments
-            .drain(..num_memory_trace_polys)
-            .collect::<Vec<_>>();
-        let range_check_commitment = trace_comitments
-            .drain(..num_range_check_polys)
-            .collect::<Vec<_>>();
-        let instruction_trace_commitment = trace_comitments;
-
-        let bytecode_t_final_commitment = PCS::commit(&self.bytecode.t_final, generators);
-        let (memory_v_final_commitment, memory_t_final_commitment) = rayon::join(
-            || PCS::commit(&self.read_write_memory.v_final, generators),
-            || PCS::commit(&self.read_write_memory.t_final, generators),
-        );
-        let instruction_final_commitment = PCS::batch_commit_polys(
-            &self.instruction_lookups.final_cts,
-            generators,
-            BatchType::Big,
+impl<'a, F, PCS, const C: usize, const M: usize, InstructionSet>
+    JoltPolynomialsBuilder<'a, F, PCS, C, M, InstructionSet>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+    InstructionSet: JoltInstructionSet,
+{
+    /// Only the inputs and memory layout of `program_io` are used, so it may be created
+    /// before the program runs.
+    pub fn new(program_io: &JoltDevice, preprocessing: &'a JoltPreprocessing<F, PCS>) -> Self {
+        Self {
+            bytecode: BytecodePolynomialsBuilder::new(&preprocessing.bytecode),
+            read_write_memory: ReadWriteMemoryBuilder::new(
+                program_io,
+                &preprocessing.read_write_memory,
+            ),
+            instruction_lookups: InstructionPolynomialsBuilder::new(
+                &preprocessing.instruction_lookups,
+            ),
+        }
+    }
+
+    /// Fills in the polynomials for the next `steps` of the trace, compressing their bytecode
+    /// addresses (see `BytecodePolynomialsBuilder::push_steps`).
+    #[tracing::instrument(skip_all, name = "JoltPolynomialsBuilder::push_steps")]
+    pub fn push_steps(&mut self, steps: &mut [JoltTraceStep<InstructionSet>]) {
+        self.bytecode.push_steps(steps);
+        let steps = &*steps;
+        rayon::join(
+            || self.instruction_lookups.push_steps(steps),
+            || self.read_write_memory.push_steps(steps),
         );
+    }
 
-        JoltCommitments {
-            bytecode: BytecodeCommitment {
-                trace_commitments: bytecode_trace_commitment,
-                t_final_commitment: bytecode_t_final_commitment,
-            },
-            read_write_memory: MemoryCommitment {
-                trace_commitments: memory_trace_commitment,
-                v_final_commitment: memory_v_final_commitment,
-                t_final_commitment: memory_t_final_commitment,
-            },
-            timestamp_range_check: RangeCheckCommitment {
-                commitments: range_check_commitment,
-            },
-            instruction_lookups: InstructionCommitment {
-                trace_commitment: instruction_trace_commitment,
-                final_commitment: instruction_final_commitment,
-            },
-            r1cs: None,
+    /// Builds the polynomials once the whole trace has been pushed, given the program's
+    /// final I/O.
+    #[tracing::instrument(skip_all, name = "JoltPolynomialsBuilder::finish")]
+    pub fn finish(self, program_io: &JoltDevice) -> JoltPolynomials<F, PCS> {
+        let Self {
+            bytecode,
+            read_write_memory,
+            instruction_lookups,
+        } = self;
+        let (read_write_memory, (bytecode, instruction_lookups)) = rayon::join(
+            || read_write_memory.finish(program_io),
+            || rayon::join(|| bytecode.finish(), || instruction_lookups.finish()),
+        );
+        let timestamp_range_check =
+            RangeCheckPolynomials::<F, PCS>::new(read_write_memory.t_read.clone());
+
+        JoltPolynomials {
+            bytecode,
+            read_write_memory,
+            timestamp_range_check,
+            instruction_lookups,
         }
     }
 }
 
 pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, const M: usize> {
-    type InstructionSet: JoltInstructionSet;
+    type InstructionSet: JoltInstructionSet + InstructionSubtables<F, Self::Subtables>;
     type Subtables: JoltSubtableSet<F>;
 
+    /// If `fixed_base_budget` is set, up to that many bytes are spent on a precomputation over
+    /// the commitment generators (see `CommitmentScheme::precompute`) that speeds up proving.
     #[tracing::instrument(skip_all, name = "Jolt::preprocess")]
     fn preprocess(
         bytecode: Vec<ELFInstruction>,
@@ -256,6 +270,7 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         max_bytecode_size: usize,
         max_memory_address: usize,
         max_trace_length: usize,
+        fixed_base_budget: Option<usize>,
     ) -> JoltPreprocessing<F, PCS> {
         let bytecode_commitment_shapes =
             BytecodePolynomials::<F, PCS>::commit_shapes(max_bytecode_size, max_trace_length);
@@ -297,76 +312,120 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
             instruction_lookups_commitment_shapes,
         ]
         .concat();
-        let generators = PCS::setup(&commitment_shapes);
+        let mut generators = PCS::setup(&commitment_shapes);
+        if let Some(budget) = fixed_base_budget {
+            PCS::precompute(&mut generators, budget);
+        }
 
         JoltPreprocessing {
             generators,
             instruction_lookups: instruction_lookups_preprocessing,
             bytecode: bytecode_preprocessing,
             read_write_memory: read_write_memory_preprocessing,
+            #[cfg(feature = "r1cs-debug")]
+            elf_bytecode: bytecode,
         }
     }
 
-    #[tracing::instrument(skip_all, name = "Jolt::prove")]
+    /// Builds the polynomials that the prover commits to before any of the subprotocols run,
+    /// from the whole trace at once. Use a `JoltPolynomialsBuilder` instead to build them while
+    /// the trace is being produced.
+    #[tracing::instrument(skip_all, name = "Jolt::polynomialize")]
+    fn polynomialize(
+        program_io: &JoltDevice,
+        trace: &mut [JoltTraceStep<Self::InstructionSet>],
+        preprocessing: &JoltPreprocessing<F, PCS>,
+    ) -> JoltPolynomials<F, PCS> {
+        let mut builder = JoltPolynomialsBuilder::<F, PCS, C, M, Self::InstructionSet>::new(
+            program_io,
+            preprocessing,
+        );
+        builder.push_steps(trace);
+        builder.finish(program_io)
+    }
+
     fn prove(
         program_io: JoltDevice,
-        mut trace: Vec<JoltTraceStep<Self::InstructionSet>>,
+        trace: Vec<JoltTraceStep<Self::InstructionSet>>,
         circuit_flags: Vec<F>,
         preprocessing: JoltPreprocessing<F, PCS>,
     ) -> (
         JoltProof<C, M, F, PCS, Self::InstructionSet, Self::Subtables>,
         JoltCommitments<PCS>,
     ) {
+        Self::prove_with_memory_budget(program_io, trace, circuit_flags, preprocessing, None)
+            .expect("nothing is spilled without a memory budget")
+    }
+
+    /// Same as `prove`, but if a `memory_budget` is given, large values that are not needed by
+    /// the subprotocol currently being proven are spilled to scratch files whenever keeping them
+    /// resident would exceed the budget. Fails if a scratch file can't be written or read.
+    fn prove_with_memory_budget(
+        program_io: JoltDevice,
+        mut trace: Vec<JoltTraceStep<Self::InstructionSet>>,
+        circuit_flags: Vec<F>,
+        preprocessing: JoltPreprocessing<F, PCS>,
+        memory_budget: Option<MemoryBudget>,
+    ) -> io::Result<(
+        JoltProof<C, M, F, PCS, Self::InstructionSet, Self::Subtables>,
+        JoltCommitments<PCS>,
+    )> {
+        let jolt_polynomials = Self::polynomialize(&program_io, &mut trace, &preprocessing);
+        Self::prove_polynomials(
+            program_io,
+            trace,
+            jolt_polynomials,
+            circuit_flags,
+            preprocessing,
+            memory_budget,
+        )
+    }
+
+    /// Same as `prove_with_memory_budget`, given the polynomials already built from `trace`,
+    /// e.g. by a `JoltPolynomialsBuilder` while the program ran. The steps of `trace` must have
+    /// been pushed to that builder, which compresses their bytecode addresses.
+    #[tracing::instrument(skip_all, name = "Jolt::prove")]
+    fn prove_polynomials(
+        program_io: JoltDevice,
+        trace: Vec<JoltTraceStep<Self::InstructionSet>>,
+        jolt_polynomials: JoltPolynomials<F, PCS>,
+        circuit_flags: Vec<F>,
+        preprocessing: JoltPreprocessing<F, PCS>,
+        memory_budget: Option<MemoryBudget>,
+    ) -> io::Result<(
+        JoltProof<C, M, F, PCS, Self::InstructionSet, Self::Subtables>,
+        JoltCommitments<PCS>,
+    )> {
         let trace_length = trace.len();
         let padded_trace_length = trace_length.next_power_of_two();
         println!("Trace length: {}", trace_length);
 
-        JoltTraceStep::pad(&mut trace);
-
         let mut transcript = ProofTranscript::new(b"Jolt transcript");
         Self::fiat_shamir_preamble(&mut transcript, &program_io, trace_length);
 
-        let instruction_polynomials = InstructionLookupsProof::<
-            C,
-            M,
-            F,
-            PCS,
-            Self::InstructionSet,
-            Self::Subtables,
-        >::polynomialize(
-            &preprocessing.instruction_lookups, &trace
-        );
-
-        let load_store_flags = &instruction_polynomials.instruction_flag_polys[5..10];
-        let (memory_polynomials, read_timestamps) = ReadWriteMemory::new(
-            &program_io,
-            load_store_flags,
-            &preprocessing.read_write_memory,
-            &trace,
-        );
-
-        let (bytecode_polynomials, range_check_polys) = rayon::join(
-            || BytecodePolynomials::<F, PCS>::new(&preprocessing.bytecode, &mut trace),
-            || RangeCheckPolynomials::<F, PCS>::new(read_timestamps),
-        );
-
-        let jolt_polynomials = JoltPolynomials {
-            bytecode: bytecode_polynomials,
-            read_write_memory: memory_polynomials,
-            timestamp_range_check: range_check_polys,
-            instruction_lookups: instruction_polynomials,
-        };
-
         let mut jolt_commitments = jolt_polynomials.commit(&preprocessing.generators);
 
-        let (spartan_key, witness_segments, r1cs_commitments) = Self::r1cs_setup(
-            padded_trace_length,
-            RAM_START_ADDRESS - program_io.memory_layout.ram_witness_offset,
-            &trace,
-            &jolt_polynomials,
-            circuit_flags,
-            &preprocessing.generators,
-        );
+        let (spartan_key, witness_segments, small_witness_segments, r1cs_commitments) =
+            Self::r1cs_setup(
+                padded_trace_length,
+                RAM_START_ADDRESS - program_io.memory_layout.ram_witness_offset,
+                &trace,
+                &jolt_polynomials,
+                circuit_flags,
+                &preprocessing,
+            );
+        // Spartan allocates the largest vectors of all the subprotocols, so under a memory
+        // budget, room is kept for them whenever deciding what to keep resident until then.
+        let memory_budget =
+            memory_budget.map(|budget| budget.reserving(spartan_key.prover_working_set_bytes()));
+        // The R1CS witness (and its copy as integers) is the largest value held by the prover,
+        // and isn't needed until the very end, when Spartan reads it in place.
+        let budget = memory_budget.as_ref();
+        let witness_segments = PaddedPolynomial::spill_batch(witness_segments, budget)?;
+        let small_witness_segments = small_witness_segments
+            .map(|segments| PaddedPolynomial::spill_batch(segments, budget))
+            .transpose()?;
+        drop(trace);
 
         // append the digest of vk (which includes R1CS matrices) and the RelaxedR1CSInstance to the transcript
         transcript.append_scalar(b"spartan key", &spartan_key.vk_digest);
@@ -375,29 +434,91 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
 
         jolt_commitments.append_to_transcript(&mut transcript);
 
+        let mut opening_accumulator = ProverOpeningAccumulator::new();
+
+        // Each subprotocol only reads its own polynomials, so under a memory budget the others
+        // are spilled while it runs and read back in once they're needed. The openings
+        // accumulated so far aren't read until the very end either.
+        let JoltPolynomials {
+            bytecode,
+            read_write_memory,
+            timestamp_range_check,
+            instruction_lookups,
+        } = jolt_polynomials;
+        // They're spilled together: each is only kept resident if all of them fit.
+        let idle_budget = memory_budget.clone().map(|budget| {
+            budget.reserving(
+                instruction_lookups.uncompressed_size()
+                    + read_write_memory.uncompressed_size()
+                    + timestamp_range_check.uncompressed_size(),
+            )
+        });
+        let instruction_lookups = Spillable::new(instruction_lookups, idle_budget.as_ref())?;
+        let read_write_memory = Spillable::new(read_write_memory, idle_budget.as_ref())?;
+        let timestamp_range_check = Spillable::new(timestamp_range_check, idle_budget.as_ref())?;
+
         let bytecode_proof = BytecodeProof::prove_memory_checking(
             &preprocessing.bytecode,
-            &jolt_polynomials.bytecode,
+            &preprocessing.generators,
+            &bytecode,
+            &mut opening_accumulator,
             &mut transcript,
         );
+        let bytecode = Spillable::new(bytecode, budget)?;
+        opening_accumulator.spill(budget)?;
 
+        let instruction_lookups = instruction_lookups.into_inner()?;
         let instruction_proof = InstructionLookupsProof::prove(
-            &jolt_polynomials.instruction_lookups,
+            &instruction_lookups,
             &preprocessing.instruction_lookups,
+            &preprocessing.generators,
+            &mut opening_accumulator,
             &mut transcript,
         );
+        if memory_budget.is_some() {
+            drop(instruction_lookups);
+        } else {
+            drop_in_background_thread(instruction_lookups);
+        }
+        opening_accumulator.spill(budget)?;
 
+        // The memory-checking proof also opens the bytecode's register addresses, but doesn't
+        // read the instruction lookup polynomials.
+        let jolt_polynomials = JoltPolynomials {
+            bytecode: bytecode.into_inner()?,
+            read_write_memory: read_write_memory.into_inner()?,
+            timestamp_range_check: timestamp_range_check.into_inner()?,
+            instruction_lookups: InstructionPolynomials::default(),
+        };
         let memory_proof = ReadWriteMemoryProof::prove(
             &preprocessing.read_write_memory,
+            &preprocessing.generators,
             &jolt_polynomials,
             &program_io,
+            &mut opening_accumulator,
             &mut transcript,
         );
 
-        drop_in_background_thread(jolt_polynomials);
+        if memory_budget.is_some() {
+            // Free the polynomials before the witness is paged in.
+            drop(jolt_polynomials);
+        } else {
+            drop_in_background_thread(jolt_polynomials);
+        }
+        opening_accumulator.spill(budget)?;
 
-        let r1cs_proof =
-            R1CSProof::prove(spartan_key, witness_segments, &mut transcript).expect("proof failed");
+        let r1cs_proof = R1CSProof::prove(
+            spartan_key,
+            witness_segments,
+            small_witness_segments,
+            &mut opening_accumulator,
+            &mut transcript,
+        )
+        .expect("proof failed");
+
+        // Batch-prove all openings accumulated over the course of the above sub-protocols
+        let opening_proof =
+            opening_accumulator.reduce_and_prove::<PCS>(&preprocessing.generators, &mut transcript);
 
         let jolt_proof = JoltProof {
             trace_length,
@@ -406,9 +527,18 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
             read_write_memory: memory_proof,
             instruction_lookups: instruction_proof,
             r1cs: r1cs_proof,
+            opening_proof,
         };
 
-        (jolt_proof, jolt_commitments)
+        if let (Some(memory_budget), Some(peak_rss)) = (memory_budget, peak_rss_bytes()) {
+            tracing::info!(
+                peak_rss_mb = peak_rss >> 20,
+                budget_mb = memory_budget.max_bytes >> 20,
+                "proved under a memory budget"
+            );
+        }
+
+        Ok((jolt_proof, jolt_commitments))
     }
 
     fn verify(
@@ -416,6 +546,8 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         proof: JoltProof<C, M, F, PCS, Self::InstructionSet, Self::Subtables>,
         commitments: JoltCommitments<PCS>,
     ) -> Result<(), ProofVerifyError> {
+        Self::verify_program_io(&proof.program_io)?;
+
         let mut transcript = ProofTranscript::new(b"Jolt transcript");
         Self::fiat_shamir_preamble(&mut transcript, &proof.program_io, proof.trace_length);
 
@@ -424,11 +556,14 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
 
         commitments.append_to_transcript(&mut transcript);
 
+        let mut opening_accumulator = VerifierOpeningAccumulator::new();
+
         Self::verify_bytecode(
             &preprocessing.bytecode,
             &preprocessing.generators,
             proof.bytecode,
             &commitments.bytecode,
+            &mut opening_accumulator,
             &mut transcript,
         )?;
         Self::verify_instruction_lookups(
@@ -436,6 +571,7 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
             &preprocessing.generators,
             proof.instruction_lookups,
             &commitments.instruction_lookups,
+            &mut opening_accumulator,
             &mut transcript,
         )?;
         Self::verify_memory(
@@ -444,15 +580,24 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
             proof.read_write_memory,
             &commitments,
             proof.program_io,
+            &mut opening_accumulator,
             &mut transcript,
         )?;
         Self::verify_r1cs(
-            &preprocessing.generators,
             proof.r1cs,
             commitments,
+            &mut opening_accumulator,
             &mut transcript,
         )?;
-        Ok(())
+
+        // Batch-verify all openings accumulated over the course of the above sub-protocols
+        opening_accumulator
+            .reduce_and_verify(
+                &preprocessing.generators,
+                &proof.opening_proof,
+                &mut transcript,
+            )
+            .map_err(ProofVerifyError::wrap(ProofVerifyError::OpeningProofError))
     }
 
     fn verify_instruction_lookups(
@@ -460,9 +605,17 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         generators: &PCS::Setup,
         proof: InstructionLookupsProof<C, M, F, PCS, Self::InstructionSet, Self::Subtables>,
         commitment: &InstructionCommitment<PCS>,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        InstructionLookupsProof::verify(preprocessing, generators, proof, commitment, transcript)
+        InstructionLookupsProof::verify(
+            preprocessing,
+            generators,
+            proof,
+            commitment,
+            opening_accumulator,
+            transcript,
+        )
     }
 
     fn verify_bytecode(
@@ -470,6 +623,7 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         generators: &PCS::Setup,
         proof: BytecodeProof<F, PCS>,
         commitment: &BytecodeCommitment<PCS>,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         BytecodeProof::verify_memory_checking(
@@ -477,8 +631,10 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
             generators,
             proof,
             commitment,
+            opening_accumulator,
             transcript,
         )
+        .map_err(ProofVerifyError::wrap(ProofVerifyError::BytecodeError))
     }
 
     fn verify_memory(
@@ -487,24 +643,53 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         proof: ReadWriteMemoryProof<F, PCS>,
         commitment: &JoltCommitments<PCS>,
         program_io: JoltDevice,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        assert!(program_io.inputs.len() <= program_io.memory_layout.max_input_size as usize);
-        assert!(program_io.outputs.len() <= program_io.memory_layout.max_output_size as usize);
         preprocessing.program_io = Some(program_io);
 
-        ReadWriteMemoryProof::verify(proof, generators, preprocessing, commitment, transcript)
+        ReadWriteMemoryProof::verify(
+            proof,
+            generators,
+            preprocessing,
+            commitment,
+            opening_accumulator,
+            transcript,
+        )
+    }
+
+    /// Checks that the claimed inputs and outputs fit the memory layout, and that the layout
+    /// is the one derived from the maximum input and output sizes.
+    fn verify_program_io(program_io: &JoltDevice) -> Result<(), ProofVerifyError> {
+        let memory_layout = &program_io.memory_layout;
+        if program_io.inputs.len() as u64 > memory_layout.max_input_size {
+            return Err(ProofVerifyError::InputTooLarge);
+        }
+        if program_io.outputs.len() as u64 > memory_layout.max_output_size {
+            return Err(ProofVerifyError::OutputTooLarge);
+        }
+        // The rest of the layout is derived from the I/O sizes, which must leave room for RAM
+        let io_size = REGISTER_COUNT
+            .checked_add(memory_layout.max_input_size)
+            .and_then(|size| size.checked_add(memory_layout.max_output_size));
+        if io_size.map_or(true, |size| size >= RAM_START_ADDRESS)
+            || *memory_layout
+                != MemoryLayout::new(memory_layout.max_input_size, memory_layout.max_output_size)
+        {
+            return Err(ProofVerifyError::InvalidMemoryLayout);
+        }
+        Ok(())
     }
 
     fn verify_r1cs(
-        generators: &PCS::Setup,
         proof: R1CSProof<F, PCS>,
         commitments: JoltCommitments<PCS>,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         proof
-            .verify(generators, commitments, C, transcript)
-            .map_err(|e| ProofVerifyError::SpartanError(e.to_string()))
+            .verify::<C, Self::InstructionSet>(commitments, opening_accumulator, transcript)
+            .map_err(ProofVerifyError::R1CSError)
     }
 
     fn r1cs_setup(
@@ -513,19 +698,30 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         instructions: &[JoltTraceStep<Self::InstructionSet>],
         polynomials: &JoltPolynomials<F, PCS>,
         circuit_flags: Vec<F>,
-        generators: &PCS::Setup,
-    ) -> (UniformSpartanKey<F>, Vec<Vec<F>>, R1CSCommitment<PCS>) {
+        preprocessing: &JoltPreprocessing<F, PCS>,
+    ) -> (
+        UniformSpartanKey<F>,
+        Vec<PaddedPolynomial<F>>,
+        Option<Vec<PaddedPolynomial<i128>>>,
+        R1CSCommitment<PCS>,
+    ) {
         let log_M = log2(M) as usize;
 
+        // The inputs are stored for every step of the trace, and for the first no-op step padding
+        // it (which the memory polynomials store, see `ReadWriteMemory::new`). Later steps take
+        // the same inputs as that one.
+        let trace_length = instructions.len();
+        let num_steps = std::cmp::min(trace_length + 1, padded_trace_length);
+
         // Assemble the polynomials and commitments from the rest of Jolt.
 
         // Derive chunks_x and chunks_y
         let span = tracing::span!(tracing::Level::INFO, "compute_chunks_operands");
         let _guard = span.enter();
 
-        let num_chunks = padded_trace_length * C;
-        let mut chunks_x: Vec<F> = unsafe_allocate_zero_vec(num_chunks);
-        let mut chunks_y: Vec<F> = unsafe_allocate_zero_vec(num_chunks);
+        let num_chunks = num_steps * C;
+        let mut chunks_x: Vec<u64> = vec![0; num_chunks];
+        let mut chunks_y: Vec<u64> = vec![0; num_chunks];
 
         for (instruction_index, op) in instructions.iter().enumerate() {
             if let Some(instr) = &op.instruction_lookup {
@@ -535,15 +731,9 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
                     .zip(chunks_y_op.into_iter())
                     .enumerate()
                 {
-                    let flat_chunk_index = instruction_index + chunk_index * padded_trace_length;
-                    chunks_x[flat_chunk_index] = F::from_u64(x).unwrap();
-                    chunks_y[flat_chunk_index] = F::from_u64(y).unwrap();
-                }
-            } else {
-                for chunk_index in 0..C {
-                    let flat_chunk_index = instruction_index + chunk_index * padded_trace_length;
-                    chunks_x[flat_chunk_index] = F::zero();
-                    chunks_y[flat_chunk_index] = F::zero();
+                    let flat_chunk_index = instruction_index + chunk_index * num_steps;
+                    chunks_x[flat_chunk_index] = x;
+                    chunks_y[flat_chunk_index] = y;
                 }
             }
         }
@@ -553,33 +743,75 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
 
         let span = tracing::span!(tracing::Level::INFO, "flatten instruction_flags");
         let _enter = span.enter();
-        let instruction_flags: Vec<F> =
-            DensePolynomial::flatten(&polynomials.instruction_lookups.instruction_flag_polys);
+        let instruction_flag_polys = &polynomials.instruction_lookups.instruction_flag_polys;
+        let mut instruction_flags: Vec<u64> = vec![0; instruction_flag_polys.len() * num_steps];
+        instruction_flags
+            .par_chunks_mut(num_steps)
+            .zip(instruction_flag_polys.par_iter())
+            .for_each(|(flags, poly)| {
+                for &index in poly.indices() {
+                    flags[index] = 1;
+                }
+            });
         drop(_enter);
         drop(span);
 
-        let (bytecode_a, bytecode_v) = polynomials.bytecode.get_polys_r1cs();
-        let (memreg_a_rw, memreg_v_reads, memreg_v_writes) =
-            polynomials.read_write_memory.get_polys_r1cs();
+        let ((bytecode_a, bytecode_a_padding), (bytecode_v, bytecode_v_padding)) =
+            polynomials.bytecode.get_polys_r1cs(num_steps);
+        let (
+            (memreg_a_rw, memreg_a_rw_padding),
+            (memreg_v_reads, memreg_v_reads_padding),
+            (memreg_v_writes, memreg_v_writes_padding),
+        ) = polynomials.read_write_memory.get_polys_r1cs(num_steps);
 
         let span = tracing::span!(tracing::Level::INFO, "chunks_query");
         let _guard = span.enter();
-        let mut chunks_query: Vec<F> =
-            Vec::with_capacity(C * polynomials.instruction_lookups.dim[0].len());
-        for i in 0..C {
-            chunks_query.par_extend(
-                polynomials.instruction_lookups.dim[i]
-                    .evals_ref()
-                    .par_iter(),
-            );
-        }
+        let (chunks_query, chunks_query_padding) =
+            CompactPolynomial::flatten(&polynomials.instruction_lookups.dim[..C], num_steps);
         drop(_guard);
 
-        // Flattening this out into a Vec<F> and chunking into padded_trace_length-sized chunks
+        let mut lookup_outputs: Vec<u64> = instructions
+            .par_iter()
+            .map(|op| {
+                op.instruction_lookup
+                    .as_ref()
+                    .map_or(0, |instr| instr.lookup_entry())
+            })
+            .collect();
+        lookup_outputs.resize(num_steps, 0);
+
+        // The circuit flags are bits, given for each step of the trace.
+        let mut circuit_flags_bits: Vec<u64> = vec![0; NUM_CIRCUIT_FLAGS * num_steps];
+        if trace_length > 0 {
+            circuit_flags_bits
+                .par_chunks_mut(num_steps)
+                .zip(circuit_flags.par_chunks(trace_length))
+                .for_each(|(bits, flags)| {
+                    for (bit, flag) in bits.iter_mut().zip(flags) {
+                        *bit = u64::from(!flag.is_zero());
+                    }
+                });
+        }
+
+        // The no-op doesn't look anything up, and sets no flags.
+        let padding: Vec<u64> = [
+            bytecode_a_padding,
+            bytecode_v_padding,
+            memreg_a_rw_padding,
+            memreg_v_reads_padding,
+            memreg_v_writes_padding,
+            vec![0; 2 * C],
+            chunks_query_padding,
+            vec![0; 1 + NUM_CIRCUIT_FLAGS + instruction_flag_polys.len()],
+        ]
+        .concat();
+
+        // Flattening this out into a Vec<F> and chunking into num_steps-sized chunks
         // will be the exact witness vector to feed into the R1CS
         // after pre-pending IO and appending the AUX
-        let inputs: R1CSInputs<F> = R1CSInputs::new(
+        let inputs = R1CSInputs::new(
             padded_trace_length,
+            num_steps,
             bytecode_a,
             bytecode_v,
             memreg_a_rw,
@@ -588,23 +820,39 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
             chunks_x,
             chunks_y,
             chunks_query,
-            polynomials.instruction_lookups.lookup_outputs.evals(),
-            circuit_flags,
+            lookup_outputs,
+            circuit_flags_bits,
             instruction_flags,
+            padding,
         );
 
-        let (spartan_key, witness_segments, r1cs_commitments) =
-            R1CSProof::<F, PCS>::compute_witness_commit(
-                32,
-                C,
-                padded_trace_length,
-                memory_start,
-                &inputs,
-                generators,
-            )
-            .expect("R1CSProof setup failed");
+        #[cfg(feature = "r1cs-debug")]
+        if let Err((step, description)) =
+            inputs.check_satisfied::<F, C, M, Self::InstructionSet>(memory_start)
+        {
+            match instructions.get(step) {
+                Some(trace_step) => {
+                    let pc = trace_step.bytecode_row.elf_address();
+                    let elf_instruction = preprocessing
+                        .elf_bytecode
+                        .iter()
+                        .find(|instruction| instruction.address == pc);
+                    panic!(
+                        "R1CS not satisfied at step {step} (pc {pc:#x}, {elf_instruction:?}): \
+                         {description}",
+                    )
+                }
+                None => panic!("R1CS not satisfied at padding step {step}: {description}"),
+            }
+        }
 
-        (spartan_key, witness_segments, r1cs_commitments)
+        R1CSProof::<F, PCS>::compute_witness_commit::<C, M, Self::InstructionSet>(
+            padded_trace_length,
+            memory_start,
+            &inputs,
+            &preprocessing.generators,
+        )
+        .expect("R1CSProof setup failed")
     }
 
     fn fiat_shamir_preamble(
@@ -630,3 +878,500 @@ pub mod instruction_lookups;
 pub mod read_write_memory;
 pub mod rv32i_vm;
 pub mod timestamp_range_check;
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::{Fr, G1Projective};
+    use ark_serialize::CanonicalSerialize;
+    use common::constants::{DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, RAM_START_ADDRESS};
+    use common::rv_trace::{ELFInstruction, JoltDevice, RVTraceRow, RegisterState, RV32IM};
+    use serde::{Deserialize, Serialize};
+    use strum::EnumCount;
+    use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
+
+    use super::bytecode::{opcode_bits, BytecodePolynomials, BytecodeRow};
+    use super::read_write_memory::ReadWriteMemory;
+    use super::{Jolt, JoltPolynomialsBuilder, JoltTraceStep};
+    use crate::host::circuit_flag_trace;
+    use crate::jolt::instruction::{
+        add::ADDInstruction, xor::XORInstruction, JoltInstruction, JoltInstructionSet,
+    };
+    use crate::jolt::subtable::{
+        identity::IdentitySubtable, truncate_overflow::TruncateOverflowSubtable, xor::XorSubtable,
+        JoltSubtableSet,
+    };
+    use crate::msm::fixed_base::FixedBaseTable;
+    use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+    use crate::poly::commitment::hyrax::HyraxScheme;
+    use crate::poly::field::JoltField;
+    use crate::poly::structured_poly::StructuredCommitment;
+    use crate::utils::memory_budget::{peak_rss_bytes, MemoryBudget};
+
+    #[allow(clippy::upper_case_acronyms)]
+    #[derive(
+        Copy, Clone, Debug, EnumIter, EnumCountMacro, Serialize, Deserialize, JoltInstructionSet,
+    )]
+    enum AddXorInstructions {
+        ADD(ADDInstruction<32>),
+        XOR(XORInstruction),
+    }
+
+    impl TryFrom<&ELFInstruction> for AddXorInstructions {
+        type Error = &'static str;
+
+        fn try_from(instruction: &ELFInstruction) -> Result<Self, Self::Error> {
+            match instruction.opcode {
+                RV32IM::ADD | RV32IM::ADDI => Ok(ADDInstruction::default().into()),
+                RV32IM::XOR | RV32IM::XORI => Ok(XORInstruction::default().into()),
+                _ => Err("No corresponding instruction"),
+            }
+        }
+    }
+
+    // Removing any of these variants makes `impl Jolt for AddXorVM` fail to compile
+    #[allow(clippy::upper_case_acronyms)]
+    #[derive(EnumCountMacro, EnumIter, JoltSubtableSet)]
+    enum AddXorSubtables<F: JoltField> {
+        IDENTITY(IdentitySubtable<F>),
+        TRUNCATE(TruncateOverflowSubtable<F, 32>),
+        XOR(XorSubtable<F>),
+    }
+
+    enum AddXorVM {}
+
+    impl<F, CS> Jolt<F, CS, 4, { 1 << 16 }> for AddXorVM
+    where
+        F: JoltField,
+        CS: CommitmentScheme<Field = F>,
+    {
+        type InstructionSet = AddXorInstructions;
+        type Subtables = AddXorSubtables<F>;
+    }
+
+    #[test]
+    fn custom_vm_e2e() {
+        let instruction = |index: u64, opcode, rs1, rd, imm: Option<u32>| ELFInstruction {
+            address: RAM_START_ADDRESS + 4 * index,
+            opcode,
+            rs1: Some(rs1),
+            rs2: if imm.is_none() { Some(11) } else { None },
+            rd: Some(rd),
+            imm,
+            virtual_sequence_index: None,
+        };
+        //     addi a0, zero, 5
+        //     xori a1, a0, 3
+        //     add  a2, a0, a1
+        let bytecode = vec![
+            instruction(0, RV32IM::ADDI, 0, 10, Some(5)),
+            instruction(1, RV32IM::XORI, 10, 11, Some(3)),
+            instruction(2, RV32IM::ADD, 10, 12, None),
+        ];
+        let lookups: [AddXorInstructions; 3] = [
+            ADDInstruction(0, 5).into(),
+            XORInstruction(5, 3).into(),
+            ADDInstruction(5, 6).into(),
+        ];
+        let registers = [(0, None, 5), (5, None, 6), (5, Some(6), 11)];
+
+        let trace: Vec<JoltTraceStep<AddXorInstructions>> = bytecode
+            .iter()
+            .zip(lookups)
+            .zip(registers)
+            .map(|((instruction, lookup), (rs1_val, rs2_val, rd_post_val))| {
+                assert_eq!(lookup.lookup_entry(), rd_post_val);
+                let row = RVTraceRow {
+                    instruction: instruction.clone(),
+                    register_state: RegisterState {
+                        rs1_val: Some(rs1_val),
+                        rs2_val,
+                        rd_post_val: Some(rd_post_val),
+                    },
+                    memory_state: None,
+                    precompile_input: None,
+                };
+                JoltTraceStep {
+                    instruction_lookup: Some(lookup),
+                    bytecode_row: BytecodeRow::from_instruction::<AddXorInstructions>(instruction),
+                    memory_ops: (&row).into(),
+                }
+            })
+            .collect();
+        let packed_circuit_flags: Vec<u64> = trace
+            .iter()
+            .map(|step| step.bytecode_row.bitflags >> opcode_bits(AddXorInstructions::COUNT))
+            .collect();
+        let circuit_flags = circuit_flag_trace(&packed_circuit_flags);
+        let io_device = JoltDevice::new(DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE);
+
+        type Pcs = HyraxScheme<G1Projective>;
+        let memory_init: Vec<(u64, u8)> = [0x00500513u32, 0x00354593, 0x00b50633]
+            .iter()
+            .enumerate()
+            .flat_map(|(index, word)| {
+                (0..4).map(move |byte| {
+                    (
+                        RAM_START_ADDRESS + 4 * index as u64 + byte,
+                        (word >> (8 * byte)) as u8,
+                    )
+                })
+            })
+            .collect();
+        let preprocessing = <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::preprocess(
+            bytecode,
+            memory_init,
+            1 << 10,
+            1 << 20,
+            1 << 16,
+            // Enough for the smallest fixed-base table over the 2^12 Hyrax generators
+            Some(FixedBaseTable::<G1Projective>::table_size(1 << 12, 1)),
+        );
+        assert!(preprocessing.generators.has_fixed_base_table());
+        let (proof, commitments) = <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::prove(
+            io_device,
+            trace,
+            circuit_flags,
+            preprocessing.clone(),
+        );
+        let verification_result =
+            <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::verify(preprocessing, proof, commitments);
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    /// `num_steps` steps of `addi a0, zero, 5`, as its bytecode, initial memory, trace and
+    /// circuit flags.
+    #[allow(clippy::type_complexity)]
+    fn addi_program(
+        num_steps: usize,
+    ) -> (
+        Vec<ELFInstruction>,
+        Vec<(u64, u8)>,
+        Vec<JoltTraceStep<AddXorInstructions>>,
+        Vec<Fr>,
+    ) {
+        let bytecode: Vec<ELFInstruction> = (0..num_steps as u64)
+            .map(|index| ELFInstruction {
+                address: RAM_START_ADDRESS + 4 * index,
+                opcode: RV32IM::ADDI,
+                rs1: Some(0),
+                rs2: None,
+                rd: Some(10),
+                imm: Some(5),
+                virtual_sequence_index: None,
+            })
+            .collect();
+        let memory_init: Vec<(u64, u8)> = bytecode
+            .iter()
+            .flat_map(|instruction| {
+                (0..4).map(move |byte| {
+                    (
+                        instruction.address + byte,
+                        (0x00500513u32 >> (8 * byte)) as u8,
+                    )
+                })
+            })
+            .collect();
+        let trace: Vec<JoltTraceStep<AddXorInstructions>> = bytecode
+            .iter()
+            .map(|instruction| {
+                let row = RVTraceRow {
+                    instruction: instruction.clone(),
+                    register_state: RegisterState {
+                        rs1_val: Some(0),
+                        rs2_val: None,
+                        rd_post_val: Some(5),
+                    },
+                    memory_state: None,
+                    precompile_input: None,
+                };
+                JoltTraceStep {
+                    instruction_lookup: Some(ADDInstruction(0, 5).into()),
+                    bytecode_row: BytecodeRow::from_instruction::<AddXorInstructions>(instruction),
+                    memory_ops: (&row).into(),
+                }
+            })
+            .collect();
+        let packed_circuit_flags: Vec<u64> = trace
+            .iter()
+            .map(|step| step.bytecode_row.bitflags >> opcode_bits(AddXorInstructions::COUNT))
+            .collect();
+        let circuit_flags = circuit_flag_trace(&packed_circuit_flags);
+        (bytecode, memory_init, trace, circuit_flags)
+    }
+
+    #[test]
+    fn padded_trace_e2e() {
+        // 2^6 + 1 steps of `addi a0, zero, 5`, padded to 2^7
+        const NUM_STEPS: usize = (1 << 6) + 1;
+        let (bytecode, memory_init, trace, circuit_flags) = addi_program(NUM_STEPS);
+        let io_device = JoltDevice::new(DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE);
+
+        type Pcs = HyraxScheme<G1Projective>;
+        let preprocessing = <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::preprocess(
+            bytecode,
+            memory_init,
+            1 << 10,
+            1 << 20,
+            1 << 16,
+            None,
+        );
+
+        // The trace polynomials store the trace (and, for memory, the first no-op step), not
+        // the padding
+        let bytecode_polys =
+            BytecodePolynomials::<Fr, Pcs>::new(&preprocessing.bytecode, &mut trace.clone());
+        let memory_polys =
+            ReadWriteMemory::<Fr, Pcs>::new(&io_device, &preprocessing.read_write_memory, &trace);
+        let trace_polys: Vec<(usize, usize)> =
+            [&bytecode_polys.a_read_write, &bytecode_polys.t_read]
+                .into_iter()
+                .chain(memory_polys.t_read.iter())
+                .chain(memory_polys.t_write_ram.iter())
+                .chain([&memory_polys.a_ram])
+                .map(|poly| (poly.coeffs().len(), poly.len()))
+                .chain(
+                    bytecode_polys
+                        .v_read_write
+                        .iter()
+                        .chain(memory_polys.v_read.iter())
+                        .chain(memory_polys.v_write_ram.iter())
+                        .chain([&memory_polys.v_write_rd])
+                        .map(|poly| (poly.coeffs().len(), poly.len())),
+                )
+                .collect();
+        for (num_stored, len) in trace_polys {
+            assert_eq!(len, 1 << 7);
+            assert!(num_stored <= NUM_STEPS + 1);
+        }
+
+        // Neither do the instruction lookup polynomials, nor the R1CS witness
+        let mut jolt_trace = trace.clone();
+        let polynomials = <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::polynomialize(
+            &io_device,
+            &mut jolt_trace,
+            &preprocessing,
+        );
+        let instruction_polys = &polynomials.instruction_lookups;
+        for poly in instruction_polys
+            .dim
+            .iter()
+            .chain(&instruction_polys.read_cts)
+            .chain(&instruction_polys.E_polys)
+            .chain([&instruction_polys.lookup_outputs])
+        {
+            assert_eq!(poly.len(), 1 << 7);
+            assert!(poly.coeffs().len() <= NUM_STEPS);
+        }
+        let (_, witness_segments, _, _) = <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::r1cs_setup(
+            1 << 7,
+            RAM_START_ADDRESS - io_device.memory_layout.ram_witness_offset,
+            &jolt_trace,
+            &polynomials,
+            circuit_flags.clone(),
+            &preprocessing,
+        );
+        for segment in witness_segments {
+            assert_eq!(segment.len(), 1 << 7);
+            assert!(segment.values().len() <= NUM_STEPS + 1);
+        }
+
+        let (proof, commitments) = <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::prove(
+            io_device,
+            trace,
+            circuit_flags,
+            preprocessing.clone(),
+        );
+        let verification_result =
+            <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::verify(preprocessing, proof, commitments);
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn polynomials_builder_matches_polynomialize() {
+        let (bytecode, memory_init, trace, _) = addi_program((1 << 6) + 3);
+        let io_device = JoltDevice::new(DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE);
+
+        type Pcs = HyraxScheme<G1Projective>;
+        let preprocessing = <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::preprocess(
+            bytecode,
+            memory_init,
+            1 << 10,
+            1 << 20,
+            1 << 16,
+            None,
+        );
+        let expected = <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::polynomialize(
+            &io_device,
+            &mut trace.clone(),
+            &preprocessing,
+        );
+
+        // Chunks that don't line up with the padded trace length
+        let mut builder =
+            JoltPolynomialsBuilder::<Fr, Pcs, 4, { 1 << 16 }, AddXorInstructions>::new(
+                &io_device,
+                &preprocessing,
+            );
+        let mut streamed_trace = trace;
+        for chunk in streamed_trace.chunks_mut(10) {
+            builder.push_steps(chunk);
+        }
+        let polynomials = builder.finish(&io_device);
+
+        fn to_bytes(value: &impl CanonicalSerialize) -> Vec<u8> {
+            let mut bytes = vec![];
+            value.serialize_compressed(&mut bytes).unwrap();
+            bytes
+        }
+        assert_eq!(
+            to_bytes(&polynomials.commit(&preprocessing.generators)),
+            to_bytes(&expected.commit(&preprocessing.generators))
+        );
+    }
+
+    #[test]
+    fn memory_budget_e2e() {
+        let (bytecode, memory_init, trace, circuit_flags) = addi_program(1 << 6);
+        let io_device = JoltDevice::new(DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE);
+
+        type Pcs = HyraxScheme<G1Projective>;
+        let preprocessing = <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::preprocess(
+            bytecode,
+            memory_init,
+            1 << 10,
+            1 << 20,
+            1 << 16,
+            None,
+        );
+        let (expected_proof, expected_commitments) =
+            <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::prove(
+                io_device.clone(),
+                trace.clone(),
+                circuit_flags.clone(),
+                preprocessing.clone(),
+            );
+
+        // A zero budget is always exceeded, so every value that can be spilled is
+        let scratch_dir =
+            std::env::temp_dir().join(format!("jolt-memory-budget-e2e-{}", std::process::id()));
+        std::fs::create_dir_all(&scratch_dir).unwrap();
+        let memory_budget = MemoryBudget::new(0).with_scratch_dir(scratch_dir.clone());
+        let (proof, commitments) =
+            <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::prove_with_memory_budget(
+                io_device,
+                trace,
+                circuit_flags,
+                preprocessing.clone(),
+                Some(memory_budget),
+            )
+            .unwrap();
+        // Every scratch file is deleted once its value is read back in
+        assert_eq!(std::fs::read_dir(&scratch_dir).unwrap().count(), 0);
+        std::fs::remove_dir(&scratch_dir).unwrap();
+
+        // Spilling doesn't change the proof
+        fn to_bytes(value: &impl CanonicalSerialize) -> Vec<u8> {
+            let mut bytes = vec![];
+            value.serialize_compressed(&mut bytes).unwrap();
+            bytes
+        }
+        assert_eq!(to_bytes(&proof), to_bytes(&expected_proof));
+        assert_eq!(to_bytes(&commitments), to_bytes(&expected_commitments));
+
+        let verification_result =
+            <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::verify(preprocessing, proof, commitments);
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    /// Proves under a memory budget that the prover exceeds without one, and checks that the
+    /// peak resident set size stays within the budget. Peak RSS is measured per process, so each
+    /// prover runs in a separate process that runs just this test.
+    #[test]
+    fn memory_budget_bounds_peak_rss() {
+        const BUDGET_VAR: &str = "JOLT_PEAK_RSS_TEST_BUDGET";
+        const PEAK_RSS_PREFIX: &str = "peak RSS: ";
+
+        if let Ok(budget) = std::env::var(BUDGET_VAR) {
+            // 2^14 steps of `addi a0, zero, 5`; the inputs are made large enough that the
+            // bytecode lies within the memory accessed by the trace
+            let (bytecode, memory_init, trace, circuit_flags) = addi_program(1 << 14);
+            let io_device = JoltDevice::new(1 << 16, DEFAULT_MAX_OUTPUT_SIZE);
+
+            type Pcs = HyraxScheme<G1Projective>;
+            let preprocessing = <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::preprocess(
+                bytecode,
+                memory_init,
+                1 << 14,
+                1 << 18,
+                1 << 14,
+                None,
+            );
+            let memory_budget = budget.parse().ok().map(MemoryBudget::new);
+            let (proof, commitments) =
+                <AddXorVM as Jolt<Fr, Pcs, 4, { 1 << 16 }>>::prove_with_memory_budget(
+                    io_device,
+                    trace,
+                    circuit_flags,
+                    preprocessing,
+                    memory_budget,
+                )
+                .unwrap();
+            println!("{PEAK_RSS_PREFIX}{}", peak_rss_bytes().unwrap());
+            drop((proof, commitments));
+            return;
+        }
+        // Peak RSS is only available on Linux
+        if peak_rss_bytes().is_none() {
+            return;
+        }
+
+        // The provers run single-threaded, so that the allocator's per-thread arenas don't make
+        // the measurements depend on the machine.
+        let peak_rss = |budget: Option<usize>| -> usize {
+            let output = std::process::Command::new(std::env::current_exe().unwrap())
+                .args([
+                    "--exact",
+                    "jolt::vm::tests::memory_budget_bounds_peak_rss",
+                    "--nocapture",
+                    "--test-threads=1",
+                ])
+                .env(
+                    BUDGET_VAR,
+                    budget.map_or(String::new(), |budget| budget.to_string()),
+                )
+                .env("RAYON_NUM_THREADS", "1")
+                .output()
+                .unwrap();
+            let stdout = String::from_utf8(output.stdout).unwrap();
+            assert!(output.status.success(), "prover failed:\n{stdout}");
+            stdout
+                .lines()
+                .find_map(|line| line.strip_prefix(PEAK_RSS_PREFIX))
+                .and_then(|peak_rss| peak_rss.parse().ok())
+                .expect("the prover's peak RSS wasn't printed")
+        };
+
+        let unbudgeted_peak_rss = peak_rss(None);
+        let budget = unbudgeted_peak_rss / 16 * 15;
+        let budgeted_peak_rss = peak_rss(Some(budget));
+        assert!(
+            budgeted_peak_rss <= budget,
+            "peak RSS of {} MiB exceeds the budget of {} MiB (without a budget: {} MiB)",
+            budgeted_peak_rss >> 20,
+            budget >> 20,
+            unbudgeted_peak_rss >> 20
+        );
+    }
+}
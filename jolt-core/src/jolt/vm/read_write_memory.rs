@@ -1,13 +1,17 @@
-use crate::jolt::instruction::JoltInstructionSet;
+use crate::jolt::instruction::{
+    lb::LBInstruction, lh::LHInstruction, sb::SBInstruction, sh::SHInstruction, sw::SWInstruction,
+    JoltInstructionSet,
+};
 use crate::poly::field::JoltField;
 use rand::rngs::StdRng;
 use rand::RngCore;
-use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
+};
+use std::any::TypeId;
 #[cfg(test)]
 use std::collections::HashSet;
 use std::marker::PhantomData;
-#[cfg(test)]
-use std::sync::{Arc, Mutex};
 
 use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
 use crate::utils::transcript::AppendToTranscript;
@@ -17,8 +21,14 @@ use crate::{
         NoPreprocessing,
     },
     poly::{
-        dense_mlpoly::DensePolynomial, eq_poly::EqPolynomial, identity_poly::IdentityPolynomial,
-        structured_poly::StructuredOpeningProof,
+        compact_polynomial::{CompactPolynomial, Padding, SmallInt},
+        dense_mlpoly::DensePolynomial,
+        eq_poly::EqPolynomial,
+        identity_poly::IdentityPolynomial,
+        multilinear_polynomial::MultilinearPolynomial,
+        opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator},
+        sparse_polynomial::SparsePolynomial,
+        structured_poly::{StructuredCommitment, StructuredOpeningProof},
     },
     subprotocols::sumcheck::SumcheckInstanceProof,
     utils::{errors::ProofVerifyError, math::Math, mul_0_optimized, transcript::ProofTranscript},
@@ -40,7 +50,7 @@ pub fn random_memory_trace<F: JoltField>(
     rng: &mut StdRng,
 ) -> (
     Vec<[MemoryOp; MEMORY_OPS_PER_INSTRUCTION]>,
-    [DensePolynomial<F>; 5],
+    [SparsePolynomial<F>; 5],
 ) {
     let mut memory: Vec<u64> = vec![0; max_memory_address];
     for (addr, byte) in memory_init {
@@ -166,7 +176,15 @@ pub fn random_memory_trace<F: JoltField>(
         memory_trace,
         load_store_flags
             .iter()
-            .map(|bitvector| DensePolynomial::from_u64(bitvector))
+            .map(|bitvector| {
+                let indices = bitvector
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, flag)| **flag != 0)
+                    .map(|(i, _)| i)
+                    .collect();
+                SparsePolynomial::from_indices(m.log_2(), indices)
+            })
             .collect::<Vec<_>>()
             .try_into()
             .unwrap(),
@@ -236,14 +254,9 @@ const RS1: usize = 0;
 const RS2: usize = 1;
 const RD: usize = 2;
 const RAM_1: usize = 3;
-const RAM_2: usize = 4;
-const RAM_3: usize = 5;
-const RAM_4: usize = 6;
 const RAM_1_INDEX: usize = RAM_1 - 3;
-const RAM_2_INDEX: usize = RAM_2 - 3;
-const RAM_3_INDEX: usize = RAM_3 - 3;
-const RAM_4_INDEX: usize = RAM_4 - 3;
 
+#[derive(CanonicalSerialize, CanonicalDeserialize)]
 pub struct ReadWriteMemory<F, C>
 where
     F: JoltField,
@@ -253,23 +266,23 @@ where
     /// Size of entire address space (i.e. registers + IO + RAM)
     memory_size: usize,
     /// MLE of initial memory values. RAM is initialized to contain the program bytecode and inputs.
-    pub v_init: DensePolynomial<F>,
+    pub v_init: CompactPolynomial<u64, F>,
     /// MLE of read/write addresses. For offline memory checking, each read is paired with a "virtual" write
     /// and vice versa, so the read addresses and write addresses are the same.
-    pub a_ram: DensePolynomial<F>,
+    pub a_ram: CompactPolynomial<u32, F>,
     /// MLE of the read values.
-    pub v_read: [DensePolynomial<F>; MEMORY_OPS_PER_INSTRUCTION],
+    pub v_read: [CompactPolynomial<u64, F>; MEMORY_OPS_PER_INSTRUCTION],
     /// MLE of the write values.
-    pub v_write_rd: DensePolynomial<F>,
-    pub v_write_ram: [DensePolynomial<F>; 4],
+    pub v_write_rd: CompactPolynomial<u64, F>,
+    pub v_write_ram: [CompactPolynomial<u64, F>; 4],
     /// MLE of the final memory state.
-    pub v_final: DensePolynomial<F>,
+    pub v_final: CompactPolynomial<u64, F>,
     /// MLE of the read timestamps.
-    pub t_read: [DensePolynomial<F>; MEMORY_OPS_PER_INSTRUCTION],
+    pub t_read: [CompactPolynomial<u32, F>; MEMORY_OPS_PER_INSTRUCTION],
     /// MLE of the write timestamps.
-    pub t_write_ram: [DensePolynomial<F>; 4],
+    pub t_write_ram: [CompactPolynomial<u32, F>; 4],
     /// MLE of the final timestamps.
-    pub t_final: DensePolynomial<F>,
+    pub t_final: CompactPolynomial<u32, F>,
 }
 
 fn merge_vec_array(
@@ -291,574 +304,444 @@ fn merge_vec_array(
     merged_arr
 }
 
-fn map_to_polys<F: JoltField, const N: usize>(vals: &[Vec<u64>; N]) -> [DensePolynomial<F>; N] {
+/// Same as `map_to_polys`, for trace polynomials that are padded to a power of two.
+fn map_to_padded_polys<T: SmallInt, F: JoltField, const N: usize>(
+    vals: &[Vec<u64>; N],
+    padding: [Padding<T>; N],
+) -> [CompactPolynomial<T, F>; N] {
     vals.par_iter()
-        .map(|vals| DensePolynomial::from_u64(vals))
-        .collect::<Vec<DensePolynomial<F>>>()
+        .zip(padding)
+        .map(|(vals, padding)| {
+            CompactPolynomial::padded(vals.iter().map(|&v| T::from_u64(v)).collect(), padding)
+        })
+        .collect::<Vec<CompactPolynomial<T, F>>>()
         .try_into()
         .unwrap()
 }
 
-impl<F: JoltField, C: CommitmentScheme<Field = F>> ReadWriteMemory<F, C> {
-    #[tracing::instrument(skip_all, name = "ReadWriteMemory::new")]
-    pub fn new<InstructionSet: JoltInstructionSet>(
-        program_io: &JoltDevice,
-        load_store_flags: &[DensePolynomial<F>],
-        preprocessing: &ReadWriteMemoryPreprocessing,
-        trace: &Vec<JoltTraceStep<InstructionSet>>,
-    ) -> (Self, [Vec<u64>; MEMORY_OPS_PER_INSTRUCTION]) {
-        assert!(program_io.inputs.len() <= program_io.memory_layout.max_input_size as usize);
-        assert!(program_io.outputs.len() <= program_io.memory_layout.max_output_size as usize);
-
-        let m = trace.len();
-        assert!(m.is_power_of_two());
+fn map_to_polys<T: SmallInt, F: JoltField, const N: usize>(
+    vals: &[Vec<u64>; N],
+) -> [CompactPolynomial<T, F>; N] {
+    vals.par_iter()
+        .map(|vals| CompactPolynomial::from_u64(vals))
+        .collect::<Vec<CompactPolynomial<T, F>>>()
+        .try_into()
+        .unwrap()
+}
 
-        let max_trace_address = trace
-            .iter()
-            .flat_map(|step| {
-                step.memory_ops.iter().map(|op| match op {
-                    MemoryOp::Read(a) => remap_address(*a, &program_io.memory_layout),
-                    MemoryOp::Write(a, _) => remap_address(*a, &program_io.memory_layout),
-                })
-            })
-            .max()
-            .unwrap_or(0);
+/// The register accesses of each step, and the registers' state after the last one.
+struct RegisterTrace {
+    v_final: Vec<u64>,
+    t_final: Vec<u64>,
+    v_read: [Vec<u64>; REG_OPS_PER_INSTRUCTION],
+    t_read: [Vec<u64>; REG_OPS_PER_INSTRUCTION],
+    v_write_rd: Vec<u64>,
+    #[cfg(test)]
+    read_tuples: HashSet<(u64, u64, u64)>,
+    #[cfg(test)]
+    write_tuples: HashSet<(u64, u64, u64)>,
+}
 
-        let memory_size = (program_io.memory_layout.ram_witness_offset + max_trace_address)
-            .next_power_of_two() as usize;
-        let mut v_init: Vec<u64> = vec![0; memory_size];
-        // Copy bytecode
-        let mut v_init_index = memory_address_to_witness_index(
-            preprocessing.min_bytecode_address,
-            program_io.memory_layout.ram_witness_offset,
-        );
-        for byte in preprocessing.bytecode_bytes.iter() {
-            v_init[v_init_index] = *byte as u64;
-            v_init_index += 1;
+impl RegisterTrace {
+    fn new(v_init: &[u64]) -> Self {
+        Self {
+            v_final: v_init.to_vec(),
+            t_final: vec![0; v_init.len()],
+            v_read: std::array::from_fn(|_| Vec::new()),
+            t_read: std::array::from_fn(|_| Vec::new()),
+            v_write_rd: Vec::new(),
+            #[cfg(test)]
+            read_tuples: HashSet::new(),
+            #[cfg(test)]
+            write_tuples: HashSet::new(),
         }
-        // Copy input bytes
-        v_init_index = memory_address_to_witness_index(
-            program_io.memory_layout.input_start,
-            program_io.memory_layout.ram_witness_offset,
-        );
-        for byte in program_io.inputs.iter() {
-            v_init[v_init_index] = *byte as u64;
-            v_init_index += 1;
+    }
+
+    /// Reads rs1 and rs2, then writes rd.
+    fn push_step(&mut self, ops: &[MemoryOp], timestamp: u64) {
+        for (i, op) in ops[..RD].iter().enumerate() {
+            match *op {
+                MemoryOp::Read(a) => {
+                    assert!(a < REGISTER_COUNT);
+                    let v = self.v_final[a as usize];
+                    let t = self.t_final[a as usize];
+
+                    #[cfg(test)]
+                    {
+                        self.read_tuples.insert((a, v, t));
+                        self.write_tuples.insert((a, v, timestamp));
+                    }
+
+                    self.v_read[i].push(v);
+                    self.t_read[i].push(t);
+                    self.t_final[a as usize] = timestamp;
+                }
+                MemoryOp::Write(a, v) => {
+                    panic!("Unexpected rs{} MemoryOp::Write({}, {})", i + 1, a, v);
+                }
+            };
         }
 
-        #[cfg(test)]
-        let mut init_tuples: HashSet<(u64, u64, u64)> = HashSet::new();
-        #[cfg(test)]
-        {
-            for (a, v) in v_init.iter().enumerate() {
-                init_tuples.insert((a as u64, *v, 0u64));
+        match ops[RD] {
+            MemoryOp::Read(a) => {
+                panic!("Unexpected rd MemoryOp::Read({})", a)
             }
-        }
-        #[cfg(test)]
-        let read_tuples: Arc<Mutex<HashSet<(u64, u64, u64)>>> =
-            Arc::new(Mutex::new(HashSet::new()));
-        #[cfg(test)]
-        let write_tuples: Arc<Mutex<HashSet<(u64, u64, u64)>>> =
-            Arc::new(Mutex::new(HashSet::new()));
+            MemoryOp::Write(a, v_new) => {
+                assert!(a < REGISTER_COUNT);
+                let v_old = self.v_final[a as usize];
+                let t_old = self.t_final[a as usize];
 
-        let (memory_trace_reg, memory_trace_ram): (Vec<Vec<MemoryOp>>, Vec<Vec<MemoryOp>>) = trace
-            .into_par_iter()
-            .map(|step| {
-                let (reg, ram) = step.memory_ops.split_at(3);
-                (reg.to_vec(), ram.to_vec())
-            })
-            .unzip();
+                #[cfg(test)]
+                {
+                    self.read_tuples.insert((a, v_old, t_old));
+                    self.write_tuples.insert((a, v_new, timestamp + 1));
+                }
 
-        let reg_count = REGISTER_COUNT as usize;
-        let mut v_final_reg = v_init[..reg_count].to_vec();
-        let mut v_final_ram = v_init[reg_count..].to_vec();
-        let mut t_final_reg = vec![0; reg_count];
-        let mut t_final_ram = vec![0; memory_size - reg_count];
-
-        let mut v_read_reg: [Vec<u64>; REG_OPS_PER_INSTRUCTION] =
-            std::array::from_fn(|_| Vec::with_capacity(m));
-        let mut v_read_ram: [Vec<u64>; RAM_OPS_PER_INSTRUCTION] =
-            std::array::from_fn(|_| Vec::with_capacity(m));
-
-        let mut t_read_reg: [Vec<u64>; REG_OPS_PER_INSTRUCTION] =
-            std::array::from_fn(|_| Vec::with_capacity(m));
-        let mut t_read_ram: [Vec<u64>; RAM_OPS_PER_INSTRUCTION] =
-            std::array::from_fn(|_| Vec::with_capacity(m));
-
-        // REG only
-        let mut v_write_rd: Vec<u64> = Vec::with_capacity(m);
-        // RAM only
-        let mut a_ram: Vec<u64> = Vec::with_capacity(m);
-        let mut v_write_ram: [Vec<u64>; 4] = std::array::from_fn(|_| Vec::with_capacity(m));
-        let mut t_write_ram: [Vec<u64>; 4] = std::array::from_fn(|_| Vec::with_capacity(m));
+                self.v_read[RD].push(v_old);
+                self.t_read[RD].push(t_old);
+                self.v_write_rd.push(v_new);
+                self.v_final[a as usize] = v_new;
+                self.t_final[a as usize] = timestamp + 1;
+            }
+        };
+    }
+}
 
-        #[cfg(test)]
-        let r_tuples_ram = read_tuples.clone();
-        #[cfg(test)]
-        let w_tuples_ram = write_tuples.clone();
-        #[cfg(test)]
-        let r_tuples_reg = read_tuples.clone();
-        #[cfg(test)]
-        let w_tuples_reg = write_tuples.clone();
+/// The RAM accesses of each step, and the state of RAM after the last one.
+struct RamTrace {
+    v_final: Vec<u64>,
+    t_final: Vec<u64>,
+    a_ram: Vec<u64>,
+    v_read: [Vec<u64>; RAM_OPS_PER_INSTRUCTION],
+    t_read: [Vec<u64>; RAM_OPS_PER_INSTRUCTION],
+    v_write: [Vec<u64>; RAM_OPS_PER_INSTRUCTION],
+    t_write: [Vec<u64>; RAM_OPS_PER_INSTRUCTION],
+    #[cfg(test)]
+    read_tuples: HashSet<(u64, u64, u64)>,
+    #[cfg(test)]
+    write_tuples: HashSet<(u64, u64, u64)>,
+}
 
-        let span = tracing::span!(tracing::Level::DEBUG, "memory_trace_processing");
-        let _enter = span.enter();
+impl RamTrace {
+    fn new(v_init: &[u64]) -> Self {
+        Self {
+            v_final: v_init.to_vec(),
+            t_final: vec![0; v_init.len()],
+            a_ram: Vec::new(),
+            v_read: std::array::from_fn(|_| Vec::new()),
+            t_read: std::array::from_fn(|_| Vec::new()),
+            v_write: std::array::from_fn(|_| Vec::new()),
+            t_write: std::array::from_fn(|_| Vec::new()),
+            #[cfg(test)]
+            read_tuples: HashSet::new(),
+            #[cfg(test)]
+            write_tuples: HashSet::new(),
+        }
+    }
 
-        let result = rayon::join(
-            move || {
-                let span = tracing::span!(tracing::Level::DEBUG, "ram_trace_processing");
-                let _enter = span.enter();
+    /// Grows the RAM state, if needed, to hold the given remapped address.
+    fn reserve(&mut self, remapped_a: u64) {
+        if remapped_a < REGISTER_COUNT {
+            return;
+        }
+        let len = remap_address_index(remapped_a) + 1;
+        if len > self.v_final.len() {
+            self.v_final.resize(len, 0);
+            self.t_final.resize(len, 0);
+        }
+    }
 
-                let lb_flag = &load_store_flags[0];
-                let lh_flag = &load_store_flags[1];
-                let sb_flag = &load_store_flags[2];
-                let sh_flag = &load_store_flags[3];
-                let sw_flag = &load_store_flags[4];
-
-                for (i, step) in memory_trace_ram.iter().enumerate() {
-                    let timestamp = i as u64;
-
-                    #[allow(unused_assignments)]
-                    let mut ram_word_address = 0;
-                    let mut is_v_write_ram = false;
-
-                    // Only the LB/SB/LH/SH/LW/SW instructions access ≥1 byte of RAM
-                    if lb_flag[i].is_one()
-                        || lh_flag[i].is_one()
-                        || sb_flag[i].is_one()
-                        || sh_flag[i].is_one()
-                        || sw_flag[i].is_one()
-                    {
-                        match step[RAM_1_INDEX] {
-                            MemoryOp::Read(a) => {
-                                assert!(a >= program_io.memory_layout.input_start);
-                                let remapped_a = remap_address(a, &program_io.memory_layout);
-                                let remapped_a_index = remap_address_index(remapped_a);
-                                let v = v_final_ram[remapped_a_index];
-
-                                #[cfg(test)]
-                                {
-                                    r_tuples_ram.lock().unwrap().insert((
-                                        remapped_a,
-                                        v,
-                                        t_final_ram[remapped_a_index],
-                                    ));
-                                    w_tuples_ram
-                                        .lock()
-                                        .unwrap()
-                                        .insert((remapped_a, v, timestamp));
-                                }
-
-                                a_ram.push(remapped_a);
-                                v_read_ram[RAM_1_INDEX].push(v);
-                                t_read_ram[RAM_1_INDEX].push(t_final_ram[remapped_a_index]);
-                                v_write_ram[0].push(v);
-                                t_write_ram[0].push(timestamp);
-                                t_final_ram[remapped_a_index] = timestamp;
-                                ram_word_address = a;
-                            }
-                            MemoryOp::Write(a, v_new) => {
-                                assert!(a >= program_io.memory_layout.input_start);
-                                let remapped_a = remap_address(a, &program_io.memory_layout);
-                                let remapped_a_index = remap_address_index(remapped_a);
-                                let v_old = v_final_ram[remapped_a_index];
-
-                                #[cfg(test)]
-                                {
-                                    r_tuples_ram.lock().unwrap().insert((
-                                        remapped_a,
-                                        v_old,
-                                        t_final_ram[remapped_a_index],
-                                    ));
-                                    w_tuples_ram.lock().unwrap().insert((
-                                        remapped_a,
-                                        v_new,
-                                        timestamp + 1,
-                                    ));
-                                }
-
-                                a_ram.push(remapped_a);
-                                v_read_ram[RAM_1_INDEX].push(v_old);
-                                t_read_ram[RAM_1_INDEX].push(t_final_ram[remapped_a_index]);
-                                v_write_ram[0].push(v_new);
-                                t_write_ram[0].push(timestamp + 1);
-                                v_final_ram[remapped_a_index] = v_new;
-                                t_final_ram[remapped_a_index] = timestamp + 1;
-                                ram_word_address = a;
-                                is_v_write_ram = true;
-                            }
-                        };
-                    } else {
-                        a_ram.push(0);
-                        for ram_byte_index in [RAM_1_INDEX, RAM_2_INDEX, RAM_3_INDEX, RAM_4_INDEX] {
-                            match step[ram_byte_index] {
-                                MemoryOp::Read(a) => {
-                                    assert_eq!(a, 0);
-                                }
-                                MemoryOp::Write(a, v) => {
-                                    assert_eq!(a, 0);
-                                    assert_eq!(v, 0);
-                                }
-                            }
-                            v_read_ram[ram_byte_index].push(0);
-                            t_read_ram[ram_byte_index].push(0);
-                        }
-                        for v in v_write_ram.iter_mut() {
-                            v.push(0);
-                        }
-                        for t in t_write_ram.iter_mut() {
-                            t.push(0);
-                        }
-                        continue;
-                    }
+    /// Accesses the `num_bytes` consecutive bytes of RAM that the step loads or stores: one
+    /// for LB/SB, two for LH/SH and four for LW/SW. Its remaining RAM operations are zero.
+    fn push_step(
+        &mut self,
+        ops: &[MemoryOp],
+        num_bytes: u8,
+        timestamp: u64,
+        memory_layout: &MemoryLayout,
+    ) {
+        let (ram_word_address, is_v_write_ram) = match ops[RAM_1_INDEX] {
+            MemoryOp::Read(a) => (a, false),
+            MemoryOp::Write(a, _) => (a, true),
+        };
 
-                    // Only the LH/SH/LW/SW instructions access ≥2 byte of RAM
-                    if lh_flag[i].is_one() || sh_flag[i].is_one() || sw_flag[i].is_one() {
-                        match step[RAM_2_INDEX] {
-                            MemoryOp::Read(a) => {
-                                assert!(!is_v_write_ram);
-                                assert_eq!(a, ram_word_address + 1);
-                                let remapped_a = remap_address(a, &program_io.memory_layout);
-                                let remapped_a_index = remap_address_index(remapped_a);
-                                let v = v_final_ram[remapped_a_index];
-
-                                #[cfg(test)]
-                                {
-                                    r_tuples_ram.lock().unwrap().insert((
-                                        remapped_a,
-                                        v,
-                                        t_final_ram[remapped_a_index],
-                                    ));
-                                    w_tuples_ram
-                                        .lock()
-                                        .unwrap()
-                                        .insert((remapped_a, v, timestamp));
-                                }
-
-                                v_read_ram[RAM_2_INDEX].push(v);
-                                t_read_ram[RAM_2_INDEX].push(t_final_ram[remapped_a_index]);
-                                v_write_ram[1].push(v);
-                                t_write_ram[1].push(timestamp);
-                                t_final_ram[remapped_a_index] = timestamp;
-                            }
-                            MemoryOp::Write(a, v_new) => {
-                                assert!(is_v_write_ram);
-                                assert_eq!(a, ram_word_address + 1);
-                                let remapped_a = remap_address(a, &program_io.memory_layout);
-                                let remapped_a_index = remap_address_index(remapped_a);
-                                let v_old = v_final_ram[remapped_a_index];
-
-                                #[cfg(test)]
-                                {
-                                    r_tuples_ram.lock().unwrap().insert((
-                                        remapped_a,
-                                        v_old,
-                                        t_final_ram[remapped_a_index],
-                                    ));
-                                    w_tuples_ram.lock().unwrap().insert((
-                                        remapped_a,
-                                        v_new,
-                                        timestamp + 1,
-                                    ));
-                                }
-
-                                v_read_ram[RAM_2_INDEX].push(v_old);
-                                t_read_ram[RAM_2_INDEX].push(t_final_ram[remapped_a_index]);
-                                v_write_ram[1].push(v_new);
-                                t_write_ram[1].push(timestamp + 1);
-                                v_final_ram[remapped_a_index] = v_new;
-                                t_final_ram[remapped_a_index] = timestamp + 1;
-                            }
-                        };
-                    } else {
-                        for ram_byte_index in [RAM_2_INDEX, RAM_3_INDEX, RAM_4_INDEX] {
-                            match step[ram_byte_index] {
-                                MemoryOp::Read(a) => {
-                                    assert_eq!(a, 0);
-                                }
-                                MemoryOp::Write(a, v) => {
-                                    assert_eq!(a, 0);
-                                    assert_eq!(v, 0);
-                                }
-                            }
-                            v_read_ram[ram_byte_index].push(0);
-                            t_read_ram[ram_byte_index].push(0);
-                        }
-                        for v in v_write_ram[1..].iter_mut() {
-                            v.push(0);
-                        }
-                        for t in t_write_ram[1..].iter_mut() {
-                            t.push(0);
-                        }
-                        continue;
+        for (ram_byte_index, op) in ops.iter().enumerate() {
+            if ram_byte_index >= num_bytes as usize {
+                match *op {
+                    MemoryOp::Read(a) => {
+                        assert_eq!(a, 0);
                     }
-
-                    // Only the LW/SW instructions access ≥3 byte of RAM
-                    // Both LW and SW are represented by `sw_flag` for the purpose of lookups
-                    if sw_flag[i].is_one() {
-                        match step[RAM_3_INDEX] {
-                            MemoryOp::Read(a) => {
-                                assert!(!is_v_write_ram);
-                                assert_eq!(a, ram_word_address + 2);
-                                let remapped_a = remap_address(a, &program_io.memory_layout);
-                                let remapped_a_index = remap_address_index(remapped_a);
-                                let v = v_final_ram[remapped_a_index];
-
-                                #[cfg(test)]
-                                {
-                                    r_tuples_ram.lock().unwrap().insert((
-                                        remapped_a,
-                                        v,
-                                        t_final_ram[remapped_a_index],
-                                    ));
-                                    w_tuples_ram
-                                        .lock()
-                                        .unwrap()
-                                        .insert((remapped_a, v, timestamp));
-                                }
-
-                                v_read_ram[RAM_3_INDEX].push(v);
-                                t_read_ram[RAM_3_INDEX].push(t_final_ram[remapped_a_index]);
-                                v_write_ram[2].push(v);
-                                t_write_ram[2].push(timestamp);
-                                t_final_ram[remapped_a_index] = timestamp;
-                            }
-                            MemoryOp::Write(a, v_new) => {
-                                assert!(is_v_write_ram);
-                                assert_eq!(a, ram_word_address + 2);
-                                let remapped_a = remap_address(a, &program_io.memory_layout);
-                                let remapped_a_index = remap_address_index(remapped_a);
-                                let v_old = v_final_ram[remapped_a_index];
-
-                                #[cfg(test)]
-                                {
-                                    r_tuples_ram.lock().unwrap().insert((
-                                        remapped_a,
-                                        v_old,
-                                        t_final_ram[remapped_a_index],
-                                    ));
-                                    w_tuples_ram.lock().unwrap().insert((
-                                        remapped_a,
-                                        v_new,
-                                        timestamp + 1,
-                                    ));
-                                }
-
-                                v_read_ram[RAM_3_INDEX].push(v_old);
-                                t_read_ram[RAM_3_INDEX].push(t_final_ram[remapped_a_index]);
-                                v_write_ram[2].push(v_new);
-                                t_write_ram[2].push(timestamp + 1);
-                                v_final_ram[remapped_a_index] = v_new;
-                                t_final_ram[remapped_a_index] = timestamp + 1;
-                            }
-                        };
-                        match step[RAM_4_INDEX] {
-                            MemoryOp::Read(a) => {
-                                assert!(!is_v_write_ram);
-                                assert_eq!(a, ram_word_address + 3);
-                                let remapped_a = remap_address(a, &program_io.memory_layout);
-                                let remapped_a_index = remap_address_index(remapped_a);
-                                let v = v_final_ram[remapped_a_index];
-
-                                #[cfg(test)]
-                                {
-                                    r_tuples_ram.lock().unwrap().insert((
-                                        remapped_a,
-                                        v,
-                                        t_final_ram[remapped_a_index],
-                                    ));
-                                    w_tuples_ram
-                                        .lock()
-                                        .unwrap()
-                                        .insert((remapped_a, v, timestamp));
-                                }
-
-                                v_read_ram[RAM_4_INDEX].push(v);
-                                t_read_ram[RAM_4_INDEX].push(t_final_ram[remapped_a_index]);
-                                v_write_ram[3].push(v);
-                                t_write_ram[3].push(timestamp);
-                                t_final_ram[remapped_a_index] = timestamp;
-                            }
-                            MemoryOp::Write(a, v_new) => {
-                                assert!(is_v_write_ram);
-                                assert_eq!(a, ram_word_address + 3);
-                                let remapped_a = remap_address(a, &program_io.memory_layout);
-                                let remapped_a_index = remap_address_index(remapped_a);
-                                let v_old = v_final_ram[remapped_a_index];
-
-                                #[cfg(test)]
-                                {
-                                    r_tuples_ram.lock().unwrap().insert((
-                                        remapped_a,
-                                        v_old,
-                                        t_final_ram[remapped_a_index],
-                                    ));
-                                    w_tuples_ram.lock().unwrap().insert((
-                                        remapped_a,
-                                        v_new,
-                                        timestamp + 1,
-                                    ));
-                                }
-
-                                v_read_ram[RAM_4_INDEX].push(v_old);
-                                t_read_ram[RAM_4_INDEX].push(t_final_ram[remapped_a_index]);
-                                v_write_ram[3].push(v_new);
-                                t_write_ram[3].push(timestamp + 1);
-                                v_final_ram[remapped_a_index] = v_new;
-                                t_final_ram[remapped_a_index] = timestamp + 1;
-                            }
-                        };
-                    } else {
-                        for ram_byte_index in [RAM_3_INDEX, RAM_4_INDEX] {
-                            match step[ram_byte_index] {
-                                MemoryOp::Read(a) => {
-                                    assert_eq!(a, 0);
-                                }
-                                MemoryOp::Write(a, v) => {
-                                    assert_eq!(a, 0);
-                                    assert_eq!(v, 0);
-                                }
-                            }
-                            v_read_ram[ram_byte_index].push(0);
-                            t_read_ram[ram_byte_index].push(0);
-                        }
-                        for v in v_write_ram[2..].iter_mut() {
-                            v.push(0);
-                        }
-                        for t in t_write_ram[2..].iter_mut() {
-                            t.push(0);
-                        }
+                    MemoryOp::Write(a, v) => {
+                        assert_eq!(a, 0);
+                        assert_eq!(v, 0);
                     }
                 }
+                if ram_byte_index == RAM_1_INDEX {
+                    self.a_ram.push(0);
+                }
+                self.v_read[ram_byte_index].push(0);
+                self.t_read[ram_byte_index].push(0);
+                self.v_write[ram_byte_index].push(0);
+                self.t_write[ram_byte_index].push(0);
+                continue;
+            }
 
-                drop(_enter);
-                drop(span);
-
-                (
-                    v_final_ram,
-                    t_final_ram,
-                    v_read_ram,
-                    t_read_ram,
-                    v_write_ram,
-                    t_write_ram,
-                    a_ram,
-                )
-            },
-            move || {
-                let span = tracing::span!(tracing::Level::DEBUG, "register_trace_processing");
-                let _enter = span.enter();
+            let (a, v_new) = match *op {
+                MemoryOp::Read(a) => {
+                    assert!(!is_v_write_ram);
+                    (a, None)
+                }
+                MemoryOp::Write(a, v_new) => {
+                    assert!(is_v_write_ram);
+                    (a, Some(v_new))
+                }
+            };
+            if ram_byte_index == RAM_1_INDEX {
+                assert!(a >= memory_layout.input_start);
+            } else {
+                assert_eq!(a, ram_word_address + ram_byte_index as u64);
+            }
+            let remapped_a = remap_address(a, memory_layout);
+            let remapped_a_index = remap_address_index(remapped_a);
+            let v_old = self.v_final[remapped_a_index];
+            let t_old = self.t_final[remapped_a_index];
+            // A load writes back the value it read
+            let (v, t) = match v_new {
+                Some(v_new) => (v_new, timestamp + 1),
+                None => (v_old, timestamp),
+            };
+
+            #[cfg(test)]
+            {
+                self.read_tuples.insert((remapped_a, v_old, t_old));
+                self.write_tuples.insert((remapped_a, v, t));
+            }
 
-                for (i, step) in memory_trace_reg.iter().enumerate() {
-                    let timestamp = i as u64;
-
-                    match step[RS1] {
-                        MemoryOp::Read(a) => {
-                            assert!(a < REGISTER_COUNT);
-                            let v = v_final_reg[a as usize];
-
-                            #[cfg(test)]
-                            {
-                                r_tuples_reg.lock().unwrap().insert((
-                                    a,
-                                    v,
-                                    t_final_reg[a as usize],
-                                ));
-                                w_tuples_reg.lock().unwrap().insert((a, v, timestamp));
-                            }
+            if ram_byte_index == RAM_1_INDEX {
+                self.a_ram.push(remapped_a);
+            }
+            self.v_read[ram_byte_index].push(v_old);
+            self.t_read[ram_byte_index].push(t_old);
+            self.v_write[ram_byte_index].push(v);
+            self.t_write[ram_byte_index].push(t);
+            self.v_final[remapped_a_index] = v;
+            self.t_final[remapped_a_index] = t;
+        }
+    }
+}
 
-                            v_read_reg[RS1].push(v);
-                            t_read_reg[RS1].push(t_final_reg[a as usize]);
-                            t_final_reg[a as usize] = timestamp;
-                        }
-                        MemoryOp::Write(a, v) => {
-                            panic!("Unexpected rs1 MemoryOp::Write({}, {})", a, v);
-                        }
-                    };
-
-                    match step[RS2] {
-                        MemoryOp::Read(a) => {
-                            assert!(a < REGISTER_COUNT);
-                            let v = v_final_reg[a as usize];
-
-                            #[cfg(test)]
-                            {
-                                r_tuples_reg.lock().unwrap().insert((
-                                    a,
-                                    v,
-                                    t_final_reg[a as usize],
-                                ));
-                                w_tuples_reg.lock().unwrap().insert((a, v, timestamp));
-                            }
+/// Builds the `ReadWriteMemory` polynomials of a trace from chunks of its steps, as they
+/// are produced. See `ReadWriteMemory::new`.
+pub struct ReadWriteMemoryBuilder<F, C, InstructionSet>
+where
+    F: JoltField,
+    C: CommitmentScheme<Field = F>,
+    InstructionSet: JoltInstructionSet,
+{
+    _marker: PhantomData<(F, C, InstructionSet)>,
+    memory_layout: MemoryLayout,
+    /// The initial memory values, up to the last byte of the bytecode and inputs. Padded to
+    /// the memory size once the trace is complete.
+    v_init: Vec<u64>,
+    /// The number of bytes of RAM accessed by each instruction, by its index in the set.
+    ram_bytes_accessed: Vec<u8>,
+    /// The largest (remapped) address accessed so far, which sizes the memory.
+    max_trace_address: u64,
+    trace_length: usize,
+    registers: RegisterTrace,
+    ram: RamTrace,
+}
 
-                            v_read_reg[RS2].push(v);
-                            t_read_reg[RS2].push(t_final_reg[a as usize]);
-                            t_final_reg[a as usize] = timestamp;
-                        }
-                        MemoryOp::Write(a, v) => {
-                            panic!("Unexpected rs2 MemoryOp::Write({}, {})", a, v)
-                        }
-                    };
+impl<F, C, InstructionSet> ReadWriteMemoryBuilder<F, C, InstructionSet>
+where
+    F: JoltField,
+    C: CommitmentScheme<Field = F>,
+    InstructionSet: JoltInstructionSet,
+{
+    /// Only the inputs and memory layout of `program_io` are used, so it may be created
+    /// before the program runs.
+    pub fn new(program_io: &JoltDevice, preprocessing: &ReadWriteMemoryPreprocessing) -> Self {
+        assert!(program_io.inputs.len() <= program_io.memory_layout.max_input_size as usize);
 
-                    match step[RD] {
-                        MemoryOp::Read(a) => {
-                            panic!("Unexpected rd MemoryOp::Read({})", a)
-                        }
-                        MemoryOp::Write(a, v_new) => {
-                            assert!(a < REGISTER_COUNT);
-                            let v_old = v_final_reg[a as usize];
-
-                            #[cfg(test)]
-                            {
-                                r_tuples_reg.lock().unwrap().insert((
-                                    a,
-                                    v_old,
-                                    t_final_reg[a as usize],
-                                ));
-                                w_tuples_reg
-                                    .lock()
-                                    .unwrap()
-                                    .insert((a, v_new, timestamp + 1));
-                            }
+        let memory_layout = program_io.memory_layout.clone();
+        let bytecode_start = memory_address_to_witness_index(
+            preprocessing.min_bytecode_address,
+            memory_layout.ram_witness_offset,
+        );
+        let input_start = memory_address_to_witness_index(
+            memory_layout.input_start,
+            memory_layout.ram_witness_offset,
+        );
+        let mut v_init: Vec<u64> = vec![
+            0;
+            (REGISTER_COUNT as usize)
+                .max(bytecode_start + preprocessing.bytecode_bytes.len())
+                .max(input_start + program_io.inputs.len())
+        ];
+        // Copy bytecode
+        for (v, byte) in v_init[bytecode_start..]
+            .iter_mut()
+            .zip(&preprocessing.bytecode_bytes)
+        {
+            *v = *byte as u64;
+        }
+        // Copy input bytes
+        for (v, byte) in v_init[input_start..].iter_mut().zip(&program_io.inputs) {
+            *v = *byte as u64;
+        }
 
-                            v_read_reg[RD].push(v_old);
-                            t_read_reg[RD].push(t_final_reg[a as usize]);
-                            v_write_rd.push(v_new);
-                            v_final_reg[a as usize] = v_new;
-                            t_final_reg[a as usize] = timestamp + 1;
-                        }
-                    };
-                }
+        // Both LW and SW are represented by the SW instruction for the purpose of lookups.
+        let mut ram_bytes_accessed = vec![0; InstructionSet::COUNT];
+        for (instruction_id, num_bytes) in [
+            (TypeId::of::<LBInstruction>(), 1),
+            (TypeId::of::<LHInstruction>(), 2),
+            (TypeId::of::<SBInstruction>(), 1),
+            (TypeId::of::<SHInstruction>(), 2),
+            (TypeId::of::<SWInstruction>(), 4),
+        ] {
+            if let Some(index) = InstructionSet::instruction_index(instruction_id) {
+                ram_bytes_accessed[index] = num_bytes;
+            }
+        }
 
-                drop(_enter);
-                drop(span);
+        let reg_count = REGISTER_COUNT as usize;
+        let registers = RegisterTrace::new(&v_init[..reg_count]);
+        let ram = RamTrace::new(&v_init[reg_count..]);
+        Self {
+            _marker: PhantomData,
+            memory_layout,
+            v_init,
+            ram_bytes_accessed,
+            max_trace_address: 0,
+            trace_length: 0,
+            registers,
+            ram,
+        }
+    }
 
-                (v_final_reg, t_final_reg, v_read_reg, t_read_reg, v_write_rd)
+    /// Performs the memory operations of the next `steps` of the trace.
+    #[tracing::instrument(skip_all, name = "ReadWriteMemoryBuilder::push_steps")]
+    pub fn push_steps(&mut self, steps: &[JoltTraceStep<InstructionSet>]) {
+        let Self {
+            memory_layout,
+            ram_bytes_accessed,
+            max_trace_address,
+            trace_length,
+            registers,
+            ram,
+            ..
+        } = self;
+
+        let max_step_address = steps
+            .par_iter()
+            .flat_map_iter(|step| {
+                step.memory_ops.iter().map(|op| match op {
+                    MemoryOp::Read(a) => remap_address(*a, memory_layout),
+                    MemoryOp::Write(a, _) => remap_address(*a, memory_layout),
+                })
+            })
+            .max();
+        *max_trace_address = (*max_trace_address).max(max_step_address.unwrap_or(0));
+        ram.reserve(*max_trace_address);
+
+        let first_timestamp = *trace_length as u64;
+        rayon::join(
+            || {
+                let span = tracing::span!(tracing::Level::DEBUG, "ram_trace_processing");
+                let _enter = span.enter();
+                for (i, step) in steps.iter().enumerate() {
+                    let num_bytes = step.instruction_lookup.as_ref().map_or(0, |instruction| {
+                        ram_bytes_accessed[InstructionSet::enum_index(instruction)]
+                    });
+                    ram.push_step(
+                        &step.memory_ops[RAM_1..],
+                        num_bytes,
+                        first_timestamp + i as u64,
+                        memory_layout,
+                    );
+                }
+            },
+            || {
+                let span = tracing::span!(tracing::Level::DEBUG, "register_trace_processing");
+                let _enter = span.enter();
+                for (i, step) in steps.iter().enumerate() {
+                    registers.push_step(&step.memory_ops[..RAM_1], first_timestamp + i as u64);
+                }
             },
         );
-        drop(_enter);
-        drop(span);
+        *trace_length += steps.len();
+    }
 
-        let (mut v_final_reg, mut t_final_reg, v_read_reg, t_read_reg, v_write_rd) = result.1;
-        let (v_final_ram, t_final_ram, v_read_ram, t_read_ram, v_write_ram, t_write_ram, a_ram) =
-            result.0;
+    /// Builds the polynomials once the whole trace has been pushed, given the program's
+    /// final I/O.
+    #[tracing::instrument(skip_all, name = "ReadWriteMemoryBuilder::finish")]
+    pub fn finish(self, program_io: &JoltDevice) -> ReadWriteMemory<F, C> {
+        assert!(program_io.outputs.len() <= program_io.memory_layout.max_output_size as usize);
+        let Self {
+            memory_layout,
+            mut v_init,
+            max_trace_address,
+            trace_length,
+            mut registers,
+            mut ram,
+            ..
+        } = self;
+
+        // The trace is implicitly padded to a power of two with no-op steps, which are never
+        // materialized. A no-op step leaves RAM untouched and reads and writes register 0, so
+        // past the first no-op step the RAM polynomials are zero and the register timestamps
+        // count up. Only the first no-op step is stored, since it reads the register 0 value
+        // and timestamp left by the trace.
+        let m = trace_length.next_power_of_two();
+        let num_steps = std::cmp::min(trace_length + 1, m);
+        if num_steps > trace_length {
+            let no_op = JoltTraceStep::<InstructionSet>::no_op();
+            let timestamp = trace_length as u64;
+            registers.push_step(&no_op.memory_ops[..RAM_1], timestamp);
+            ram.push_step(&no_op.memory_ops[RAM_1..], 0, timestamp, &memory_layout);
+        }
+
+        let memory_size =
+            (memory_layout.ram_witness_offset + max_trace_address).next_power_of_two() as usize;
+        assert!(
+            v_init.len() <= memory_size,
+            "the bytecode and inputs lie past the memory accessed by the trace"
+        );
+        let reg_count = REGISTER_COUNT as usize;
+        v_init.resize(memory_size, 0);
+        ram.v_final.resize(memory_size - reg_count, 0);
+        ram.t_final.resize(memory_size - reg_count, 0);
+
+        // Each of the remaining no-op steps ends by writing register 0
+        if num_steps < m {
+            registers.t_final[0] = m as u64;
+        }
 
         let v_final = {
-            v_final_reg.extend(v_final_ram);
-            v_final_reg
+            registers.v_final.extend(ram.v_final);
+            registers.v_final
         };
         let t_final = {
-            t_final_reg.extend(t_final_ram);
-            t_final_reg
+            registers.t_final.extend(ram.t_final);
+            registers.t_final
         };
-        let v_read: [Vec<u64>; MEMORY_OPS_PER_INSTRUCTION] =
-            merge_vec_array(v_read_reg, v_read_ram, m);
-        let t_read: [Vec<u64>; MEMORY_OPS_PER_INSTRUCTION] =
-            merge_vec_array(t_read_reg, t_read_ram, m);
 
         #[cfg(test)]
         {
-            let read_tuples = Arc::try_unwrap(read_tuples).unwrap().into_inner().unwrap();
-            let write_tuples = Arc::try_unwrap(write_tuples).unwrap().into_inner().unwrap();
+            let mut init_tuples: HashSet<(u64, u64, u64)> = HashSet::new();
+            for (a, v) in v_init.iter().enumerate() {
+                init_tuples.insert((a as u64, *v, 0u64));
+            }
+
+            let mut read_tuples = registers.read_tuples;
+            read_tuples.extend(ram.read_tuples);
+            let mut write_tuples = registers.write_tuples;
+            write_tuples.extend(ram.write_tuples);
+            for timestamp in num_steps as u64..m as u64 {
+                read_tuples.insert((0, 0, timestamp));
+                write_tuples.insert((0, 0, timestamp));
+                write_tuples.insert((0, 0, timestamp + 1));
+            }
 
             let mut final_tuples: HashSet<(u64, u64, u64)> = HashSet::new();
             for (a, (v, t)) in v_final.iter().zip(t_final.iter()).enumerate() {
@@ -871,61 +754,105 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> ReadWriteMemory<F, C> {
             assert_eq!(set_difference.len(), 0);
         }
 
+        let v_read: [Vec<u64>; MEMORY_OPS_PER_INSTRUCTION] =
+            merge_vec_array(registers.v_read, ram.v_read, num_steps);
+        let t_read: [Vec<u64>; MEMORY_OPS_PER_INSTRUCTION] =
+            merge_vec_array(registers.t_read, ram.t_read, num_steps);
+        let (a_ram, v_write_rd, v_write_ram, t_write_ram) =
+            (ram.a_ram, registers.v_write_rd, ram.v_write, ram.t_write);
+
+        let reg_timestamp = Padding::Counter(num_steps as u32);
         let (
-            [a_ram, v_write_rd, v_init, v_final, t_final],
+            ([a_ram], [t_final], [v_write_rd], [v_init, v_final]),
             v_read,
             v_write_ram,
-            t_read_polys,
+            t_read,
             t_write_ram,
         ): (
-            [DensePolynomial<F>; 5],
-            [DensePolynomial<F>; MEMORY_OPS_PER_INSTRUCTION],
-            [DensePolynomial<F>; 4],
-            [DensePolynomial<F>; MEMORY_OPS_PER_INSTRUCTION],
-            [DensePolynomial<F>; 4],
+            (
+                [CompactPolynomial<u32, F>; 1],
+                [CompactPolynomial<u32, F>; 1],
+                [CompactPolynomial<u64, F>; 1],
+                [CompactPolynomial<u64, F>; 2],
+            ),
+            [CompactPolynomial<u64, F>; MEMORY_OPS_PER_INSTRUCTION],
+            [CompactPolynomial<u64, F>; 4],
+            [CompactPolynomial<u32, F>; MEMORY_OPS_PER_INSTRUCTION],
+            [CompactPolynomial<u32, F>; 4],
         ) = common::par_join_5!(
-            || map_to_polys(&[a_ram, v_write_rd, v_init, v_final, t_final]),
-            || map_to_polys(&v_read),
-            || map_to_polys(&v_write_ram),
-            || map_to_polys(&t_read),
-            || map_to_polys(&t_write_ram)
+            || (
+                map_to_padded_polys(&[a_ram], [Padding::Constant(0)]),
+                map_to_polys(&[t_final]),
+                map_to_padded_polys(&[v_write_rd], [Padding::Constant(0)]),
+                map_to_polys(&[v_init, v_final])
+            ),
+            || map_to_padded_polys(&v_read, [Padding::Constant(0); MEMORY_OPS_PER_INSTRUCTION]),
+            || map_to_padded_polys(&v_write_ram, [Padding::Constant(0); 4]),
+            || map_to_padded_polys(
+                &t_read,
+                [
+                    reg_timestamp,
+                    reg_timestamp,
+                    reg_timestamp,
+                    Padding::Constant(0),
+                    Padding::Constant(0),
+                    Padding::Constant(0),
+                    Padding::Constant(0)
+                ]
+            ),
+            || map_to_padded_polys(&t_write_ram, [Padding::Constant(0); 4])
         );
-        (
-            Self {
-                _group: PhantomData,
-                memory_size,
-                v_init,
-                a_ram,
-                v_read,
-                v_write_rd,
-                v_write_ram,
-                v_final,
-                t_read: t_read_polys,
-                t_write_ram,
-                t_final,
-            },
+
+        ReadWriteMemory {
+            _group: PhantomData,
+            memory_size,
+            v_init,
+            a_ram,
+            v_read,
+            v_write_rd,
+            v_write_ram,
+            v_final,
             t_read,
-        )
+            t_write_ram,
+            t_final,
+        }
+    }
+}
+
+impl<F: JoltField, C: CommitmentScheme<Field = F>> ReadWriteMemory<F, C> {
+    #[tracing::instrument(skip_all, name = "ReadWriteMemory::new")]
+    pub fn new<InstructionSet: JoltInstructionSet>(
+        program_io: &JoltDevice,
+        preprocessing: &ReadWriteMemoryPreprocessing,
+        trace: &[JoltTraceStep<InstructionSet>],
+    ) -> Self {
+        let mut builder =
+            ReadWriteMemoryBuilder::<F, C, InstructionSet>::new(program_io, preprocessing);
+        builder.push_steps(trace);
+        builder.finish(program_io)
     }
 
+    /// Returns the first `num_steps` rows of the polynomials that are inputs to the R1CS,
+    /// along with their values at later (padding) steps. See `CompactPolynomial::flatten`.
     #[tracing::instrument(skip_all, name = "ReadWriteMemory::get_polys_r1cs")]
-    pub fn get_polys_r1cs<'a>(&'a self) -> (&'a [F], Vec<&'a F>, Vec<&'a F>) {
+    pub fn get_polys_r1cs(
+        &self,
+        num_steps: usize,
+    ) -> (
+        (Vec<u64>, Vec<u64>),
+        (Vec<u64>, Vec<u64>),
+        (Vec<u64>, Vec<u64>),
+    ) {
         let (a_polys, (v_read_polys, v_write_polys)) = rayon::join(
-            || self.a_ram.evals_ref(),
+            || CompactPolynomial::flatten([&self.a_ram], num_steps),
             || {
                 rayon::join(
+                    || CompactPolynomial::flatten(&self.v_read, num_steps),
                     || {
-                        self.v_read
-                            .par_iter()
-                            .flat_map(|poly| poly.evals_ref().par_iter())
-                            .collect::<Vec<_>>()
-                    },
-                    || {
-                        [&self.v_write_rd]
-                            .into_par_iter()
-                            .chain(self.v_write_ram.par_iter())
-                            .flat_map(|poly| poly.evals_ref().par_iter())
-                            .collect::<Vec<_>>()
+                        CompactPolynomial::flatten(
+                            [&self.v_write_rd].into_iter().chain(&self.v_write_ram),
+                            num_steps,
+                        )
                     },
                 )
             },
@@ -956,6 +883,50 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> ReadWriteMemory<F, C> {
     }
 }
 
+impl<F, C> StructuredCommitment<C> for ReadWriteMemory<F, C>
+where
+    F: JoltField,
+    C: CommitmentScheme<Field = F>,
+{
+    type Commitment = MemoryCommitment<C>;
+
+    #[tracing::instrument(skip_all, name = "ReadWriteMemory::commit")]
+    fn commit(&self, generators: &C::Setup) -> Self::Commitment {
+        let value_polys: Vec<&CompactPolynomial<u64, F>> = self
+            .v_read
+            .iter()
+            .chain([&self.v_write_rd].into_iter())
+            .chain(self.v_write_ram.iter())
+            .collect();
+        let timestamp_polys: Vec<&CompactPolynomial<u32, F>> =
+            self.t_read.iter().chain(self.t_write_ram.iter()).collect();
+
+        let mut trace_commitments =
+            C::batch_commit_compact(&[&self.a_ram], generators, BatchType::Big);
+        trace_commitments.extend(C::batch_commit_compact(
+            &value_polys,
+            generators,
+            BatchType::Big,
+        ));
+        trace_commitments.extend(C::batch_commit_compact(
+            &timestamp_polys,
+            generators,
+            BatchType::Big,
+        ));
+
+        let (mut v_final_commitment, mut t_final_commitment) = rayon::join(
+            || C::batch_commit_compact(&[&self.v_final], generators, BatchType::Small),
+            || C::batch_commit_compact(&[&self.t_final], generators, BatchType::Small),
+        );
+
+        Self::Commitment {
+            trace_commitments,
+            v_final_commitment: v_final_commitment.pop().unwrap(),
+            t_final_commitment: t_final_commitment.pop().unwrap(),
+        }
+    }
+}
+
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
 pub struct MemoryCommitment<C: CommitmentScheme> {
     pub trace_commitments: Vec<C::Commitment>,
@@ -996,31 +967,63 @@ where
     pub identity_poly_opening: Option<F>,
 }
 
-impl<F, C> StructuredOpeningProof<F, C, JoltPolynomials<F, C>> for MemoryReadWriteOpenings<F, C>
+impl<F, C> MemoryReadWriteOpenings<F, C>
 where
     F: JoltField,
     C: CommitmentScheme<Field = F>,
 {
-    type Proof = C::BatchedProof;
+    /// The polynomials opened by `MemoryReadWriteOpenings`, in the order of its fields.
+    fn read_write_polys(polynomials: &JoltPolynomials<F, C>) -> Vec<&dyn MultilinearPolynomial<F>> {
+        let bytecode = &polynomials.bytecode;
+        let memory = &polynomials.read_write_memory;
+        let mut polys: Vec<&dyn MultilinearPolynomial<F>> = vec![
+            &bytecode.v_read_write[2], // rd
+            &bytecode.v_read_write[3], // rs1
+            &bytecode.v_read_write[4], // rs2
+            &memory.a_ram,
+        ];
+        polys.extend(
+            memory
+                .v_read
+                .iter()
+                .map(|poly| poly as &dyn MultilinearPolynomial<F>),
+        );
+        polys.push(&memory.v_write_rd);
+        polys.extend(
+            memory
+                .v_write_ram
+                .iter()
+                .map(|poly| poly as &dyn MultilinearPolynomial<F>),
+        );
+        polys.extend(
+            memory
+                .t_read
+                .iter()
+                .map(|poly| poly as &dyn MultilinearPolynomial<F>),
+        );
+        polys.extend(
+            memory
+                .t_write_ram
+                .iter()
+                .map(|poly| poly as &dyn MultilinearPolynomial<F>),
+        );
+        polys
+    }
+}
 
+impl<F, C> StructuredOpeningProof<F, C, JoltPolynomials<F, C>> for MemoryReadWriteOpenings<F, C>
+where
+    F: JoltField,
+    C: CommitmentScheme<Field = F>,
+{
     #[tracing::instrument(skip_all, name = "MemoryReadWriteOpenings::open")]
     fn open(polynomials: &JoltPolynomials<F, C>, opening_point: &[F]) -> Self {
         let chis = EqPolynomial::evals(opening_point);
-        let mut openings = [
-            &polynomials.bytecode.v_read_write[2], // rd
-            &polynomials.bytecode.v_read_write[3], // rs1
-            &polynomials.bytecode.v_read_write[4], // rs2
-            &polynomials.read_write_memory.a_ram,
-        ]
-        .into_par_iter()
-        .chain(polynomials.read_write_memory.v_read.par_iter())
-        .chain([&polynomials.read_write_memory.v_write_rd].into_par_iter())
-        .chain(polynomials.read_write_memory.v_write_ram.par_iter())
-        .chain(polynomials.read_write_memory.t_read.par_iter())
-        .chain(polynomials.read_write_memory.t_write_ram.par_iter())
-        .map(|poly| poly.evaluate_at_chi(&chis))
-        .collect::<Vec<F>>()
-        .into_iter();
+        let mut openings = Self::read_write_polys(polynomials)
+            .into_par_iter()
+            .map(|poly| poly.evaluate_at_chi(&chis))
+            .collect::<Vec<F>>()
+            .into_iter();
 
         let a_read_write_opening = openings.next_chunk().unwrap();
         let v_read_opening = openings.next_chunk().unwrap();
@@ -1043,21 +1046,10 @@ where
         polynomials: &JoltPolynomials<F, C>,
         opening_point: &[F],
         openings: &Self,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
-    ) -> Self::Proof {
-        let read_write_polys = [
-            &polynomials.bytecode.v_read_write[2], // rd
-            &polynomials.bytecode.v_read_write[3], // rs1
-            &polynomials.bytecode.v_read_write[4], // rs2
-            &polynomials.read_write_memory.a_ram,
-        ]
-        .into_iter()
-        .chain(polynomials.read_write_memory.v_read.iter())
-        .chain([&polynomials.read_write_memory.v_write_rd].into_iter())
-        .chain(polynomials.read_write_memory.v_write_ram.iter())
-        .chain(polynomials.read_write_memory.t_read.iter())
-        .chain(polynomials.read_write_memory.t_write_ram.iter())
-        .collect::<Vec<_>>();
+    ) {
+        let read_write_polys = Self::read_write_polys(polynomials);
         let read_write_openings = openings
             .a_read_write_opening
             .into_iter()
@@ -1066,26 +1058,29 @@ where
             .chain(openings.t_read_opening.into_iter())
             .chain(openings.t_write_ram_opening.into_iter())
             .collect::<Vec<_>>();
-        C::batch_prove(
+        opening_accumulator.append(
             &read_write_polys,
             opening_point,
             &read_write_openings,
-            BatchType::Big,
             transcript,
-        )
+        );
     }
 
-    fn compute_verifier_openings(&mut self, _: &NoPreprocessing, opening_point: &[F]) {
+    fn compute_verifier_openings(
+        &mut self,
+        _: &NoPreprocessing,
+        opening_point: &[F],
+    ) -> Result<(), ProofVerifyError> {
         self.identity_poly_opening =
             Some(IdentityPolynomial::new(opening_point.len()).evaluate(opening_point));
+        Ok(())
     }
 
     fn verify_openings(
         &self,
-        generators: &C::Setup,
-        opening_proof: &Self::Proof,
         commitment: &JoltCommitments<C>,
         opening_point: &[F],
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         let openings = self
@@ -1096,15 +1091,16 @@ where
             .chain(self.t_read_opening)
             .chain(self.t_write_ram_opening)
             .collect::<Vec<_>>();
-        C::batch_verify(
-            opening_proof,
-            generators,
-            opening_point,
-            &openings,
-            &commitment.bytecode.trace_commitments[4..7]
+        let bytecode_commitments = commitment.bytecode.trace_commitments.get(4..7).ok_or(
+            ProofVerifyError::InvalidInputLength(7, commitment.bytecode.trace_commitments.len()),
+        )?;
+        opening_accumulator.append(
+            &bytecode_commitments
                 .iter()
                 .chain(commitment.read_write_memory.trace_commitments.iter())
                 .collect::<Vec<_>>(),
+            opening_point,
+            &openings,
             transcript,
         )
     }
@@ -1125,21 +1121,11 @@ where
     t_final: F,
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
-pub struct MemoryInitFinalOpeningProof<F, C>
-where
-    F: JoltField,
-    C: CommitmentScheme<Field = F>,
-{
-    v_t_opening_proof: C::BatchedProof,
-}
-
 impl<F, C> StructuredOpeningProof<F, C, JoltPolynomials<F, C>> for MemoryInitFinalOpenings<F>
 where
     F: JoltField,
     C: CommitmentScheme<Field = F>,
 {
-    type Proof = MemoryInitFinalOpeningProof<F, C>;
     type Preprocessing = ReadWriteMemoryPreprocessing;
 
     #[tracing::instrument(skip_all, name = "MemoryInitFinalOpenings::open")]
@@ -1163,78 +1149,85 @@ where
         polynomials: &JoltPolynomials<F, C>,
         opening_point: &[F],
         openings: &Self,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
-    ) -> Self::Proof {
-        let v_t_opening_proof = C::batch_prove(
+    ) {
+        opening_accumulator.append::<dyn MultilinearPolynomial<F>>(
             &[
                 &polynomials.read_write_memory.v_final,
                 &polynomials.read_write_memory.t_final,
             ],
             opening_point,
             &[openings.v_final, openings.t_final],
-            BatchType::Small,
             transcript,
         );
-
-        Self::Proof { v_t_opening_proof }
     }
 
     fn compute_verifier_openings(
         &mut self,
         preprocessing: &Self::Preprocessing,
         opening_point: &[F],
-    ) {
+    ) -> Result<(), ProofVerifyError> {
+        let program_io = preprocessing.program_io.as_ref().unwrap();
+        let memory_layout = &program_io.memory_layout;
+
+        // RAM must hold the bytecode and inputs, and fit in the 32-bit address space
+        let bytecode_start = memory_address_to_witness_index(
+            preprocessing.min_bytecode_address,
+            memory_layout.ram_witness_offset,
+        );
+        let input_start = memory_address_to_witness_index(
+            memory_layout.input_start,
+            memory_layout.ram_witness_offset,
+        );
+        let min_memory_size = (bytecode_start + preprocessing.bytecode_bytes.len())
+            .max(input_start + program_io.inputs.len());
+        if opening_point.len() > 32 || opening_point.len().pow2() < min_memory_size {
+            return Err(ProofVerifyError::InvalidInputLength(
+                min_memory_size.next_power_of_two().log_2(),
+                opening_point.len(),
+            ));
+        }
+
         self.a_init_final =
             Some(IdentityPolynomial::new(opening_point.len()).evaluate(opening_point));
 
-        let memory_layout = &preprocessing.program_io.as_ref().unwrap().memory_layout;
-
         // TODO(moodlezoup): Compute opening without instantiating v_init polynomial itself
         let memory_size = opening_point.len().pow2();
         let mut v_init: Vec<u64> = vec![0; memory_size];
         // Copy bytecode
-        let mut v_init_index = memory_address_to_witness_index(
-            preprocessing.min_bytecode_address,
-            memory_layout.ram_witness_offset,
-        );
+        let mut v_init_index = bytecode_start;
         for byte in preprocessing.bytecode_bytes.iter() {
             v_init[v_init_index] = *byte as u64;
             v_init_index += 1;
         }
         // Copy input bytes
-        v_init_index = memory_address_to_witness_index(
-            memory_layout.input_start,
-            memory_layout.ram_witness_offset,
-        );
-        for byte in preprocessing.program_io.as_ref().unwrap().inputs.iter() {
+        v_init_index = input_start;
+        for byte in program_io.inputs.iter() {
             v_init[v_init_index] = *byte as u64;
             v_init_index += 1;
         }
 
         self.v_init = Some(DensePolynomial::from_u64(&v_init).evaluate(opening_point));
+        Ok(())
     }
 
     fn verify_openings(
         &self,
-        generators: &C::Setup,
-        opening_proof: &Self::Proof,
         commitment: &JoltCommitments<C>,
         opening_point: &[F],
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        C::batch_verify(
-            &opening_proof.v_t_opening_proof,
-            generators,
-            opening_point,
-            &[self.v_final, self.t_final],
+        opening_accumulator.append(
             &[
                 &commitment.read_write_memory.v_final_commitment,
                 &commitment.read_write_memory.t_final_commitment,
             ],
+            opening_point,
+            &[self.v_final, self.t_final],
             transcript,
-        )?;
-
-        Ok(())
+        )
     }
 }
 
@@ -1272,16 +1265,19 @@ where
                     .into_par_iter()
                     .map(|j| {
                         let a = match i {
-                            RS1 => polynomials.bytecode.v_read_write[3][j],
-                            RS2 => polynomials.bytecode.v_read_write[4][j],
-                            RD => polynomials.bytecode.v_read_write[2][j],
+                            RS1 => polynomials.bytecode.v_read_write[3].get_coeff(j),
+                            RS2 => polynomials.bytecode.v_read_write[4].get_coeff(j),
+                            RD => polynomials.bytecode.v_read_write[2].get_coeff(j),
                             _ => {
-                                polynomials.read_write_memory.a_ram[j]
+                                polynomials.read_write_memory.a_ram.get_coeff(j)
                                     + F::from_u64((i - RAM_1) as u64).unwrap()
                             }
                         };
-                        polynomials.read_write_memory.t_read[i][j] * gamma_squared
-                            + mul_0_optimized(&polynomials.read_write_memory.v_read[i][j], gamma)
+                        polynomials.read_write_memory.t_read[i].get_coeff(j) * gamma_squared
+                            + mul_0_optimized(
+                                &polynomials.read_write_memory.v_read[i].get_coeff(j),
+                                gamma,
+                            )
                             + a
                             - *tau
                     })
@@ -1297,26 +1293,27 @@ where
                     .map(|j| match i {
                         RS1 => {
                             F::from_u64(j as u64).unwrap() * gamma_squared
-                                + mul_0_optimized(&v_write[j], gamma)
-                                + polynomials.bytecode.v_read_write[3][j]
+                                + mul_0_optimized(&v_write.get_coeff(j), gamma)
+                                + polynomials.bytecode.v_read_write[3].get_coeff(j)
                                 - *tau
                         }
                         RS2 => {
                             F::from_u64(j as u64).unwrap() * gamma_squared
-                                + mul_0_optimized(&v_write[j], gamma)
-                                + polynomials.bytecode.v_read_write[4][j]
+                                + mul_0_optimized(&v_write.get_coeff(j), gamma)
+                                + polynomials.bytecode.v_read_write[4].get_coeff(j)
                                 - *tau
                         }
                         RD => {
                             F::from_u64(j as u64 + 1).unwrap() * gamma_squared
-                                + mul_0_optimized(&v_write[j], gamma)
-                                + polynomials.bytecode.v_read_write[2][j]
+                                + mul_0_optimized(&v_write.get_coeff(j), gamma)
+                                + polynomials.bytecode.v_read_write[2].get_coeff(j)
                                 - *tau
                         }
                         _ => {
-                            polynomials.read_write_memory.t_write_ram[i - RAM_1][j] * gamma_squared
-                                + mul_0_optimized(&v_write[j], gamma)
-                                + polynomials.read_write_memory.a_ram[j]
+                            polynomials.read_write_memory.t_write_ram[i - RAM_1].get_coeff(j)
+                                * gamma_squared
+                                + mul_0_optimized(&v_write.get_coeff(j), gamma)
+                                + polynomials.read_write_memory.a_ram.get_coeff(j)
                                 + F::from_u64((i - RAM_1) as u64).unwrap()
                                 - *tau
                         }
@@ -1328,13 +1325,15 @@ where
 
         let init_fingerprints = (0..polynomials.read_write_memory.memory_size)
             .into_par_iter()
-            .map(|i| /* 0 * gamma^2 + */ mul_0_optimized(&polynomials.read_write_memory.v_init[i], gamma) + F::from_u64(i as u64).unwrap() - *tau)
+            .map(|i| /* 0 * gamma^2 + */ mul_0_optimized(&polynomials.read_write_memory.v_init.get_coeff(i), gamma) + F::from_u64(i as u64).unwrap() - *tau)
             .collect();
         let final_fingerprints = (0..polynomials.read_write_memory.memory_size)
             .into_par_iter()
             .map(|i| {
-                mul_0_optimized(&polynomials.read_write_memory.t_final[i], &gamma_squared)
-                    + mul_0_optimized(&polynomials.read_write_memory.v_final[i], gamma)
+                mul_0_optimized(
+                    &polynomials.read_write_memory.t_final.get_coeff(i),
+                    &gamma_squared,
+                ) + mul_0_optimized(&polynomials.read_write_memory.v_final.get_coeff(i), gamma)
                     + F::from_u64(i as u64).unwrap()
                     - *tau
             })
@@ -1374,28 +1373,36 @@ where
     fn check_multiset_equality(
         _preprocessing: &Self::Preprocessing,
         multiset_hashes: &MultisetHashes<F>,
-    ) {
-        assert_eq!(
+    ) -> Result<(), ProofVerifyError> {
+        for len in [
             multiset_hashes.read_hashes.len(),
-            MEMORY_OPS_PER_INSTRUCTION
-        );
-        assert_eq!(
             multiset_hashes.write_hashes.len(),
-            MEMORY_OPS_PER_INSTRUCTION
-        );
-        assert_eq!(multiset_hashes.init_hashes.len(), 1);
-        assert_eq!(multiset_hashes.final_hashes.len(), 1);
+        ] {
+            if len != MEMORY_OPS_PER_INSTRUCTION {
+                return Err(ProofVerifyError::InvalidInputLength(
+                    MEMORY_OPS_PER_INSTRUCTION,
+                    len,
+                ));
+            }
+        }
+        for len in [
+            multiset_hashes.init_hashes.len(),
+            multiset_hashes.final_hashes.len(),
+        ] {
+            if len != 1 {
+                return Err(ProofVerifyError::InvalidInputLength(1, len));
+            }
+        }
 
         let read_hash: F = multiset_hashes.read_hashes.iter().product();
         let write_hash: F = multiset_hashes.write_hashes.iter().product();
         let init_hash = multiset_hashes.init_hashes[0];
         let final_hash = multiset_hashes.final_hashes[0];
 
-        assert_eq!(
-            init_hash * write_hash,
-            final_hash * read_hash,
-            "Multiset hashes don't match"
-        );
+        if init_hash * write_hash != final_hash * read_hash {
+            return Err(ProofVerifyError::MultisetHashMismatch(0));
+        }
+        Ok(())
     }
 
     fn protocol_name() -> &'static [u8] {
@@ -1485,13 +1492,12 @@ where
     F: JoltField,
     C: CommitmentScheme<Field = F>,
 {
+    _marker: PhantomData<C>,
     num_rounds: usize,
     /// Sumcheck proof that v_final is equal to the program outputs at the relevant indices.
     sumcheck_proof: SumcheckInstanceProof<F>,
     /// Opening of v_final at the random point chosen over the course of sumcheck
     opening: F,
-    /// Hyrax opening proof of the v_final opening
-    opening_proof: C::Proof,
 }
 
 impl<F, C> OutputSumcheckProof<F, C>
@@ -1502,6 +1508,7 @@ where
     fn prove_outputs(
         polynomials: &ReadWriteMemory<F, C>,
         program_io: &JoltDevice,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
     ) -> Self {
         let num_rounds = polynomials.memory_size.log_2();
@@ -1548,7 +1555,7 @@ where
         let mut sumcheck_polys = vec![
             eq,
             DensePolynomial::new(io_witness_range),
-            polynomials.v_final.clone(),
+            polynomials.v_final.to_dense(),
             DensePolynomial::from_u64(&v_io),
         ];
 
@@ -1565,23 +1572,43 @@ where
                 transcript,
             );
 
-        let sumcheck_opening_proof = C::prove(&polynomials.v_final, &r_sumcheck, transcript);
+        opening_accumulator.append(
+            &[&polynomials.v_final],
+            &r_sumcheck,
+            &[sumcheck_openings[2]],
+            transcript,
+        );
 
         Self {
+            _marker: PhantomData,
             num_rounds,
             sumcheck_proof,
             opening: sumcheck_openings[2], // only need v_final; verifier computes the rest on its own
-            opening_proof: sumcheck_opening_proof,
         }
     }
 
     fn verify(
         proof: &Self,
         preprocessing: &ReadWriteMemoryPreprocessing,
-        generators: &C::Setup,
         commitment: &MemoryCommitment<C>,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
+        let memory_layout = &preprocessing.program_io.as_ref().unwrap().memory_layout;
+
+        let nonzero_memory_size = memory_layout.ram_witness_offset as usize;
+        if !nonzero_memory_size.is_power_of_two() {
+            return Err(ProofVerifyError::InvalidMemoryLayout);
+        }
+        let log_nonzero_memory_size = nonzero_memory_size.log_2();
+        // The sumcheck is over all of RAM, which spans at most the 32-bit address space
+        if proof.num_rounds < log_nonzero_memory_size || proof.num_rounds > 32 {
+            return Err(ProofVerifyError::InvalidInputLength(
+                log_nonzero_memory_size,
+                proof.num_rounds,
+            ));
+        }
+
         let r_eq = transcript.challenge_vector(b"output_sumcheck", proof.num_rounds);
 
         let (sumcheck_claim, r_sumcheck) =
@@ -1591,15 +1618,6 @@ where
 
         let eq_eval = EqPolynomial::new(r_eq.to_vec()).evaluate(&r_sumcheck);
 
-        let memory_layout = &preprocessing.program_io.as_ref().unwrap().memory_layout;
-
-        let nonzero_memory_size = memory_layout.ram_witness_offset as usize;
-        let log_nonzero_memory_size = nonzero_memory_size.log_2();
-        assert!(
-            nonzero_memory_size.is_power_of_two(),
-            "Ram witness offset must be a power of two"
-        );
-
         let io_witness_range: Vec<_> = (0..nonzero_memory_size as u64)
             .map(|i| {
                 if i >= memory_layout.input_start {
@@ -1643,19 +1661,15 @@ where
             DensePolynomial::from_u64(&v_io).evaluate(&r_sumcheck[..log_nonzero_memory_size]);
         v_io_eval *= r_prod;
 
-        assert_eq!(
-            eq_eval * io_witness_range_eval * (proof.opening - v_io_eval),
-            sumcheck_claim,
-            "Output sumcheck check failed."
-        );
+        if eq_eval * io_witness_range_eval * (proof.opening - v_io_eval) != sumcheck_claim {
+            return Err(ProofVerifyError::SumcheckClaimError);
+        }
 
-        C::verify(
-            &proof.opening_proof,
-            generators,
-            transcript,
+        opening_accumulator.append(
+            &[&commitment.v_final_commitment],
             &r_sumcheck,
-            &proof.opening,
-            &commitment.v_final_commitment,
+            &[proof.opening],
+            transcript,
         )
     }
 }
@@ -1685,22 +1699,31 @@ where
     #[tracing::instrument(skip_all, name = "ReadWriteMemoryProof::prove")]
     pub fn prove(
         preprocessing: &ReadWriteMemoryPreprocessing,
+        generators: &C::Setup,
         polynomials: &JoltPolynomials<F, C>,
         program_io: &JoltDevice,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
     ) -> Self {
-        let memory_checking_proof =
-            ReadWriteMemoryProof::prove_memory_checking(preprocessing, polynomials, transcript);
+        let memory_checking_proof = ReadWriteMemoryProof::prove_memory_checking(
+            preprocessing,
+            generators,
+            polynomials,
+            opening_accumulator,
+            transcript,
+        );
 
         let output_proof = OutputSumcheckProof::prove_outputs(
             &polynomials.read_write_memory,
             program_io,
+            opening_accumulator,
             transcript,
         );
 
         let timestamp_validity_proof = TimestampValidityProof::prove(
             &polynomials.timestamp_range_check,
             &polynomials.read_write_memory.t_read,
+            opening_accumulator,
             transcript,
         );
 
@@ -1716,6 +1739,7 @@ where
         generators: &C::Setup,
         preprocessing: &ReadWriteMemoryPreprocessing,
         commitment: &JoltCommitments<C>,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         ReadWriteMemoryProof::verify_memory_checking(
@@ -1723,21 +1747,25 @@ where
             generators,
             self.memory_checking_proof,
             commitment,
+            opening_accumulator,
             transcript,
-        )?;
+        )
+        .map_err(ProofVerifyError::wrap(ProofVerifyError::RamError))?;
         OutputSumcheckProof::verify(
             &self.output_proof,
             preprocessing,
-            generators,
             &commitment.read_write_memory,
+            opening_accumulator,
             transcript,
-        )?;
+        )
+        .map_err(ProofVerifyError::wrap(ProofVerifyError::OutputCheckError))?;
         TimestampValidityProof::verify(
             &mut self.timestamp_validity_proof,
-            generators,
             &commitment.timestamp_range_check,
             &commitment.read_write_memory,
+            opening_accumulator,
             transcript,
         )
+        .map_err(ProofVerifyError::wrap(ProofVerifyError::RangeCheckError))
     }
 }
@@ -1,147 +1,169 @@
 use crate::poly::field::JoltField;
-use enum_dispatch::enum_dispatch;
-use rand::{prelude::StdRng, RngCore};
 use serde::{Deserialize, Serialize};
-use std::any::TypeId;
-use strum::{EnumCount, IntoEnumIterator};
 use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
 
 use super::{Jolt, JoltProof};
 use crate::jolt::instruction::{
-    add::ADDInstruction, and::ANDInstruction, beq::BEQInstruction, bge::BGEInstruction,
-    bgeu::BGEUInstruction, bne::BNEInstruction, lb::LBInstruction, lh::LHInstruction,
-    movsign::MOVSIGNInstruction, mul::MULInstruction, mulhu::MULHUInstruction,
-    mulu::MULUInstruction, or::ORInstruction, sb::SBInstruction, sh::SHInstruction,
-    sll::SLLInstruction, slt::SLTInstruction, sltu::SLTUInstruction, sra::SRAInstruction,
-    srl::SRLInstruction, sub::SUBInstruction, sw::SWInstruction, xor::XORInstruction,
-    JoltInstruction, JoltInstructionSet, SubtableIndices,
+    add::ADDInstruction, advice::ADVICEInstruction, and::ANDInstruction, andn::ANDNInstruction,
+    beq::BEQInstruction, bge::BGEInstruction, bgeu::BGEUInstruction, bne::BNEInstruction,
+    clz::CLZInstruction, cpop::CPOPInstruction, ctz::CTZInstruction, lb::LBInstruction,
+    lh::LHInstruction, max::MAXInstruction, maxu::MAXUInstruction, min::MINInstruction,
+    minu::MINUInstruction, movsign::MOVSIGNInstruction, mul::MULInstruction,
+    mulhu::MULHUInstruction, mulu::MULUInstruction, or::ORInstruction, orcb::ORCBInstruction,
+    orn::ORNInstruction, rev8::REV8Instruction, rol::ROLInstruction, ror::RORInstruction,
+    sb::SBInstruction, sextb::SEXTBInstruction, sexth::SEXTHInstruction, sh::SHInstruction,
+    shadd::SHADDInstruction, sll::SLLInstruction, slt::SLTInstruction, sltu::SLTUInstruction,
+    sra::SRAInstruction, srl::SRLInstruction, sub::SUBInstruction, sw::SWInstruction,
+    xnor::XNORInstruction, xor::XORInstruction, JoltInstructionSet,
 };
 use crate::jolt::subtable::{
-    and::AndSubtable, eq::EqSubtable, eq_abs::EqAbsSubtable, eq_msb::EqMSBSubtable,
-    gt_msb::GtMSBSubtable, identity::IdentitySubtable, lt_abs::LtAbsSubtable, ltu::LtuSubtable,
-    or::OrSubtable, sign_extend::SignExtendSubtable, sll::SllSubtable, sra_sign::SraSignSubtable,
-    srl::SrlSubtable, truncate_overflow::TruncateOverflowSubtable, xor::XorSubtable,
-    JoltSubtableSet, LassoSubtable, SubtableId,
+    and::AndSubtable, andn::AndnSubtable, eq::EqSubtable, eq_abs::EqAbsSubtable,
+    eq_msb::EqMSBSubtable, gt_msb::GtMSBSubtable, identity::IdentitySubtable,
+    leading_zeros::LeadingZerosSubtable, left_msb::LeftMSBSubtable,
+    left_operand::LeftOperandSubtable, lt_abs::LtAbsSubtable, ltu::LtuSubtable, or::OrSubtable,
+    orn::OrnSubtable, popcount::PopcountSubtable, right_operand::RightOperandSubtable,
+    rotl::RotlSubtable, rotr::RotrSubtable, sign_extend::SignExtendSubtable, sll::SllSubtable,
+    sra_sign::SraSignSubtable, srl::SrlSubtable, trailing_zeros::TrailingZerosSubtable,
+    truncate_overflow::TruncateOverflowSubtable, xor::XorSubtable, JoltSubtableSet,
 };
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 
-/// Generates an enum out of a list of JoltInstruction types. All JoltInstruction methods
-/// are callable on the enum type via enum_dispatch.
-macro_rules! instruction_set {
-    ($enum_name:ident, $($alias:ident: $struct:ty),+) => {
-        #[allow(non_camel_case_types)]
-        #[repr(u8)]
-        #[derive(Copy, Clone, Debug, EnumIter, EnumCountMacro, Serialize, Deserialize)]
-        #[enum_dispatch(JoltInstruction)]
-        pub enum $enum_name { $($alias($struct)),+ }
-        impl JoltInstructionSet for $enum_name {}
-        impl $enum_name {
-            pub fn random_instruction(rng: &mut StdRng) -> Self {
-                let index = rng.next_u64() as usize % $enum_name::COUNT;
-                let instruction = $enum_name::iter()
-                    .enumerate()
-                    .filter(|(i, _)| *i == index)
-                    .map(|(_, x)| x)
-                    .next()
-                    .unwrap();
-                instruction.random(rng)
-            }
-        }
-    };
-}
-
-/// Generates an enum out of a list of LassoSubtable types. All LassoSubtable methods
-/// are callable on the enum type via enum_dispatch.
-macro_rules! subtable_enum {
-    ($enum_name:ident, $($alias:ident: $struct:ty),+) => {
-        #[allow(non_camel_case_types)]
-        #[repr(usize)]
-        #[enum_dispatch(LassoSubtable<F>)]
-        #[derive(EnumCountMacro, EnumIter)]
-        pub enum $enum_name<F: JoltField> { $($alias($struct)),+ }
-        impl<F: JoltField> From<SubtableId> for $enum_name<F> {
-          fn from(subtable_id: SubtableId) -> Self {
-            $(
-              if subtable_id == TypeId::of::<$struct>() {
-                $enum_name::from(<$struct>::new())
-              } else
-            )+
-            { panic!("Unexpected subtable id {:?}", subtable_id) } // TODO(moodlezoup): better error handling
-          }
-        }
-
-        impl<F: JoltField> From<$enum_name<F>> for usize {
-            fn from(subtable: $enum_name<F>) -> usize {
-                unsafe { *<*const _>::from(&subtable).cast::<usize>() }
-            }
-        }
-        impl<F: JoltField> JoltSubtableSet<F> for $enum_name<F> {}
-    };
-}
-
 const WORD_SIZE: usize = 32;
 
-instruction_set!(
-  RV32I,
-  ADD: ADDInstruction<WORD_SIZE>,
-  SUB: SUBInstruction<WORD_SIZE>,
-  AND: ANDInstruction,
-  OR: ORInstruction,
-  XOR: XORInstruction,
-  LB: LBInstruction,
-  LH: LHInstruction,
-  SB: SBInstruction,
-  SH: SHInstruction,
-  SW: SWInstruction,
-  BEQ: BEQInstruction,
-  BGE: BGEInstruction,
-  BGEU: BGEUInstruction,
-  BNE: BNEInstruction,
-  SLT: SLTInstruction,
-  SLTU: SLTUInstruction,
-  SLL: SLLInstruction<WORD_SIZE>,
-  SRA: SRAInstruction<WORD_SIZE>,
-  SRL: SRLInstruction<WORD_SIZE>,
-  MOVSIGN: MOVSIGNInstruction<WORD_SIZE>,
-  MUL: MULInstruction<WORD_SIZE>,
-  MULU: MULUInstruction<WORD_SIZE>,
-  MULHU: MULHUInstruction<WORD_SIZE>
-);
-subtable_enum!(
-  RV32ISubtables,
-  AND: AndSubtable<F>,
-  EQ_ABS: EqAbsSubtable<F>,
-  EQ_MSB: EqMSBSubtable<F>,
-  EQ: EqSubtable<F>,
-  GT_MSB: GtMSBSubtable<F>,
-  IDENTITY: IdentitySubtable<F>,
-  LT_ABS: LtAbsSubtable<F>,
-  LTU: LtuSubtable<F>,
-  OR: OrSubtable<F>,
-  SIGN_EXTEND_8: SignExtendSubtable<F, 8>,
-  SIGN_EXTEND_16: SignExtendSubtable<F, 16>,
-  SLL0: SllSubtable<F, 0, WORD_SIZE>,
-  SLL1: SllSubtable<F, 1, WORD_SIZE>,
-  SLL2: SllSubtable<F, 2, WORD_SIZE>,
-  SLL3: SllSubtable<F, 3, WORD_SIZE>,
-  SRA_SIGN: SraSignSubtable<F, WORD_SIZE>,
-  SRL0: SrlSubtable<F, 0, WORD_SIZE>,
-  SRL1: SrlSubtable<F, 1, WORD_SIZE>,
-  SRL2: SrlSubtable<F, 2, WORD_SIZE>,
-  SRL3: SrlSubtable<F, 3, WORD_SIZE>,
-  TRUNCATE: TruncateOverflowSubtable<F, WORD_SIZE>,
-  TRUNCATE_BYTE: TruncateOverflowSubtable<F, 8>,
-  XOR: XorSubtable<F>
-);
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(
+    Copy, Clone, Debug, EnumIter, EnumCountMacro, Serialize, Deserialize, JoltInstructionSet,
+)]
+pub enum RV32I {
+    ADD(ADDInstruction<WORD_SIZE>),
+    SUB(SUBInstruction<WORD_SIZE>),
+    AND(ANDInstruction),
+    OR(ORInstruction),
+    XOR(XORInstruction),
+    LB(LBInstruction),
+    LH(LHInstruction),
+    SB(SBInstruction),
+    SH(SHInstruction),
+    SW(SWInstruction),
+    BEQ(BEQInstruction),
+    BGE(BGEInstruction),
+    BGEU(BGEUInstruction),
+    BNE(BNEInstruction),
+    SLT(SLTInstruction),
+    SLTU(SLTUInstruction),
+    SLL(SLLInstruction<WORD_SIZE>),
+    SRA(SRAInstruction<WORD_SIZE>),
+    SRL(SRLInstruction<WORD_SIZE>),
+    MOVSIGN(MOVSIGNInstruction<WORD_SIZE>),
+    MUL(MULInstruction<WORD_SIZE>),
+    MULU(MULUInstruction<WORD_SIZE>),
+    MULHU(MULHUInstruction<WORD_SIZE>),
+    ADVICE(ADVICEInstruction<WORD_SIZE>),
+    ANDN(ANDNInstruction),
+    ORN(ORNInstruction<WORD_SIZE>),
+    XNOR(XNORInstruction<WORD_SIZE>),
+    CLZ(CLZInstruction<WORD_SIZE>),
+    CTZ(CTZInstruction<WORD_SIZE>),
+    CPOP(CPOPInstruction),
+    SEXTB(SEXTBInstruction<WORD_SIZE>),
+    SEXTH(SEXTHInstruction<WORD_SIZE>),
+    MAX(MAXInstruction),
+    MAXU(MAXUInstruction),
+    MIN(MINInstruction),
+    MINU(MINUInstruction),
+    ROL(ROLInstruction<WORD_SIZE>),
+    ROR(RORInstruction<WORD_SIZE>),
+    ORCB(ORCBInstruction),
+    REV8(REV8Instruction<WORD_SIZE>),
+    SH1ADD(SHADDInstruction<WORD_SIZE, 1>),
+    SH2ADD(SHADDInstruction<WORD_SIZE, 2>),
+    SH3ADD(SHADDInstruction<WORD_SIZE, 3>),
+}
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(EnumCountMacro, EnumIter, JoltSubtableSet)]
+pub enum RV32ISubtables<F: JoltField> {
+    AND(AndSubtable<F>),
+    ANDN(AndnSubtable<F>),
+    EQ_ABS(EqAbsSubtable<F>),
+    EQ_MSB(EqMSBSubtable<F>),
+    EQ(EqSubtable<F>),
+    GT_MSB(GtMSBSubtable<F>),
+    IDENTITY(IdentitySubtable<F>),
+    LEADING_ZEROS(LeadingZerosSubtable<F>),
+    LEFT_MSB(LeftMSBSubtable<F>),
+    LEFT_OPERAND(LeftOperandSubtable<F>),
+    LT_ABS(LtAbsSubtable<F>),
+    LTU(LtuSubtable<F>),
+    OR(OrSubtable<F>),
+    ORN(OrnSubtable<F>),
+    POPCOUNT(PopcountSubtable<F>),
+    RIGHT_OPERAND(RightOperandSubtable<F>),
+    ROTL0(RotlSubtable<F, 0, WORD_SIZE>),
+    ROTL1(RotlSubtable<F, 1, WORD_SIZE>),
+    ROTL2(RotlSubtable<F, 2, WORD_SIZE>),
+    ROTL3(RotlSubtable<F, 3, WORD_SIZE>),
+    ROTL4(RotlSubtable<F, 4, WORD_SIZE>),
+    ROTL5(RotlSubtable<F, 5, WORD_SIZE>),
+    ROTL6(RotlSubtable<F, 6, WORD_SIZE>),
+    ROTL7(RotlSubtable<F, 7, WORD_SIZE>),
+    ROTL8(RotlSubtable<F, 8, WORD_SIZE>),
+    ROTL9(RotlSubtable<F, 9, WORD_SIZE>),
+    ROTR0(RotrSubtable<F, 0, WORD_SIZE>),
+    ROTR1(RotrSubtable<F, 1, WORD_SIZE>),
+    ROTR2(RotrSubtable<F, 2, WORD_SIZE>),
+    ROTR3(RotrSubtable<F, 3, WORD_SIZE>),
+    ROTR4(RotrSubtable<F, 4, WORD_SIZE>),
+    ROTR5(RotrSubtable<F, 5, WORD_SIZE>),
+    ROTR6(RotrSubtable<F, 6, WORD_SIZE>),
+    ROTR7(RotrSubtable<F, 7, WORD_SIZE>),
+    ROTR8(RotrSubtable<F, 8, WORD_SIZE>),
+    ROTR9(RotrSubtable<F, 9, WORD_SIZE>),
+    SIGN_EXTEND_8(SignExtendSubtable<F, 8>),
+    SIGN_EXTEND_16(SignExtendSubtable<F, 16>),
+    SLL0(SllSubtable<F, 0, WORD_SIZE>),
+    SLL1(SllSubtable<F, 1, WORD_SIZE>),
+    SLL2(SllSubtable<F, 2, WORD_SIZE>),
+    SLL3(SllSubtable<F, 3, WORD_SIZE>),
+    SLL4(SllSubtable<F, 4, WORD_SIZE>),
+    SLL5(SllSubtable<F, 5, WORD_SIZE>),
+    SLL6(SllSubtable<F, 6, WORD_SIZE>),
+    SLL7(SllSubtable<F, 7, WORD_SIZE>),
+    SLL8(SllSubtable<F, 8, WORD_SIZE>),
+    SLL9(SllSubtable<F, 9, WORD_SIZE>),
+    SRA_SIGN(SraSignSubtable<F, WORD_SIZE>),
+    SRL0(SrlSubtable<F, 0, WORD_SIZE>),
+    SRL1(SrlSubtable<F, 1, WORD_SIZE>),
+    SRL2(SrlSubtable<F, 2, WORD_SIZE>),
+    SRL3(SrlSubtable<F, 3, WORD_SIZE>),
+    SRL4(SrlSubtable<F, 4, WORD_SIZE>),
+    SRL5(SrlSubtable<F, 5, WORD_SIZE>),
+    SRL6(SrlSubtable<F, 6, WORD_SIZE>),
+    SRL7(SrlSubtable<F, 7, WORD_SIZE>),
+    SRL8(SrlSubtable<F, 8, WORD_SIZE>),
+    SRL9(SrlSubtable<F, 9, WORD_SIZE>),
+    TRAILING_ZEROS(TrailingZerosSubtable<F>),
+    TRUNCATE(TruncateOverflowSubtable<F, WORD_SIZE>),
+    TRUNCATE_BYTE(TruncateOverflowSubtable<F, 8>),
+    XOR(XorSubtable<F>),
+}
 
 // ==================== JOLT ====================
 
-pub enum RV32IJoltVM {}
+/// The RV32I zkVM, with lookup queries decomposed into `C` chunks of `log2(M)` bits.
+///
+/// The shift instructions read the whole shift amount from a single operand chunk, and the
+/// byte-oriented instructions (loads, stores, SEXT.B, REV8, ...) assume 16-bit chunks, so `M`
+/// must be `1 << 16`; `Jolt` is only implemented for that `M`, so other values don't compile.
+/// The R1CS combines the operand chunks with i64 coefficients, so `C` can be anywhere from 4
+/// to 8.
+pub enum GenericRV32IJoltVM<const C: usize, const M: usize> {}
 
 pub const C: usize = 4;
 pub const M: usize = 1 << 16;
 
-impl<F, CS> Jolt<F, CS, C, M> for RV32IJoltVM
+pub type RV32IJoltVM = GenericRV32IJoltVM<C, M>;
+
+impl<F, CS, const C: usize> Jolt<F, CS, C, M> for GenericRV32IJoltVM<C, M>
 where
     F: JoltField,
     CS: CommitmentScheme<Field = F>,
@@ -158,34 +180,62 @@ pub type RV32IJoltProof<F, CS> = JoltProof<C, M, F, CS, RV32I, RV32ISubtables<F>
 mod tests {
     use ark_bn254::{Fr, G1Projective};
 
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
 
     use crate::host;
-    use crate::jolt::instruction::JoltInstruction;
-    use crate::jolt::vm::rv32i_vm::{Jolt, RV32IJoltVM, C, M};
+    use crate::jolt::instruction::{InstructionSubtables, JoltInstruction};
+    use crate::jolt::subtable::LassoSubtable;
+    use crate::jolt::vm::rv32i_vm::{
+        GenericRV32IJoltVM, Jolt, RV32IJoltVM, RV32ISubtables, C, M, RV32I,
+    };
     use crate::poly::commitment::hyrax::HyraxScheme;
+    use crate::utils::errors::ProofVerifyError;
+    use common::constants::RAM_START_ADDRESS;
     use std::sync::Mutex;
     use strum::{EnumCount, IntoEnumIterator};
 
     // If multiple tests try to read the same trace artifacts simultaneously, they will fail
     lazy_static::lazy_static! {
         static ref FIB_FILE_LOCK: Mutex<()> = Mutex::new(());
+        static ref SHA2_FILE_LOCK: Mutex<()> = Mutex::new(());
         static ref SHA3_FILE_LOCK: Mutex<()> = Mutex::new(());
     }
 
     #[test]
     fn instruction_set_subtables() {
+        // Whether each instruction's subtables are in the subtable set is checked at compile
+        // time; check that each one's `#[subtables]` list covers what `subtables` returns, and
+        // that every variant is used. The shift instructions use one subtable per dimension, so
+        // the set has enough of them for any dimension up to 10.
+        let variants: Vec<_> =
+            <RV32IJoltVM as Jolt<_, HyraxScheme<G1Projective>, C, M>>::Subtables::iter().collect();
         let mut subtable_set: HashSet<_> = HashSet::new();
-        for instruction in
-            <RV32IJoltVM as Jolt<_, HyraxScheme<G1Projective>, C, M>>::InstructionSet::iter()
-        {
-            for (subtable, _) in instruction.subtables::<Fr>(C, M) {
-                // panics if subtable cannot be cast to enum variant
-                let _ = <RV32IJoltVM as Jolt<_, HyraxScheme<G1Projective>, C, M>>::Subtables::from(
+        let mut check = |instruction: RV32I, c: usize| {
+            for (subtable, _) in instruction.subtables::<Fr>(c, M) {
+                let index = InstructionSubtables::<Fr, RV32ISubtables<Fr>>::subtable_index(
+                    &instruction,
                     subtable.subtable_id(),
                 );
+                assert_eq!(variants[index].subtable_id(), subtable.subtable_id());
                 subtable_set.insert(subtable.subtable_id());
             }
+        };
+        for instruction in
+            <RV32IJoltVM as Jolt<_, HyraxScheme<G1Projective>, C, M>>::InstructionSet::iter()
+        {
+            check(instruction, C);
+        }
+        let shifts = [
+            RV32I::SLL(Default::default()),
+            RV32I::SRL(Default::default()),
+            RV32I::SRA(Default::default()),
+            RV32I::ROL(Default::default()),
+            RV32I::ROR(Default::default()),
+        ];
+        for c in C..=10 {
+            for instruction in shifts {
+                check(instruction, c);
+            }
         }
         assert_eq!(
             subtable_set.len(),
@@ -203,8 +253,14 @@ mod tests {
         let (bytecode, memory_init) = program.decode();
         let (io_device, trace, circuit_flags) = program.trace();
 
-        let preprocessing =
-            RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
+        let preprocessing = RV32IJoltVM::preprocess(
+            bytecode.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+            None,
+        );
         let (proof, commitments) =
             <RV32IJoltVM as Jolt<Fr, HyraxScheme<G1Projective>, C, M>>::prove(
                 io_device,
@@ -220,6 +276,418 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fib_e2e_from_recorded_trace() {
+        fib_e2e_from_trace_file(false);
+    }
+
+    #[cfg(feature = "trace-compression")]
+    #[test]
+    fn fib_e2e_from_compressed_recorded_trace() {
+        fib_e2e_from_trace_file(true);
+    }
+
+    fn fib_e2e_from_trace_file(compress: bool) {
+        let _guard = FIB_FILE_LOCK.lock().unwrap();
+
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let trace_path = std::env::temp_dir().join(format!(
+            "jolt-fib-e2e-{}-{compress}.trace",
+            std::process::id()
+        ));
+        let io_device = program.clone().record_trace(&trace_path, compress).unwrap();
+
+        let preprocessing = RV32IJoltVM::preprocess(
+            bytecode.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+            None,
+        );
+        let (proof, commitments) = program
+            .prove_from_trace::<Fr, HyraxScheme<G1Projective>>(&trace_path, preprocessing.clone())
+            .unwrap();
+        std::fs::remove_file(&trace_path).unwrap();
+
+        assert_eq!(proof.program_io, io_device);
+        let verification_result = RV32IJoltVM::verify(preprocessing, proof, commitments);
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn recorded_trace_from_other_program_is_rejected() {
+        let _fib_guard = FIB_FILE_LOCK.lock().unwrap();
+        let _sha3_guard = SHA3_FILE_LOCK.lock().unwrap();
+
+        // The trace file's header records the digest of the sha3 guest's ELF file.
+        let mut sha3_program = host::Program::new("sha3-guest");
+        sha3_program.set_input(&[5u8; 32]);
+        let trace_path =
+            std::env::temp_dir().join(format!("jolt-sha3-for-fib-{}.trace", std::process::id()));
+        sha3_program.record_trace(&trace_path, false).unwrap();
+
+        let result = host::Program::new("fibonacci-guest").trace_from_file::<Fr>(&trace_path);
+        std::fs::remove_file(&trace_path).unwrap();
+        assert_eq!(
+            result.err().unwrap().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn fib_e2e_rejects_mutated_proofs() {
+        use crate::jolt::vm::rv32i_vm::RV32IJoltProof;
+        use crate::jolt::vm::JoltCommitments;
+        use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+        type Pcs = HyraxScheme<G1Projective>;
+        type Mutation = fn(&mut RV32IJoltProof<Fr, Pcs>, RV32IJoltProof<Fr, Pcs>);
+
+        let _guard = FIB_FILE_LOCK.lock().unwrap();
+
+        let mut program = host::Program::new("fibonacci-guest");
+        let (bytecode, memory_init) = program.decode();
+        let preprocessing =
+            RV32IJoltVM::preprocess(bytecode, memory_init, 1 << 20, 1 << 20, 1 << 20, None);
+
+        // Proofs of the same program on different inputs, serialized so that they can be
+        // mutated repeatedly; the second one donates components to the first.
+        let prove = |input: u32| {
+            let mut program = program.clone();
+            program.set_input(&input);
+            let (io_device, trace, circuit_flags) = program.trace();
+            let (proof, commitments) = <RV32IJoltVM as Jolt<Fr, Pcs, C, M>>::prove(
+                io_device,
+                trace,
+                circuit_flags,
+                preprocessing.clone(),
+            );
+            let mut bytes = vec![];
+            proof.serialize_compressed(&mut bytes).unwrap();
+            commitments.serialize_compressed(&mut bytes).unwrap();
+            bytes
+        };
+        let proof_bytes = prove(9);
+        let donor_bytes = prove(10);
+        let load = |bytes: &[u8]| {
+            let mut reader = bytes;
+            let proof = RV32IJoltProof::<Fr, Pcs>::deserialize_compressed(&mut reader).unwrap();
+            let commitments = JoltCommitments::<Pcs>::deserialize_compressed(&mut reader).unwrap();
+            (proof, commitments)
+        };
+
+        let (proof, commitments) = load(&proof_bytes);
+        RV32IJoltVM::verify(preprocessing.clone(), proof, commitments).unwrap();
+
+        // The trace length and program I/O are bound by the Fiat-Shamir preamble, so changing
+        // them derails the first subprotocol to be verified, which is the bytecode's.
+        let mutations: Vec<(&str, Mutation, fn(&ProofVerifyError) -> bool)> = vec![
+            (
+                "trace_length",
+                |proof, _| proof.trace_length += 1,
+                |e| matches!(e, ProofVerifyError::BytecodeError(_)),
+            ),
+            (
+                "program_io.inputs",
+                |proof, _| proof.program_io.inputs[0] ^= 1,
+                |e| matches!(e, ProofVerifyError::BytecodeError(_)),
+            ),
+            (
+                "program_io.outputs",
+                |proof, _| proof.program_io.outputs[0] ^= 1,
+                |e| matches!(e, ProofVerifyError::BytecodeError(_)),
+            ),
+            (
+                "program_io.outputs too large",
+                |proof, _| {
+                    let max_output_size = proof.program_io.memory_layout.max_output_size;
+                    proof.program_io.outputs = vec![0; max_output_size as usize + 1];
+                },
+                |e| matches!(e, ProofVerifyError::OutputTooLarge),
+            ),
+            (
+                "program_io.panic",
+                |proof, _| proof.program_io.panic = !proof.program_io.panic,
+                |e| matches!(e, ProofVerifyError::BytecodeError(_)),
+            ),
+            (
+                "program_io.memory_layout",
+                |proof, _| proof.program_io.memory_layout.output_start += 1,
+                |e| matches!(e, ProofVerifyError::InvalidMemoryLayout),
+            ),
+            (
+                "bytecode",
+                |proof, donor| proof.bytecode = donor.bytecode,
+                |e| matches!(e, ProofVerifyError::BytecodeError(_)),
+            ),
+            (
+                "bytecode.multiset_hashes",
+                |proof, _| {
+                    proof.bytecode.multiset_hashes.read_hashes.pop();
+                },
+                |e| matches!(e, ProofVerifyError::BytecodeError(_)),
+            ),
+            (
+                "instruction_lookups",
+                |proof, donor| proof.instruction_lookups = donor.instruction_lookups,
+                |e| matches!(e, ProofVerifyError::PrimarySumcheckError(_)),
+            ),
+            (
+                "read_write_memory.memory_checking_proof",
+                |proof, donor| {
+                    proof.read_write_memory.memory_checking_proof =
+                        donor.read_write_memory.memory_checking_proof
+                },
+                |e| matches!(e, ProofVerifyError::RamError(_)),
+            ),
+            (
+                "read_write_memory.memory_checking_proof.multiset_hashes",
+                |proof, _| {
+                    proof
+                        .read_write_memory
+                        .memory_checking_proof
+                        .multiset_hashes
+                        .init_hashes
+                        .push(Fr::from(1u64));
+                },
+                |e| matches!(e, ProofVerifyError::RamError(_)),
+            ),
+            (
+                "read_write_memory.output_proof",
+                |proof, donor| {
+                    proof.read_write_memory.output_proof = donor.read_write_memory.output_proof
+                },
+                |e| matches!(e, ProofVerifyError::OutputCheckError(_)),
+            ),
+            (
+                "read_write_memory.timestamp_validity_proof",
+                |proof, donor| {
+                    proof.read_write_memory.timestamp_validity_proof =
+                        donor.read_write_memory.timestamp_validity_proof
+                },
+                |e| matches!(e, ProofVerifyError::RangeCheckError(_)),
+            ),
+            (
+                "r1cs",
+                |proof, donor| {
+                    // Keep the key, whose digest is part of the preamble.
+                    let mut r1cs = donor.r1cs;
+                    std::mem::swap(&mut r1cs.key, &mut proof.r1cs.key);
+                    proof.r1cs = r1cs;
+                },
+                |e| matches!(e, ProofVerifyError::R1CSError(_)),
+            ),
+            (
+                "opening_proof",
+                |proof, donor| proof.opening_proof = donor.opening_proof,
+                |e| matches!(e, ProofVerifyError::OpeningProofError(_)),
+            ),
+        ];
+        for (field, mutate, expected_error) in mutations {
+            let (mut proof, commitments) = load(&proof_bytes);
+            let (donor, _) = load(&donor_bytes);
+            mutate(&mut proof, donor);
+            match RV32IJoltVM::verify(preprocessing.clone(), proof, commitments) {
+                Ok(()) => panic!("Proof with mutated {field} verified"),
+                Err(e) => assert!(expected_error(&e), "Unexpected error for {field}: {e}"),
+            }
+        }
+    }
+
+    #[test]
+    fn bare_metal_trace_matches_full_emulation() {
+        use common::constants::{DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE};
+
+        let guests: [(&str, &Mutex<()>, Vec<u8>); 3] = [
+            (
+                "fibonacci-guest",
+                &FIB_FILE_LOCK,
+                postcard::to_stdvec(&9u32).unwrap(),
+            ),
+            (
+                "sha2-guest",
+                &SHA2_FILE_LOCK,
+                postcard::to_stdvec(&[5u8; 100][..]).unwrap(),
+            ),
+            (
+                "sha3-guest",
+                &SHA3_FILE_LOCK,
+                postcard::to_stdvec(&[5u8; 200][..]).unwrap(),
+            ),
+        ];
+        for (guest, lock, inputs) in guests {
+            let _guard = lock.lock().unwrap();
+
+            let mut program = host::Program::new(guest);
+            program.decode();
+            let elf = program.elf.as_ref().unwrap();
+            let (rows, device) = tracer::trace(
+                elf,
+                &inputs,
+                &[],
+                DEFAULT_MAX_INPUT_SIZE,
+                DEFAULT_MAX_OUTPUT_SIZE,
+            );
+            let (expected_rows, expected_device) = tracer::trace_full_emulation(
+                elf,
+                &inputs,
+                &[],
+                DEFAULT_MAX_INPUT_SIZE,
+                DEFAULT_MAX_OUTPUT_SIZE,
+            );
+
+            assert_eq!(
+                rows.len(),
+                expected_rows.len(),
+                "{guest}: trace lengths differ"
+            );
+            for (i, (row, expected)) in rows.iter().zip(&expected_rows).enumerate() {
+                assert_eq!(row, expected, "{guest}: rows differ at step {i}");
+            }
+            assert_eq!(device, expected_device, "{guest}: program I/O differs");
+        }
+    }
+
+    /// Proves a hand-assembled program that calls `bigint_mulmod` once, computing 3 * 2 mod 5,
+    /// with the given quotient and remainder as the routine's advice.
+    fn prove_mulmod<const C: usize>(advice: [u32; 2]) -> Result<(), ProofVerifyError> {
+        use crate::host::{circuit_flag_trace, trace_step};
+        use crate::jolt::instruction::bigint::BIGINTMULMODInstruction;
+        use crate::jolt::instruction::precompile::execute_routine_unchecked;
+        use crate::jolt::vm::bytecode::opcode_bits;
+        use common::constants::{DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE};
+        use common::rv_trace::{JoltDevice, RegisterState};
+        use tracer::{ELFInstruction, RVTraceRow, RV32IM};
+
+        const TEXT: u64 = RAM_START_ADDRESS;
+        const A_ADDRESS: u64 = RAM_START_ADDRESS + 0x1000;
+        const B_ADDRESS: u64 = RAM_START_ADDRESS + 0x2000;
+        let instruction = |index: u64, opcode, rs1, rs2, rd, imm| ELFInstruction {
+            address: TEXT + 4 * index,
+            opcode,
+            rs1,
+            rs2,
+            rd,
+            imm,
+            virtual_sequence_index: None,
+        };
+        let row = |instruction: ELFInstruction, rs1_val, rd_post_val| RVTraceRow {
+            instruction,
+            register_state: RegisterState {
+                rs1_val,
+                rs2_val: None,
+                rd_post_val,
+            },
+            memory_state: None,
+            precompile_input: None,
+        };
+
+        // a and p are consecutive, followed by b; each is eight little-endian limbs
+        let mut words = HashMap::new();
+        for (address, value) in [(A_ADDRESS, 3), (A_ADDRESS + 32, 5), (B_ADDRESS, 2)] {
+            for limb in 0..8 {
+                words.insert(address + 4 * limb, if limb == 0 { value } else { 0 });
+            }
+        }
+        let memory_init: Vec<(u64, u8)> = words
+            .iter()
+            .flat_map(|(address, word)| {
+                (0..4).map(move |byte| (address + byte, (*word >> (8 * byte)) as u8))
+            })
+            .collect();
+
+        //     lui a0, A_ADDRESS
+        //     lui a1, B_ADDRESS
+        //     bigint_mulmod a0, a1
+        //     addi a2, zero, 0
+        let lui_a = instruction(0, RV32IM::LUI, None, None, Some(10), Some(A_ADDRESS as u32));
+        let lui_b = instruction(1, RV32IM::LUI, None, None, Some(11), Some(B_ADDRESS as u32));
+        let call = instruction(2, RV32IM::BIGINT_MULMOD, Some(10), Some(11), None, None);
+        let addi = instruction(3, RV32IM::ADDI, Some(0), None, Some(12), Some(0));
+        let routine_address = TEXT + 16;
+        let routine = BIGINTMULMODInstruction::<32>::routine(&call, routine_address);
+
+        let mut bytecode = vec![
+            lui_a.clone(),
+            lui_b.clone(),
+            routine.instructions()[0].clone(),
+            addi.clone(),
+        ];
+        bytecode.extend(routine.instructions()[1..].iter().cloned());
+
+        let mut advice_words = vec![0; 16];
+        advice_words[0] = advice[0];
+        advice_words[8] = advice[1];
+        let registers = HashMap::from([(10, A_ADDRESS), (11, B_ADDRESS)]);
+        let mut rows = vec![
+            row(lui_a, None, Some(A_ADDRESS)),
+            row(lui_b, None, Some(B_ADDRESS)),
+        ];
+        rows.extend(execute_routine_unchecked(
+            routine,
+            registers,
+            words,
+            &advice_words,
+        ));
+        rows.push(row(addi, Some(0), Some(0)));
+
+        let trace: Vec<_> = rows.into_iter().map(trace_step).collect();
+        let packed_circuit_flags: Vec<u64> = trace
+            .iter()
+            .map(|step| step.bytecode_row.bitflags >> opcode_bits(RV32I::COUNT))
+            .collect();
+        let circuit_flags = circuit_flag_trace(&packed_circuit_flags);
+        let io_device = JoltDevice::new(DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE);
+
+        type Pcs = HyraxScheme<G1Projective>;
+        let preprocessing = <GenericRV32IJoltVM<C, M> as Jolt<Fr, Pcs, C, M>>::preprocess(
+            bytecode,
+            memory_init,
+            1 << 12,
+            1 << 20,
+            1 << 12,
+            None,
+        );
+        let (proof, commitments) = <GenericRV32IJoltVM<C, M> as Jolt<Fr, Pcs, C, M>>::prove(
+            io_device,
+            trace,
+            circuit_flags,
+            preprocessing.clone(),
+        );
+        <GenericRV32IJoltVM<C, M> as Jolt<Fr, Pcs, C, M>>::verify(preprocessing, proof, commitments)
+    }
+
+    #[test]
+    fn bigint_mulmod_e2e() {
+        // 3 * 2 = 1 * 5 + 1
+        let result = prove_mulmod::<C>([1, 1]);
+        assert!(result.is_ok(), "Verification failed with error: {result:?}");
+    }
+
+    #[test]
+    fn bigint_mulmod_with_8_chunks_e2e() {
+        let result = prove_mulmod::<8>([1, 1]);
+        assert!(result.is_ok(), "Verification failed with error: {result:?}");
+    }
+
+    #[test]
+    fn bigint_mulmod_forged_advice_is_rejected() {
+        // 3 * 2 = 0 * 5 + 6 passes the product check, but the remainder is not reduced. The
+        // assertion that catches this must be enforced by the proof, not just by the tracer.
+        let result = std::panic::catch_unwind(|| prove_mulmod::<C>([0, 6]));
+        assert!(
+            !matches!(result, Ok(Ok(()))),
+            "proof of a forged remainder verified"
+        );
+    }
+
     #[test]
     fn sha3_e2e() {
         let _guard = SHA3_FILE_LOCK.lock().unwrap();
@@ -229,8 +697,14 @@ mod tests {
         let (bytecode, memory_init) = program.decode();
         let (io_device, trace, circuit_flags) = program.trace();
 
-        let preprocessing =
-            RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
+        let preprocessing = RV32IJoltVM::preprocess(
+            bytecode.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+            None,
+        );
         let (jolt_proof, jolt_commitments) =
             <RV32IJoltVM as Jolt<_, HyraxScheme<G1Projective>, C, M>>::prove(
                 io_device,
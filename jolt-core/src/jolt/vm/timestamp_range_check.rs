@@ -12,7 +12,7 @@ use rayon::iter::{
 };
 #[cfg(test)]
 use std::collections::HashSet;
-use std::{iter::zip, marker::PhantomData};
+use std::marker::PhantomData;
 
 use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
 use crate::utils::transcript::AppendToTranscript;
@@ -22,9 +22,12 @@ use crate::{
         NoPreprocessing,
     },
     poly::{
+        compact_polynomial::CompactPolynomial,
         dense_mlpoly::DensePolynomial,
         eq_poly::EqPolynomial,
         identity_poly::IdentityPolynomial,
+        multilinear_polynomial::MultilinearPolynomial,
+        opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator},
         structured_poly::{StructuredCommitment, StructuredOpeningProof},
     },
     utils::{errors::ProofVerifyError, mul_0_1_optimized, transcript::ProofTranscript},
@@ -32,13 +35,14 @@ use crate::{
 
 use super::read_write_memory::MemoryCommitment;
 
+#[derive(CanonicalSerialize, CanonicalDeserialize)]
 pub struct RangeCheckPolynomials<F, C>
 where
     F: JoltField,
     C: CommitmentScheme<Field = F>,
 {
     _group: PhantomData<C>,
-    pub read_timestamps: [Vec<u64>; MEMORY_OPS_PER_INSTRUCTION],
+    pub read_timestamps: [CompactPolynomial<u32, F>; MEMORY_OPS_PER_INSTRUCTION],
     pub read_cts_read_timestamp: [DensePolynomial<F>; MEMORY_OPS_PER_INSTRUCTION],
     pub read_cts_global_minus_read: [DensePolynomial<F>; MEMORY_OPS_PER_INSTRUCTION],
     pub final_cts_read_timestamp: [DensePolynomial<F>; MEMORY_OPS_PER_INSTRUCTION],
@@ -51,7 +55,7 @@ where
     C: CommitmentScheme<Field = F>,
 {
     #[tracing::instrument(skip_all, name = "RangeCheckPolynomials::new")]
-    pub fn new(read_timestamps: [Vec<u64>; MEMORY_OPS_PER_INSTRUCTION]) -> Self {
+    pub fn new(read_timestamps: [CompactPolynomial<u32, F>; MEMORY_OPS_PER_INSTRUCTION]) -> Self {
         let M = read_timestamps[0].len();
 
         #[cfg(test)]
@@ -71,17 +75,20 @@ where
                 let mut final_cts_read_timestamp: Vec<u64> = vec![0; M];
                 let mut final_cts_global_minus_read: Vec<u64> = vec![0; M];
 
-                for (j, read_timestamp) in read_timestamps[i].iter().enumerate() {
-                    read_cts_read_timestamp[j] = final_cts_read_timestamp[*read_timestamp as usize];
-                    final_cts_read_timestamp[*read_timestamp as usize] += 1;
-                    let lookup_index = j - *read_timestamp as usize;
+                for j in 0..M {
+                    let read_timestamp = read_timestamps[i].get_u64(j) as usize;
+                    read_cts_read_timestamp[j] = final_cts_read_timestamp[read_timestamp];
+                    final_cts_read_timestamp[read_timestamp] += 1;
+                    let lookup_index = j - read_timestamp;
                     read_cts_global_minus_read[j] = final_cts_global_minus_read[lookup_index];
                     final_cts_global_minus_read[lookup_index] += 1;
                 }
 
                 #[cfg(test)]
                 {
-                    let global_minus_read_timestamps = &read_timestamps[i]
+                    let read_timestamps: Vec<u64> =
+                        (0..M).map(|j| read_timestamps[i].get_u64(j)).collect();
+                    let global_minus_read_timestamps = &read_timestamps
                         .iter()
                         .enumerate()
                         .map(|(j, timestamp)| j as u64 - *timestamp)
@@ -89,7 +96,7 @@ where
 
                     for (lookup_indices, read_cts, final_cts) in [
                         (
-                            &read_timestamps[i],
+                            &read_timestamps,
                             &read_cts_read_timestamp,
                             &final_cts_read_timestamp,
                         ),
@@ -222,8 +229,6 @@ where
     F: JoltField,
     C: CommitmentScheme<Field = F>,
 {
-    type Proof = C::BatchedProof;
-
     fn open(_polynomials: &RangeCheckPolynomials<F, C>, _opening_point: &[F]) -> Self {
         unimplemented!("Openings are computed in TimestampValidityProof::prove");
     }
@@ -232,22 +237,27 @@ where
         _polynomials: &RangeCheckPolynomials<F, C>,
         _opening_point: &[F],
         _openings: &RangeCheckOpenings<F, C>,
+        _opening_accumulator: &mut ProverOpeningAccumulator<F>,
         _transcript: &mut ProofTranscript,
-    ) -> Self::Proof {
+    ) {
         unimplemented!("Openings are proved in TimestampValidityProof::prove")
     }
 
-    fn compute_verifier_openings(&mut self, _: &NoPreprocessing, opening_point: &[F]) {
+    fn compute_verifier_openings(
+        &mut self,
+        _: &NoPreprocessing,
+        opening_point: &[F],
+    ) -> Result<(), ProofVerifyError> {
         self.identity_poly_opening =
             Some(IdentityPolynomial::new(opening_point.len()).evaluate(opening_point));
+        Ok(())
     }
 
     fn verify_openings(
         &self,
-        _generators: &C::Setup,
-        _opening_proof: &Self::Proof,
         _commitment: &RangeCheckCommitment<C>,
         _opening_point: &[F],
+        _opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         _transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         unimplemented!("Openings are verified in TimestampValidityProof::verify");
@@ -267,7 +277,9 @@ where
 
     fn prove_memory_checking(
         _: &NoPreprocessing,
+        _generators: &C::Setup,
         _polynomials: &RangeCheckPolynomials<F, C>,
+        _opening_accumulator: &mut ProverOpeningAccumulator<F>,
         _transcript: &mut ProofTranscript,
     ) -> MemoryCheckingProof<
         F,
@@ -307,8 +319,7 @@ where
                 let read_fingerprints_0: Vec<F> = (0..M)
                     .into_par_iter()
                     .map(|j| {
-                        let read_timestamp =
-                            F::from_u64(polynomials.read_timestamps[i][j]).unwrap();
+                        let read_timestamp = polynomials.read_timestamps[i].get_coeff(j);
                         polynomials.read_cts_read_timestamp[i][j] * gamma_squared
                             + read_timestamp * gamma
                             + read_timestamp
@@ -324,7 +335,8 @@ where
                     .into_par_iter()
                     .map(|j| {
                         let global_minus_read =
-                            F::from_u64(j as u64 - polynomials.read_timestamps[i][j]).unwrap();
+                            F::from_u64(j as u64 - polynomials.read_timestamps[i].get_u64(j))
+                                .unwrap();
                         polynomials.read_cts_global_minus_read[i][j] * gamma_squared
                             + global_minus_read * gamma
                             + global_minus_read
@@ -430,24 +442,23 @@ where
         }
     }
 
-    fn check_multiset_equality(_: &NoPreprocessing, multiset_hashes: &MultisetHashes<F>) {
+    fn check_multiset_equality(
+        _: &NoPreprocessing,
+        multiset_hashes: &MultisetHashes<F>,
+    ) -> Result<(), ProofVerifyError> {
         let num_memories = 2 * MEMORY_OPS_PER_INSTRUCTION;
-        assert_eq!(multiset_hashes.read_hashes.len(), num_memories);
-        assert_eq!(multiset_hashes.write_hashes.len(), num_memories);
-        assert_eq!(multiset_hashes.final_hashes.len(), num_memories);
-        assert_eq!(multiset_hashes.init_hashes.len(), 1);
+        multiset_hashes.check_lengths(num_memories, 1)?;
 
-        (0..num_memories).into_par_iter().for_each(|i| {
+        (0..num_memories).into_par_iter().try_for_each(|i| {
             let read_hash = multiset_hashes.read_hashes[i];
             let write_hash = multiset_hashes.write_hashes[i];
             let init_hash = multiset_hashes.init_hashes[0];
             let final_hash = multiset_hashes.final_hashes[i];
-            assert_eq!(
-                init_hash * write_hash,
-                final_hash * read_hash,
-                "Multiset hashes don't match"
-            );
-        });
+            if init_hash * write_hash != final_hash * read_hash {
+                return Err(ProofVerifyError::MultisetHashMismatch(i));
+            }
+            Ok(())
+        })
     }
 
     fn protocol_name() -> &'static [u8] {
@@ -472,6 +483,7 @@ where
             Self::InitFinalOpenings,
         >,
         _commitments: &RangeCheckCommitment<C>,
+        _opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         _transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         unimplemented!("Use TimestampValidityProof::verify instead");
@@ -560,7 +572,11 @@ where
 }
 
 pub struct NoopGrandProduct;
-impl<F: JoltField> BatchedGrandProduct<F> for NoopGrandProduct {
+impl<F, C> BatchedGrandProduct<F, C> for NoopGrandProduct
+where
+    F: JoltField,
+    C: CommitmentScheme<Field = F>,
+{
     type Leaves = ();
 
     fn construct(_leaves: Self::Leaves) -> Self {
@@ -579,15 +595,19 @@ impl<F: JoltField> BatchedGrandProduct<F> for NoopGrandProduct {
 
     fn prove_grand_product(
         &mut self,
+        _opening_accumulator: Option<&mut ProverOpeningAccumulator<F>>,
         _transcript: &mut ProofTranscript,
-    ) -> (BatchedGrandProductProof<F>, Vec<F>) {
+        _setup: Option<&C::Setup>,
+    ) -> (BatchedGrandProductProof<C>, Vec<F>) {
         unimplemented!("init/final grand products are batched with read/write grand products")
     }
     fn verify_grand_product(
-        _proof: &BatchedGrandProductProof<F>,
+        _proof: &BatchedGrandProductProof<C>,
         _claims: &Vec<F>,
+        _opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, C>>,
         _transcript: &mut ProofTranscript,
-    ) -> (Vec<F>, Vec<F>) {
+        _setup: Option<&C::Setup>,
+    ) -> Result<(Vec<F>, Vec<F>), ProofVerifyError> {
         unimplemented!("init/final grand products are batched with read/write grand products")
     }
 }
@@ -600,8 +620,7 @@ where
 {
     multiset_hashes: MultisetHashes<F>,
     openings: RangeCheckOpenings<F, C>,
-    opening_proof: C::BatchedProof,
-    batched_grand_product: BatchedGrandProductProof<F>,
+    batched_grand_product: BatchedGrandProductProof<C>,
 }
 
 impl<F, C> TimestampValidityProof<F, C>
@@ -612,35 +631,38 @@ where
     #[tracing::instrument(skip_all, name = "TimestampValidityProof::prove")]
     pub fn prove(
         range_check_polys: &RangeCheckPolynomials<F, C>,
-        t_read_polynomials: &[DensePolynomial<F>; MEMORY_OPS_PER_INSTRUCTION],
+        t_read_polynomials: &[CompactPolynomial<u32, F>; MEMORY_OPS_PER_INSTRUCTION],
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
     ) -> Self {
         let (batched_grand_product, multiset_hashes, r_grand_product) =
-            TimestampValidityProof::prove_grand_products(range_check_polys, transcript);
+            TimestampValidityProof::prove_grand_products(
+                range_check_polys,
+                opening_accumulator,
+                transcript,
+            );
 
-        let polys_iter = range_check_polys
+        let polys: Vec<&dyn MultilinearPolynomial<F>> = range_check_polys
             .read_cts_read_timestamp
-            .par_iter()
-            .chain(range_check_polys.read_cts_global_minus_read.par_iter())
-            .chain(range_check_polys.final_cts_read_timestamp.par_iter())
-            .chain(range_check_polys.final_cts_global_minus_read.par_iter())
-            .chain(t_read_polynomials.par_iter());
-
-        let polys: Vec<_> = polys_iter.clone().collect();
+            .iter()
+            .chain(range_check_polys.read_cts_global_minus_read.iter())
+            .chain(range_check_polys.final_cts_read_timestamp.iter())
+            .chain(range_check_polys.final_cts_global_minus_read.iter())
+            .map(|poly| poly as &dyn MultilinearPolynomial<F>)
+            .chain(
+                t_read_polynomials
+                    .iter()
+                    .map(|poly| poly as &dyn MultilinearPolynomial<F>),
+            )
+            .collect();
 
         let chis = EqPolynomial::evals(&r_grand_product);
-        let openings = polys_iter
-            .clone()
+        let openings = polys
+            .par_iter()
             .map(|poly| poly.evaluate_at_chi(&chis))
             .collect::<Vec<F>>();
 
-        let opening_proof = C::batch_prove(
-            &polys,
-            &r_grand_product,
-            &openings,
-            BatchType::Big,
-            transcript,
-        );
+        opening_accumulator.append(&polys, &r_grand_product, &openings, transcript);
 
         let mut openings = openings.into_iter();
         let read_cts_read_timestamp: [F; MEMORY_OPS_PER_INSTRUCTION] =
@@ -662,7 +684,6 @@ where
         Self {
             multiset_hashes,
             openings,
-            opening_proof,
             batched_grand_product,
         }
     }
@@ -670,8 +691,9 @@ where
     #[tracing::instrument(skip_all, name = "TimestampValidityProof::prove_grand_products")]
     fn prove_grand_products(
         polynomials: &RangeCheckPolynomials<F, C>,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
-    ) -> (BatchedGrandProductProof<F>, MultisetHashes<F>, Vec<F>) {
+    ) -> (BatchedGrandProductProof<C>, MultisetHashes<F>, Vec<F>) {
         // Fiat-Shamir randomness for multiset hashes
         let gamma: F = transcript.challenge_scalar(b"Memory checking gamma");
         let tau: F = transcript.challenge_scalar(b"Memory checking tau");
@@ -681,9 +703,11 @@ where
         let (leaves, _) =
             TimestampValidityProof::compute_leaves(&NoPreprocessing, polynomials, &gamma, &tau);
 
-        let mut batched_circuit = BatchedDenseGrandProduct::construct(leaves);
+        let mut batched_circuit =
+            <BatchedDenseGrandProduct<F> as BatchedGrandProduct<F, C>>::construct(leaves);
 
-        let hashes: Vec<F> = batched_circuit.claims();
+        let hashes: Vec<F> =
+            <BatchedDenseGrandProduct<F> as BatchedGrandProduct<F, C>>::claims(&batched_circuit);
         let (read_write_hashes, init_final_hashes) =
             hashes.split_at(4 * MEMORY_OPS_PER_INSTRUCTION);
         let multiset_hashes = TimestampValidityProof::<F, C>::uninterleave_hashes(
@@ -691,11 +715,12 @@ where
             read_write_hashes.to_vec(),
             init_final_hashes.to_vec(),
         );
-        TimestampValidityProof::<F, C>::check_multiset_equality(&NoPreprocessing, &multiset_hashes);
+        TimestampValidityProof::<F, C>::check_multiset_equality(&NoPreprocessing, &multiset_hashes)
+            .expect("Multiset hashes don't match");
         multiset_hashes.append_to_transcript(transcript);
 
         let (batched_grand_product, r_grand_product) =
-            batched_circuit.prove_grand_product(transcript);
+            batched_circuit.prove_grand_product(Some(opening_accumulator), transcript, None);
 
         drop_in_background_thread(batched_circuit);
 
@@ -704,9 +729,9 @@ where
 
     pub fn verify(
         &mut self,
-        generators: &C::Setup,
         range_check_commitment: &RangeCheckCommitment<C>,
         memory_commitment: &MemoryCommitment<C>,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         // Fiat-Shamir randomness for multiset hashes
@@ -719,7 +744,7 @@ where
         TimestampValidityProof::<F, C>::check_multiset_equality(
             &NoPreprocessing,
             &self.multiset_hashes,
-        );
+        )?;
         self.multiset_hashes.append_to_transcript(transcript);
 
         let (read_write_hashes, init_final_hashes) =
@@ -732,8 +757,10 @@ where
             BatchedDenseGrandProduct::verify_grand_product(
                 &self.batched_grand_product,
                 &concatenated_hashes,
+                Some(opening_accumulator),
                 transcript,
-            );
+                None,
+            )?;
 
         let openings: Vec<_> = self
             .openings
@@ -746,25 +773,23 @@ where
             .collect();
 
         // TODO(moodlezoup): Make indexing less disgusting
-        let t_read_commitments = &memory_commitment.trace_commitments
-            [1 + MEMORY_OPS_PER_INSTRUCTION + 5..4 + 2 * MEMORY_OPS_PER_INSTRUCTION + 5];
+        let t_read_commitments = memory_commitment
+            .trace_commitments
+            .get(1 + MEMORY_OPS_PER_INSTRUCTION + 5..1 + 2 * MEMORY_OPS_PER_INSTRUCTION + 5)
+            .ok_or(ProofVerifyError::InvalidInputLength(
+                1 + 2 * MEMORY_OPS_PER_INSTRUCTION + 5,
+                memory_commitment.trace_commitments.len(),
+            ))?;
         let commitments: Vec<_> = range_check_commitment
             .commitments
             .iter()
             .chain(t_read_commitments.iter())
             .collect();
 
-        C::batch_verify(
-            &self.opening_proof,
-            generators,
-            &r_grand_product,
-            &openings,
-            &commitments,
-            transcript,
-        )?;
+        opening_accumulator.append(&commitments, &r_grand_product, &openings, transcript)?;
 
         self.openings
-            .compute_verifier_openings(&NoPreprocessing, &r_grand_product);
+            .compute_verifier_openings(&NoPreprocessing, &r_grand_product)?;
 
         let read_hashes: Vec<_> =
             TimestampValidityProof::read_tuples(&NoPreprocessing, &self.openings)
@@ -787,10 +812,12 @@ where
                 .map(|tuple| TimestampValidityProof::<F, C>::fingerprint(tuple, &gamma, &tau))
                 .collect();
 
-        assert_eq!(
-            grand_product_claims.len(),
-            6 * MEMORY_OPS_PER_INSTRUCTION + 1
-        );
+        if grand_product_claims.len() != 6 * MEMORY_OPS_PER_INSTRUCTION + 1 {
+            return Err(ProofVerifyError::InvalidInputLength(
+                6 * MEMORY_OPS_PER_INSTRUCTION + 1,
+                grand_product_claims.len(),
+            ));
+        }
         let (read_write_claims, init_final_claims) =
             grand_product_claims.split_at(4 * MEMORY_OPS_PER_INSTRUCTION);
 
@@ -803,11 +830,8 @@ where
         let (read_write_hashes, init_final_hashes) =
             TimestampValidityProof::<F, C>::interleave_hashes(&NoPreprocessing, &multiset_hashes);
 
-        for (claim, fingerprint) in zip(read_write_claims, read_write_hashes) {
-            assert_eq!(*claim, fingerprint);
-        }
-        for (claim, fingerprint) in zip(init_final_claims, init_final_hashes) {
-            assert_eq!(*claim, fingerprint);
+        if read_write_claims != read_write_hashes || init_final_claims != init_final_hashes {
+            return Err(ProofVerifyError::FingerprintMismatch);
         }
 
         Ok(())
@@ -8,6 +8,7 @@ use crate::utils::transcript::ProofTranscript;
 use crate::{
     poly::{
         commitment::commitment_scheme::CommitmentScheme,
+        opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator},
         structured_poly::{StructuredCommitment, StructuredOpeningProof},
     },
     subprotocols::grand_product::{BatchedGrandProduct, BatchedGrandProductProof},
@@ -17,7 +18,6 @@ use crate::poly::field::JoltField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use itertools::interleave;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use std::iter::zip;
 use std::marker::PhantomData;
 
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
@@ -39,6 +39,31 @@ impl<F: JoltField> MultisetHashes<F> {
         transcript.append_scalars(b"Init multiset hashes", &self.init_hashes);
         transcript.append_scalars(b"Final multiset hashes", &self.final_hashes);
     }
+
+    /// Checks that there is one read, write, and final hash per memory, and `num_init_hashes`
+    /// init hashes.
+    pub fn check_lengths(
+        &self,
+        num_memories: usize,
+        num_init_hashes: usize,
+    ) -> Result<(), ProofVerifyError> {
+        for len in [
+            self.read_hashes.len(),
+            self.write_hashes.len(),
+            self.final_hashes.len(),
+        ] {
+            if len != num_memories {
+                return Err(ProofVerifyError::InvalidInputLength(num_memories, len));
+            }
+        }
+        if self.init_hashes.len() != num_init_hashes {
+            return Err(ProofVerifyError::InvalidInputLength(
+                num_init_hashes,
+                self.init_hashes.len(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
@@ -55,16 +80,14 @@ where
     pub multiset_hashes: MultisetHashes<F>,
     /// The read and write grand products for every memory has the same size,
     /// so they can be batched.
-    pub read_write_grand_product: BatchedGrandProductProof<F>,
+    pub read_write_grand_product: BatchedGrandProductProof<C>,
     /// The init and final grand products for every memory has the same size,
     /// so they can be batched.
-    pub init_final_grand_product: BatchedGrandProductProof<F>,
-    /// The opening proofs associated with the read/write grand product.
+    pub init_final_grand_product: BatchedGrandProductProof<C>,
+    /// The openings associated with the read/write grand product.
     pub read_write_openings: ReadWriteOpenings,
-    pub read_write_opening_proof: ReadWriteOpenings::Proof,
-    /// The opening proofs associated with the init/final grand product.
+    /// The openings associated with the init/final grand product.
     pub init_final_openings: InitFinalOpenings,
-    pub init_final_opening_proof: InitFinalOpenings::Proof,
 }
 
 // Empty struct to represent that no preprocessing data is used.
@@ -77,9 +100,12 @@ where
     Polynomials: StructuredCommitment<C>,
     Self: std::marker::Sync,
 {
-    type ReadWriteGrandProduct: BatchedGrandProduct<F> + Send + 'static =
+    /// The grand product arguments used for the read/write and init/final multisets.
+    /// Implementors may pick e.g. `QuarkGrandProduct` to trade prover time for a smaller
+    /// proof and a faster verifier.
+    type ReadWriteGrandProduct: BatchedGrandProduct<F, C> + Send + 'static =
         BatchedDenseGrandProduct<F>;
-    type InitFinalGrandProduct: BatchedGrandProduct<F> + Send + 'static =
+    type InitFinalGrandProduct: BatchedGrandProduct<F, C> + Send + 'static =
         BatchedDenseGrandProduct<F>;
 
     type Preprocessing = NoPreprocessing;
@@ -102,7 +128,9 @@ where
     /// Generates a memory checking proof for the given committed polynomials.
     fn prove_memory_checking(
         preprocessing: &Self::Preprocessing,
+        generators: &C::Setup,
         polynomials: &Polynomials,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
     ) -> MemoryCheckingProof<F, C, Polynomials, Self::ReadWriteOpenings, Self::InitFinalOpenings>
     {
@@ -112,20 +140,28 @@ where
             multiset_hashes,
             r_read_write,
             r_init_final,
-        ) = Self::prove_grand_products(preprocessing, polynomials, transcript);
+        ) = Self::prove_grand_products(
+            preprocessing,
+            generators,
+            polynomials,
+            opening_accumulator,
+            transcript,
+        );
 
         let read_write_openings = Self::ReadWriteOpenings::open(polynomials, &r_read_write);
-        let read_write_opening_proof = Self::ReadWriteOpenings::prove_openings(
+        Self::ReadWriteOpenings::prove_openings(
             polynomials,
             &r_read_write,
             &read_write_openings,
+            opening_accumulator,
             transcript,
         );
         let init_final_openings = Self::InitFinalOpenings::open(polynomials, &r_init_final);
-        let init_final_opening_proof = Self::InitFinalOpenings::prove_openings(
+        Self::InitFinalOpenings::prove_openings(
             polynomials,
             &r_init_final,
             &init_final_openings,
+            opening_accumulator,
             transcript,
         );
 
@@ -135,9 +171,7 @@ where
             read_write_grand_product,
             init_final_grand_product,
             read_write_openings,
-            read_write_opening_proof,
             init_final_openings,
-            init_final_opening_proof,
         }
     }
 
@@ -145,11 +179,13 @@ where
     /// Proves the grand products for the memory checking multisets (init, read, write, final).
     fn prove_grand_products(
         preprocessing: &Self::Preprocessing,
+        generators: &C::Setup,
         polynomials: &Polynomials,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
     ) -> (
-        BatchedGrandProductProof<F>,
-        BatchedGrandProductProof<F>,
+        BatchedGrandProductProof<C>,
+        BatchedGrandProductProof<C>,
         MultisetHashes<F>,
         Vec<F>,
         Vec<F>,
@@ -169,13 +205,20 @@ where
 
         let multiset_hashes =
             Self::uninterleave_hashes(preprocessing, read_write_hashes, init_final_hashes);
-        Self::check_multiset_equality(preprocessing, &multiset_hashes);
+        Self::check_multiset_equality(preprocessing, &multiset_hashes)
+            .expect("Multiset hashes don't match");
         multiset_hashes.append_to_transcript(transcript);
 
-        let (read_write_grand_product, r_read_write) =
-            read_write_circuit.prove_grand_product(transcript);
-        let (init_final_grand_product, r_init_final) =
-            init_final_circuit.prove_grand_product(transcript);
+        let (read_write_grand_product, r_read_write) = read_write_circuit.prove_grand_product(
+            Some(opening_accumulator),
+            transcript,
+            Some(generators),
+        );
+        let (init_final_grand_product, r_init_final) = init_final_circuit.prove_grand_product(
+            Some(opening_accumulator),
+            transcript,
+            Some(generators),
+        );
 
         drop_in_background_thread(read_write_circuit);
         drop_in_background_thread(init_final_circuit);
@@ -195,7 +238,7 @@ where
     fn read_write_grand_product(
         _preprocessing: &Self::Preprocessing,
         _polynomials: &Polynomials,
-        read_write_leaves: <Self::ReadWriteGrandProduct as BatchedGrandProduct<F>>::Leaves,
+        read_write_leaves: <Self::ReadWriteGrandProduct as BatchedGrandProduct<F, C>>::Leaves,
     ) -> (Self::ReadWriteGrandProduct, Vec<F>) {
         let batched_circuit = Self::ReadWriteGrandProduct::construct(read_write_leaves);
         let claims = batched_circuit.claims();
@@ -208,7 +251,7 @@ where
     fn init_final_grand_product(
         _preprocessing: &Self::Preprocessing,
         _polynomials: &Polynomials,
-        init_final_leaves: <Self::InitFinalGrandProduct as BatchedGrandProduct<F>>::Leaves,
+        init_final_leaves: <Self::InitFinalGrandProduct as BatchedGrandProduct<F, C>>::Leaves,
     ) -> (Self::InitFinalGrandProduct, Vec<F>) {
         let batched_circuit = Self::InitFinalGrandProduct::construct(init_final_leaves);
         let claims = batched_circuit.claims();
@@ -266,23 +309,20 @@ where
     fn check_multiset_equality(
         _preprocessing: &Self::Preprocessing,
         multiset_hashes: &MultisetHashes<F>,
-    ) {
+    ) -> Result<(), ProofVerifyError> {
         let num_memories = multiset_hashes.read_hashes.len();
-        assert_eq!(multiset_hashes.final_hashes.len(), num_memories);
-        assert_eq!(multiset_hashes.write_hashes.len(), num_memories);
-        assert_eq!(multiset_hashes.init_hashes.len(), num_memories);
+        multiset_hashes.check_lengths(num_memories, num_memories)?;
 
-        (0..num_memories).into_par_iter().for_each(|i| {
+        (0..num_memories).into_par_iter().try_for_each(|i| {
             let read_hash = multiset_hashes.read_hashes[i];
             let write_hash = multiset_hashes.write_hashes[i];
             let init_hash = multiset_hashes.init_hashes[i];
             let final_hash = multiset_hashes.final_hashes[i];
-            assert_eq!(
-                init_hash * write_hash,
-                final_hash * read_hash,
-                "Multiset hashes don't match"
-            );
-        });
+            if init_hash * write_hash != final_hash * read_hash {
+                return Err(ProofVerifyError::MultisetHashMismatch(i));
+            }
+            Ok(())
+        })
     }
 
     /// Computes the MLE of the leaves of the read, write, init, and final grand product circuits,
@@ -294,8 +334,8 @@ where
         gamma: &F,
         tau: &F,
     ) -> (
-        <Self::ReadWriteGrandProduct as BatchedGrandProduct<F>>::Leaves,
-        <Self::InitFinalGrandProduct as BatchedGrandProduct<F>>::Leaves,
+        <Self::ReadWriteGrandProduct as BatchedGrandProduct<F, C>>::Leaves,
+        <Self::InitFinalGrandProduct as BatchedGrandProduct<F, C>>::Leaves,
     );
 
     /// Computes the Reed-Solomon fingerprint (parametrized by `gamma` and `tau`) of the given memory `tuple`.
@@ -325,6 +365,7 @@ where
             Self::InitFinalOpenings,
         >,
         commitments: &Polynomials::Commitment,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         // Fiat-Shamir randomness for multiset hashes
@@ -333,7 +374,7 @@ where
 
         transcript.append_protocol_name(Self::protocol_name());
 
-        Self::check_multiset_equality(preprocessing, &proof.multiset_hashes);
+        Self::check_multiset_equality(preprocessing, &proof.multiset_hashes)?;
         proof.multiset_hashes.append_to_transcript(transcript);
 
         let (read_write_hashes, init_final_hashes) =
@@ -342,35 +383,37 @@ where
         let (claims_read_write, r_read_write) = Self::ReadWriteGrandProduct::verify_grand_product(
             &proof.read_write_grand_product,
             &read_write_hashes,
+            Some(opening_accumulator),
             transcript,
-        );
+            Some(generators),
+        )?;
         let (claims_init_final, r_init_final) = Self::InitFinalGrandProduct::verify_grand_product(
             &proof.init_final_grand_product,
             &init_final_hashes,
+            Some(opening_accumulator),
             transcript,
-        );
+            Some(generators),
+        )?;
 
         proof.read_write_openings.verify_openings(
-            generators,
-            &proof.read_write_opening_proof,
             commitments,
             &r_read_write,
+            opening_accumulator,
             transcript,
         )?;
         proof.init_final_openings.verify_openings(
-            generators,
-            &proof.init_final_opening_proof,
             commitments,
             &r_init_final,
+            opening_accumulator,
             transcript,
         )?;
 
         proof
             .read_write_openings
-            .compute_verifier_openings(&NoPreprocessing, &r_read_write);
+            .compute_verifier_openings(&NoPreprocessing, &r_read_write)?;
         proof
             .init_final_openings
-            .compute_verifier_openings(preprocessing, &r_init_final);
+            .compute_verifier_openings(preprocessing, &r_init_final)?;
 
         Self::check_fingerprints(
             preprocessing,
@@ -380,9 +423,7 @@ where
             &proof.init_final_openings,
             &gamma,
             &tau,
-        );
-
-        Ok(())
+        )
     }
 
     /// Computes "read" memory tuples (one per memory) from the given `openings`.
@@ -416,7 +457,7 @@ where
         init_final_openings: &Self::InitFinalOpenings,
         gamma: &F,
         tau: &F,
-    ) {
+    ) -> Result<(), ProofVerifyError> {
         let read_hashes: Vec<_> = Self::read_tuples(preprocessing, read_write_openings)
             .iter()
             .map(|tuple| Self::fingerprint(tuple, gamma, tau))
@@ -433,14 +474,18 @@ where
             .iter()
             .map(|tuple| Self::fingerprint(tuple, gamma, tau))
             .collect();
-        assert_eq!(
-            read_hashes.len() + write_hashes.len(),
-            claims_read_write.len()
-        );
-        assert_eq!(
-            init_hashes.len() + final_hashes.len(),
-            claims_init_final.len()
-        );
+        if read_hashes.len() + write_hashes.len() != claims_read_write.len() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                read_hashes.len() + write_hashes.len(),
+                claims_read_write.len(),
+            ));
+        }
+        if init_hashes.len() + final_hashes.len() != claims_init_final.len() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                init_hashes.len() + final_hashes.len(),
+                claims_init_final.len(),
+            ));
+        }
 
         let multiset_hashes = MultisetHashes {
             read_hashes,
@@ -451,11 +496,9 @@ where
         let (read_write_hashes, init_final_hashes) =
             Self::interleave_hashes(preprocessing, &multiset_hashes);
 
-        for (claim, fingerprint) in zip(claims_read_write, read_write_hashes) {
-            assert_eq!(claim, fingerprint);
-        }
-        for (claim, fingerprint) in zip(claims_init_final, init_final_hashes) {
-            assert_eq!(claim, fingerprint);
+        if claims_read_write != read_write_hashes || claims_init_final != init_final_hashes {
+            return Err(ProofVerifyError::FingerprintMismatch);
         }
+        Ok(())
     }
 }
@@ -11,6 +11,9 @@ use crate::{
         dense_mlpoly::DensePolynomial,
         eq_poly::EqPolynomial,
         identity_poly::IdentityPolynomial,
+        opening_proof::{
+            ProverOpeningAccumulator, ReducedOpeningProof, VerifierOpeningAccumulator,
+        },
         structured_poly::{StructuredCommitment, StructuredOpeningProof},
     },
     subprotocols::sumcheck::SumcheckInstanceProof,
@@ -75,8 +78,6 @@ where
     F: JoltField,
     PCS: CommitmentScheme<Field = F>,
 {
-    type Proof = PCS::BatchedProof;
-
     #[tracing::instrument(skip_all, name = "PrimarySumcheckOpenings::open")]
     fn open(polynomials: &SurgePolys<F, PCS>, opening_point: &[F]) -> Self {
         let chis = EqPolynomial::evals(opening_point);
@@ -92,31 +93,28 @@ where
         polynomials: &SurgePolys<F, PCS>,
         opening_point: &[F],
         E_poly_openings: &Vec<F>,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
-    ) -> Self::Proof {
-        PCS::batch_prove(
+    ) {
+        opening_accumulator.append(
             &polynomials.E_polys.iter().collect::<Vec<_>>(),
             opening_point,
             E_poly_openings,
-            BatchType::SurgeReadWrite,
             transcript,
-        )
+        );
     }
 
     fn verify_openings(
         &self,
-        generators: &PCS::Setup,
-        opening_proof: &Self::Proof,
         commitment: &SurgeCommitment<PCS>,
         opening_point: &[F],
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        PCS::batch_verify(
-            opening_proof,
-            generators,
+        opening_accumulator.append(
+            &commitment.E_commitment.iter().collect::<Vec<_>>(),
             opening_point,
             self,
-            &commitment.E_commitment.iter().collect::<Vec<_>>(),
             transcript,
         )
     }
@@ -137,8 +135,6 @@ where
     F: JoltField,
     PCS: CommitmentScheme<Field = F>,
 {
-    type Proof = PCS::BatchedProof;
-
     #[tracing::instrument(skip_all, name = "SurgeReadWriteOpenings::open")]
     fn open(polynomials: &SurgePolys<F, PCS>, opening_point: &[F]) -> Self {
         let chis = EqPolynomial::evals(opening_point);
@@ -155,8 +151,9 @@ where
         polynomials: &SurgePolys<F, PCS>,
         opening_point: &[F],
         openings: &Self,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
-    ) -> Self::Proof {
+    ) {
         let read_write_polys = polynomials
             .dim
             .iter()
@@ -170,21 +167,19 @@ where
         ]
         .concat();
 
-        PCS::batch_prove(
+        opening_accumulator.append(
             &read_write_polys,
             opening_point,
             &read_write_openings,
-            BatchType::SurgeReadWrite,
             transcript,
-        )
+        );
     }
 
     fn verify_openings(
         &self,
-        generators: &PCS::Setup,
-        opening_proof: &Self::Proof,
         commitment: &SurgeCommitment<PCS>,
         opening_point: &[F],
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         let read_write_openings: Vec<F> = [
@@ -193,16 +188,14 @@ where
             self.E_poly_openings.as_slice(),
         ]
         .concat();
-        PCS::batch_verify(
-            opening_proof,
-            generators,
-            opening_point,
-            &read_write_openings,
+        opening_accumulator.append(
             &commitment
                 .dim_read_commitment
                 .iter()
                 .chain(commitment.E_commitment.iter())
                 .collect::<Vec<_>>(),
+            opening_point,
+            &read_write_openings,
             transcript,
         )
     }
@@ -227,7 +220,6 @@ where
     PCS: CommitmentScheme<Field = F>,
     Instruction: JoltInstruction + Default,
 {
-    type Proof = PCS::BatchedProof;
     type Preprocessing = SurgePreprocessing<F, Instruction, C, M>;
 
     #[tracing::instrument(skip_all, name = "SurgeFinalOpenings::open")]
@@ -251,18 +243,29 @@ where
         polynomials: &SurgePolys<F, PCS>,
         opening_point: &[F],
         openings: &Self,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
-    ) -> Self::Proof {
-        PCS::batch_prove(
+    ) {
+        opening_accumulator.append(
             &polynomials.final_cts.iter().collect::<Vec<_>>(),
             opening_point,
             &openings.final_openings,
-            BatchType::SurgeInitFinal,
             transcript,
-        )
+        );
     }
 
-    fn compute_verifier_openings(&mut self, _: &Self::Preprocessing, opening_point: &[F]) {
+    fn compute_verifier_openings(
+        &mut self,
+        _: &Self::Preprocessing,
+        opening_point: &[F],
+    ) -> Result<(), ProofVerifyError> {
+        if opening_point.len() != M.log_2() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                M.log_2(),
+                opening_point.len(),
+            ));
+        }
+
         self.a_init_final =
             Some(IdentityPolynomial::new(opening_point.len()).evaluate(opening_point));
         self.v_init_final = Some(
@@ -272,22 +275,20 @@ where
                 .map(|(subtable, _)| subtable.evaluate_mle(opening_point))
                 .collect(),
         );
+        Ok(())
     }
 
     fn verify_openings(
         &self,
-        generators: &PCS::Setup,
-        opening_proof: &Self::Proof,
         commitment: &SurgeCommitment<PCS>,
         opening_point: &[F],
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        PCS::batch_verify(
-            opening_proof,
-            generators,
+        opening_accumulator.append(
+            &commitment.final_commitment.iter().collect::<Vec<_>>(),
             opening_point,
             &self.final_openings,
-            &commitment.final_commitment.iter().collect::<Vec<_>>(),
             transcript,
         )
     }
@@ -454,16 +455,14 @@ where
     }
 }
 
-pub struct SurgePrimarySumcheck<F, PCS>
+pub struct SurgePrimarySumcheck<F>
 where
     F: JoltField,
-    PCS: CommitmentScheme<Field = F>,
 {
     sumcheck_proof: SumcheckInstanceProof<F>,
     num_rounds: usize,
     claimed_evaluation: F,
     openings: PrimarySumcheckOpenings<F>,
-    opening_proof: PCS::BatchedProof,
 }
 
 pub struct SurgePreprocessing<F, Instruction, const C: usize, const M: usize>
@@ -486,7 +485,7 @@ where
     commitment: SurgeCommitment<PCS>,
 
     /// Primary collation sumcheck proof
-    primary_sumcheck: SurgePrimarySumcheck<F, PCS>,
+    primary_sumcheck: SurgePrimarySumcheck<F>,
 
     memory_checking: MemoryCheckingProof<
         F,
@@ -495,6 +494,9 @@ where
         SurgeReadWriteOpenings<F>,
         SurgeFinalOpenings<F, Instruction, C, M>,
     >,
+
+    /// Single proof for all of the polynomial openings above
+    opening_proof: ReducedOpeningProof<F, PCS>,
 }
 
 impl<F, Instruction, const C: usize, const M: usize> SurgePreprocessing<F, Instruction, C, M>
@@ -595,11 +597,13 @@ where
             transcript,
         );
 
+        let mut opening_accumulator = ProverOpeningAccumulator::new();
         let sumcheck_openings = PrimarySumcheckOpenings::open(&polynomials, &r_z); // TODO: use return value from prove_arbitrary?
-        let sumcheck_opening_proof = PrimarySumcheckOpenings::prove_openings(
+        PrimarySumcheckOpenings::prove_openings(
             &polynomials,
             &r_z,
             &sumcheck_openings,
+            &mut opening_accumulator,
             transcript,
         );
 
@@ -608,16 +612,23 @@ where
             sumcheck_proof: primary_sumcheck_proof,
             num_rounds,
             openings: sumcheck_openings,
-            opening_proof: sumcheck_opening_proof,
         };
 
-        let memory_checking =
-            SurgeProof::prove_memory_checking(preprocessing, &polynomials, transcript);
+        let memory_checking = SurgeProof::prove_memory_checking(
+            preprocessing,
+            generators,
+            &polynomials,
+            &mut opening_accumulator,
+            transcript,
+        );
+
+        let opening_proof = opening_accumulator.reduce_and_prove::<PCS>(generators, transcript);
 
         SurgeProof {
             commitment,
             primary_sumcheck,
             memory_checking,
+            opening_proof,
         }
     }
 
@@ -638,35 +649,45 @@ where
             &proof.primary_sumcheck.claimed_evaluation,
         );
         let primary_sumcheck_poly_degree = instruction.g_poly_degree(C) + 1;
-        let (claim_last, r_z) = proof.primary_sumcheck.sumcheck_proof.verify(
-            proof.primary_sumcheck.claimed_evaluation,
-            proof.primary_sumcheck.num_rounds,
-            primary_sumcheck_poly_degree,
-            transcript,
-        )?;
-
-        let eq_eval = EqPolynomial::new(r_primary_sumcheck.to_vec()).evaluate(&r_z);
-        assert_eq!(
-            eq_eval * instruction.combine_lookups(&proof.primary_sumcheck.openings, C, M),
-            claim_last,
-            "Primary sumcheck check failed."
-        );
-
-        proof.primary_sumcheck.openings.verify_openings(
-            generators,
-            &proof.primary_sumcheck.opening_proof,
-            &proof.commitment,
-            &r_z,
-            transcript,
-        )?;
+        let mut opening_accumulator = VerifierOpeningAccumulator::new();
+        (|| {
+            let (claim_last, r_z) = proof.primary_sumcheck.sumcheck_proof.verify(
+                proof.primary_sumcheck.claimed_evaluation,
+                proof.primary_sumcheck.num_rounds,
+                primary_sumcheck_poly_degree,
+                transcript,
+            )?;
+
+            // Checks the number of openings before `combine_lookups` indexes into them
+            proof.primary_sumcheck.openings.verify_openings(
+                &proof.commitment,
+                &r_z,
+                &mut opening_accumulator,
+                transcript,
+            )?;
+
+            let eq_eval = EqPolynomial::new(r_primary_sumcheck.to_vec()).evaluate(&r_z);
+            if eq_eval * instruction.combine_lookups(&proof.primary_sumcheck.openings, C, M)
+                != claim_last
+            {
+                return Err(ProofVerifyError::SumcheckClaimError);
+            }
+            Ok(())
+        })()
+        .map_err(ProofVerifyError::wrap(
+            ProofVerifyError::PrimarySumcheckError,
+        ))?;
 
         Self::verify_memory_checking(
             preprocessing,
             generators,
             proof.memory_checking,
             &proof.commitment,
+            &mut opening_accumulator,
             transcript,
-        )
+        )?;
+
+        opening_accumulator.reduce_and_verify(generators, &proof.opening_proof, transcript)
     }
 
     #[tracing::instrument(skip_all, name = "Surge::construct_polys")]
@@ -781,7 +802,10 @@ mod tests {
     use crate::{
         jolt::instruction::xor::XORInstruction,
         lasso::surge::SurgeProof,
-        poly::{commitment::hyrax::HyraxScheme, commitment::pedersen::PedersenGenerators},
+        poly::{
+            commitment::hyrax::{HyraxGenerators, HyraxScheme},
+            commitment::pedersen::PedersenGenerators,
+        },
         utils::transcript::ProofTranscript,
     };
     use ark_bn254::{Fr, G1Projective};
@@ -799,10 +823,10 @@ mod tests {
 
         let mut transcript = ProofTranscript::new(b"test_transcript");
         let preprocessing = SurgePreprocessing::preprocess();
-        let generators = PedersenGenerators::new(
+        let generators = HyraxGenerators::new(PedersenGenerators::new(
             SurgeProof::<Fr, HyraxScheme<G1Projective>, XORInstruction, C, M>::num_generators(16),
             b"LassoV1",
-        );
+        ));
         let proof = SurgeProof::<Fr, HyraxScheme<G1Projective>, XORInstruction, C, M>::prove(
             &preprocessing,
             &generators,
@@ -829,10 +853,10 @@ mod tests {
 
         let mut transcript = ProofTranscript::new(b"test_transcript");
         let preprocessing = SurgePreprocessing::preprocess();
-        let generators = PedersenGenerators::new(
+        let generators = HyraxGenerators::new(PedersenGenerators::new(
             SurgeProof::<Fr, HyraxScheme<G1Projective>, XORInstruction, C, M>::num_generators(16),
             b"LassoV1",
-        );
+        ));
         let proof = SurgeProof::<Fr, HyraxScheme<G1Projective>, XORInstruction, C, M>::prove(
             &preprocessing,
             &generators,
@@ -9,6 +9,9 @@
 #![allow(long_running_const_eval)]
 #![feature(return_position_impl_trait_in_trait)]
 
+// Lets code generated by the `jolt-core-macros` derives refer to `::jolt_core` within this crate.
+extern crate self as jolt_core;
+
 pub mod benches;
 pub mod host;
 pub mod jolt;
@@ -0,0 +1,182 @@
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, PrimeField};
+use rayon::prelude::*;
+
+use super::negate_bigint;
+
+/// Precomputed windowed multiples of a fixed set of bases, so that an MSM over those bases can
+/// be computed with table lookups and additions only (no doublings, no bucket reduction).
+///
+/// For each base `G_j`, window `w` and digit `d` in `1..2^c`, the table stores the affine point
+/// `d * 2^(c * w) * G_j`, where `c` is the window size.
+#[derive(Clone)]
+pub struct FixedBaseTable<G: CurveGroup> {
+    window_size: usize,
+    num_windows: usize,
+    tables: Vec<Vec<G::Affine>>,
+}
+
+impl<G: CurveGroup> FixedBaseTable<G> {
+    /// Largest window size considered by `window_size_for_budget`.
+    const MAX_WINDOW_SIZE: usize = 8;
+
+    #[tracing::instrument(skip_all, name = "FixedBaseTable::new")]
+    pub fn new(bases: &[G], window_size: usize) -> Self {
+        assert!(window_size > 0 && window_size <= Self::MAX_WINDOW_SIZE);
+        let num_windows = Self::num_windows(window_size);
+        let digits_per_window = (1 << window_size) - 1;
+
+        let tables = bases
+            .par_iter()
+            .map(|base| {
+                let mut multiples = Vec::with_capacity(num_windows * digits_per_window);
+                let mut window_base = *base;
+                for _ in 0..num_windows {
+                    let mut multiple = window_base;
+                    for _ in 0..digits_per_window {
+                        multiples.push(multiple);
+                        multiple += window_base;
+                    }
+                    for _ in 0..window_size {
+                        window_base.double_in_place();
+                    }
+                }
+                G::normalize_batch(&multiples)
+            })
+            .collect();
+
+        Self {
+            window_size,
+            num_windows,
+            tables,
+        }
+    }
+
+    /// Returns the largest window size for which a table over `num_bases` bases fits in
+    /// `memory_budget` bytes, or `None` if no window size does.
+    pub fn window_size_for_budget(num_bases: usize, memory_budget: usize) -> Option<usize> {
+        (1..=Self::MAX_WINDOW_SIZE)
+            .rev()
+            .find(|&window_size| Self::table_size(num_bases, window_size) <= memory_budget)
+    }
+
+    /// The size in bytes of a table over `num_bases` bases with the given window size.
+    pub fn table_size(num_bases: usize, window_size: usize) -> usize {
+        num_bases
+            * Self::num_windows(window_size)
+            * ((1 << window_size) - 1)
+            * std::mem::size_of::<G::Affine>()
+    }
+
+    fn num_windows(window_size: usize) -> usize {
+        let num_bits = G::ScalarField::MODULUS_BIT_SIZE as usize;
+        (num_bits + window_size - 1) / window_size
+    }
+
+    /// The number of bases covered by this table.
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Computes Σ scalars[j] * G_j over the first `scalars.len()` bases.
+    pub fn msm(&self, scalars: &[G::ScalarField]) -> G {
+        assert!(scalars.len() <= self.len());
+        let mut result = G::zero();
+        for (table, scalar) in self.tables.iter().zip(scalars.iter()) {
+            let bigint = scalar.into_bigint();
+            if bigint.num_bits() <= 64 {
+                result += self.mul_u64(table, bigint.as_ref()[0]);
+                continue;
+            }
+            // Small negative values are committed as the negation of their (small) magnitude.
+            let negated = negate_bigint::<G::ScalarField>(bigint);
+            if negated.num_bits() <= 64 {
+                result -= self.mul_u64(table, negated.as_ref()[0]);
+                continue;
+            }
+
+            let mask = (1u64 << self.window_size) - 1;
+            let limbs = bigint.as_ref();
+            for window in 0..self.num_windows {
+                let bit_offset = window * self.window_size;
+                let (limb, bit) = (bit_offset / 64, bit_offset % 64);
+                let mut digit = limbs[limb] >> bit;
+                if bit + self.window_size > 64 && limb + 1 < limbs.len() {
+                    digit |= limbs[limb + 1] << (64 - bit);
+                }
+                result += self.lookup(table, window, digit & mask);
+            }
+        }
+        result
+    }
+
+    /// Computes Σ scalars[j] * G_j over the first `scalars.len()` bases.
+    pub fn msm_u64(&self, scalars: &[u64]) -> G {
+        assert!(scalars.len() <= self.len());
+        self.tables
+            .iter()
+            .zip(scalars.iter())
+            .fold(G::zero(), |sum, (table, scalar)| {
+                sum + self.mul_u64(table, *scalar)
+            })
+    }
+
+    fn mul_u64(&self, table: &[G::Affine], mut scalar: u64) -> G {
+        let mask = (1u64 << self.window_size) - 1;
+        let mut result = G::zero();
+        let mut window = 0;
+        while scalar != 0 {
+            result += self.lookup(table, window, scalar & mask);
+            scalar >>= self.window_size;
+            window += 1;
+        }
+        result
+    }
+
+    fn lookup(&self, table: &[G::Affine], window: usize, digit: u64) -> G {
+        if digit == 0 {
+            G::zero()
+        } else {
+            let digits_per_window = (1 << self.window_size) - 1;
+            table[window * digits_per_window + digit as usize - 1].into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msm::VariableBaseMSM;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_std::{test_rng, UniformRand};
+    use rand_core::RngCore;
+
+    #[test]
+    fn fixed_base_msm_matches_variable_base() {
+        let mut rng = test_rng();
+        let bases: Vec<G1Projective> = (0..32).map(|_| G1Projective::rand(&mut rng)).collect();
+        let affine_bases = G1Projective::normalize_batch(&bases);
+
+        let random_scalars: Vec<Fr> = (0..bases.len()).map(|_| Fr::rand(&mut rng)).collect();
+        let small_scalars: Vec<u64> = (0..bases.len()).map(|_| rng.next_u64()).collect();
+        let negative_scalars: Vec<Fr> = small_scalars.iter().map(|s| -Fr::from(*s)).collect();
+
+        for window_size in [1, 3, 4, 8] {
+            let table = FixedBaseTable::new(&bases, window_size);
+            for scalars in [&random_scalars, &negative_scalars] {
+                assert_eq!(
+                    table.msm(scalars),
+                    G1Projective::msm(&affine_bases, scalars).unwrap()
+                );
+            }
+            assert_eq!(
+                table.msm_u64(&small_scalars),
+                G1Projective::msm_u64(&affine_bases, &small_scalars).unwrap()
+            );
+        }
+    }
+}
@@ -4,6 +4,8 @@ use ark_std::cmp::Ordering;
 use ark_std::vec::Vec;
 use rayon::prelude::*;
 
+pub mod fixed_base;
+
 impl<G: CurveGroup> VariableBaseMSM for G {}
 
 /// Copy of ark_ec::VariableBaseMSM with minor modifications to speed up
@@ -12,29 +14,32 @@ pub trait VariableBaseMSM: ScalarMul {
     fn msm(bases: &[Self::MulBase], scalars: &[Self::ScalarField]) -> Result<Self, usize> {
         (bases.len() == scalars.len())
             .then(|| {
-                let max_num_bits = scalars
+                // Small negative values (e.g. -1) are full-width field elements, but their
+                // negations are small, so also track the bit width of each scalar's negation.
+                let (max_num_bits, max_signed_num_bits) = scalars
                     .par_iter()
-                    .map(|s| s.into_bigint().num_bits())
-                    .max()
-                    .unwrap();
+                    .map(|s| {
+                        let bigint = s.into_bigint();
+                        let num_bits = bigint.num_bits();
+                        let signed_num_bits = if num_bits <= 64 {
+                            num_bits
+                        } else {
+                            negate_bigint::<Self::ScalarField>(bigint).num_bits()
+                        };
+                        (num_bits, signed_num_bits)
+                    })
+                    .reduce(|| (0, 0), |(a, b), (c, d)| (a.max(c), b.max(d)));
 
                 match max_num_bits {
                     0 => Self::zero(),
-                    1 => {
+                    1..=64 => {
                         let scalars_u64 = &map_field_elements_to_u64::<Self>(scalars);
-                        msm_binary(bases, scalars_u64)
+                        msm_small_scalars(bases, scalars_u64, max_num_bits as usize)
                     }
-                    2..=10 => {
-                        let scalars_u64 = &map_field_elements_to_u64::<Self>(scalars);
-                        msm_small(bases, scalars_u64, max_num_bits as usize)
-                    }
-                    11..=64 => {
-                        let scalars_u64 = &map_field_elements_to_u64::<Self>(scalars);
-                        if Self::NEGATION_IS_CHEAP {
-                            msm_u64_wnaf(bases, scalars_u64, max_num_bits as usize)
-                        } else {
-                            msm_u64(bases, scalars_u64, max_num_bits as usize)
-                        }
+                    _ if max_signed_num_bits <= 64 => {
+                        let (positive, negative) =
+                            map_field_elements_to_signed_u64::<Self>(scalars);
+                        msm_signed_small_scalars(bases, &positive, &negative)
                     }
                     _ => {
                         let scalars = scalars
@@ -51,6 +56,88 @@ pub trait VariableBaseMSM: ScalarMul {
             })
             .ok_or_else(|| bases.len().min(scalars.len()))
     }
+
+    /// Same as `msm`, for scalars that are already known to fit in a `u64` (e.g. the
+    /// coefficients of a `CompactPolynomial`), which skips the conversion from field elements.
+    fn msm_u64(bases: &[Self::MulBase], scalars: &[u64]) -> Result<Self, usize> {
+        (bases.len() == scalars.len())
+            .then(|| {
+                let max_num_bits = scalars
+                    .par_iter()
+                    .map(|s| u64::BITS - s.leading_zeros())
+                    .max()
+                    .unwrap();
+                msm_small_scalars(bases, scalars, max_num_bits as usize)
+            })
+            .ok_or_else(|| bases.len().min(scalars.len()))
+    }
+}
+
+/// Dispatches to the MSM implementation best suited to scalars of at most `max_num_bits` bits.
+fn msm_small_scalars<V: VariableBaseMSM>(
+    bases: &[V::MulBase],
+    scalars: &[u64],
+    max_num_bits: usize,
+) -> V {
+    match max_num_bits {
+        0 => V::zero(),
+        1 => msm_binary(bases, scalars),
+        2..=10 => msm_small(bases, scalars, max_num_bits),
+        _ => {
+            if V::NEGATION_IS_CHEAP {
+                msm_u64_wnaf(bases, scalars, max_num_bits)
+            } else {
+                msm_u64(bases, scalars, max_num_bits)
+            }
+        }
+    }
+}
+
+/// Computes Σ positive[i] * bases[i] - Σ negative[i] * bases[i], where at most one of
+/// `positive[i]` and `negative[i]` is non-zero. Each -k * G is committed as the negation of k * G.
+fn msm_signed_small_scalars<V: VariableBaseMSM>(
+    bases: &[V::MulBase],
+    positive: &[u64],
+    negative: &[u64],
+) -> V {
+    let max_num_bits = |scalars: &[u64]| {
+        scalars
+            .par_iter()
+            .map(|s| u64::BITS - s.leading_zeros())
+            .max()
+            .unwrap() as usize
+    };
+    let (positive_sum, negative_sum): (V, V) = rayon::join(
+        || msm_small_scalars(bases, positive, max_num_bits(positive)),
+        || msm_small_scalars(bases, negative, max_num_bits(negative)),
+    );
+    positive_sum - negative_sum
+}
+
+/// The negation of a non-zero field element, computed on its canonical representation, which is
+/// cheaper than negating the field element and converting it again.
+pub(crate) fn negate_bigint<F: PrimeField>(bigint: F::BigInt) -> F::BigInt {
+    let mut negated = F::MODULUS;
+    negated.sub_with_borrow(&bigint);
+    negated
+}
+
+/// Splits field elements that are each either a small non-negative value or the negation of one
+/// into (positive, negative) magnitudes.
+fn map_field_elements_to_signed_u64<V: VariableBaseMSM>(
+    field_elements: &[V::ScalarField],
+) -> (Vec<u64>, Vec<u64>) {
+    field_elements
+        .par_iter()
+        .map(|s| {
+            let bigint = s.into_bigint();
+            if bigint.num_bits() <= 64 {
+                (bigint.as_ref()[0], 0)
+            } else {
+                (0, negate_bigint::<V::ScalarField>(bigint).as_ref()[0])
+            }
+        })
+        .unzip()
 }
 
 fn map_field_elements_to_u64<V: VariableBaseMSM>(field_elements: &[V::ScalarField]) -> Vec<u64> {
@@ -479,3 +566,44 @@ fn ln_without_floats(a: usize) -> usize {
     // log2(a) * ln(2)
     (ark_std::log2(a) * 69 / 100) as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Affine, G1Projective};
+    use ark_std::{test_rng, UniformRand};
+    use rand_core::RngCore;
+
+    #[test]
+    fn msm_small_negative_scalars() {
+        let mut rng = test_rng();
+        let bases: Vec<G1Affine> = (0..256).map(|_| G1Affine::rand(&mut rng)).collect();
+
+        for max_value in [2, 1 << 8, 1 << 20, u64::MAX] {
+            let scalars: Vec<Fr> = (0..bases.len())
+                .map(|_| {
+                    let value = Fr::from(rng.next_u64() % max_value);
+                    if rng.next_u32() % 2 == 0 {
+                        value
+                    } else {
+                        -value
+                    }
+                })
+                .collect();
+            let (positive, negative) = map_field_elements_to_signed_u64::<G1Projective>(&scalars);
+            for ((scalar, positive), negative) in scalars.iter().zip(positive).zip(negative) {
+                assert_eq!(*scalar, Fr::from(positive) - Fr::from(negative));
+            }
+
+            let expected: G1Projective = bases
+                .iter()
+                .zip(scalars.iter())
+                .map(|(base, scalar)| *base * scalar)
+                .sum();
+            assert_eq!(
+                <G1Projective as VariableBaseMSM>::msm(&bases, &scalars).unwrap(),
+                expected
+            );
+        }
+    }
+}
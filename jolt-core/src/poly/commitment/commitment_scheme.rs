@@ -1,7 +1,14 @@
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
 use crate::{
-    poly::{dense_mlpoly::DensePolynomial, field::JoltField},
+    poly::{
+        compact_polynomial::{CompactPolynomial, SmallInt},
+        dense_mlpoly::DensePolynomial,
+        field::JoltField,
+        multilinear_polynomial::MultilinearPolynomial,
+        padded_polynomial::PaddedPolynomial,
+        sparse_polynomial::SparsePolynomial,
+    },
     utils::{
         errors::ProofVerifyError,
         transcript::{AppendToTranscript, ProofTranscript},
@@ -23,7 +30,7 @@ impl CommitShape {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum BatchType {
     Big,
     Small,
@@ -39,6 +46,9 @@ pub trait CommitmentScheme: Clone + Sync + Send + 'static {
     type BatchedProof: Sync + Send + CanonicalSerialize + CanonicalDeserialize;
 
     fn setup(shapes: &[CommitShape]) -> Self::Setup;
+    /// Spends up to `memory_budget` bytes on prover-side precomputation over `setup` that
+    /// speeds up later commitments. Schemes without such a precomputation ignore it.
+    fn precompute(_setup: &mut Self::Setup, _memory_budget: usize) {}
     fn commit(poly: &DensePolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment;
     fn batch_commit(
         evals: &[&[Self::Field]],
@@ -46,6 +56,13 @@ pub trait CommitmentScheme: Clone + Sync + Send + 'static {
         batch_type: BatchType,
     ) -> Vec<Self::Commitment>;
     fn commit_slice(evals: &[Self::Field], setup: &Self::Setup) -> Self::Commitment;
+    /// Computes the commitment to Σ coeffs[i] * polys[i] from the commitments to polys[i].
+    /// Commitments to smaller polynomials are combined as commitments to their zero-padded
+    /// extensions.
+    fn combine_commitments(
+        commitments: &[&Self::Commitment],
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment;
     fn batch_commit_polys(
         polys: &[DensePolynomial<Self::Field>],
         setup: &Self::Setup,
@@ -62,16 +79,54 @@ pub trait CommitmentScheme: Clone + Sync + Send + 'static {
         let slices: Vec<&[Self::Field]> = polys.iter().map(|poly| poly.evals_ref()).collect();
         Self::batch_commit(&slices, setup, batch_type)
     }
+    /// Commits to polynomials with small integer coefficients. Schemes that can take advantage
+    /// of the small coefficients (e.g. with a small-scalar MSM) should override this.
+    fn batch_commit_compact<T: SmallInt>(
+        polys: &[&CompactPolynomial<T, Self::Field>],
+        setup: &Self::Setup,
+        batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        let evals: Vec<Vec<Self::Field>> = polys.iter().map(|poly| poly.evals()).collect();
+        let slices: Vec<&[Self::Field]> = evals.iter().map(|evals| evals.as_slice()).collect();
+        Self::batch_commit(&slices, setup, batch_type)
+    }
+    /// Commits to polynomials that are zero almost everywhere. Schemes that can skip the zero
+    /// terms (e.g. by only including the non-zero terms in each MSM) should override this.
+    fn batch_commit_sparse(
+        polys: &[&SparsePolynomial<Self::Field>],
+        setup: &Self::Setup,
+        batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        let evals: Vec<Vec<Self::Field>> = polys.iter().map(|poly| poly.evals()).collect();
+        let slices: Vec<&[Self::Field]> = evals.iter().map(|evals| evals.as_slice()).collect();
+        Self::batch_commit(&slices, setup, batch_type)
+    }
+    /// Commits to polynomials of which only a prefix is stored. Schemes that can commit to the
+    /// constant padding without materializing it should override this.
+    fn batch_commit_padded(
+        polys: &[&PaddedPolynomial<Self::Field>],
+        setup: &Self::Setup,
+        batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        let polys: Vec<DensePolynomial<Self::Field>> =
+            polys.iter().map(|poly| poly.to_dense()).collect();
+        Self::batch_commit_polys(&polys, setup, batch_type)
+    }
     fn prove(
+        setup: &Self::Setup,
         poly: &DensePolynomial<Self::Field>,
         opening_point: &[Self::Field], // point at which the polynomial is evaluated
         transcript: &mut ProofTranscript,
     ) -> Self::Proof;
+    /// Proves the evaluations of `polynomials` at `opening_point` with a single proof. The
+    /// polynomials may have any number of variables up to `opening_point.len()`, and are
+    /// opened as their extensions padded with zeros, which must be what their commitments
+    /// commit to as well.
     fn batch_prove(
-        polynomials: &[&DensePolynomial<Self::Field>],
+        setup: &Self::Setup,
+        polynomials: &[&dyn MultilinearPolynomial<Self::Field>],
         opening_point: &[Self::Field],
         openings: &[Self::Field],
-        batch_type: BatchType,
         transcript: &mut ProofTranscript,
     ) -> Self::BatchedProof;
 
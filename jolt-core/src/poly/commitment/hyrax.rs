@@ -2,19 +2,26 @@ use std::marker::PhantomData;
 
 use super::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
 use super::pedersen::{PedersenCommitment, PedersenGenerators};
+use crate::poly::compact_polynomial::{CompactPolynomial, Padding, SmallInt};
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::eq_poly::EqPolynomial;
 use crate::poly::field::JoltField;
+use crate::poly::multilinear_polynomial::MultilinearPolynomial;
+use crate::poly::padded_polynomial::PaddedPolynomial;
+use crate::poly::sparse_polynomial::SparsePolynomial;
+use crate::utils::compute_dotproduct;
 use crate::utils::errors::ProofVerifyError;
 use crate::utils::math::Math;
 use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
-use crate::utils::{compute_dotproduct, mul_0_1_optimized};
 use ark_ec::CurveGroup;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use num_integer::Roots;
 use rayon::prelude::*;
-use tracing::trace_span;
 
+use crate::msm::fixed_base::FixedBaseTable;
 use crate::msm::VariableBaseMSM;
 
 #[derive(Clone)]
@@ -49,7 +56,7 @@ pub fn matrix_dimensions(num_vars: usize, ratio: usize) -> (usize, usize) {
 
 impl<F: JoltField, G: CurveGroup<ScalarField = F>> CommitmentScheme for HyraxScheme<G> {
     type Field = G::ScalarField;
-    type Setup = PedersenGenerators<G>;
+    type Setup = HyraxGenerators<G>;
     type Commitment = HyraxCommitment<G>;
     type Proof = HyraxOpeningProof<G>;
     type BatchedProof = BatchedHyraxOpeningProof<G>;
@@ -66,43 +73,72 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> CommitmentScheme for HyraxSch
                 max_len = len;
             }
         }
-        PedersenGenerators::new(max_len, b"Jolt v1 Hyrax generators")
+        HyraxGenerators::new(PedersenGenerators::new(
+            max_len,
+            b"Jolt v1 Hyrax generators",
+        ))
     }
+
+    fn precompute(setup: &mut Self::Setup, memory_budget: usize) {
+        setup.precompute_fixed_base(memory_budget);
+    }
+
     fn commit(poly: &DensePolynomial<Self::Field>, gens: &Self::Setup) -> Self::Commitment {
         HyraxCommitment::commit(poly, gens)
     }
     fn batch_commit(
         evals: &[&[Self::Field]],
         gens: &Self::Setup,
-        batch_type: BatchType,
+        _batch_type: BatchType,
     ) -> Vec<Self::Commitment> {
-        HyraxCommitment::batch_commit(evals, gens, batch_type)
+        HyraxCommitment::batch_commit(evals, gens)
     }
     fn commit_slice(eval_slice: &[Self::Field], generators: &Self::Setup) -> Self::Commitment {
         HyraxCommitment::commit_slice(eval_slice, generators)
     }
+    fn batch_commit_compact<T: SmallInt>(
+        polys: &[&CompactPolynomial<T, Self::Field>],
+        gens: &Self::Setup,
+        _batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        HyraxCommitment::batch_commit_compact(polys, gens)
+    }
+    fn batch_commit_sparse(
+        polys: &[&SparsePolynomial<Self::Field>],
+        gens: &Self::Setup,
+        _batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        HyraxCommitment::batch_commit_sparse(polys, gens)
+    }
+    fn batch_commit_padded(
+        polys: &[&PaddedPolynomial<Self::Field>],
+        gens: &Self::Setup,
+        _batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        HyraxCommitment::batch_commit_padded(polys, gens)
+    }
+    fn combine_commitments(
+        commitments: &[&Self::Commitment],
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment {
+        HyraxCommitment::combine(commitments, coeffs)
+    }
     fn prove(
+        setup: &Self::Setup,
         poly: &DensePolynomial<Self::Field>,
         opening_point: &[Self::Field],
         transcript: &mut ProofTranscript,
     ) -> Self::Proof {
-        // Implicitly prove is "prove_single", with a ratio = 1
-        HyraxOpeningProof::prove(poly, opening_point, 1, transcript)
+        HyraxOpeningProof::prove(setup, poly, opening_point, transcript)
     }
     fn batch_prove(
-        polynomials: &[&DensePolynomial<Self::Field>],
+        setup: &Self::Setup,
+        polynomials: &[&dyn MultilinearPolynomial<Self::Field>],
         opening_point: &[Self::Field],
         openings: &[Self::Field],
-        batch_type: BatchType,
         transcript: &mut ProofTranscript,
     ) -> Self::BatchedProof {
-        BatchedHyraxOpeningProof::prove(
-            polynomials,
-            opening_point,
-            openings,
-            batch_type,
-            transcript,
-        )
+        BatchedHyraxOpeningProof::prove(setup, polynomials, opening_point, openings, transcript)
     }
     fn verify(
         proof: &Self::Proof,
@@ -112,7 +148,6 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> CommitmentScheme for HyraxSch
         opening: &Self::Field,
         commitment: &Self::Commitment,
     ) -> Result<(), ProofVerifyError> {
-        // Implicitly verify is "prove_single", with a ratio = 1
         HyraxOpeningProof::verify(
             proof,
             generators,
@@ -120,7 +155,6 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> CommitmentScheme for HyraxSch
             opening_point,
             opening,
             commitment,
-            1,
         )
     }
     fn batch_verify(
@@ -145,9 +179,220 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> CommitmentScheme for HyraxSch
     }
 }
 
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
+/// The Pedersen generators used by Hyrax, along with an optional `FixedBaseTable` over them.
+/// The generators are fixed once `Jolt::preprocess` runs, so a prover that commits many times
+/// can trade memory for faster commitments by passing a fixed-base budget to `Jolt::preprocess`,
+/// which calls `precompute_fixed_base`.
+#[derive(Clone)]
 pub struct HyraxGenerators<G: CurveGroup> {
     pub gens: PedersenGenerators<G>,
+    fixed_base_table: Option<FixedBaseTable<G>>,
+}
+
+// Only the generators are serialized: the fixed-base table is a prover-side cache that can be
+// rebuilt from them with `precompute_fixed_base`, so deserialized generators come without one.
+impl<G: CurveGroup> CanonicalSerialize for HyraxGenerators<G> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.gens.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.gens.serialized_size(compress)
+    }
+}
+
+impl<G: CurveGroup> Valid for HyraxGenerators<G> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.gens.check()
+    }
+}
+
+impl<G: CurveGroup> CanonicalDeserialize for HyraxGenerators<G> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            gens: PedersenGenerators::deserialize_with_mode(reader, compress, validate)?,
+            fixed_base_table: None,
+        })
+    }
+}
+
+impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxGenerators<G> {
+    pub fn new(gens: PedersenGenerators<G>) -> Self {
+        Self {
+            gens,
+            fixed_base_table: None,
+        }
+    }
+
+    /// Precomputes a fixed-base table over all of the generators, using the largest window
+    /// size whose table fits in `memory_budget` bytes. If no window size fits, commitments
+    /// keep using the variable-base MSM.
+    #[tracing::instrument(skip_all, name = "HyraxGenerators::precompute_fixed_base")]
+    pub fn precompute_fixed_base(&mut self, memory_budget: usize) {
+        let num_generators = self.gens.generators.len();
+        self.fixed_base_table =
+            FixedBaseTable::<G>::window_size_for_budget(num_generators, memory_budget)
+                .map(|window_size| FixedBaseTable::new(&self.gens.generators, window_size));
+    }
+
+    pub fn has_fixed_base_table(&self) -> bool {
+        self.fixed_base_table.is_some()
+    }
+
+    /// The dimensions `(L_size, R_size)` of the matrix that the evaluations of a polynomial over
+    /// `num_vars` variables are arranged in. Every row is as long as there are generators (a
+    /// power of two, see `HyraxScheme::setup`), so the matrix of a smaller polynomial is the
+    /// first rows of that of a larger one padded with zeros, and a commitment to it is also a
+    /// commitment to the padded polynomial. Polynomials shorter than a row take up one row.
+    pub fn matrix_dimensions(&self, num_vars: usize) -> (usize, usize) {
+        let R_size = std::cmp::min(self.gens.generators.len(), num_vars.pow2());
+        (num_vars.pow2() / R_size, R_size)
+    }
+
+    /// Computes the Pedersen commitment to each row, where each row has `R_size` elements.
+    fn commit_rows<'a>(
+        &self,
+        rows: impl ParallelIterator<Item = &'a [F]>,
+        R_size: usize,
+    ) -> Vec<G> {
+        match &self.fixed_base_table {
+            Some(table) => rows.map(|row| table.msm(row)).collect(),
+            None => {
+                let gens = CurveGroup::normalize_batch(&self.gens.generators[..R_size]);
+                rows.map(|row| PedersenCommitment::commit_vector(row, &gens))
+                    .collect()
+            }
+        }
+    }
+
+    /// Same as `commit_rows`, for the rows of `R_size` small integers of each of `polys`.
+    /// Rows that lie entirely in a polynomial's padding are committed in closed form, as the
+    /// row `c, c + s, c + 2s, ...` commits to `c * Σ_j G_j + s * Σ_j j * G_j`.
+    fn commit_rows_compact<T: SmallInt>(
+        &self,
+        polys: &[&CompactPolynomial<T, F>],
+        R_size: usize,
+    ) -> Vec<G> {
+        let gens = match &self.fixed_base_table {
+            Some(_) => vec![],
+            None => CurveGroup::normalize_batch(&self.gens.generators[..R_size]),
+        };
+        let msm = |row: &[u64]| match &self.fixed_base_table {
+            Some(table) => table.msm_u64(row),
+            None => VariableBaseMSM::msm_u64(&gens, row).unwrap(),
+        };
+
+        let has_padding_rows = polys
+            .iter()
+            .any(|poly| poly.coeffs().len() + R_size <= poly.len());
+        let padding_bases: Option<(G, G)> = has_padding_rows.then(|| {
+            let indices: Vec<u64> = (0..R_size as u64).collect();
+            (self.gens.generators[..R_size].iter().sum(), msm(&indices))
+        });
+
+        polys
+            .par_iter()
+            .flat_map(|poly| {
+                (0..poly.len() / R_size)
+                    .into_par_iter()
+                    .map(move |row_index| (poly, row_index * R_size))
+            })
+            .map(|(poly, row_start)| {
+                let num_stored = poly.coeffs().len();
+                if row_start + R_size <= num_stored {
+                    let row: Vec<u64> = poly.coeffs()[row_start..row_start + R_size]
+                        .iter()
+                        .map(|coeff| coeff.to_u64())
+                        .collect();
+                    msm(&row)
+                } else if row_start >= num_stored {
+                    let (sum, weighted_sum) = padding_bases.unwrap();
+                    let first = sum * F::from_u64(poly.get_u64(row_start)).unwrap();
+                    match poly.padding() {
+                        Padding::Constant(_) => first,
+                        Padding::Counter(_) => first + weighted_sum,
+                    }
+                } else {
+                    let row: Vec<u64> = (row_start..row_start + R_size)
+                        .map(|index| poly.get_u64(index))
+                        .collect();
+                    msm(&row)
+                }
+            })
+            .collect()
+    }
+
+    /// Same as `commit_rows`, for the rows of `R_size` evaluations of each of `polys`. Rows
+    /// that lie entirely in a polynomial's padding `c` are committed as `c * Σ_j G_j`.
+    fn commit_rows_padded(&self, polys: &[&PaddedPolynomial<F>], R_size: usize) -> Vec<G> {
+        // The row in which each polynomial's stored values end, completed with its padding
+        let partial_rows: Vec<Option<Vec<F>>> = polys
+            .iter()
+            .map(|poly| {
+                let row_start = poly.values().len() / R_size * R_size;
+                (row_start < poly.values().len())
+                    .then(|| (row_start..row_start + R_size).map(|i| poly[i]).collect())
+            })
+            .collect();
+        let rows = polys
+            .par_iter()
+            .zip(partial_rows.par_iter())
+            .flat_map(|(poly, partial_row)| {
+                poly.values()
+                    .par_chunks_exact(R_size)
+                    .chain(partial_row.par_iter().map(Vec::as_slice))
+            });
+        let mut stored_commitments = self.commit_rows(rows, R_size).into_iter();
+
+        let padding_base: G = self.gens.generators[..R_size].iter().sum();
+        polys
+            .iter()
+            .flat_map(|poly| {
+                let num_stored_rows = poly.values().len().div_ceil(R_size);
+                let padding_row = padding_base * poly.padding();
+                stored_commitments
+                    .by_ref()
+                    .take(num_stored_rows)
+                    .collect::<Vec<G>>()
+                    .into_iter()
+                    .chain(
+                        std::iter::repeat(padding_row).take(poly.len() / R_size - num_stored_rows),
+                    )
+            })
+            .collect()
+    }
+
+    /// Same as `commit_rows`, for rows given as the sorted `(index, value)` pairs of their
+    /// non-zero entries, along with the index at which the row starts. Rows whose non-zero
+    /// entries are all one (e.g. one-hot flags) are committed by summing the generators.
+    fn commit_rows_sparse<'a>(
+        &self,
+        rows: impl ParallelIterator<Item = (usize, &'a [usize], &'a [F])>,
+    ) -> Vec<G> {
+        rows.map(|(row_start, indices, values)| {
+            if values.iter().all(|value| value.is_one()) {
+                indices
+                    .iter()
+                    .map(|index| self.gens.generators[index - row_start])
+                    .sum()
+            } else {
+                let bases: Vec<G> = indices
+                    .iter()
+                    .map(|index| self.gens.generators[index - row_start])
+                    .collect();
+                VariableBaseMSM::msm(&CurveGroup::normalize_batch(&bases), values).unwrap()
+            }
+        })
+        .collect()
+    }
 }
 
 #[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
@@ -157,50 +402,150 @@ pub struct HyraxCommitment<G: CurveGroup> {
 
 impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxCommitment<G> {
     #[tracing::instrument(skip_all, name = "HyraxCommitment::commit")]
-    pub fn commit(
-        poly: &DensePolynomial<G::ScalarField>,
-        generators: &PedersenGenerators<G>,
-    ) -> Self {
+    pub fn commit(poly: &DensePolynomial<G::ScalarField>, generators: &HyraxGenerators<G>) -> Self {
         Self::commit_slice(poly.evals_ref(), generators)
     }
 
     #[tracing::instrument(skip_all, name = "HyraxCommitment::commit_slice")]
-    pub fn commit_slice(eval_slice: &[G::ScalarField], generators: &PedersenGenerators<G>) -> Self {
+    pub fn commit_slice(eval_slice: &[G::ScalarField], generators: &HyraxGenerators<G>) -> Self {
         let n = eval_slice.len();
         let ell = n.log_2();
 
-        let (L_size, R_size) = matrix_dimensions(ell, 1);
+        let (L_size, R_size) = generators.matrix_dimensions(ell);
         assert_eq!(L_size * R_size, n);
 
-        let gens = CurveGroup::normalize_batch(&generators.generators[..R_size]);
-        let row_commitments = eval_slice
-            .par_chunks(R_size)
-            .map(|row| PedersenCommitment::commit_vector(row, &gens))
-            .collect();
+        let row_commitments = generators.commit_rows(eval_slice.par_chunks(R_size), R_size);
+        Self { row_commitments }
+    }
+
+    /// Computes the commitment to Σ coeffs[i] * polys[i], row by row. Commitments with fewer
+    /// rows than the others are padded with identity rows, i.e. their polynomials with zeros.
+    pub fn combine(commitments: &[&Self], coeffs: &[G::ScalarField]) -> Self {
+        let num_rows = commitments
+            .iter()
+            .map(|commitment| commitment.row_commitments.len())
+            .max()
+            .unwrap_or(0);
+        let row_commitments = coeffs
+            .par_iter()
+            .zip(commitments.par_iter())
+            .fold(
+                || vec![G::zero(); num_rows],
+                |mut running, (coeff, commitment)| {
+                    for (r, row_commitment) in
+                        running.iter_mut().zip(commitment.row_commitments.iter())
+                    {
+                        *r += *row_commitment * coeff;
+                    }
+                    running
+                },
+            )
+            .reduce(
+                || vec![G::zero(); num_rows],
+                |running, new: Vec<G>| {
+                    running
+                        .iter()
+                        .zip(new.iter())
+                        .map(|(r, n)| *r + n)
+                        .collect()
+                },
+            );
         Self { row_commitments }
     }
 
     #[tracing::instrument(skip_all, name = "HyraxCommitment::batch_commit")]
-    pub fn batch_commit(
-        batch: &[&[G::ScalarField]],
-        generators: &PedersenGenerators<G>,
-        batch_type: BatchType,
+    pub fn batch_commit(batch: &[&[G::ScalarField]], generators: &HyraxGenerators<G>) -> Vec<Self> {
+        let n = batch[0].len();
+        batch.iter().for_each(|poly| assert_eq!(poly.len(), n));
+        let ell = n.log_2();
+
+        let (L_size, R_size) = generators.matrix_dimensions(ell);
+        assert_eq!(L_size * R_size, n);
+
+        let rows = batch.par_iter().flat_map(|poly| poly.par_chunks(R_size));
+        let row_commitments: Vec<G> = generators.commit_rows(rows, R_size);
+
+        row_commitments
+            .par_chunks(L_size)
+            .map(|chunk| Self {
+                row_commitments: chunk.to_vec(),
+            })
+            .collect()
+    }
+
+    /// Same as `batch_commit`, but each row commitment is computed with a small-scalar MSM
+    /// directly over the polynomials' integer coefficients.
+    #[tracing::instrument(skip_all, name = "HyraxCommitment::batch_commit_compact")]
+    pub fn batch_commit_compact<T: SmallInt>(
+        batch: &[&CompactPolynomial<T, G::ScalarField>],
+        generators: &HyraxGenerators<G>,
     ) -> Vec<Self> {
         let n = batch[0].len();
         batch.iter().for_each(|poly| assert_eq!(poly.len(), n));
         let ell = n.log_2();
 
-        let ratio = batch_type_to_ratio(&batch_type);
+        let (L_size, R_size) = generators.matrix_dimensions(ell);
+        assert_eq!(L_size * R_size, n);
+
+        let row_commitments: Vec<G> = generators.commit_rows_compact(batch, R_size);
+
+        row_commitments
+            .par_chunks(L_size)
+            .map(|chunk| Self {
+                row_commitments: chunk.to_vec(),
+            })
+            .collect()
+    }
+
+    /// Same as `batch_commit`, without materializing the padding of the polynomials.
+    #[tracing::instrument(skip_all, name = "HyraxCommitment::batch_commit_padded")]
+    pub fn batch_commit_padded(
+        batch: &[&PaddedPolynomial<G::ScalarField>],
+        generators: &HyraxGenerators<G>,
+    ) -> Vec<Self> {
+        let n = batch[0].len();
+        batch.iter().for_each(|poly| assert_eq!(poly.len(), n));
+        let ell = n.log_2();
 
-        let (L_size, R_size) = matrix_dimensions(ell, ratio);
+        let (L_size, R_size) = generators.matrix_dimensions(ell);
         assert_eq!(L_size * R_size, n);
 
-        let gens = CurveGroup::normalize_batch(&generators.generators[..R_size]);
+        let row_commitments: Vec<G> = generators.commit_rows_padded(batch, R_size);
 
-        let rows = batch.par_iter().flat_map(|poly| poly.par_chunks(R_size));
-        let row_commitments: Vec<G> = rows
-            .map(|row| PedersenCommitment::commit_vector(row, &gens))
-            .collect();
+        row_commitments
+            .par_chunks(L_size)
+            .map(|chunk| Self {
+                row_commitments: chunk.to_vec(),
+            })
+            .collect()
+    }
+
+    /// Same as `batch_commit`, but each row commitment only includes the non-zero terms of
+    /// the row.
+    #[tracing::instrument(skip_all, name = "HyraxCommitment::batch_commit_sparse")]
+    pub fn batch_commit_sparse(
+        batch: &[&SparsePolynomial<G::ScalarField>],
+        generators: &HyraxGenerators<G>,
+    ) -> Vec<Self> {
+        let n = batch[0].len();
+        batch.iter().for_each(|poly| assert_eq!(poly.len(), n));
+        let ell = n.log_2();
+
+        let (L_size, R_size) = generators.matrix_dimensions(ell);
+        assert_eq!(L_size * R_size, n);
+
+        let rows = batch.par_iter().flat_map(|poly| {
+            (0..L_size).into_par_iter().map(move |row_index| {
+                let row_start = row_index * R_size;
+                let (start, end) = poly.index_range(row_start, row_start + R_size);
+                (
+                    row_start,
+                    &poly.indices()[start..end],
+                    &poly.values()[start..end],
+                )
+            })
+        });
+        let row_commitments: Vec<G> = generators.commit_rows_sparse(rows);
 
         row_commitments
             .par_chunks(L_size)
@@ -234,23 +579,68 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxOpeningProof<G> {
 
     #[tracing::instrument(skip_all, name = "HyraxOpeningProof::prove")]
     pub fn prove(
+        generators: &HyraxGenerators<G>,
         poly: &DensePolynomial<G::ScalarField>,
         opening_point: &[G::ScalarField], // point at which the polynomial is evaluated
-        ratio: usize,
         transcript: &mut ProofTranscript,
     ) -> HyraxOpeningProof<G> {
-        transcript.append_protocol_name(Self::protocol_name());
-
         // assert vectors are of the right size
         assert_eq!(poly.get_num_vars(), opening_point.len());
 
+        Self::prove_linear_combination(
+            generators,
+            &[poly],
+            &[G::ScalarField::one()],
+            opening_point,
+            transcript,
+        )
+    }
+
+    /// Proves the evaluation of Σ coeffs[i] * polynomials[i] at `opening_point`, where each
+    /// polynomial with fewer variables than `opening_point` is padded with zeros. The linear
+    /// combination is never materialized: each row of the vector-matrix product is read
+    /// straight from the polynomials.
+    fn prove_linear_combination(
+        generators: &HyraxGenerators<G>,
+        polynomials: &[&dyn MultilinearPolynomial<G::ScalarField>],
+        coeffs: &[G::ScalarField],
+        opening_point: &[G::ScalarField],
+        transcript: &mut ProofTranscript,
+    ) -> HyraxOpeningProof<G> {
+        transcript.append_protocol_name(Self::protocol_name());
+
         // compute the L and R vectors
-        let (L_size, _R_size) = matrix_dimensions(poly.get_num_vars(), ratio);
+        let (L_size, R_size) = generators.matrix_dimensions(opening_point.len());
         let eq = EqPolynomial::new(opening_point.to_vec());
         let (L, _R) = eq.compute_factored_evals(L_size);
+        let L = &L;
 
         // compute vector-matrix product between L and Z viewed as a matrix
-        let vector_matrix_product = Self::vector_matrix_product(poly, &L, ratio);
+        let vector_matrix_product = polynomials
+            .par_iter()
+            .zip(coeffs.par_iter())
+            .flat_map(|(poly, coeff)| {
+                assert!(poly.get_num_vars() <= opening_point.len());
+                let row_len = std::cmp::min(poly.len(), R_size);
+                (0..poly.len() / row_len)
+                    .into_par_iter()
+                    .map(move |row_index| (poly, *coeff * L[row_index], row_index * R_size))
+            })
+            .fold(
+                || vec![G::ScalarField::zero(); R_size],
+                |mut acc, (poly, row_coeff, row_start)| {
+                    let row_len = std::cmp::min(poly.len(), R_size);
+                    poly.add_scaled_chunk(&row_coeff, row_start, &mut acc[..row_len]);
+                    acc
+                },
+            )
+            .reduce(
+                || vec![G::ScalarField::zero(); R_size],
+                |mut acc: Vec<_>, row| {
+                    acc.iter_mut().zip(row).for_each(|(x, y)| *x += y);
+                    acc
+                },
+            );
 
         HyraxOpeningProof {
             vector_matrix_product,
@@ -259,29 +649,46 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxOpeningProof<G> {
 
     pub fn verify(
         &self,
-        pedersen_generators: &PedersenGenerators<G>,
+        generators: &HyraxGenerators<G>,
         transcript: &mut ProofTranscript,
         opening_point: &[G::ScalarField], // point at which the polynomial is evaluated
         opening: &G::ScalarField,         // evaluation \widetilde{Z}(r)
         commitment: &HyraxCommitment<G>,
-        ratio: usize,
     ) -> Result<(), ProofVerifyError> {
         transcript.append_protocol_name(Self::protocol_name());
 
+        if opening_point.is_empty() {
+            return Err(ProofVerifyError::InvalidInputLength(1, 0));
+        }
+
         // compute L and R
-        let (L_size, R_size) = matrix_dimensions(opening_point.len(), ratio);
+        let (L_size, R_size) = generators.matrix_dimensions(opening_point.len());
+        if commitment.row_commitments.len() != L_size {
+            return Err(ProofVerifyError::InvalidInputLength(
+                L_size,
+                commitment.row_commitments.len(),
+            ));
+        }
+        if self.vector_matrix_product.len() != R_size {
+            return Err(ProofVerifyError::InvalidInputLength(
+                R_size,
+                self.vector_matrix_product.len(),
+            ));
+        }
+        let row_generators = &generators.gens.generators[..R_size];
         let eq: EqPolynomial<_> = EqPolynomial::new(opening_point.to_vec());
         let (L, R) = eq.compute_factored_evals(L_size);
 
         // Verifier-derived commitment to u * a = \prod Com(u_j)^{a_j}
         let homomorphically_derived_commitment: G =
-            VariableBaseMSM::msm(&G::normalize_batch(&commitment.row_commitments), &L).unwrap();
+            VariableBaseMSM::msm(&G::normalize_batch(&commitment.row_commitments), &L)
+                .map_err(|len| ProofVerifyError::InvalidInputLength(L_size, len))?;
 
         let product_commitment = VariableBaseMSM::msm(
-            &G::normalize_batch(&pedersen_generators.generators[..R_size]),
+            &G::normalize_batch(row_generators),
             &self.vector_matrix_product,
         )
-        .unwrap();
+        .map_err(|len| ProofVerifyError::InvalidInputLength(R_size, len))?;
 
         let dot_product = compute_dotproduct(&self.vector_matrix_product, &R);
 
@@ -291,47 +698,27 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxOpeningProof<G> {
             Err(ProofVerifyError::InternalError)
         }
     }
-
-    #[tracing::instrument(skip_all, name = "HyraxOpeningProof::vector_matrix_product")]
-    fn vector_matrix_product(
-        poly: &DensePolynomial<G::ScalarField>,
-        L: &[G::ScalarField],
-        ratio: usize,
-    ) -> Vec<G::ScalarField> {
-        let (_, R_size) = matrix_dimensions(poly.get_num_vars(), ratio);
-
-        poly.evals_ref()
-            .par_chunks(R_size)
-            .enumerate()
-            .map(|(i, row)| {
-                row.iter()
-                    .map(|x| mul_0_1_optimized(&L[i], x))
-                    .collect::<Vec<G::ScalarField>>()
-            })
-            .reduce(
-                || vec![G::ScalarField::zero(); R_size],
-                |mut acc: Vec<_>, row| {
-                    acc.iter_mut().zip(row).for_each(|(x, y)| *x += y);
-                    acc
-                },
-            )
-    }
 }
 
+/// A single opening proof for polynomials of any sizes, all opened at one point. Since every
+/// commitment uses rows as long as there are generators, a commitment to a smaller polynomial
+/// is also one to its zero-padded extension (with identity rows appended), so all of the
+/// polynomials are padded to the size of the opening point and combined into one.
 #[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct BatchedHyraxOpeningProof<G: CurveGroup> {
     pub joint_proof: HyraxOpeningProof<G>,
-    pub ratio: usize,
 }
 
 /// See Section 16.1 of Thaler's Proofs, Arguments, and Zero-Knowledge
 impl<F: JoltField, G: CurveGroup<ScalarField = F>> BatchedHyraxOpeningProof<G> {
+    /// Proves that the zero-padded extension of each of `polynomials` evaluates to the
+    /// corresponding entry of `openings` at `opening_point`.
     #[tracing::instrument(skip_all, name = "BatchedHyraxOpeningProof::prove")]
     pub fn prove(
-        polynomials: &[&DensePolynomial<G::ScalarField>],
+        generators: &HyraxGenerators<G>,
+        polynomials: &[&dyn MultilinearPolynomial<G::ScalarField>],
         opening_point: &[G::ScalarField],
         openings: &[G::ScalarField],
-        batch_type: BatchType,
         transcript: &mut ProofTranscript,
     ) -> Self {
         transcript.append_protocol_name(Self::protocol_name());
@@ -342,80 +729,43 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> BatchedHyraxOpeningProof<G> {
         let rlc_coefficients: Vec<_> =
             transcript.challenge_vector(b"challenge_combine_n_to_one", polynomials.len());
 
-        let _span = trace_span!("Compute RLC of polynomials");
-        let _enter = _span.enter();
-
-        let poly_len = polynomials[0].len();
-
-        let num_chunks = rayon::current_num_threads().next_power_of_two();
-        let chunk_size = poly_len / num_chunks;
-
-        let rlc_poly = if chunk_size > 0 {
-            (0..num_chunks)
-                .into_par_iter()
-                .flat_map_iter(|chunk_index| {
-                    let mut chunk = vec![G::ScalarField::zero(); chunk_size];
-                    for (coeff, poly) in rlc_coefficients.iter().zip(polynomials.iter()) {
-                        for (rlc, poly_eval) in chunk
-                            .iter_mut()
-                            .zip(poly.evals_ref()[chunk_index * chunk_size..].iter())
-                        {
-                            *rlc += mul_0_1_optimized(poly_eval, coeff);
-                        }
-                    }
-                    chunk
-                })
-                .collect::<Vec<_>>()
-        } else {
-            rlc_coefficients
-                .par_iter()
-                .zip(polynomials.par_iter())
-                .map(|(coeff, poly)| poly.evals_ref().iter().map(|eval| *coeff * eval).collect())
-                .reduce(
-                    || vec![G::ScalarField::zero(); poly_len],
-                    |running, new| {
-                        debug_assert_eq!(running.len(), new.len());
-                        running
-                            .iter()
-                            .zip(new.iter())
-                            .map(|(r, n)| *r + n)
-                            .collect()
-                    },
-                )
-        };
-
-        drop(_enter);
-        drop(_span);
-
-        let ratio = batch_type_to_ratio(&batch_type);
-        let joint_proof = HyraxOpeningProof::prove(
-            &DensePolynomial::new(rlc_poly),
+        let joint_proof = HyraxOpeningProof::prove_linear_combination(
+            generators,
+            polynomials,
+            &rlc_coefficients,
             opening_point,
-            ratio,
             transcript,
         );
 
-        Self { joint_proof, ratio }
+        Self { joint_proof }
     }
 
     pub fn verify(
         &self,
-        pedersen_generators: &PedersenGenerators<G>,
+        generators: &HyraxGenerators<G>,
         opening_point: &[G::ScalarField],
         openings: &[G::ScalarField],
         commitments: &[&HyraxCommitment<G>],
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        let (L_size, _R_size) = matrix_dimensions(opening_point.len(), self.ratio);
-        commitments.iter().enumerate().for_each(|(i, commitment)| {
-            assert_eq!(
-                L_size,
-                commitment.row_commitments.len(),
-                "Row commitment {}/{} wrong length.",
-                i,
-                commitments.len()
-            )
-        });
+        if opening_point.is_empty() {
+            return Err(ProofVerifyError::InvalidInputLength(1, 0));
+        }
+        if commitments.is_empty() || commitments.len() != openings.len() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                commitments.len(),
+                openings.len(),
+            ));
+        }
+        let (L_size, _R_size) = generators.matrix_dimensions(opening_point.len());
+        for commitment in commitments.iter() {
+            if commitment.row_commitments.is_empty() || commitment.row_commitments.len() > L_size {
+                return Err(ProofVerifyError::InvalidInputLength(
+                    L_size,
+                    commitment.row_commitments.len(),
+                ));
+            }
+        }
 
         transcript.append_protocol_name(Self::protocol_name());
 
@@ -427,37 +777,15 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> BatchedHyraxOpeningProof<G> {
 
         let rlc_eval = compute_dotproduct(&rlc_coefficients, openings);
 
-        let rlc_commitment = rlc_coefficients
-            .par_iter()
-            .zip(commitments.par_iter())
-            .map(|(coeff, commitment)| {
-                commitment
-                    .row_commitments
-                    .iter()
-                    .map(|row_commitment| *row_commitment * coeff)
-                    .collect()
-            })
-            .reduce(
-                || vec![G::zero(); L_size],
-                |running, new| {
-                    debug_assert_eq!(running.len(), new.len());
-                    running
-                        .iter()
-                        .zip(new.iter())
-                        .map(|(r, n)| *r + n)
-                        .collect()
-                },
-            );
+        let mut rlc_commitment = HyraxCommitment::combine(commitments, &rlc_coefficients);
+        rlc_commitment.row_commitments.resize(L_size, G::zero());
 
         self.joint_proof.verify(
-            pedersen_generators,
+            generators,
             transcript,
             opening_point,
             &rlc_eval,
-            &HyraxCommitment {
-                row_commitments: rlc_commitment,
-            },
-            self.ratio,
+            &rlc_commitment,
         )
     }
 
@@ -470,18 +798,18 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> BatchedHyraxOpeningProof<G> {
 mod tests {
     use super::*;
     use ark_bn254::{Fr, G1Projective};
+    use rand_core::RngCore;
 
     #[test]
     fn check_polynomial_commit() {
-        check_polynomial_commit_helper::<Fr, G1Projective, 1>();
-        check_polynomial_commit_helper::<Fr, G1Projective, 4>();
+        // With two generators, the polynomial's four evaluations span two rows
+        check_polynomial_commit_helper::<Fr, G1Projective>(2);
+        check_polynomial_commit_helper::<Fr, G1Projective>(1 << 8);
     }
 
-    fn check_polynomial_commit_helper<
-        F: JoltField,
-        G: CurveGroup<ScalarField = F>,
-        const RATIO: usize,
-    >() {
+    fn check_polynomial_commit_helper<F: JoltField, G: CurveGroup<ScalarField = F>>(
+        num_generators: usize,
+    ) {
         let Z = vec![
             G::ScalarField::one(),
             G::ScalarField::from_u64(2u64).unwrap(),
@@ -498,11 +826,12 @@ mod tests {
         let eval = poly.evaluate(&r);
         assert_eq!(eval, G::ScalarField::from_u64(28u64).unwrap());
 
-        let generators: PedersenGenerators<G> = PedersenGenerators::new(1 << 8, b"test-two");
+        let generators =
+            HyraxGenerators::new(PedersenGenerators::<G>::new(num_generators, b"test-two"));
         let poly_commitment: HyraxCommitment<G> = HyraxCommitment::commit(&poly, &generators);
 
         let mut prover_transcript = ProofTranscript::new(b"example");
-        let proof = HyraxOpeningProof::prove(&poly, &r, RATIO, &mut prover_transcript);
+        let proof = HyraxOpeningProof::prove(&generators, &poly, &r, &mut prover_transcript);
 
         let mut verifier_transcript = ProofTranscript::new(b"example");
 
@@ -512,9 +841,154 @@ mod tests {
                 &mut verifier_transcript,
                 &r,
                 &eval,
-                &poly_commitment,
-                RATIO
+                &poly_commitment
             )
             .is_ok());
     }
+
+    #[test]
+    fn compact_commit_matches_dense() {
+        let mut rng = ark_std::test_rng();
+        let coeffs: Vec<u64> = (0..1 << 10).map(|_| rng.next_u64() % 1000).collect();
+        let compact = CompactPolynomial::<u32, Fr>::from_u64(&coeffs);
+        let dense = DensePolynomial::<Fr>::from_u64(&coeffs);
+
+        let generators = HyraxGenerators::new(PedersenGenerators::<G1Projective>::new(
+            1 << 8,
+            b"test-compact",
+        ));
+        let compact_commitment = HyraxCommitment::batch_commit_compact(&[&compact], &generators);
+        let dense_commitment = HyraxCommitment::batch_commit(&[dense.evals_ref()], &generators);
+        assert_eq!(
+            compact_commitment[0].row_commitments,
+            dense_commitment[0].row_commitments
+        );
+    }
+
+    #[test]
+    fn padded_compact_commit_matches_dense() {
+        let mut rng = ark_std::test_rng();
+        let mut generators = HyraxGenerators::new(PedersenGenerators::<G1Projective>::new(
+            1 << 8,
+            b"test-padded",
+        ));
+        for fixed_base in [false, true] {
+            if fixed_base {
+                generators.precompute_fixed_base(1 << 30);
+            }
+            for padding in [Padding::Constant(7u32), Padding::Counter(3u32)] {
+                let coeffs: Vec<u32> = (0..(1 << 9) + 1).map(|_| rng.next_u32() % 1000).collect();
+                let compact = CompactPolynomial::<u32, Fr>::padded(coeffs, padding);
+                let dense = compact.to_dense();
+                assert_eq!(
+                    HyraxCommitment::batch_commit_compact(&[&compact], &generators)[0]
+                        .row_commitments,
+                    HyraxCommitment::batch_commit(&[dense.evals_ref()], &generators)[0]
+                        .row_commitments
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn padded_commit_matches_dense() {
+        let mut rng = ark_std::test_rng();
+        let generators = HyraxGenerators::new(PedersenGenerators::<G1Projective>::new(
+            1 << 8,
+            b"test-padded-field",
+        ));
+        for num_stored in [(1 << 9) + 1, 1 << 8, 0] {
+            let values: Vec<Fr> = (0..num_stored).map(|_| Fr::random(&mut rng)).collect();
+            let padded = PaddedPolynomial::new(11, values, Fr::from_u64(7).unwrap());
+            let dense = padded.to_dense();
+            assert_eq!(
+                HyraxCommitment::batch_commit_padded(&[&padded, &padded], &generators)[1]
+                    .row_commitments,
+                HyraxCommitment::batch_commit(&[dense.evals_ref()], &generators)[0].row_commitments
+            );
+        }
+    }
+
+    #[test]
+    fn sparse_commit_matches_dense() {
+        let mut rng = ark_std::test_rng();
+        let evals: Vec<Fr> = (0..1 << 10)
+            .map(|_| match rng.next_u64() % 4 {
+                0 => Fr::one(),
+                1 => Fr::random(&mut rng),
+                _ => Fr::zero(),
+            })
+            .collect();
+        let dense = DensePolynomial::new(evals);
+        let one_hot = DensePolynomial::new(
+            (0..1 << 10)
+                .map(|i| if i % 3 == 0 { Fr::one() } else { Fr::zero() })
+                .collect(),
+        );
+
+        let generators = HyraxGenerators::new(PedersenGenerators::<G1Projective>::new(
+            1 << 8,
+            b"test-sparse",
+        ));
+        for poly in [dense, one_hot] {
+            let sparse = SparsePolynomial::from_dense(&poly);
+            assert_eq!(
+                HyraxCommitment::batch_commit_sparse(&[&sparse], &generators)[0].row_commitments,
+                HyraxCommitment::batch_commit(&[poly.evals_ref()], &generators)[0].row_commitments
+            );
+        }
+    }
+
+    #[test]
+    fn fixed_base_commit_matches_variable_base() {
+        let mut rng = ark_std::test_rng();
+        let coeffs: Vec<u64> = (0..1 << 10).map(|_| rng.next_u64() % 1000).collect();
+        let compact = CompactPolynomial::<u32, Fr>::from_u64(&coeffs);
+        let dense =
+            DensePolynomial::<Fr>::new((0..1 << 10).map(|_| Fr::random(&mut rng)).collect());
+
+        let generators = HyraxGenerators::new(PedersenGenerators::<G1Projective>::new(
+            1 << 8,
+            b"test-fixed-base",
+        ));
+        let mut precomputed = generators.clone();
+        precomputed.precompute_fixed_base(1 << 30);
+        assert!(precomputed.has_fixed_base_table());
+
+        assert_eq!(
+            HyraxCommitment::commit(&dense, &precomputed).row_commitments,
+            HyraxCommitment::commit(&dense, &generators).row_commitments
+        );
+        assert_eq!(
+            HyraxCommitment::batch_commit_compact(&[&compact], &precomputed)[0].row_commitments,
+            HyraxCommitment::batch_commit_compact(&[&compact], &generators)[0].row_commitments
+        );
+
+        // A budget too small for any table falls back to the variable-base MSM.
+        let mut no_table = generators.clone();
+        no_table.precompute_fixed_base(0);
+        assert!(!no_table.has_fixed_base_table());
+    }
+
+    #[test]
+    fn generators_serialization_skips_fixed_base_table() {
+        let mut generators = HyraxGenerators::new(PedersenGenerators::<G1Projective>::new(
+            1 << 8,
+            b"test-serialization",
+        ));
+        let mut serialized = vec![];
+        generators.serialize_compressed(&mut serialized).unwrap();
+
+        generators.precompute_fixed_base(1 << 30);
+        let mut serialized_with_table = vec![];
+        generators
+            .serialize_compressed(&mut serialized_with_table)
+            .unwrap();
+        assert_eq!(serialized, serialized_with_table);
+
+        let deserialized =
+            HyraxGenerators::<G1Projective>::deserialize_compressed(&serialized[..]).unwrap();
+        assert!(!deserialized.has_fixed_base_table());
+        assert_eq!(deserialized.gens.generators, generators.gens.generators);
+    }
 }
@@ -0,0 +1,386 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use rayon::prelude::*;
+
+use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::poly::eq_poly::EqPolynomial;
+use crate::poly::field::JoltField;
+use crate::poly::multilinear_polynomial::CompactForm;
+use crate::utils;
+use crate::utils::math::Math;
+
+/// Unsigned integer types that the coefficients of a `CompactPolynomial` can be stored as.
+pub trait SmallInt:
+    Copy
+    + Default
+    + Debug
+    + PartialEq
+    + Sync
+    + Send
+    + CanonicalSerialize
+    + CanonicalDeserialize
+    + 'static
+{
+    /// Converts a `u64` to `Self`, panicking if it does not fit.
+    fn from_u64(value: u64) -> Self;
+    fn to_u64(self) -> u64;
+    /// Wraps a polynomial with coefficients of this type in a `CompactForm`.
+    fn compact_form<F: JoltField>(poly: CompactPolynomial<Self, F>) -> CompactForm<F>;
+    fn to_field<F: JoltField>(self) -> F {
+        match self.to_u64() {
+            0 => F::zero(),
+            1 => F::one(),
+            value => F::from_u64(value).unwrap(),
+        }
+    }
+}
+
+macro_rules! impl_small_int {
+    ($($t:ty => $variant:ident),*) => {
+        $(
+            impl SmallInt for $t {
+                fn from_u64(value: u64) -> Self {
+                    Self::try_from(value).unwrap_or_else(|_| {
+                        panic!("{value} does not fit in a {}", stringify!($t))
+                    })
+                }
+
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+
+                fn compact_form<F: JoltField>(poly: CompactPolynomial<Self, F>) -> CompactForm<F> {
+                    CompactForm::$variant(poly)
+                }
+            }
+        )*
+    };
+}
+
+impl_small_int!(u8 => U8, u16 => U16, u32 => U32, u64 => U64);
+
+/// How a `CompactPolynomial` fills in the evaluations past its stored coefficients, up to the
+/// next power of two. Trace polynomials are padded with no-op steps, whose values are known
+/// without materializing them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Padding<T: SmallInt> {
+    /// Every padded evaluation is the given value.
+    Constant(T),
+    /// The padded evaluations count up by one, starting from the given value.
+    Counter(T),
+}
+
+impl<T: SmallInt> Padding<T> {
+    /// Returns the `offset`-th padded evaluation.
+    pub fn value(&self, offset: usize) -> u64 {
+        match self {
+            Padding::Constant(value) => value.to_u64(),
+            Padding::Counter(start) => start.to_u64() + offset as u64,
+        }
+    }
+}
+
+impl<T: SmallInt> CanonicalSerialize for Padding<T> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        let (tag, value) = match self {
+            Padding::Constant(value) => (0u8, value),
+            Padding::Counter(start) => (1u8, start),
+        };
+        tag.serialize_with_mode(&mut writer, compress)?;
+        value.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        let value = match self {
+            Padding::Constant(value) | Padding::Counter(value) => value,
+        };
+        1 + value.serialized_size(compress)
+    }
+}
+
+impl<T: SmallInt> Valid for Padding<T> {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl<T: SmallInt> CanonicalDeserialize for Padding<T> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let tag = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+        let value = T::deserialize_with_mode(&mut reader, compress, validate)?;
+        match tag {
+            0 => Ok(Padding::Constant(value)),
+            1 => Ok(Padding::Counter(value)),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
+/// A multilinear polynomial whose evaluations over the Boolean hypercube are small unsigned
+/// integers. Storing them as `T` rather than as field elements saves up to 32x memory (8x for
+/// `u32`), and lets commitments use the small-scalar MSM paths. The polynomial is promoted to
+/// a `DensePolynomial` the first time one of its variables is bound.
+///
+/// Only the first `coeffs().len()` evaluations are stored; the rest are given by `padding`.
+/// The coefficients are never modified, so clones share them.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct CompactPolynomial<T: SmallInt, F: JoltField> {
+    num_vars: usize,
+    coeffs: Arc<Vec<T>>,
+    padding: Padding<T>,
+    _field: std::marker::PhantomData<F>,
+}
+
+impl<T: SmallInt, F: JoltField> CompactPolynomial<T, F> {
+    pub fn from_coeffs(coeffs: Vec<T>) -> Self {
+        assert!(
+            utils::is_power_of_two(coeffs.len()),
+            "Compact multi-linear polynomials must be made from a power of 2 (not {})",
+            coeffs.len()
+        );
+
+        Self {
+            num_vars: coeffs.len().log_2(),
+            coeffs: Arc::new(coeffs),
+            padding: Padding::Constant(T::default()),
+            _field: std::marker::PhantomData,
+        }
+    }
+
+    /// Creates a polynomial over the next power of two above `coeffs.len()` whose remaining
+    /// evaluations are given by `padding`.
+    pub fn padded(coeffs: Vec<T>, padding: Padding<T>) -> Self {
+        Self {
+            num_vars: coeffs.len().next_power_of_two().log_2(),
+            coeffs: Arc::new(coeffs),
+            padding,
+            _field: std::marker::PhantomData,
+        }
+    }
+
+    #[tracing::instrument(skip_all, name = "CompactPolynomial::from")]
+    pub fn from_u64(coeffs: &[u64]) -> Self {
+        Self::from_coeffs(coeffs.par_iter().map(|&c| T::from_u64(c)).collect())
+    }
+
+    #[tracing::instrument(skip_all, name = "CompactPolynomial::from")]
+    pub fn from_usize(coeffs: &[usize]) -> Self {
+        Self::from_coeffs(coeffs.par_iter().map(|&c| T::from_u64(c as u64)).collect())
+    }
+
+    pub fn get_num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// The number of evaluations, including the padding.
+    pub fn len(&self) -> usize {
+        1 << self.num_vars
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// The stored coefficients, i.e. the evaluations before the padding.
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    pub fn padding(&self) -> Padding<T> {
+        self.padding
+    }
+
+    /// Returns the evaluation at the `index`-th point of the Boolean hypercube.
+    pub fn get_u64(&self, index: usize) -> u64 {
+        match self.coeffs.get(index) {
+            Some(coeff) => coeff.to_u64(),
+            None => self.padding.value(index - self.coeffs.len()),
+        }
+    }
+
+    /// Returns the evaluation at the `index`-th point of the Boolean hypercube, as a field element.
+    pub fn get_coeff(&self, index: usize) -> F {
+        match self.coeffs.get(index) {
+            Some(coeff) => coeff.to_field(),
+            None => self.get_u64(index).to_field(),
+        }
+    }
+
+    /// Returns the evaluations over the Boolean hypercube as field elements.
+    #[tracing::instrument(skip_all, name = "CompactPolynomial::evals")]
+    pub fn evals(&self) -> Vec<F> {
+        (0..self.len())
+            .into_par_iter()
+            .map(|i| self.get_coeff(i))
+            .collect()
+    }
+
+    pub fn to_dense(&self) -> DensePolynomial<F> {
+        DensePolynomial::new(self.evals())
+    }
+
+    /// Concatenates the first `num_rows` evaluations of each of `polys`, which must all have
+    /// the same length, and returns them along with the value each polynomial takes past
+    /// them. Past `num_rows`, the polynomials must be padded with a constant.
+    #[tracing::instrument(skip_all, name = "CompactPolynomial::flatten")]
+    pub fn flatten<'a>(
+        polys: impl IntoIterator<Item = &'a Self>,
+        num_rows: usize,
+    ) -> (Vec<u64>, Vec<u64>) {
+        let polys: Vec<&Self> = polys.into_iter().collect();
+        let poly_len = polys[0].len();
+        assert!(num_rows <= poly_len);
+        polys.iter().for_each(|poly| {
+            assert_eq!(poly_len, poly.len());
+            assert!(
+                num_rows == poly_len
+                    || (poly.coeffs.len() <= num_rows
+                        && matches!(poly.padding, Padding::Constant(_)))
+            );
+        });
+
+        let flattened = polys
+            .par_iter()
+            .flat_map(|poly| (0..num_rows).into_par_iter().map(|i| poly.get_u64(i)))
+            .collect();
+        let padding = polys
+            .iter()
+            .map(|poly| poly.get_u64(poly_len - 1))
+            .collect();
+        (flattened, padding)
+    }
+
+    // returns Z(r) in O(n) time
+    pub fn evaluate(&self, r: &[F]) -> F {
+        // r must have a value for each variable
+        assert_eq!(r.len(), self.get_num_vars());
+        let chis = EqPolynomial::evals(r);
+        self.evaluate_at_chi(&chis)
+    }
+
+    pub fn evaluate_at_chi(&self, chis: &[F]) -> F {
+        assert_eq!(self.len(), chis.len());
+        let (stored_chis, padding_chis) = chis.split_at(self.coeffs.len());
+        let stored: F = self
+            .coeffs
+            .par_iter()
+            .zip(stored_chis.par_iter())
+            .map(|(coeff, chi)| mul_small(coeff.to_u64(), chi))
+            .sum();
+        let padding: F = match self.padding {
+            Padding::Constant(value) => {
+                mul_small(value.to_u64(), &padding_chis.par_iter().sum::<F>())
+            }
+            Padding::Counter(_) => padding_chis
+                .par_iter()
+                .enumerate()
+                .map(|(offset, chi)| mul_small(self.padding.value(offset), chi))
+                .sum(),
+        };
+        stored + padding
+    }
+
+    /// Binds the most significant variable to `r`, promoting the result to field elements.
+    /// The padding is read in place rather than materialized.
+    #[tracing::instrument(skip_all, name = "CompactPolynomial::new_poly_from_bound_poly_var_top")]
+    pub fn new_poly_from_bound_poly_var_top(&self, r: &F) -> DensePolynomial<F> {
+        let n = self.len() / 2;
+        let bound_evals: Vec<F> = (0..n)
+            .into_par_iter()
+            .map(|i| {
+                // low' = low + r * (high - low)
+                let (low, high) = (self.get_u64(i), self.get_u64(i + n));
+                let low_field: F = low.to_field();
+                match high as i128 - low as i128 {
+                    0 => low_field,
+                    1 => low_field + r,
+                    -1 => low_field - r,
+                    m => low_field + F::from_i128(m) * r,
+                }
+            })
+            .collect();
+        DensePolynomial::new(bound_evals)
+    }
+}
+
+fn mul_small<F: JoltField>(value: u64, chi: &F) -> F {
+    match value {
+        0 => F::zero(),
+        1 => *chi,
+        value => F::from_u64(value).unwrap() * chi,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_core::RngCore;
+
+    #[test]
+    fn matches_dense() {
+        let mut rng = test_rng();
+        let coeffs: Vec<u32> = (0..1 << 6).map(|_| rng.next_u32() % 4).collect();
+        let coeffs_u64: Vec<u64> = coeffs.iter().map(|&c| c as u64).collect();
+        let compact = CompactPolynomial::<u32, Fr>::from_coeffs(coeffs);
+        let dense = DensePolynomial::<Fr>::from_u64(&coeffs_u64);
+        assert_eq!(compact.to_dense(), dense);
+
+        let r: Vec<Fr> = (0..6).map(|_| Fr::random(&mut rng)).collect();
+        assert_eq!(compact.evaluate(&r), dense.evaluate(&r));
+
+        assert_eq!(
+            compact.new_poly_from_bound_poly_var_top(&r[0]),
+            dense.new_poly_from_bound_poly_var_top(&r[0])
+        );
+    }
+
+    #[test]
+    fn padded_matches_dense() {
+        let mut rng = test_rng();
+        for padding in [Padding::Constant(3u32), Padding::Counter(5u32)] {
+            let coeffs: Vec<u32> = (0..(1 << 5) + 1).map(|_| rng.next_u32() % 4).collect();
+            let compact = CompactPolynomial::<u32, Fr>::padded(coeffs.clone(), padding);
+            assert_eq!(compact.len(), 1 << 6);
+
+            let materialized: Vec<u64> = coeffs
+                .iter()
+                .map(|&c| c as u64)
+                .chain((0..(1 << 6) - coeffs.len()).map(|offset| padding.value(offset)))
+                .collect();
+            let dense = DensePolynomial::<Fr>::from_u64(&materialized);
+            assert_eq!(compact.to_dense(), dense);
+            assert_eq!(
+                CompactPolynomial::flatten([&compact], compact.len()).0,
+                materialized
+            );
+
+            let r: Vec<Fr> = (0..6).map(|_| Fr::random(&mut rng)).collect();
+            assert_eq!(compact.evaluate(&r), dense.evaluate(&r));
+            assert_eq!(
+                compact.new_poly_from_bound_poly_var_top(&r[0]),
+                dense.new_poly_from_bound_poly_var_top(&r[0])
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn coefficient_out_of_range() {
+        CompactPolynomial::<u8, Fr>::from_u64(&[1, 2, 3, 256]);
+    }
+}
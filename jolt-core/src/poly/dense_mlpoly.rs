@@ -5,6 +5,10 @@ use crate::utils::{self, compute_dotproduct, compute_dotproduct_low_optimized};
 
 use crate::poly::field::JoltField;
 use crate::utils::math::Math;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use core::ops::Index;
 use rayon::prelude::*;
 use std::ops::AddAssign;
@@ -16,6 +20,44 @@ pub struct DensePolynomial<F> {
     Z: Vec<F>, // evaluations of the polynomial in all the 2^num_vars Boolean inputs
 }
 
+impl<F: JoltField> CanonicalSerialize for DensePolynomial<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.num_vars.serialize_with_mode(&mut writer, compress)?;
+        self.len.serialize_with_mode(&mut writer, compress)?;
+        self.Z.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.num_vars.serialized_size(compress)
+            + self.len.serialized_size(compress)
+            + self.Z.serialized_size(compress)
+    }
+}
+
+impl<F: JoltField> Valid for DensePolynomial<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.Z.check()
+    }
+}
+
+impl<F: JoltField> CanonicalDeserialize for DensePolynomial<F> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            num_vars: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+            len: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+            Z: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
 impl<F: JoltField> DensePolynomial<F> {
     pub fn new(Z: Vec<F>) -> Self {
         assert!(
@@ -234,6 +276,10 @@ impl<F: JoltField> DensePolynomial<F> {
         self.Z.as_ref()
     }
 
+    pub fn evals_mut(&mut self) -> &mut [F] {
+        self.Z.as_mut()
+    }
+
     #[tracing::instrument(skip_all, name = "DensePoly::flatten")]
     pub fn flatten(polys: &[DensePolynomial<F>]) -> Vec<F> {
         let poly_len = polys[0].len();
@@ -1,4 +1,5 @@
 use crate::poly::field::JoltField;
+use crate::poly::unipoly::UniPoly;
 use rayon::prelude::*;
 
 use crate::utils::{math::Math, thread::unsafe_allocate_zero_vec};
@@ -86,3 +87,143 @@ impl<F: JoltField> EqPolynomial<F> {
         (L, R)
     }
 }
+
+/// The order in which a sumcheck binds the variables of its polynomials: `Top` binds the most
+/// significant variable first (as `DensePolynomial::bound_poly_var_top`), `Bottom` the least
+/// significant one (as `DensePolynomial::bound_poly_var_bot`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindingOrder {
+    Top,
+    Bottom,
+}
+
+/// eq(w, x) as seen by a sumcheck prover using Gruen's optimization
+/// (Section 3.2 of https://eprint.iacr.org/2024/108.pdf). In the round binding x_j,
+///     eq(w, (r_1, ..., r_{j-1}, x_j, x')) = scalar * eq(w_j, x_j) * eq(w', x')
+/// where `scalar` is the product of eq(w_i, r_i) over the variables bound so far, and w', x' are
+/// the variables not yet bound other than x_j. We only keep the table of eq(w', x'), which is
+/// half the size of the table of eq(w, x) and is never multiplied by the challenges.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitEqPolynomial<F> {
+    w: Vec<F>,
+    binding_order: BindingOrder,
+    num_bound: usize,
+    scalar: F,
+    /// Evaluations of eq(w', x') over the hypercube, indexed like the unbound variables of the
+    /// polynomials being summed over, excluding x_j.
+    evals: Vec<F>,
+}
+
+impl<F: JoltField> SplitEqPolynomial<F> {
+    #[tracing::instrument(skip_all, name = "SplitEqPolynomial::new")]
+    pub fn new(w: &[F], binding_order: BindingOrder) -> Self {
+        let evals = match (w.len(), binding_order) {
+            (0, _) => vec![F::one()],
+            (_, BindingOrder::Top) => EqPolynomial::evals(&w[1..]),
+            (_, BindingOrder::Bottom) => EqPolynomial::evals(&w[..w.len() - 1]),
+        };
+        Self {
+            w: w.to_vec(),
+            binding_order,
+            num_bound: 0,
+            scalar: F::one(),
+            evals,
+        }
+    }
+
+    /// The number of variables not yet bound, including x_j.
+    pub fn get_num_vars(&self) -> usize {
+        self.w.len() - self.num_bound
+    }
+
+    /// The evaluations of eq(w', x'), i.e. eq over the unbound variables other than x_j.
+    pub fn evals(&self) -> &[F] {
+        &self.evals
+    }
+
+    /// The product of eq(w_i, r_i) over the bound variables. Once every variable is bound, this
+    /// is eq(w, r).
+    pub fn current_scalar(&self) -> F {
+        self.scalar
+    }
+
+    fn current_w(&self) -> F {
+        match self.binding_order {
+            BindingOrder::Top => self.w[self.num_bound],
+            BindingOrder::Bottom => self.w[self.w.len() - 1 - self.num_bound],
+        }
+    }
+
+    /// Binds x_j to `r`. Since eq(w_k, 0) + eq(w_k, 1) = 1, the table for the next round is
+    /// obtained by summing over the next variable, which costs no multiplications.
+    #[tracing::instrument(skip_all, name = "SplitEqPolynomial::bind")]
+    pub fn bind(&mut self, r: &F) {
+        let w = self.current_w();
+        self.scalar *= w * r + (F::one() - w) * (F::one() - r);
+        self.num_bound += 1;
+
+        if self.evals.len() == 1 {
+            return;
+        }
+        let n = self.evals.len() / 2;
+        match self.binding_order {
+            BindingOrder::Top => {
+                let (low, high) = self.evals.split_at_mut(n);
+                low.par_iter_mut()
+                    .zip(high.par_iter())
+                    .for_each(|(low, high)| *low += *high);
+                self.evals.truncate(n);
+            }
+            BindingOrder::Bottom => {
+                self.evals = (0..n)
+                    .into_par_iter()
+                    .map(|i| self.evals[2 * i] + self.evals[2 * i + 1])
+                    .collect();
+            }
+        }
+    }
+
+    /// Whether `gruen_cubic` needs q(1) from the caller. It otherwise recovers q(1) from the
+    /// round claim by dividing by scalar * w_j, which is impossible when w_j is zero.
+    pub fn needs_q_1(&self) -> bool {
+        self.current_w().is_zero() && !self.scalar.is_zero()
+    }
+
+    /// Returns the round polynomial s(X) = scalar * eq(w_j, X) * q(X) of a cubic sumcheck over
+    /// eq(w, x) * g(x), where g is quadratic in x_j and q(X) = Σ eq(w', x') * g(X, x').
+    /// The caller provides q(0) and the leading coefficient of q; q(1) is then determined by
+    /// s(0) + s(1) = `previous_round_claim`, unless `needs_q_1` holds, in which case the caller
+    /// must provide it too.
+    pub fn gruen_cubic(
+        &self,
+        q_0: F,
+        q_infty: F,
+        q_1: Option<F>,
+        previous_round_claim: F,
+    ) -> UniPoly<F> {
+        if self.scalar.is_zero() {
+            return UniPoly::from_evals(&[F::zero(); 4]);
+        }
+
+        let w = self.current_w();
+        // eq(w_j, X) = (1 - w_j) + (2w_j - 1) * X
+        let eq_0 = F::one() - w;
+        let m_eq = w - eq_0;
+        let eq_2 = w + m_eq;
+        let eq_3 = eq_2 + m_eq;
+
+        let s_0 = self.scalar * eq_0 * q_0;
+        let s_1 = previous_round_claim - s_0;
+        let q_1 = if w.is_zero() {
+            q_1.expect("q(1) must be provided when w_j is zero")
+        } else {
+            s_1 / (self.scalar * w)
+        };
+
+        // q(X) = q(0) + (q(1) - q(0) - q_infty) * X + q_infty * X^2
+        let q_2 = q_1.double() - q_0 + q_infty.double();
+        let q_3 = q_1 + q_1.double() - q_0.double() + (q_infty + q_infty.double()).double();
+
+        UniPoly::from_evals(&[s_0, s_1, self.scalar * eq_2 * q_2, self.scalar * eq_3 * q_3])
+    }
+}
@@ -52,6 +52,33 @@ pub trait JoltField:
     fn double(&self) -> Self;
     fn square(&self) -> Self;
     fn from_bytes(bytes: &[u8]) -> Self;
+    /// Returns the element as a signed integer if either it or its negation fits in a `u64`.
+    fn to_i128(&self) -> Option<i128>;
+    fn from_i64(val: i64) -> Self {
+        Self::from_i128(val as i128)
+    }
+    fn from_i128(val: i128) -> Self {
+        match val {
+            0 => return Self::zero(),
+            1 => return Self::one(),
+            -1 => return -Self::one(),
+            _ => {}
+        }
+        let magnitude = val.unsigned_abs();
+        let low = Self::from_u64(magnitude as u64).unwrap();
+        let high = (magnitude >> 64) as u64;
+        let result = if high == 0 {
+            low
+        } else {
+            let two_pow_64 = Self::from_u64(u64::MAX).unwrap() + Self::one();
+            Self::from_u64(high).unwrap() * two_pow_64 + low
+        };
+        if val < 0 {
+            -result
+        } else {
+            result
+        }
+    }
     #[inline(always)]
     fn mul_0_optimized(self, other: Self) -> Self {
         if self.is_zero() || other.is_zero() {
@@ -123,4 +150,17 @@ impl JoltField for ark_bn254::Fr {
         assert_eq!(bytes.len(), Self::NUM_BYTES);
         ark_bn254::Fr::from_le_bytes_mod_order(bytes)
     }
+
+    fn to_i128(&self) -> Option<i128> {
+        let to_u64 = |x: &Self| {
+            let bigint = x.into_bigint();
+            bigint.0[1..]
+                .iter()
+                .all(|limb| *limb == 0)
+                .then_some(bigint.0[0])
+        };
+        to_u64(self)
+            .map(|val| val as i128)
+            .or_else(|| to_u64(&-*self).map(|val| -(val as i128)))
+    }
 }
@@ -1,7 +1,12 @@
 pub mod commitment;
+pub mod compact_polynomial;
 pub mod dense_mlpoly;
 pub mod eq_poly;
 pub mod field;
 pub mod identity_poly;
+pub mod multilinear_polynomial;
+pub mod opening_proof;
+pub mod padded_polynomial;
+pub mod sparse_polynomial;
 pub mod structured_poly;
 pub mod unipoly;
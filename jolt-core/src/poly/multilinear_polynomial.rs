@@ -0,0 +1,176 @@
+use crate::poly::compact_polynomial::{CompactPolynomial, SmallInt};
+use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::poly::eq_poly::EqPolynomial;
+use crate::poly::field::JoltField;
+use crate::poly::sparse_polynomial::SparsePolynomial;
+use crate::utils::mul_0_1_optimized;
+
+/// Read access to the evaluations of a multilinear polynomial over the Boolean hypercube,
+/// regardless of how they are stored. Allows code that only needs to read a polynomial
+/// (e.g. computing memory-checking leaves or opening claims) to handle both
+/// `DensePolynomial`, `CompactPolynomial` and `SparsePolynomial`.
+pub trait MultilinearPolynomial<F: JoltField>: Send + Sync {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get_num_vars(&self) -> usize;
+
+    /// Returns the evaluation at the `index`-th point of the Boolean hypercube.
+    fn get_coeff(&self, index: usize) -> F;
+
+    fn evaluate_at_chi(&self, chis: &[F]) -> F;
+
+    fn evaluate(&self, r: &[F]) -> F {
+        assert_eq!(r.len(), self.get_num_vars());
+        self.evaluate_at_chi(&EqPolynomial::evals(r))
+    }
+
+    /// Returns the polynomial as a `DensePolynomial`, i.e. with its evaluations stored as field elements.
+    fn to_dense(&self) -> DensePolynomial<F>;
+
+    /// Adds `coeff` times the evaluations at indices `offset..offset + chunk.len()` to `chunk`.
+    fn add_scaled_chunk(&self, coeff: &F, offset: usize, chunk: &mut [F]) {
+        for (i, value) in chunk.iter_mut().enumerate() {
+            *value += mul_0_1_optimized(&self.get_coeff(offset + i), coeff);
+        }
+    }
+
+    /// Returns a copy of the polynomial in a form smaller than a `DensePolynomial`, if it is
+    /// stored as one. Copies of a `CompactPolynomial` share its coefficients.
+    fn compact_form(&self) -> Option<CompactForm<F>> {
+        None
+    }
+}
+
+/// A `CompactPolynomial` of any coefficient type, or a `SparsePolynomial`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CompactForm<F: JoltField> {
+    U8(CompactPolynomial<u8, F>),
+    U16(CompactPolynomial<u16, F>),
+    U32(CompactPolynomial<u32, F>),
+    U64(CompactPolynomial<u64, F>),
+    Sparse(SparsePolynomial<F>),
+}
+
+impl<F: JoltField> CompactForm<F> {
+    fn as_polynomial(&self) -> &dyn MultilinearPolynomial<F> {
+        match self {
+            CompactForm::U8(poly) => poly,
+            CompactForm::U16(poly) => poly,
+            CompactForm::U32(poly) => poly,
+            CompactForm::U64(poly) => poly,
+            CompactForm::Sparse(poly) => poly,
+        }
+    }
+}
+
+impl<F: JoltField> MultilinearPolynomial<F> for CompactForm<F> {
+    fn len(&self) -> usize {
+        self.as_polynomial().len()
+    }
+
+    fn get_num_vars(&self) -> usize {
+        self.as_polynomial().get_num_vars()
+    }
+
+    fn get_coeff(&self, index: usize) -> F {
+        self.as_polynomial().get_coeff(index)
+    }
+
+    fn evaluate_at_chi(&self, chis: &[F]) -> F {
+        self.as_polynomial().evaluate_at_chi(chis)
+    }
+
+    fn to_dense(&self) -> DensePolynomial<F> {
+        self.as_polynomial().to_dense()
+    }
+
+    fn add_scaled_chunk(&self, coeff: &F, offset: usize, chunk: &mut [F]) {
+        self.as_polynomial().add_scaled_chunk(coeff, offset, chunk)
+    }
+
+    fn compact_form(&self) -> Option<CompactForm<F>> {
+        Some(self.clone())
+    }
+}
+
+impl<F: JoltField> MultilinearPolynomial<F> for DensePolynomial<F> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn get_num_vars(&self) -> usize {
+        self.get_num_vars()
+    }
+
+    fn get_coeff(&self, index: usize) -> F {
+        self[index]
+    }
+
+    fn evaluate_at_chi(&self, chis: &[F]) -> F {
+        self.evaluate_at_chi(chis)
+    }
+
+    fn to_dense(&self) -> DensePolynomial<F> {
+        self.clone()
+    }
+}
+
+impl<T: SmallInt, F: JoltField> MultilinearPolynomial<F> for CompactPolynomial<T, F> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn get_num_vars(&self) -> usize {
+        self.get_num_vars()
+    }
+
+    fn get_coeff(&self, index: usize) -> F {
+        self.get_coeff(index)
+    }
+
+    fn evaluate_at_chi(&self, chis: &[F]) -> F {
+        self.evaluate_at_chi(chis)
+    }
+
+    fn to_dense(&self) -> DensePolynomial<F> {
+        self.to_dense()
+    }
+
+    fn compact_form(&self) -> Option<CompactForm<F>> {
+        Some(T::compact_form(self.clone()))
+    }
+}
+
+impl<F: JoltField> MultilinearPolynomial<F> for SparsePolynomial<F> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn get_num_vars(&self) -> usize {
+        self.get_num_vars()
+    }
+
+    fn get_coeff(&self, index: usize) -> F {
+        self.get_coeff(index)
+    }
+
+    fn evaluate_at_chi(&self, chis: &[F]) -> F {
+        self.evaluate_at_chi(chis)
+    }
+
+    fn to_dense(&self) -> DensePolynomial<F> {
+        self.to_dense()
+    }
+
+    fn add_scaled_chunk(&self, coeff: &F, offset: usize, chunk: &mut [F]) {
+        self.add_scaled_chunk(coeff, offset, chunk)
+    }
+
+    fn compact_form(&self) -> Option<CompactForm<F>> {
+        Some(CompactForm::Sparse(self.clone()))
+    }
+}
@@ -0,0 +1,815 @@
+//! Rather than proving each batch of polynomial openings as soon as it arises, the subprotocols
+//! of Jolt register their (polynomials, opening point, claimed evaluations) with an opening
+//! accumulator. Batches opened at a point that was already registered are folded into the
+//! existing opening, so the accumulator holds one random linear combination per distinct point.
+//! At the end of the proof, all of the accumulated openings are reduced to openings at a single
+//! point with one sumcheck, and the PCS proves all of those with one batched opening proof:
+//! polynomials with fewer variables than the largest one are opened as their zero-padded
+//! extensions (see `CommitmentScheme::batch_prove`).
+
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::poly::eq_poly::EqPolynomial;
+use crate::poly::field::JoltField;
+use crate::poly::multilinear_polynomial::{CompactForm, MultilinearPolynomial};
+use crate::poly::unipoly::{CompressedUniPoly, UniPoly};
+use crate::subprotocols::sumcheck::SumcheckInstanceProof;
+use crate::utils::compute_dotproduct;
+use crate::utils::errors::ProofVerifyError;
+use crate::utils::math::Math;
+use crate::utils::memory_budget::{MemoryBudget, SpillableVec};
+use crate::utils::mul_0_1_optimized;
+use crate::utils::thread::{drop_in_background_thread, unsafe_allocate_zero_vec};
+use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
+use ark_serialize::*;
+use rayon::prelude::*;
+use std::io;
+
+/// The number of evaluations of a `ProverOpening` materialized at once when it is first bound.
+const MATERIALIZED_CHUNK_SIZE: usize = 1 << 12;
+
+/// The polynomials opened at the same point, represented by a random linear combination of
+/// the polynomials and the corresponding linear combination of their claimed evaluations.
+/// Polynomials that have a compact form are kept in it (a `CompactPolynomial` shares its
+/// coefficients with the committed polynomial), so only the opened `DensePolynomial`s are
+/// combined into a new dense polynomial, which can be spilled under a memory budget.
+pub struct ProverOpening<F: JoltField> {
+    /// The evaluations of the linear combination of the opened dense polynomials, if there
+    /// are any.
+    dense: Option<SpillableVec<F>>,
+    /// The rest of the opened polynomials, with their coefficients in the linear combination.
+    compact: Vec<(F, CompactForm<F>)>,
+    opening_point: Vec<F>,
+    claim: F,
+}
+
+/// The verifier's counterpart to `ProverOpening`: the random linear combination of the
+/// commitments to the opened polynomials, along with the combined claim.
+pub struct VerifierOpening<F: JoltField, C: CommitmentScheme<Field = F>> {
+    commitment: C::Commitment,
+    opening_point: Vec<F>,
+    claim: F,
+}
+
+/// Accumulates the openings proven over the course of a Jolt proof.
+pub struct ProverOpeningAccumulator<F: JoltField> {
+    openings: Vec<ProverOpening<F>>,
+}
+
+/// Accumulates the openings verified over the course of a Jolt proof.
+pub struct VerifierOpeningAccumulator<F: JoltField, C: CommitmentScheme<Field = F>> {
+    openings: Vec<VerifierOpening<F, C>>,
+}
+
+/// Proves all of the openings accumulated by a `ProverOpeningAccumulator`.
+#[derive(CanonicalSerialize, CanonicalDeserialize)]
+pub struct ReducedOpeningProof<F: JoltField, C: CommitmentScheme<Field = F>> {
+    sumcheck_proof: SumcheckInstanceProof<F>,
+    /// The evaluations of the accumulated polynomials at (a suffix of) the sumcheck point
+    sumcheck_claims: Vec<F>,
+    joint_opening_proof: C::BatchedProof,
+}
+
+/// Draws the coefficients used to take a random linear combination of openings at the same
+/// point, and returns them along with the combined claim. When the openings are folded into an
+/// existing combination (which keeps coefficient 1), the powers of the challenge start at 1.
+fn rlc_coefficients<F: JoltField>(
+    claims: &[F],
+    fold: bool,
+    transcript: &mut ProofTranscript,
+) -> (Vec<F>, F) {
+    transcript.append_scalars(b"opening claims", claims);
+    let rho: F = transcript.challenge_scalar(b"opening_rlc");
+    let mut coeffs = Vec::with_capacity(claims.len());
+    let mut coeff = if fold { rho } else { F::one() };
+    for _ in 0..claims.len() {
+        coeffs.push(coeff);
+        coeff *= rho;
+    }
+    let claim = coeffs
+        .iter()
+        .zip(claims.iter())
+        .map(|(coeff, claim)| *coeff * claim)
+        .sum();
+    (coeffs, claim)
+}
+
+/// The claims that the zero-padded extensions of polynomials over `num_vars` variables each
+/// are opened at by the joint opening proof, given their evaluations `sumcheck_claims` at the
+/// corresponding suffix of `r`. Padding a polynomial with zeros multiplies its evaluation by
+/// eq(r', 0) over the extra (high-order) variables r'.
+fn padded_claims<F: JoltField>(num_vars: &[usize], sumcheck_claims: &[F], r: &[F]) -> Vec<F> {
+    num_vars
+        .iter()
+        .zip(sumcheck_claims.iter())
+        .map(|(num_vars, claim)| {
+            r[..r.len() - num_vars]
+                .iter()
+                .fold(*claim, |padded_claim, r_j| padded_claim * (F::one() - r_j))
+        })
+        .collect()
+}
+
+impl<F: JoltField> ProverOpening<F> {
+    /// Computes the evaluations of the sumcheck summand eq * self at 0 and 2 in the most
+    /// significant variable, reading `self` a chunk at a time.
+    fn unbound_round_evals(&self, eq_poly: &DensePolynomial<F>) -> (F, F) {
+        let len = self.len() / 2;
+        let chunk_size = std::cmp::min(len, MATERIALIZED_CHUNK_SIZE);
+        (0..len / chunk_size)
+            .into_par_iter()
+            .map(|chunk_index| {
+                let start = chunk_index * chunk_size;
+                let mut low = vec![F::zero(); chunk_size];
+                let mut high = vec![F::zero(); chunk_size];
+                self.add_scaled_chunk(&F::one(), start, &mut low);
+                self.add_scaled_chunk(&F::one(), len + start, &mut high);
+                low.iter()
+                    .zip(high.iter())
+                    .enumerate()
+                    .map(|(i, (low, high))| {
+                        let (eq_low, eq_high) = (eq_poly[start + i], eq_poly[len + start + i]);
+                        (
+                            mul_0_1_optimized(low, &eq_low),
+                            (*high + high - low) * (eq_high + eq_high - eq_low),
+                        )
+                    })
+                    .fold((F::zero(), F::zero()), |sum, evals| {
+                        (sum.0 + evals.0, sum.1 + evals.1)
+                    })
+            })
+            .reduce(
+                || (F::zero(), F::zero()),
+                |sum, evals| (sum.0 + evals.0, sum.1 + evals.1),
+            )
+    }
+
+    /// Binds the most significant variable to `r`, reading `self` a chunk at a time.
+    fn new_poly_from_bound_poly_var_top(&self, r: &F) -> DensePolynomial<F> {
+        let len = self.len() / 2;
+        let chunk_size = std::cmp::min(len, MATERIALIZED_CHUNK_SIZE);
+        let mut bound: Vec<F> = unsafe_allocate_zero_vec(len);
+        bound
+            .par_chunks_mut(chunk_size)
+            .enumerate()
+            .for_each(|(chunk_index, chunk)| {
+                let start = chunk_index * chunk_size;
+                let mut high = vec![F::zero(); chunk.len()];
+                self.add_scaled_chunk(&F::one(), start, chunk);
+                self.add_scaled_chunk(&F::one(), len + start, &mut high);
+                for (low, high) in chunk.iter_mut().zip(high.iter()) {
+                    *low += *r * (*high - *low);
+                }
+            });
+        DensePolynomial::new(bound)
+    }
+}
+
+impl<F: JoltField> MultilinearPolynomial<F> for ProverOpening<F> {
+    fn len(&self) -> usize {
+        self.opening_point.len().pow2()
+    }
+
+    fn get_num_vars(&self) -> usize {
+        self.opening_point.len()
+    }
+
+    fn get_coeff(&self, index: usize) -> F {
+        let dense = self.dense.as_ref().map_or(F::zero(), |dense| dense[index]);
+        self.compact.iter().fold(dense, |sum, (coeff, poly)| {
+            sum + *coeff * poly.get_coeff(index)
+        })
+    }
+
+    fn evaluate_at_chi(&self, chis: &[F]) -> F {
+        let dense = self
+            .dense
+            .as_ref()
+            .map_or(F::zero(), |dense| compute_dotproduct(dense, chis));
+        self.compact.iter().fold(dense, |sum, (coeff, poly)| {
+            sum + *coeff * poly.evaluate_at_chi(chis)
+        })
+    }
+
+    fn to_dense(&self) -> DensePolynomial<F> {
+        let mut evals: Vec<F> = unsafe_allocate_zero_vec(self.len());
+        self.add_scaled_chunk(&F::one(), 0, &mut evals);
+        DensePolynomial::new(evals)
+    }
+
+    fn add_scaled_chunk(&self, coeff: &F, offset: usize, chunk: &mut [F]) {
+        if let Some(dense) = &self.dense {
+            for (value, dense_eval) in chunk.iter_mut().zip(dense[offset..].iter()) {
+                *value += mul_0_1_optimized(dense_eval, coeff);
+            }
+        }
+        for (poly_coeff, poly) in self.compact.iter() {
+            poly.add_scaled_chunk(&(*poly_coeff * coeff), offset, chunk);
+        }
+    }
+}
+
+impl<F: JoltField> Default for ProverOpeningAccumulator<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: JoltField> ProverOpeningAccumulator<F> {
+    pub fn new() -> Self {
+        Self { openings: vec![] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.openings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.openings.is_empty()
+    }
+
+    /// Registers the openings of `polynomials` at `opening_point`, whose evaluations are
+    /// claimed to be `claims`. The polynomials must all have the same size. If polynomials
+    /// were already opened at `opening_point`, the new ones are folded into that opening
+    /// instead of being kept apart.
+    #[tracing::instrument(skip_all, name = "ProverOpeningAccumulator::append")]
+    pub fn append<P: MultilinearPolynomial<F> + ?Sized>(
+        &mut self,
+        polynomials: &[&P],
+        opening_point: &[F],
+        claims: &[F],
+        transcript: &mut ProofTranscript,
+    ) {
+        assert_eq!(polynomials.len(), claims.len());
+        let poly_len = polynomials[0].len();
+        for poly in polynomials.iter() {
+            assert_eq!(poly.len(), poly_len);
+        }
+        assert_eq!(poly_len.log_2(), opening_point.len());
+
+        let existing = self
+            .openings
+            .iter()
+            .position(|opening| opening.opening_point == opening_point);
+        let (coeffs, claim) = rlc_coefficients(claims, existing.is_some(), transcript);
+
+        let opening = match existing {
+            Some(index) => &mut self.openings[index],
+            None => {
+                self.openings.push(ProverOpening {
+                    dense: None,
+                    compact: vec![],
+                    opening_point: opening_point.to_vec(),
+                    claim: F::zero(),
+                });
+                self.openings.last_mut().unwrap()
+            }
+        };
+        opening.claim += claim;
+
+        let mut dense_polys: Vec<(F, &P)> = vec![];
+        for (coeff, poly) in coeffs.into_iter().zip(polynomials.iter()) {
+            match poly.compact_form() {
+                Some(compact) => opening.compact.push((coeff, compact)),
+                None => dense_polys.push((coeff, *poly)),
+            }
+        }
+        if dense_polys.is_empty() {
+            return;
+        }
+
+        // Polynomials folded into a spilled combination are added to a copy of it on the heap
+        let mut rlc_evals = match opening.dense.take() {
+            Some(dense) => dense.into_vec(),
+            None => unsafe_allocate_zero_vec(poly_len),
+        };
+        let num_chunks = rayon::current_num_threads().next_power_of_two();
+        let chunk_size = std::cmp::max(1, poly_len / num_chunks);
+        rlc_evals
+            .par_chunks_mut(chunk_size)
+            .enumerate()
+            .for_each(|(chunk_index, chunk)| {
+                let offset = chunk_index * chunk_size;
+                for (coeff, poly) in dense_polys.iter() {
+                    poly.add_scaled_chunk(coeff, offset, chunk);
+                }
+            });
+        opening.dense = Some(SpillableVec::Resident(rlc_evals));
+    }
+
+    /// Spills the linear combinations of the dense polynomials opened so far to scratch files
+    /// if keeping them resident would exceed `budget`. `reduce_and_prove` reads them in place.
+    #[tracing::instrument(skip_all, name = "ProverOpeningAccumulator::spill")]
+    pub fn spill(&mut self, budget: Option<&MemoryBudget>) -> io::Result<()> {
+        let mut resident: Vec<&mut Option<SpillableVec<F>>> = self
+            .openings
+            .iter_mut()
+            .map(|opening| &mut opening.dense)
+            .filter(|dense| matches!(dense, Some(SpillableVec::Resident(_))))
+            .collect();
+        let evals: Vec<Vec<F>> = resident
+            .iter_mut()
+            .map(|dense| dense.take().unwrap().into_vec())
+            .collect();
+        for (dense, evals) in resident
+            .into_iter()
+            .zip(SpillableVec::new_batch(evals, budget)?)
+        {
+            *dense = Some(evals);
+        }
+        Ok(())
+    }
+
+    /// Reduces all of the accumulated openings to openings at a single point via sumcheck, and
+    /// proves the latter with one batched opening proof.
+    ///
+    /// The sumcheck is over
+    ///     Σ_i coeff_i * Σ_x eq(r_i, x) * P_i(x)
+    /// where polynomials with fewer variables than the largest one are treated as constant in
+    /// the extra (high-order) variables, so each P_i ends up evaluated at a suffix of the
+    /// sumcheck point.
+    #[tracing::instrument(skip_all, name = "ProverOpeningAccumulator::reduce_and_prove")]
+    pub fn reduce_and_prove<C: CommitmentScheme<Field = F>>(
+        self,
+        setup: &C::Setup,
+        transcript: &mut ProofTranscript,
+    ) -> ReducedOpeningProof<F, C> {
+        transcript.append_protocol_name(Self::protocol_name());
+
+        let coeffs: Vec<F> =
+            transcript.challenge_vector(b"rand_coeffs_opening_reduction", self.openings.len());
+        let num_vars: Vec<usize> = self
+            .openings
+            .iter()
+            .map(|opening| opening.opening_point.len())
+            .collect();
+        let num_rounds = num_vars.iter().copied().max().unwrap_or(0);
+
+        let mut eq_polys: Vec<DensePolynomial<F>> = self
+            .openings
+            .par_iter()
+            .map(|opening| DensePolynomial::new(EqPolynomial::evals(&opening.opening_point)))
+            .collect();
+        // An opening with fewer variables contributes its claim once for each assignment
+        // of the extra variables
+        let mut previous_claim: F = self
+            .openings
+            .iter()
+            .zip(num_vars.iter())
+            .zip(coeffs.iter())
+            .map(|((opening, num_vars), coeff)| {
+                *coeff * opening.claim * F::from_u64((num_rounds - num_vars).pow2() as u64).unwrap()
+            })
+            .sum();
+
+        // The openings bound to the sumcheck challenges so far. The openings themselves are
+        // needed for the final opening proof, so they are left untouched, and each one is only
+        // materialized as a dense polynomial once its first variable is bound (at half size).
+        let mut bound_polys: Vec<Option<DensePolynomial<F>>> =
+            (0..self.openings.len()).map(|_| None).collect();
+
+        let mut r: Vec<F> = Vec::with_capacity(num_rounds);
+        let mut compressed_polys: Vec<CompressedUniPoly<F>> = Vec::with_capacity(num_rounds);
+        for round in 0..num_rounds {
+            let (eval_point_0, eval_point_2) = self
+                .openings
+                .par_iter()
+                .zip(bound_polys.par_iter())
+                .zip(eq_polys.par_iter())
+                .zip(num_vars.par_iter())
+                .zip(coeffs.par_iter())
+                .map(|((((opening, bound_poly), eq_poly), num_vars), coeff)| {
+                    if round < num_rounds - num_vars {
+                        // The summand doesn't depend on this variable yet
+                        let num_extra_vars = num_rounds - num_vars - round - 1;
+                        let eval = *coeff
+                            * opening.claim
+                            * F::from_u64(num_extra_vars.pow2() as u64).unwrap();
+                        return (eval, eval);
+                    }
+                    let poly = match bound_poly {
+                        Some(poly) => poly,
+                        None => {
+                            let (eval_0, eval_2) = opening.unbound_round_evals(eq_poly);
+                            return (*coeff * eval_0, *coeff * eval_2);
+                        }
+                    };
+                    let len = poly.len() / 2;
+                    let (eval_0, eval_2) = (0..len)
+                        .into_par_iter()
+                        .map(|i| {
+                            let poly_bound_point = poly[len + i] + poly[len + i] - poly[i];
+                            let eq_bound_point = eq_poly[len + i] + eq_poly[len + i] - eq_poly[i];
+                            (
+                                mul_0_1_optimized(&poly[i], &eq_poly[i]),
+                                poly_bound_point * eq_bound_point,
+                            )
+                        })
+                        .reduce(
+                            || (F::zero(), F::zero()),
+                            |sum, evals| (sum.0 + evals.0, sum.1 + evals.1),
+                        );
+                    (*coeff * eval_0, *coeff * eval_2)
+                })
+                .reduce(
+                    || (F::zero(), F::zero()),
+                    |sum, evals| (sum.0 + evals.0, sum.1 + evals.1),
+                );
+
+            let round_poly =
+                UniPoly::from_evals(&[eval_point_0, previous_claim - eval_point_0, eval_point_2]);
+            round_poly.append_to_transcript(b"poly", transcript);
+            let r_j: F = transcript.challenge_scalar(b"challenge_nextround");
+            r.push(r_j);
+
+            self.openings
+                .par_iter()
+                .zip(bound_polys.par_iter_mut())
+                .zip(eq_polys.par_iter_mut())
+                .zip(num_vars.par_iter())
+                .filter(|(_, num_vars)| round >= num_rounds - **num_vars)
+                .for_each(|(((opening, bound_poly), eq_poly), _)| {
+                    rayon::join(
+                        || match bound_poly {
+                            Some(poly) => poly.bound_poly_var_top(&r_j),
+                            None => {
+                                *bound_poly = Some(opening.new_poly_from_bound_poly_var_top(&r_j))
+                            }
+                        },
+                        || eq_poly.bound_poly_var_top(&r_j),
+                    );
+                });
+
+            previous_claim = round_poly.evaluate(&r_j);
+            compressed_polys.push(round_poly.compress());
+        }
+
+        let sumcheck_claims: Vec<F> = self
+            .openings
+            .iter()
+            .zip(bound_polys.iter())
+            .map(|(opening, bound_poly)| match bound_poly {
+                Some(poly) => poly[0],
+                None => opening.get_coeff(0),
+            })
+            .collect();
+        drop_in_background_thread(bound_polys);
+        drop_in_background_thread(eq_polys);
+
+        let polys: Vec<&dyn MultilinearPolynomial<F>> = self
+            .openings
+            .iter()
+            .map(|opening| opening as &dyn MultilinearPolynomial<F>)
+            .collect();
+        let joint_opening_proof = C::batch_prove(
+            setup,
+            &polys,
+            &r,
+            &padded_claims(&num_vars, &sumcheck_claims, &r),
+            transcript,
+        );
+
+        ReducedOpeningProof {
+            sumcheck_proof: SumcheckInstanceProof::new(compressed_polys),
+            sumcheck_claims,
+            joint_opening_proof,
+        }
+    }
+
+    fn protocol_name() -> &'static [u8] {
+        b"Jolt opening reduction"
+    }
+}
+
+impl<F: JoltField, C: CommitmentScheme<Field = F>> Default for VerifierOpeningAccumulator<F, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: JoltField, C: CommitmentScheme<Field = F>> VerifierOpeningAccumulator<F, C> {
+    pub fn new() -> Self {
+        Self { openings: vec![] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.openings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.openings.is_empty()
+    }
+
+    /// Registers the openings of the polynomials committed to in `commitments` at
+    /// `opening_point`, whose evaluations are claimed to be `claims`. Must be called at the
+    /// same point in the transcript as the corresponding `ProverOpeningAccumulator::append`.
+    pub fn append(
+        &mut self,
+        commitments: &[&C::Commitment],
+        opening_point: &[F],
+        claims: &[F],
+        transcript: &mut ProofTranscript,
+    ) -> Result<(), ProofVerifyError> {
+        if commitments.len() != claims.len() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                commitments.len(),
+                claims.len(),
+            ));
+        }
+
+        let existing = self
+            .openings
+            .iter()
+            .position(|opening| opening.opening_point == opening_point);
+        let (coeffs, claim) = rlc_coefficients(claims, existing.is_some(), transcript);
+
+        match existing {
+            Some(index) => {
+                let opening = &mut self.openings[index];
+                let mut all_commitments = vec![&opening.commitment];
+                all_commitments.extend_from_slice(commitments);
+                let mut all_coeffs = vec![F::one()];
+                all_coeffs.extend(coeffs);
+                opening.commitment = C::combine_commitments(&all_commitments, &all_coeffs);
+                opening.claim += claim;
+            }
+            None => {
+                let commitment = C::combine_commitments(commitments, &coeffs);
+                self.openings.push(VerifierOpening {
+                    commitment,
+                    opening_point: opening_point.to_vec(),
+                    claim,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies a `ReducedOpeningProof` for all of the accumulated openings.
+    pub fn reduce_and_verify(
+        self,
+        setup: &C::Setup,
+        proof: &ReducedOpeningProof<F, C>,
+        transcript: &mut ProofTranscript,
+    ) -> Result<(), ProofVerifyError> {
+        transcript.append_protocol_name(ProverOpeningAccumulator::<F>::protocol_name());
+
+        let num_openings = self.openings.len();
+        if proof.sumcheck_claims.len() != num_openings {
+            return Err(ProofVerifyError::InvalidInputLength(
+                num_openings,
+                proof.sumcheck_claims.len(),
+            ));
+        }
+
+        let coeffs: Vec<F> =
+            transcript.challenge_vector(b"rand_coeffs_opening_reduction", num_openings);
+        let num_vars: Vec<usize> = self
+            .openings
+            .iter()
+            .map(|opening| opening.opening_point.len())
+            .collect();
+        let num_rounds = num_vars.iter().copied().max().unwrap_or(0);
+        if proof.sumcheck_proof.num_rounds() != num_rounds {
+            return Err(ProofVerifyError::InvalidInputLength(
+                num_rounds,
+                proof.sumcheck_proof.num_rounds(),
+            ));
+        }
+
+        let claim: F = self
+            .openings
+            .iter()
+            .zip(num_vars.iter())
+            .zip(coeffs.iter())
+            .map(|((opening, num_vars), coeff)| {
+                *coeff * opening.claim * F::from_u64((num_rounds - num_vars).pow2() as u64).unwrap()
+            })
+            .sum();
+        let (sumcheck_claim, r) = proof
+            .sumcheck_proof
+            .verify(claim, num_rounds, 2, transcript)?;
+
+        let expected_sumcheck_claim: F = self
+            .openings
+            .iter()
+            .zip(num_vars.iter())
+            .zip(coeffs.iter())
+            .zip(proof.sumcheck_claims.iter())
+            .map(|(((opening, num_vars), coeff), sumcheck_claim)| {
+                let eq_eval = EqPolynomial::new(opening.opening_point.clone())
+                    .evaluate(&r[num_rounds - num_vars..]);
+                *coeff * eq_eval * sumcheck_claim
+            })
+            .sum();
+        if expected_sumcheck_claim != sumcheck_claim {
+            return Err(ProofVerifyError::SumcheckClaimError);
+        }
+
+        let commitments: Vec<&C::Commitment> = self
+            .openings
+            .iter()
+            .map(|opening| &opening.commitment)
+            .collect();
+        C::batch_verify(
+            &proof.joint_opening_proof,
+            setup,
+            &r,
+            &padded_claims(&num_vars, &proof.sumcheck_claims, &r),
+            &commitments,
+            transcript,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape};
+    use crate::poly::commitment::hyrax::HyraxScheme;
+    use crate::poly::compact_polynomial::CompactPolynomial;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_std::{rand::RngCore, test_rng};
+
+    type Pcs = HyraxScheme<G1Projective>;
+
+    /// Opens batches of polynomials of various sizes and batch types at random points. The
+    /// second batch is opened at the same point as the first, so it's folded into it; if
+    /// `wrong_folded_claim` is set, one of its claims is wrong. If `spill` is set, the
+    /// accumulated openings are spilled after each batch.
+    fn prove_and_verify(
+        tamper: bool,
+        wrong_folded_claim: bool,
+        spill: bool,
+    ) -> Result<(), ProofVerifyError> {
+        let mut rng = test_rng();
+        let batches = [
+            (8, 3, BatchType::Big),
+            (8, 2, BatchType::Big),
+            (4, 2, BatchType::Small),
+            (8, 1, BatchType::Small),
+            (8, 2, BatchType::Big),
+            (6, 4, BatchType::Small),
+        ];
+        let setup = Pcs::setup(
+            &batches
+                .iter()
+                .map(|(num_vars, _, batch_type)| {
+                    CommitShape::new(num_vars.pow2(), batch_type.clone())
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        let mut prover_accumulator = ProverOpeningAccumulator::<Fr>::new();
+        let mut verifier_accumulator = VerifierOpeningAccumulator::<Fr, Pcs>::new();
+        let mut prover_transcript = ProofTranscript::new(b"test_transcript");
+        let mut verifier_transcript = ProofTranscript::new(b"test_transcript");
+        let scratch_dir =
+            std::env::temp_dir().join(format!("jolt-opening-reduction-{}", std::process::id()));
+        let budget = MemoryBudget::new(0).with_scratch_dir(scratch_dir.clone());
+        if spill {
+            std::fs::create_dir_all(&scratch_dir).unwrap();
+        }
+        let mut previous_point: Vec<Fr> = vec![];
+        for (batch_index, (num_vars, batch_size, batch_type)) in batches.into_iter().enumerate() {
+            let polys: Vec<DensePolynomial<Fr>> = (0..batch_size)
+                .map(|_| {
+                    DensePolynomial::new(
+                        (0..num_vars.pow2()).map(|_| Fr::random(&mut rng)).collect(),
+                    )
+                })
+                .collect();
+            let commitments = Pcs::batch_commit_polys(&polys, &setup, batch_type);
+            let opening_point: Vec<Fr> = if batch_index == 1 {
+                previous_point.clone()
+            } else {
+                (0..num_vars).map(|_| Fr::random(&mut rng)).collect()
+            };
+            let mut claims: Vec<Fr> = polys
+                .iter()
+                .map(|poly| poly.evaluate(&opening_point))
+                .collect();
+            if batch_index == 1 && wrong_folded_claim {
+                claims[1] += Fr::from_u64(1).unwrap();
+            }
+            previous_point = opening_point.clone();
+
+            prover_accumulator.append(
+                &polys.iter().collect::<Vec<_>>(),
+                &opening_point,
+                &claims,
+                &mut prover_transcript,
+            );
+            verifier_accumulator.append(
+                &commitments.iter().collect::<Vec<_>>(),
+                &opening_point,
+                &claims,
+                &mut verifier_transcript,
+            )?;
+            if spill {
+                prover_accumulator.spill(Some(&budget)).unwrap();
+                assert!(prover_accumulator.openings.iter().all(|opening| opening
+                    .dense
+                    .as_ref()
+                    .unwrap()
+                    .is_spilled()));
+            }
+        }
+
+        assert_eq!(prover_accumulator.len(), 5);
+        assert_eq!(verifier_accumulator.len(), 5);
+
+        let mut proof = prover_accumulator.reduce_and_prove::<Pcs>(&setup, &mut prover_transcript);
+        if spill {
+            // The scratch files are deleted along with the accumulator
+            std::fs::remove_dir(&scratch_dir).unwrap();
+        }
+        if tamper {
+            proof.sumcheck_claims[0] += Fr::from_u64(1).unwrap();
+        }
+        verifier_accumulator.reduce_and_verify(&setup, &proof, &mut verifier_transcript)
+    }
+
+    #[test]
+    fn opening_reduction() {
+        assert!(prove_and_verify(false, false, false).is_ok());
+    }
+
+    #[test]
+    fn spilled_opening_reduction() {
+        assert!(prove_and_verify(false, false, true).is_ok());
+    }
+
+    #[test]
+    fn opening_reduction_wrong_claim() {
+        assert!(prove_and_verify(true, false, false).is_err());
+    }
+
+    #[test]
+    fn opening_reduction_wrong_folded_claim() {
+        assert!(prove_and_verify(false, true, false).is_err());
+    }
+
+    #[test]
+    fn compact_openings() {
+        let mut rng = test_rng();
+        let setup = Pcs::setup(&[CommitShape::new(1 << 6, BatchType::Big)]);
+        let compact: Vec<CompactPolynomial<u32, Fr>> = (0..3)
+            .map(|_| CompactPolynomial::from_coeffs((0..1 << 6).map(|_| rng.next_u32()).collect()))
+            .collect();
+        let dense = DensePolynomial::new((0..1 << 6).map(|_| Fr::random(&mut rng)).collect());
+        let mut commitments =
+            Pcs::batch_commit_compact(&compact.iter().collect::<Vec<_>>(), &setup, BatchType::Big);
+        commitments.push(Pcs::commit(&dense, &setup));
+        let polys: Vec<&dyn MultilinearPolynomial<Fr>> = compact
+            .iter()
+            .map(|poly| poly as &dyn MultilinearPolynomial<Fr>)
+            .chain([&dense as &dyn MultilinearPolynomial<Fr>])
+            .collect();
+        let opening_point: Vec<Fr> = (0..6).map(|_| Fr::random(&mut rng)).collect();
+        let claims: Vec<Fr> = polys
+            .iter()
+            .map(|poly| poly.to_dense().evaluate(&opening_point))
+            .collect();
+
+        let mut prover_accumulator = ProverOpeningAccumulator::<Fr>::new();
+        let mut verifier_accumulator = VerifierOpeningAccumulator::<Fr, Pcs>::new();
+        let mut prover_transcript = ProofTranscript::new(b"test_transcript");
+        let mut verifier_transcript = ProofTranscript::new(b"test_transcript");
+        // Open the compact polynomials in two batches, so that the second is folded
+        for (range, polys) in [(0..2, &polys[..2]), (2..4, &polys[2..])] {
+            prover_accumulator.append(
+                polys,
+                &opening_point,
+                &claims[range.clone()],
+                &mut prover_transcript,
+            );
+            verifier_accumulator
+                .append(
+                    &commitments[range.clone()].iter().collect::<Vec<_>>(),
+                    &opening_point,
+                    &claims[range],
+                    &mut verifier_transcript,
+                )
+                .unwrap();
+        }
+
+        // Only the dense polynomial is copied into the accumulator; the compact ones share
+        // their coefficients with the committed polynomials
+        assert_eq!(prover_accumulator.len(), 1);
+        let opening = &prover_accumulator.openings[0];
+        assert_eq!(opening.compact.len(), 3);
+        assert!(opening.dense.is_some());
+        for ((_, form), poly) in opening.compact.iter().zip(compact.iter()) {
+            match form {
+                CompactForm::U32(form) => assert!(std::ptr::eq(form.coeffs(), poly.coeffs())),
+                _ => panic!("compact polynomial changed width"),
+            }
+        }
+
+        let proof = prover_accumulator.reduce_and_prove::<Pcs>(&setup, &mut prover_transcript);
+        assert!(verifier_accumulator
+            .reduce_and_verify(&setup, &proof, &mut verifier_transcript)
+            .is_ok());
+    }
+}
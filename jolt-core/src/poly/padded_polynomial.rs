@@ -0,0 +1,213 @@
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use rayon::prelude::*;
+use std::io;
+
+use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::poly::field::JoltField;
+use crate::poly::multilinear_polynomial::MultilinearPolynomial;
+use crate::utils::compute_dotproduct_low_optimized;
+use crate::utils::math::Math;
+use crate::utils::memory_budget::{MemoryBudget, PlainData, SpillableVec};
+
+/// A multilinear polynomial of which only the first `values().len()` evaluations over the
+/// Boolean hypercube are stored; the remaining ones are all `padding`. The evaluations are
+/// field elements, or (e.g. for the R1CS witness) small integers standing in for them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaddedPolynomial<T: Send + Sync> {
+    num_vars: usize,
+    values: SpillableVec<T>,
+    padding: T,
+}
+
+impl<T: Send + Sync> PaddedPolynomial<T> {
+    /// Creates a polynomial over `num_vars` variables whose first evaluations are `values`.
+    pub fn new(num_vars: usize, values: Vec<T>, padding: T) -> Self {
+        assert!(values.len() <= num_vars.pow2());
+        Self {
+            num_vars,
+            values: SpillableVec::Resident(values),
+            padding,
+        }
+    }
+
+    pub fn get_num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// The number of evaluations, including the padding.
+    pub fn len(&self) -> usize {
+        self.num_vars.pow2()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The stored evaluations, i.e. those before the padding.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn padding(&self) -> &T {
+        &self.padding
+    }
+}
+
+impl<T: PlainData> PaddedPolynomial<T> {
+    /// Spills the stored evaluations of `polys`, which are read together, to scratch files if
+    /// keeping them resident would exceed `budget`. They are then read in place (see
+    /// `SpillableVec`).
+    pub fn spill_batch(polys: Vec<Self>, budget: Option<&MemoryBudget>) -> io::Result<Vec<Self>> {
+        let (values, rest): (Vec<Vec<T>>, Vec<(usize, T)>) = polys
+            .into_iter()
+            .map(|poly| (poly.values.into_vec(), (poly.num_vars, poly.padding)))
+            .unzip();
+        let values = SpillableVec::new_batch(values, budget)?;
+        Ok(values
+            .into_iter()
+            .zip(rest)
+            .map(|(values, (num_vars, padding))| Self {
+                num_vars,
+                values,
+                padding,
+            })
+            .collect())
+    }
+
+    pub fn is_spilled(&self) -> bool {
+        self.values.is_spilled()
+    }
+}
+
+impl<T: Send + Sync> std::ops::Index<usize> for PaddedPolynomial<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        assert!(index < self.len(), "index too high");
+        self.values.get(index).unwrap_or(&self.padding)
+    }
+}
+
+// Implemented by hand rather than derived, so that integer-valued polynomials need not be
+// serializable.
+impl<T: CanonicalSerialize + Send + Sync> CanonicalSerialize for PaddedPolynomial<T> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.num_vars.serialize_with_mode(&mut writer, compress)?;
+        self.values().serialize_with_mode(&mut writer, compress)?;
+        self.padding.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.num_vars.serialized_size(compress)
+            + self.values().serialized_size(compress)
+            + self.padding.serialized_size(compress)
+    }
+}
+
+impl<T: Valid + Send + Sync> Valid for PaddedPolynomial<T> {
+    fn check(&self) -> Result<(), SerializationError> {
+        T::batch_check(self.values().iter())?;
+        self.padding.check()
+    }
+}
+
+impl<T: CanonicalDeserialize + Send + Sync> CanonicalDeserialize for PaddedPolynomial<T> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let num_vars = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let values = Vec::<T>::deserialize_with_mode(&mut reader, compress, validate)?;
+        let padding = T::deserialize_with_mode(&mut reader, compress, validate)?;
+        if num_vars >= usize::BITS as usize || values.len() > num_vars.pow2() {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(Self::new(num_vars, values, padding))
+    }
+}
+
+impl<F: JoltField> MultilinearPolynomial<F> for PaddedPolynomial<F> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn get_num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    fn get_coeff(&self, index: usize) -> F {
+        self[index]
+    }
+
+    fn evaluate_at_chi(&self, chis: &[F]) -> F {
+        assert_eq!(self.len(), chis.len());
+        let (stored_chis, padding_chis) = chis.split_at(self.values.len());
+        let stored = compute_dotproduct_low_optimized(stored_chis, &self.values);
+        if self.padding.is_zero() {
+            stored
+        } else {
+            stored + self.padding * padding_chis.par_iter().sum::<F>()
+        }
+    }
+
+    fn to_dense(&self) -> DensePolynomial<F> {
+        DensePolynomial::new((0..self.len()).map(|i| self[i]).collect())
+    }
+
+    fn add_scaled_chunk(&self, coeff: &F, offset: usize, chunk: &mut [F]) {
+        let num_stored = self.values.len().clamp(offset, offset + chunk.len()) - offset;
+        let (stored, padding) = chunk.split_at_mut(num_stored);
+        for (value, stored_value) in stored.iter_mut().zip(&self.values[offset..]) {
+            *value += *stored_value * coeff;
+        }
+        if !self.padding.is_zero() {
+            let scaled_padding = self.padding * coeff;
+            padding
+                .iter_mut()
+                .for_each(|value| *value += scaled_padding);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::eq_poly::EqPolynomial;
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+
+    #[test]
+    fn matches_dense() {
+        let mut rng = test_rng();
+        let values: Vec<Fr> = (0..5).map(|_| Fr::random(&mut rng)).collect();
+        let padding = Fr::random(&mut rng);
+        let padded = PaddedPolynomial::new(3, values.clone(), padding);
+        let mut evals = values;
+        evals.resize(8, padding);
+        let dense = DensePolynomial::new(evals);
+        assert_eq!(padded.to_dense(), dense);
+
+        let r: Vec<Fr> = (0..3).map(|_| Fr::random(&mut rng)).collect();
+        assert_eq!(
+            padded.evaluate_at_chi(&EqPolynomial::evals(&r)),
+            dense.evaluate(&r)
+        );
+
+        let coeff = Fr::random(&mut rng);
+        for offset in [0, 4] {
+            let mut chunk = vec![Fr::from_u64(1).unwrap(); 4];
+            let mut expected = chunk.clone();
+            padded.add_scaled_chunk(&coeff, offset, &mut chunk);
+            MultilinearPolynomial::add_scaled_chunk(&dense, &coeff, offset, &mut expected);
+            assert_eq!(chunk, expected);
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rayon::prelude::*;
+use std::cmp::Ordering;
+
+use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::poly::eq_poly::EqPolynomial;
+use crate::poly::field::JoltField;
+use crate::utils::mul_0_1_optimized;
+
+/// A multilinear polynomial represented by its non-zero evaluations over the Boolean hypercube,
+/// stored as `(index, value)` pairs sorted by index. Suited to polynomials that are zero almost
+/// everywhere, e.g. the instruction flag polynomials, which are one-hot per step: each flag is
+/// non-zero only at the steps executing its instruction.
+///
+/// Binding a variable keeps the polynomial sparse (the bound polynomial is non-zero only where
+/// the low or high half was), so sumcheck over sparse polynomials costs time proportional to the
+/// number of non-zero evaluations rather than to the size of the hypercube.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SparsePolynomial<F: JoltField> {
+    num_vars: usize,
+    indices: Vec<usize>,
+    values: Vec<F>,
+}
+
+impl<F: JoltField> SparsePolynomial<F> {
+    /// Creates a polynomial over `num_vars` variables whose evaluation at each of `indices` is
+    /// the corresponding entry of `values`, and zero elsewhere. `indices` must be strictly increasing.
+    pub fn new(num_vars: usize, indices: Vec<usize>, values: Vec<F>) -> Self {
+        assert_eq!(indices.len(), values.len());
+        debug_assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+        if let Some(&last) = indices.last() {
+            assert!(last < 1 << num_vars, "Index {last} out of bounds");
+        }
+        Self {
+            num_vars,
+            indices,
+            values,
+        }
+    }
+
+    /// Creates a polynomial over `num_vars` variables that is one at each of `indices` and zero
+    /// elsewhere. `indices` must be strictly increasing.
+    pub fn from_indices(num_vars: usize, indices: Vec<usize>) -> Self {
+        let values = vec![F::one(); indices.len()];
+        Self::new(num_vars, indices, values)
+    }
+
+    #[tracing::instrument(skip_all, name = "SparsePolynomial::from_dense")]
+    pub fn from_dense(poly: &DensePolynomial<F>) -> Self {
+        let (indices, values) = poly
+            .evals_ref()
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(index, value)| (index, *value))
+            .unzip();
+        Self::new(poly.get_num_vars(), indices, values)
+    }
+
+    pub fn get_num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    pub fn len(&self) -> usize {
+        1 << self.num_vars
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of (explicitly stored) non-zero evaluations.
+    pub fn num_nonzero(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    pub fn values(&self) -> &[F] {
+        &self.values
+    }
+
+    /// Returns the evaluation at the `index`-th point of the Boolean hypercube.
+    pub fn get_coeff(&self, index: usize) -> F {
+        match self.indices.binary_search(&index) {
+            Ok(position) => self.values[position],
+            Err(_) => F::zero(),
+        }
+    }
+
+    /// Returns the evaluations over the Boolean hypercube.
+    #[tracing::instrument(skip_all, name = "SparsePolynomial::evals")]
+    pub fn evals(&self) -> Vec<F> {
+        let mut evals = vec![F::zero(); self.len()];
+        for (index, value) in self.indices.iter().zip(self.values.iter()) {
+            evals[*index] = *value;
+        }
+        evals
+    }
+
+    pub fn to_dense(&self) -> DensePolynomial<F> {
+        DensePolynomial::new(self.evals())
+    }
+
+    #[tracing::instrument(skip_all, name = "SparsePolynomial::flatten")]
+    pub fn flatten(polys: &[Self]) -> Vec<F> {
+        let poly_len = polys[0].len();
+        polys
+            .iter()
+            .for_each(|poly| assert_eq!(poly_len, poly.len()));
+
+        let mut flattened = vec![F::zero(); poly_len * polys.len()];
+        flattened
+            .par_chunks_mut(poly_len)
+            .zip(polys.par_iter())
+            .for_each(|(chunk, poly)| {
+                for (index, value) in poly.indices.iter().zip(poly.values.iter()) {
+                    chunk[*index] = *value;
+                }
+            });
+        flattened
+    }
+
+    // returns Z(r) in O(n) time
+    pub fn evaluate(&self, r: &[F]) -> F {
+        // r must have a value for each variable
+        assert_eq!(r.len(), self.get_num_vars());
+        let chis = EqPolynomial::evals(r);
+        self.evaluate_at_chi(&chis)
+    }
+
+    pub fn evaluate_at_chi(&self, chis: &[F]) -> F {
+        assert_eq!(self.len(), chis.len());
+        self.indices
+            .par_iter()
+            .zip(self.values.par_iter())
+            .map(|(index, value)| mul_0_1_optimized(value, &chis[*index]))
+            .sum()
+    }
+
+    /// Returns the range of positions in `indices()` (and `values()`) holding the non-zero
+    /// evaluations at indices in `start..end`.
+    pub fn index_range(&self, start: usize, end: usize) -> (usize, usize) {
+        (
+            self.indices.partition_point(|&index| index < start),
+            self.indices.partition_point(|&index| index < end),
+        )
+    }
+
+    /// Adds `coeff` times the evaluations at indices `offset..offset + chunk.len()` to `chunk`.
+    pub fn add_scaled_chunk(&self, coeff: &F, offset: usize, chunk: &mut [F]) {
+        let (start, end) = self.index_range(offset, offset + chunk.len());
+        for (index, value) in self.indices[start..end]
+            .iter()
+            .zip(self.values[start..end].iter())
+        {
+            chunk[index - offset] += mul_0_1_optimized(value, coeff);
+        }
+    }
+
+    /// Returns `(index, low, high)` for each index into the lower half of the hypercube at which
+    /// the polynomial is non-zero in either half, where `low` and `high` are the evaluations at
+    /// `index` and `index + len / 2` respectively. These are the only terms that contribute to a
+    /// sumcheck round over the most significant variable.
+    pub fn low_high_pairs(&self) -> Vec<(usize, F, F)> {
+        let n = self.len() / 2;
+        let split = self.indices.partition_point(|&index| index < n);
+        let (low_indices, high_indices) = self.indices.split_at(split);
+        let (low_values, high_values) = self.values.split_at(split);
+
+        let mut pairs = Vec::with_capacity(self.indices.len());
+        let (mut i, mut j) = (0, 0);
+        while i < low_indices.len() || j < high_indices.len() {
+            let low_index = low_indices.get(i).copied().unwrap_or(usize::MAX);
+            let high_index = high_indices.get(j).map_or(usize::MAX, |index| index - n);
+            match low_index.cmp(&high_index) {
+                Ordering::Less => {
+                    pairs.push((low_index, low_values[i], F::zero()));
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    pairs.push((high_index, F::zero(), high_values[j]));
+                    j += 1;
+                }
+                Ordering::Equal => {
+                    pairs.push((low_index, low_values[i], high_values[j]));
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Binds the most significant variable to `r`.
+    #[tracing::instrument(skip_all, name = "SparsePolynomial::bound_poly_var_top")]
+    pub fn bound_poly_var_top(&mut self, r: &F) {
+        *self = self.new_poly_from_bound_poly_var_top(r);
+    }
+
+    /// Returns the polynomial with its most significant variable bound to `r`.
+    pub fn new_poly_from_bound_poly_var_top(&self, r: &F) -> Self {
+        let (indices, values) = self
+            .low_high_pairs()
+            .into_iter()
+            .filter_map(|(index, low, high)| {
+                // low' = low + r * (high - low)
+                let value = if low == high {
+                    low
+                } else {
+                    low + *r * (high - low)
+                };
+                (!value.is_zero()).then_some((index, value))
+            })
+            .unzip();
+        Self {
+            num_vars: self.num_vars - 1,
+            indices,
+            values,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_core::RngCore;
+
+    #[test]
+    fn matches_dense() {
+        let mut rng = test_rng();
+        let num_vars = 8;
+        let evals: Vec<Fr> = (0..1 << num_vars)
+            .map(|_| match rng.next_u32() % 8 {
+                0 => Fr::one(),
+                1 => Fr::random(&mut rng),
+                _ => Fr::zero(),
+            })
+            .collect();
+        let mut dense = DensePolynomial::new(evals);
+        let mut sparse = SparsePolynomial::from_dense(&dense);
+        assert_eq!(sparse.to_dense(), dense);
+
+        let r: Vec<Fr> = (0..num_vars).map(|_| Fr::random(&mut rng)).collect();
+        assert_eq!(sparse.evaluate(&r), dense.evaluate(&r));
+
+        let mut chunk = vec![Fr::zero(); 16];
+        sparse.add_scaled_chunk(&r[0], 32, &mut chunk);
+        for (i, value) in chunk.iter().enumerate() {
+            assert_eq!(*value, dense[32 + i] * r[0]);
+        }
+
+        for r_i in r.iter() {
+            dense = dense.new_poly_from_bound_poly_var_top(r_i);
+            sparse.bound_poly_var_top(r_i);
+            assert_eq!(sparse.to_dense(), dense);
+        }
+    }
+
+    #[test]
+    fn one_hot() {
+        let sparse = SparsePolynomial::<Fr>::from_indices(3, vec![1, 4, 6]);
+        assert_eq!(
+            sparse.evals(),
+            [0, 1, 0, 0, 1, 0, 1, 0]
+                .map(Fr::from_u64)
+                .map(Option::unwrap)
+        );
+        assert_eq!(
+            sparse.low_high_pairs(),
+            vec![
+                (0, Fr::zero(), Fr::one()),
+                (1, Fr::one(), Fr::zero()),
+                (2, Fr::zero(), Fr::one())
+            ]
+        );
+    }
+}
@@ -2,6 +2,7 @@ use crate::poly::field::JoltField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
 use super::commitment::commitment_scheme::CommitmentScheme;
+use super::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
 use crate::{
     lasso::memory_checking::NoPreprocessing,
     utils::{errors::ProofVerifyError, transcript::ProofTranscript},
@@ -20,8 +21,9 @@ pub trait StructuredCommitment<C: CommitmentScheme>: Send + Sync + Sized {
 
 /// Encapsulates the pattern of opening a batched polynomial commitment at a single point.
 /// Note that there may be a one-to-many mapping from `StructuredCommitment` to `StructuredOpeningProof`:
-/// different subset of the same polynomials may be opened at different points, resulting in
-/// different opening proofs.
+/// different subset of the same polynomials may be opened at different points.
+/// The openings themselves are proven all at once at the end of the proof, by an opening
+/// accumulator.
 pub trait StructuredOpeningProof<F, C, Polynomials>:
     Sync + CanonicalSerialize + CanonicalDeserialize
 where
@@ -30,37 +32,39 @@ where
     Polynomials: StructuredCommitment<C> + ?Sized,
 {
     type Preprocessing = NoPreprocessing;
-    type Proof: Sync + CanonicalSerialize + CanonicalDeserialize;
 
     /// Evaluates each of the given `polynomials` at the given `opening_point`.
     fn open(polynomials: &Polynomials, opening_point: &[F]) -> Self;
 
-    /// Proves that the `polynomials`, evaluated at `opening_point`, output the values given
-    /// by `openings`. The polynomials should already be committed by the prover.
+    /// Registers the claim that the `polynomials`, evaluated at `opening_point`, output the
+    /// values given by `openings` with the `opening_accumulator`. The polynomials should
+    /// already be committed by the prover.
     fn prove_openings(
         polynomials: &Polynomials,
         opening_point: &[F],
         openings: &Self,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
-    ) -> Self::Proof;
+    );
 
     /// Often some of the openings do not require an opening proof provided by the prover, and
     /// instead can be efficiently computed by the verifier by itself. This function populates
-    /// any such fields in `self`.
+    /// any such fields in `self`. Fails if `opening_point` has the wrong number of variables.
     fn compute_verifier_openings(
         &mut self,
         _preprocessing: &Self::Preprocessing,
         _opening_point: &[F],
-    ) {
+    ) -> Result<(), ProofVerifyError> {
+        Ok(())
     }
 
-    /// Verifies an opening proof, given the associated polynomial `commitment` and `opening_point`.
+    /// Registers these openings, given the associated polynomial `commitment` and `opening_point`,
+    /// with the `opening_accumulator`, to be verified at the end of the proof.
     fn verify_openings(
         &self,
-        generators: &C::Setup,
-        opening_proof: &Self::Proof,
         commitment: &Polynomials::Commitment,
         opening_point: &[F],
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError>;
 }
@@ -86,6 +86,11 @@ impl<F: JoltField> UniPoly<F> {
 }
 
 impl<F: JoltField> CompressedUniPoly<F> {
+    /// The degree of the decompressed polynomial.
+    pub fn degree(&self) -> usize {
+        self.coeffs_except_linear_term.len()
+    }
+
     // we require eval(0) + eval(1) = hint, so we can solve for the linear term as:
     // linear_term = hint - 2 * constant_term - deg2 term - deg3 term
     pub fn decompress(&self, hint: &F) -> UniPoly<F> {
@@ -0,0 +1,553 @@
+//! A small typed DSL for describing the uniform (per-step) R1CS constraints of Jolt.
+//!
+//! Constraints are written over linear combinations (`LC`) of named `Variable`s. Auxiliary
+//! variables are allocated by the builder together with a description of how to compute them,
+//! so the witness for a step is derived from the very same description as the constraints.
+use crate::poly::field::JoltField;
+use smallvec::SmallVec;
+use std::ops::{Add, Mul, Neg, Sub};
+
+const LC_INLINE_TERMS: usize = 4;
+
+/// A variable of the per-step constraint system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Variable {
+    /// The constant 1.
+    Constant,
+    /// The i-th input of the step, as laid out in the witness.
+    Input(usize),
+    /// The i-th auxiliary variable allocated by the builder.
+    Auxiliary(usize),
+}
+
+/// A linear combination of variables with small integer coefficients.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LC(SmallVec<[(Variable, i64); LC_INLINE_TERMS]>);
+
+impl LC {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn terms(&self) -> &[(Variable, i64)] {
+        &self.0
+    }
+
+    /// Returns `Σ coefficient_i · variable_i`.
+    pub fn sum<V: Into<Variable>>(terms: impl IntoIterator<Item = (V, i64)>) -> Self {
+        let mut lc = Self::zero();
+        for (variable, coefficient) in terms {
+            lc.add_term(variable.into(), coefficient);
+        }
+        lc
+    }
+
+    fn add_term(&mut self, variable: Variable, coefficient: i64) {
+        if coefficient == 0 {
+            return;
+        }
+        if let Some(index) = self.0.iter().position(|(v, _)| *v == variable) {
+            self.0[index].1 += coefficient;
+            if self.0[index].1 == 0 {
+                self.0.remove(index);
+            }
+        } else {
+            self.0.push((variable, coefficient));
+        }
+    }
+
+    /// Evaluates the linear combination given the values of the inputs and of the auxiliary
+    /// variables computed so far.
+    pub fn evaluate<F: JoltField>(&self, inputs: &[F], aux: &[F]) -> F {
+        self.0
+            .iter()
+            .map(|&(variable, coefficient)| {
+                let value = match variable {
+                    Variable::Constant => F::one(),
+                    Variable::Input(index) => inputs[index],
+                    Variable::Auxiliary(index) => aux[index],
+                };
+                match coefficient {
+                    1 => value,
+                    -1 => -value,
+                    _ => value * i64_to_f::<F>(coefficient),
+                }
+            })
+            .sum()
+    }
+
+    /// Same as `evaluate`, but over the integers. Returns `None` on overflow.
+    pub fn evaluate_small(&self, inputs: &[i128], aux: &[i128]) -> Option<i128> {
+        self.0
+            .iter()
+            .try_fold(0i128, |sum, &(variable, coefficient)| {
+                let value = match variable {
+                    Variable::Constant => 1,
+                    Variable::Input(index) => inputs[index],
+                    Variable::Auxiliary(index) => aux[index],
+                };
+                sum.checked_add(value.checked_mul(coefficient as i128)?)
+            })
+    }
+}
+
+impl From<Variable> for LC {
+    fn from(variable: Variable) -> Self {
+        Self::sum([(variable, 1)])
+    }
+}
+
+impl From<i64> for LC {
+    fn from(constant: i64) -> Self {
+        Self::sum([(Variable::Constant, constant)])
+    }
+}
+
+impl<T: Into<LC>> Add<T> for LC {
+    type Output = LC;
+
+    fn add(mut self, other: T) -> LC {
+        for &(variable, coefficient) in other.into().terms() {
+            self.add_term(variable, coefficient);
+        }
+        self
+    }
+}
+
+impl<T: Into<LC>> Sub<T> for LC {
+    type Output = LC;
+
+    fn sub(self, other: T) -> LC {
+        self + -other.into()
+    }
+}
+
+impl Neg for LC {
+    type Output = LC;
+
+    fn neg(self) -> LC {
+        self * -1
+    }
+}
+
+impl Mul<i64> for LC {
+    type Output = LC;
+
+    fn mul(self, scalar: i64) -> LC {
+        Self::sum(
+            self.0
+                .into_iter()
+                .map(|(variable, coefficient)| (variable, coefficient * scalar)),
+        )
+    }
+}
+
+impl<T: Into<LC>> Add<T> for Variable {
+    type Output = LC;
+
+    fn add(self, other: T) -> LC {
+        LC::from(self) + other
+    }
+}
+
+impl<T: Into<LC>> Sub<T> for Variable {
+    type Output = LC;
+
+    fn sub(self, other: T) -> LC {
+        LC::from(self) - other
+    }
+}
+
+impl Mul<i64> for Variable {
+    type Output = LC;
+
+    fn mul(self, scalar: i64) -> LC {
+        LC::from(self) * scalar
+    }
+}
+
+/// A single named constraint `a * b == c`.
+#[derive(Clone, Debug)]
+pub struct Constraint {
+    pub name: &'static str,
+    pub a: LC,
+    pub b: LC,
+    pub c: LC,
+}
+
+/// How the value of an auxiliary variable is computed from the inputs and the auxiliary
+/// variables allocated before it.
+#[derive(Clone, Debug)]
+enum AuxComputation {
+    LinearCombination(LC),
+    Product(LC, LC),
+    IfElse {
+        condition: LC,
+        if_false: LC,
+        if_true: LC,
+    },
+}
+
+impl AuxComputation {
+    fn evaluate<F: JoltField>(&self, inputs: &[F], aux: &[F]) -> F {
+        match self {
+            AuxComputation::LinearCombination(lc) => lc.evaluate(inputs, aux),
+            AuxComputation::Product(x, y) => x.evaluate(inputs, aux) * y.evaluate(inputs, aux),
+            AuxComputation::IfElse {
+                condition,
+                if_false,
+                if_true,
+            } => {
+                let condition = condition.evaluate(inputs, aux);
+                if condition.is_zero() {
+                    if_false.evaluate(inputs, aux)
+                } else if condition.is_one() {
+                    if_true.evaluate(inputs, aux)
+                } else {
+                    let if_false = if_false.evaluate(inputs, aux);
+                    if_false + condition * (if_true.evaluate(inputs, aux) - if_false)
+                }
+            }
+        }
+    }
+
+    fn evaluate_small(&self, inputs: &[i128], aux: &[i128]) -> Option<i128> {
+        match self {
+            AuxComputation::LinearCombination(lc) => lc.evaluate_small(inputs, aux),
+            AuxComputation::Product(x, y) => x
+                .evaluate_small(inputs, aux)?
+                .checked_mul(y.evaluate_small(inputs, aux)?),
+            AuxComputation::IfElse {
+                condition,
+                if_false,
+                if_true,
+            } => match condition.evaluate_small(inputs, aux)? {
+                0 => if_false.evaluate_small(inputs, aux),
+                1 => if_true.evaluate_small(inputs, aux),
+                condition => {
+                    let if_false = if_false.evaluate_small(inputs, aux)?;
+                    let difference = if_true.evaluate_small(inputs, aux)?.checked_sub(if_false)?;
+                    if_false.checked_add(condition.checked_mul(difference)?)
+                }
+            },
+        }
+    }
+}
+
+/// A constraint that is not satisfied by the witness of a step.
+#[derive(Clone, Debug)]
+pub struct UnsatisfiedConstraint<F: JoltField> {
+    /// The index of the constraint, i.e. its row in the matrices.
+    pub index: usize,
+    pub name: &'static str,
+    /// The evaluations of `a`, `b` and `c`.
+    pub evaluations: (F, F, F),
+    /// The variables involved in the constraint, with their values.
+    pub values: Vec<(Variable, F)>,
+}
+
+/// Collects the constraints of a single step over `num_inputs` input variables, allocating
+/// auxiliary variables as needed. Constraints and auxiliary variables are named, so that an
+/// unsatisfied constraint can be reported in terms of the circuit description.
+#[derive(Clone, Debug)]
+pub struct ConstraintBuilder {
+    num_inputs: usize,
+    constraints: Vec<Constraint>,
+    aux: Vec<(&'static str, AuxComputation)>,
+}
+
+impl ConstraintBuilder {
+    pub fn new(num_inputs: usize) -> Self {
+        Self {
+            num_inputs,
+            constraints: vec![],
+            aux: vec![],
+        }
+    }
+
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    pub fn num_aux(&self) -> usize {
+        self.aux.len()
+    }
+
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Returns the name the auxiliary variable `index` was allocated with.
+    pub fn aux_name(&self, index: usize) -> &'static str {
+        self.aux[index].0
+    }
+
+    /// Constrains `a * b == c`.
+    pub fn constrain_prod(
+        &mut self,
+        name: &'static str,
+        a: impl Into<LC>,
+        b: impl Into<LC>,
+        c: impl Into<LC>,
+    ) {
+        self.constraints.push(Constraint {
+            name,
+            a: a.into(),
+            b: b.into(),
+            c: c.into(),
+        });
+    }
+
+    /// Constrains `x == y`.
+    pub fn constrain_eq(&mut self, name: &'static str, x: impl Into<LC>, y: impl Into<LC>) {
+        self.constrain_prod(name, x.into() - y, Variable::Constant, LC::zero());
+    }
+
+    /// Constrains `x == y` whenever `condition` is non-zero.
+    pub fn constrain_if(
+        &mut self,
+        name: &'static str,
+        condition: impl Into<LC>,
+        x: impl Into<LC>,
+        y: impl Into<LC>,
+    ) {
+        self.constrain_prod(name, condition, x.into() - y, LC::zero());
+    }
+
+    /// Constrains `x` to be 0 or 1.
+    pub fn constrain_binary(&mut self, name: &'static str, x: impl Into<LC>) {
+        let x = x.into();
+        self.constrain_prod(name, x.clone(), LC::from(1) - x, LC::zero());
+    }
+
+    /// Constrains `x * y * z == 0`, allocating an auxiliary variable for `x * y`.
+    pub fn constrain_prod_0(
+        &mut self,
+        name: &'static str,
+        x: impl Into<LC>,
+        y: impl Into<LC>,
+        z: impl Into<LC>,
+    ) {
+        let xy = self.allocate_prod(name, x, y);
+        self.constrain_prod(name, xy, z, LC::zero());
+    }
+
+    fn allocate(&mut self, name: &'static str, computation: AuxComputation) -> Variable {
+        self.aux.push((name, computation));
+        Variable::Auxiliary(self.aux.len() - 1)
+    }
+
+    /// Allocates an auxiliary variable equal to `lc`.
+    pub fn allocate_lc(&mut self, name: &'static str, lc: impl Into<LC>) -> Variable {
+        let lc = lc.into();
+        let result = self.allocate(name, AuxComputation::LinearCombination(lc.clone()));
+        self.constrain_prod(name, lc, Variable::Constant, result);
+        result
+    }
+
+    /// Allocates an auxiliary variable equal to `x * y`.
+    pub fn allocate_prod(
+        &mut self,
+        name: &'static str,
+        x: impl Into<LC>,
+        y: impl Into<LC>,
+    ) -> Variable {
+        let (x, y) = (x.into(), y.into());
+        let result = self.allocate(name, AuxComputation::Product(x.clone(), y.clone()));
+        self.constrain_prod(name, x, y, result);
+        result
+    }
+
+    /// Allocates an auxiliary variable equal to `if_false` if `condition` is 0 and to `if_true`
+    /// if `condition` is 1.
+    pub fn allocate_if_else(
+        &mut self,
+        name: &'static str,
+        condition: impl Into<LC>,
+        if_false: impl Into<LC>,
+        if_true: impl Into<LC>,
+    ) -> Variable {
+        let (condition, if_false, if_true) = (condition.into(), if_false.into(), if_true.into());
+        let result = self.allocate(
+            name,
+            AuxComputation::IfElse {
+                condition: condition.clone(),
+                if_false: if_false.clone(),
+                if_true: if_true.clone(),
+            },
+        );
+        self.constrain_prod(
+            name,
+            condition,
+            if_true - if_false.clone(),
+            result - if_false,
+        );
+        result
+    }
+
+    /// Computes the auxiliary variables of a step from its `inputs`.
+    pub fn compute_aux<F: JoltField>(&self, inputs: &[F]) -> Vec<F> {
+        debug_assert_eq!(inputs.len(), self.num_inputs);
+        let mut aux = Vec::with_capacity(self.aux.len());
+        for (_, computation) in &self.aux {
+            let value = computation.evaluate(inputs, &aux);
+            aux.push(value);
+        }
+        aux
+    }
+
+    /// Same as `compute_aux`, but over the integers. Returns `None` if some auxiliary variable
+    /// does not fit in an `i128`.
+    pub fn compute_aux_small(&self, inputs: &[i128]) -> Option<Vec<i128>> {
+        debug_assert_eq!(inputs.len(), self.num_inputs);
+        let mut aux = Vec::with_capacity(self.aux.len());
+        for (_, computation) in &self.aux {
+            let value = computation.evaluate_small(inputs, &aux)?;
+            aux.push(value);
+        }
+        Some(aux)
+    }
+
+    /// Checks the constraints against the witness of a step given by its `inputs`, returning
+    /// the first unsatisfied constraint, if any.
+    pub fn check_step<F: JoltField>(&self, inputs: &[F]) -> Result<(), UnsatisfiedConstraint<F>> {
+        let aux = self.compute_aux(inputs);
+        for (index, constraint) in self.constraints.iter().enumerate() {
+            let evaluations = (
+                constraint.a.evaluate(inputs, &aux),
+                constraint.b.evaluate(inputs, &aux),
+                constraint.c.evaluate(inputs, &aux),
+            );
+            if evaluations.0 * evaluations.1 != evaluations.2 {
+                let mut values: Vec<(Variable, F)> = vec![];
+                for lc in [&constraint.a, &constraint.b, &constraint.c] {
+                    for &(variable, _) in lc.terms() {
+                        if variable != Variable::Constant
+                            && !values.iter().any(|(v, _)| *v == variable)
+                        {
+                            values.push((variable, LC::from(variable).evaluate(inputs, &aux)));
+                        }
+                    }
+                }
+                return Err(UnsatisfiedConstraint {
+                    index,
+                    name: constraint.name,
+                    evaluations,
+                    values,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the witness column of `variable` in `[inputs, aux, 1]`, the layout Spartan
+    /// expects (the constant goes last).
+    pub fn column(&self, variable: Variable) -> usize {
+        match variable {
+            Variable::Input(index) => index,
+            Variable::Auxiliary(index) => self.num_inputs + index,
+            Variable::Constant => self.num_inputs + self.aux.len(),
+        }
+    }
+
+    /// Returns the sparse (row, column, coefficient) entries of the A, B and C matrices.
+    #[allow(clippy::type_complexity)]
+    pub fn materialize(
+        &self,
+    ) -> (
+        Vec<(usize, usize, i64)>,
+        Vec<(usize, usize, i64)>,
+        Vec<(usize, usize, i64)>,
+    ) {
+        let mut matrices = (vec![], vec![], vec![]);
+        for (row, constraint) in self.constraints.iter().enumerate() {
+            for (lc, matrix) in [
+                (&constraint.a, &mut matrices.0),
+                (&constraint.b, &mut matrices.1),
+                (&constraint.c, &mut matrices.2),
+            ] {
+                matrix.extend(
+                    lc.terms()
+                        .iter()
+                        .map(|&(variable, coefficient)| (row, self.column(variable), coefficient)),
+                );
+            }
+        }
+        matrices
+    }
+}
+
+pub(crate) fn i64_to_f<F: JoltField>(num: i64) -> F {
+    if num < 0 {
+        F::zero() - F::from_u64(num.unsigned_abs()).unwrap()
+    } else {
+        F::from_u64(num as u64).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn lc_arithmetic() {
+        let (x, y) = (Variable::Input(0), Variable::Input(1));
+        let lc = (x * 3 + y - 2) - (x + y);
+        assert_eq!(lc, LC::sum([(x, 2), (Variable::Constant, -2)]));
+        assert_eq!(-lc.clone() + lc, LC::zero());
+
+        let inputs = [Fr::from(5u64), Fr::from(7u64)];
+        assert_eq!((x * 3 - y).evaluate(&inputs, &[]), Fr::from(8u64));
+    }
+
+    #[test]
+    fn aux_satisfies_constraints() {
+        let (flag, x, y) = (Variable::Input(0), Variable::Input(1), Variable::Input(2));
+        let mut builder = ConstraintBuilder::new(3);
+        builder.constrain_binary("flag is binary", flag);
+        let sum = builder.allocate_lc("sum", x + y * 4);
+        let product = builder.allocate_prod("product", sum, flag);
+        let selected = builder.allocate_if_else("selected", flag, x, y - 1);
+        builder.constrain_if("selected is y - 1", flag, selected + 1, y);
+        builder.constrain_prod_0("product is sum", flag, x, product - sum);
+        assert_eq!(builder.num_aux(), 4);
+
+        for flag in [0u64, 1] {
+            let inputs = [Fr::from(flag), Fr::from(3u64), Fr::from(9u64)];
+            let aux = builder.compute_aux(&inputs);
+            assert_eq!(aux[0], Fr::from(39u64));
+            assert_eq!(aux[1], Fr::from(39 * flag));
+            assert!(builder.check_step(&inputs).is_ok());
+
+            let small_aux = builder.compute_aux_small(&[flag as i128, 3, 9]).unwrap();
+            assert_eq!(
+                small_aux.into_iter().map(Fr::from_i128).collect::<Vec<_>>(),
+                aux
+            );
+        }
+        assert_eq!(builder.compute_aux_small(&[1, 3, i128::MAX]), None);
+
+        let unsatisfied = builder
+            .check_step(&[Fr::from(2u64), Fr::from(3u64), Fr::from(9u64)])
+            .unwrap_err();
+        assert_eq!((unsatisfied.index, unsatisfied.name), (0, "flag is binary"));
+        assert_eq!(unsatisfied.values, vec![(flag, Fr::from(2u64))]);
+        assert_eq!(
+            unsatisfied.evaluations,
+            (Fr::from(2u64), -Fr::from(1u64), Fr::from(0u64))
+        );
+    }
+
+    #[test]
+    fn materialize_puts_constant_last() {
+        let x = Variable::Input(0);
+        let mut builder = ConstraintBuilder::new(1);
+        let doubled = builder.allocate_lc("doubled", x * 2);
+        builder.constrain_eq("doubled is 6", doubled, 6);
+        let (a, b, c) = builder.materialize();
+        assert_eq!(a, vec![(0, 0, 2), (1, 1, 1), (1, 2, -6)]);
+        assert_eq!(b, vec![(0, 2, 1), (1, 2, 1)]);
+        assert_eq!(c, vec![(0, 1, 1)]);
+    }
+}
@@ -0,0 +1,516 @@
+//! Export (and import) of R1CS instances and witnesses in the binary formats used by circom:
+//! `.r1cs` for the constraints and `.wtns` for the witness, as specified in
+//! https://github.com/iden3/r1csfile/blob/master/doc/r1cs_bin_format.md and read by snarkjs.
+//!
+//! In both formats, wire 0 is the constant 1 and is followed by the public outputs, the public
+//! inputs, the private inputs and finally the internal wires.
+use crate::poly::field::JoltField;
+use std::io::{self, Read, Write};
+
+use super::{builder::i64_to_f, constraints::R1CSBuilder, r1cs_shape::R1CSShape};
+use crate::jolt::instruction::JoltInstructionSet;
+
+const R1CS_MAGIC: &[u8; 4] = b"r1cs";
+const R1CS_VERSION: u32 = 1;
+const R1CS_HEADER_SECTION: u32 = 1;
+const R1CS_CONSTRAINTS_SECTION: u32 = 2;
+const R1CS_WIRE_TO_LABEL_SECTION: u32 = 3;
+
+const WTNS_MAGIC: &[u8; 4] = b"wtns";
+const WTNS_VERSION: u32 = 2;
+const WTNS_HEADER_SECTION: u32 = 1;
+const WTNS_WITNESS_SECTION: u32 = 2;
+
+/// A linear combination of wires.
+pub type CircomLC<F> = Vec<(u32, F)>;
+
+/// An R1CS instance in circom's wire layout. Each constraint `(a, b, c)` reads
+/// `<a, w> * <b, w> == <c, w>` for the wire assignment `w`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircomR1CS<F: JoltField> {
+    pub num_wires: u32,
+    pub num_pub_out: u32,
+    pub num_pub_in: u32,
+    pub num_prv_in: u32,
+    pub constraints: Vec<(CircomLC<F>, CircomLC<F>, CircomLC<F>)>,
+}
+
+impl<F: JoltField> CircomR1CS<F> {
+    /// Builds the instance from sparse (row, column, value) matrices, mapping column `col` to
+    /// wire `wire(col)`.
+    fn from_matrices<T: Copy>(
+        num_constraints: usize,
+        matrices: [&[(usize, usize, T)]; 3],
+        wire: impl Fn(usize) -> u32,
+        to_field: impl Fn(T) -> F,
+    ) -> Vec<(CircomLC<F>, CircomLC<F>, CircomLC<F>)> {
+        let mut lcs: [Vec<CircomLC<F>>; 3] = Default::default();
+        for (lcs, matrix) in lcs.iter_mut().zip(matrices) {
+            *lcs = vec![vec![]; num_constraints];
+            for &(row, col, value) in matrix {
+                lcs[row].push((wire(col), to_field(value)));
+            }
+            for lc in lcs.iter_mut() {
+                lc.sort_by_key(|(wire, _)| *wire);
+            }
+        }
+        let [a, b, c] = lcs;
+        a.into_iter()
+            .zip(b)
+            .zip(c)
+            .map(|((a, b), c)| (a, b, c))
+            .collect()
+    }
+
+    /// Returns whether the wire assignment `witness` satisfies every constraint.
+    pub fn is_satisfied(&self, witness: &[F]) -> bool {
+        let evaluate = |lc: &CircomLC<F>| -> F {
+            lc.iter()
+                .map(|(wire, coefficient)| witness[*wire as usize] * coefficient)
+                .sum()
+        };
+        witness.len() == self.num_wires as usize
+            && witness[0].is_one()
+            && self
+                .constraints
+                .iter()
+                .all(|(a, b, c)| evaluate(a) * evaluate(b) == evaluate(c))
+    }
+
+    /// Writes the instance in the `.r1cs` format.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let field_size = field_size::<F>();
+
+        let mut header = vec![];
+        header.extend((field_size as u32).to_le_bytes());
+        header.extend(modulus_bytes::<F>());
+        for value in [
+            self.num_wires,
+            self.num_pub_out,
+            self.num_pub_in,
+            self.num_prv_in,
+        ] {
+            header.extend(value.to_le_bytes());
+        }
+        header.extend((self.num_wires as u64).to_le_bytes()); // nLabels
+        header.extend((self.constraints.len() as u32).to_le_bytes());
+
+        let mut constraints = vec![];
+        for (a, b, c) in &self.constraints {
+            for lc in [a, b, c] {
+                constraints.extend((lc.len() as u32).to_le_bytes());
+                for (wire, coefficient) in lc {
+                    constraints.extend(wire.to_le_bytes());
+                    write_field(&mut constraints, coefficient);
+                }
+            }
+        }
+
+        // Wires are their own labels.
+        let labels: Vec<u8> = (0..self.num_wires as u64)
+            .flat_map(u64::to_le_bytes)
+            .collect();
+
+        writer.write_all(R1CS_MAGIC)?;
+        writer.write_all(&R1CS_VERSION.to_le_bytes())?;
+        writer.write_all(&3u32.to_le_bytes())?;
+        write_section(&mut writer, R1CS_HEADER_SECTION, &header)?;
+        write_section(&mut writer, R1CS_CONSTRAINTS_SECTION, &constraints)?;
+        write_section(&mut writer, R1CS_WIRE_TO_LABEL_SECTION, &labels)
+    }
+
+    /// Reads an instance in the `.r1cs` format over the field `F`.
+    pub fn read<R: Read>(reader: R) -> io::Result<Self> {
+        let sections = read_sections(reader, R1CS_MAGIC, R1CS_VERSION)?;
+
+        let mut header = find_section(&sections, R1CS_HEADER_SECTION)?;
+        read_field_header::<F>(&mut header)?;
+        let num_wires = read_u32(&mut header)?;
+        let num_pub_out = read_u32(&mut header)?;
+        let num_pub_in = read_u32(&mut header)?;
+        let num_prv_in = read_u32(&mut header)?;
+        let _num_labels = read_u64(&mut header)?;
+        let num_constraints = read_u32(&mut header)?;
+
+        let mut data = find_section(&sections, R1CS_CONSTRAINTS_SECTION)?;
+        let mut read_lc = || -> io::Result<CircomLC<F>> {
+            let num_terms = read_u32(&mut data)?;
+            (0..num_terms)
+                .map(|_| {
+                    let wire = read_u32(&mut data)?;
+                    if wire >= num_wires {
+                        return Err(invalid_data("wire out of range"));
+                    }
+                    Ok((wire, read_field(&mut data)?))
+                })
+                .collect()
+        };
+        let constraints = (0..num_constraints)
+            .map(|_| Ok((read_lc()?, read_lc()?, read_lc()?)))
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self {
+            num_wires,
+            num_pub_out,
+            num_pub_in,
+            num_prv_in,
+            constraints,
+        })
+    }
+}
+
+/// Exports a Spartan R1CS shape, whose witness vector is `z = [vars, 1, io]`. The IO become
+/// public inputs and the variables private inputs.
+impl<F: JoltField> From<&R1CSShape<F>> for CircomR1CS<F> {
+    fn from(shape: &R1CSShape<F>) -> Self {
+        let (num_vars, num_io) = (shape.num_vars, shape.num_io);
+        let wire = |col: usize| -> u32 {
+            if col < num_vars {
+                (1 + num_io + col) as u32
+            } else {
+                (col - num_vars) as u32
+            }
+        };
+        Self {
+            num_wires: (1 + num_io + num_vars) as u32,
+            num_pub_out: 0,
+            num_pub_in: num_io as u32,
+            num_prv_in: num_vars as u32,
+            constraints: Self::from_matrices(
+                shape.num_cons,
+                [&shape.A[..], &shape.B[..], &shape.C[..]],
+                wire,
+                |value| value,
+            ),
+        }
+    }
+}
+
+impl<F: JoltField> R1CSShape<F> {
+    /// Maps the witness vector `z = [vars, 1, io]` of this shape to circom wires.
+    pub fn circom_witness(&self, z: &[F]) -> Vec<F> {
+        let (vars, io) = z.split_at(self.num_vars);
+        io.iter().chain(vars).copied().collect()
+    }
+}
+
+impl<const C: usize, const M: usize, InstructionSet: JoltInstructionSet>
+    R1CSBuilder<C, M, InstructionSet>
+{
+    fn num_step_inputs(&self) -> usize {
+        self.num_variables - 1 - self.num_internal
+    }
+
+    /// Exports the uniform constraints of a single step, built by `jolt_r1cs_matrices`. The
+    /// step inputs become private inputs and the auxiliary variables internal wires.
+    pub fn to_circom<F: JoltField>(&self) -> CircomR1CS<F> {
+        // The constant is the last column of the builder's witness and the first circom wire.
+        let num_variables = self.num_variables;
+        let wire = |col: usize| ((col + 1) % num_variables) as u32;
+        CircomR1CS {
+            num_wires: num_variables as u32,
+            num_pub_out: 0,
+            num_pub_in: 0,
+            num_prv_in: self.num_step_inputs() as u32,
+            constraints: CircomR1CS::from_matrices(
+                self.num_constraints,
+                [&self.A[..], &self.B[..], &self.C[..]],
+                wire,
+                i64_to_f::<F>,
+            ),
+        }
+    }
+
+    /// Returns the circom wire assignment of a step given its `inputs`, laid out as in the
+    /// witness.
+    pub fn circom_witness<F: JoltField>(&self, inputs: &[F]) -> Vec<F> {
+        assert_eq!(inputs.len(), self.num_step_inputs());
+        let mut witness = Vec::with_capacity(self.num_variables);
+        witness.push(F::one());
+        witness.extend_from_slice(inputs);
+        witness.extend(self.compute_aux(inputs));
+        witness
+    }
+}
+
+/// Writes a wire assignment in the `.wtns` format.
+pub fn write_wtns<F: JoltField, W: Write>(witness: &[F], mut writer: W) -> io::Result<()> {
+    let field_size = field_size::<F>();
+
+    let mut header = vec![];
+    header.extend((field_size as u32).to_le_bytes());
+    header.extend(modulus_bytes::<F>());
+    header.extend((witness.len() as u32).to_le_bytes());
+
+    let mut values = Vec::with_capacity(witness.len() * field_size);
+    for value in witness {
+        write_field(&mut values, value);
+    }
+
+    writer.write_all(WTNS_MAGIC)?;
+    writer.write_all(&WTNS_VERSION.to_le_bytes())?;
+    writer.write_all(&2u32.to_le_bytes())?;
+    write_section(&mut writer, WTNS_HEADER_SECTION, &header)?;
+    write_section(&mut writer, WTNS_WITNESS_SECTION, &values)
+}
+
+/// Reads a wire assignment in the `.wtns` format over the field `F`.
+pub fn read_wtns<F: JoltField, R: Read>(reader: R) -> io::Result<Vec<F>> {
+    let sections = read_sections(reader, WTNS_MAGIC, WTNS_VERSION)?;
+
+    let mut header = find_section(&sections, WTNS_HEADER_SECTION)?;
+    read_field_header::<F>(&mut header)?;
+    let num_values = read_u32(&mut header)?;
+
+    let mut data = find_section(&sections, WTNS_WITNESS_SECTION)?;
+    (0..num_values).map(|_| read_field(&mut data)).collect()
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// The number of bytes of a serialized field element.
+fn field_size<F: JoltField>() -> usize {
+    F::zero().compressed_size()
+}
+
+/// The field modulus, in little-endian bytes.
+fn modulus_bytes<F: JoltField>() -> Vec<u8> {
+    let mut bytes = vec![];
+    write_field(&mut bytes, &-F::one());
+    // p = (p - 1) + 1
+    for byte in bytes.iter_mut() {
+        let (sum, carry) = byte.overflowing_add(1);
+        *byte = sum;
+        if !carry {
+            break;
+        }
+    }
+    bytes
+}
+
+/// Field elements are serialized in little-endian, non-Montgomery form.
+fn write_field<F: JoltField>(bytes: &mut Vec<u8>, value: &F) {
+    value.serialize_compressed(bytes).unwrap();
+}
+
+fn read_field<F: JoltField>(data: &mut &[u8]) -> io::Result<F> {
+    let bytes = read_bytes(data, field_size::<F>())?;
+    F::deserialize_compressed(bytes).map_err(|_| invalid_data("invalid field element"))
+}
+
+/// Checks that the field described by a header is `F`.
+fn read_field_header<F: JoltField>(header: &mut &[u8]) -> io::Result<()> {
+    let size = read_u32(header)? as usize;
+    if size != field_size::<F>() || read_bytes(header, size)? != modulus_bytes::<F>() {
+        return Err(invalid_data("field mismatch"));
+    }
+    Ok(())
+}
+
+fn write_section<W: Write>(writer: &mut W, section_type: u32, content: &[u8]) -> io::Result<()> {
+    writer.write_all(&section_type.to_le_bytes())?;
+    writer.write_all(&(content.len() as u64).to_le_bytes())?;
+    writer.write_all(content)
+}
+
+/// Reads the whole file and splits it into (type, content) sections.
+fn read_sections<R: Read>(
+    mut reader: R,
+    magic: &[u8; 4],
+    version: u32,
+) -> io::Result<Vec<(u32, Vec<u8>)>> {
+    let mut file = vec![];
+    reader.read_to_end(&mut file)?;
+    let mut data = file.as_slice();
+
+    if read_bytes(&mut data, 4)? != magic {
+        return Err(invalid_data("invalid magic"));
+    }
+    if read_u32(&mut data)? != version {
+        return Err(invalid_data("unsupported version"));
+    }
+    let num_sections = read_u32(&mut data)?;
+    (0..num_sections)
+        .map(|_| {
+            let section_type = read_u32(&mut data)?;
+            let size = read_u64(&mut data)?;
+            let size = usize::try_from(size).map_err(|_| invalid_data("section too large"))?;
+            Ok((section_type, read_bytes(&mut data, size)?.to_vec()))
+        })
+        .collect()
+}
+
+fn find_section(sections: &[(u32, Vec<u8>)], section_type: u32) -> io::Result<&[u8]> {
+    sections
+        .iter()
+        .find(|(t, _)| *t == section_type)
+        .map(|(_, content)| content.as_slice())
+        .ok_or_else(|| invalid_data("missing section"))
+}
+
+fn read_bytes<'a>(data: &mut &'a [u8], len: usize) -> io::Result<&'a [u8]> {
+    if data.len() < len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+    let (bytes, rest) = data.split_at(len);
+    *data = rest;
+    Ok(bytes)
+}
+
+fn read_u32(data: &mut &[u8]) -> io::Result<u32> {
+    Ok(u32::from_le_bytes(read_bytes(data, 4)?.try_into().unwrap()))
+}
+
+fn read_u64(data: &mut &[u8]) -> io::Result<u64> {
+    Ok(u64::from_le_bytes(read_bytes(data, 8)?.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jolt::vm::rv32i_vm::RV32I;
+    use crate::r1cs::{builder::Variable, constraints::JoltIn, spartan::UniformShapeBuilder};
+    use ark_bn254::Fr;
+    use common::constants::RAM_START_ADDRESS;
+
+    fn jolt_shape() -> R1CSBuilder<4, { 1 << 16 }, RV32I> {
+        let mut jolt_shape = R1CSBuilder::default();
+        R1CSBuilder::jolt_r1cs_matrices(&mut jolt_shape, RAM_START_ADDRESS);
+        jolt_shape
+    }
+
+    #[test]
+    fn r1cs_round_trip() {
+        let jolt_shape = jolt_shape();
+        let r1cs: CircomR1CS<Fr> = jolt_shape.to_circom();
+        assert_eq!(r1cs.constraints.len(), jolt_shape.num_constraints);
+
+        let mut bytes = vec![];
+        r1cs.write(&mut bytes).unwrap();
+        assert_eq!(&bytes[..4], b"r1cs");
+        // The header starts with the field size, followed by the BN254 scalar field modulus.
+        assert_eq!(bytes[24..28], 32u32.to_le_bytes());
+        assert_eq!(bytes[28..32], [0x01, 0x00, 0x00, 0xf0]);
+        assert_eq!(CircomR1CS::<Fr>::read(bytes.as_slice()).unwrap(), r1cs);
+
+        assert!(CircomR1CS::<Fr>::read(&bytes[..bytes.len() - 1]).is_err());
+        bytes[0] = b'x';
+        assert!(CircomR1CS::<Fr>::read(bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn step_witness_satisfies_exported_r1cs() {
+        let jolt_shape = jolt_shape();
+        let r1cs: CircomR1CS<Fr> = jolt_shape.to_circom();
+
+        // A no-op padding step.
+        let inputs = vec![Fr::zero(); r1cs.num_prv_in as usize];
+        let witness = jolt_shape.circom_witness(&inputs);
+        assert!(r1cs.is_satisfied(&witness));
+
+        let mut bytes = vec![];
+        write_wtns(&witness, &mut bytes).unwrap();
+        assert_eq!(&bytes[..4], b"wtns");
+        let mut witness = read_wtns::<Fr, _>(bytes.as_slice()).unwrap();
+        assert!(r1cs.is_satisfied(&witness));
+
+        // Claim the no-op is a load without a matching memory address.
+        let Variable::Input(is_load) =
+            R1CSBuilder::<4, { 1 << 16 }, RV32I>::input(JoltIn::OpFlag(2))
+        else {
+            unreachable!()
+        };
+        witness[1 + is_load] = Fr::one();
+        assert!(!r1cs.is_satisfied(&witness));
+    }
+
+    #[test]
+    fn spartan_shape_export() {
+        let jolt_shape = jolt_shape();
+        let shape: R1CSShape<Fr> = jolt_shape.single_step_shape(RAM_START_ADDRESS);
+        let r1cs = CircomR1CS::from(&shape);
+        assert_eq!(r1cs.num_prv_in as usize, shape.num_vars);
+
+        let inputs = vec![Fr::zero(); jolt_shape.num_step_inputs()];
+        let mut z = jolt_shape.circom_witness(&inputs)[1..].to_vec();
+        z.resize(shape.num_vars, Fr::zero());
+        z.push(Fr::one());
+        assert!(r1cs.is_satisfied(&shape.circom_witness(&z)));
+
+        let mut bytes = vec![];
+        r1cs.write(&mut bytes).unwrap();
+        assert_eq!(CircomR1CS::<Fr>::read(bytes.as_slice()).unwrap(), r1cs);
+    }
+
+    /// A file written by circom, taken from the ark-circom test suite.
+    #[test]
+    fn read_circom_sample() {
+        let hex = "
+            72316373
+            01000000
+            03000000
+            01000000 40000000 00000000
+            20000000
+            010000f0 93f5e143 9170b979 48e83328 5d588181 b64550b8 29a031e1 724e6430
+            07000000
+            01000000
+            02000000
+            03000000
+            e8030000 00000000
+            03000000
+            02000000 88020000 00000000
+            02000000
+            05000000 03000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            06000000 08000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            03000000
+            00000000 02000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            02000000 14000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            03000000 0C000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            02000000
+            00000000 05000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            02000000 07000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            03000000
+            01000000 04000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            04000000 08000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            05000000 03000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            02000000
+            03000000 2C000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            06000000 06000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            00000000
+            01000000
+            06000000 04000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            03000000
+            00000000 06000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            02000000 0B000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            03000000 05000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            01000000
+            06000000 58020000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+            03000000 38000000 00000000
+            00000000 00000000
+            03000000 00000000
+            0a000000 00000000
+            0b000000 00000000
+            0c000000 00000000
+            0f000000 00000000
+            44010000 00000000
+        ";
+        let hex: String = hex.split_whitespace().collect();
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+
+        let r1cs = CircomR1CS::<Fr>::read(bytes.as_slice()).unwrap();
+        assert_eq!(r1cs.num_wires, 7);
+        assert_eq!(r1cs.num_pub_out, 1);
+        assert_eq!(r1cs.num_pub_in, 2);
+        assert_eq!(r1cs.num_prv_in, 3);
+        assert_eq!(r1cs.constraints.len(), 3);
+        assert_eq!(r1cs.constraints[0].0.len(), 2);
+        assert_eq!(r1cs.constraints[0].0[0], (5, Fr::from(3u64)));
+        assert_eq!(r1cs.constraints[2].1[0], (0, Fr::from(6u64)));
+        assert!(r1cs.constraints[1].2.is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+pub mod builder;
+pub mod circom;
 pub mod constraints;
 pub mod r1cs_shape;
 pub mod snark;
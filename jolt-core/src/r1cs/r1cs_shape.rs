@@ -1,6 +1,7 @@
 use std::cmp::max;
 
 use crate::poly::field::JoltField;
+use crate::poly::padded_polynomial::PaddedPolynomial;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rayon::prelude::*;
 
@@ -8,6 +9,11 @@ use crate::utils::mul_0_1_optimized;
 
 use super::spartan::{IndexablePoly, SpartanError};
 
+/// Bound on the absolute value of the entries of Az, Bz and Cz computed by
+/// `R1CSShape::multiply_vec_uniform_small`. It leaves enough headroom to evaluate them at 2 and 3
+/// in the first round of the outer sumcheck without overflowing an `i128`.
+pub const SMALL_VALUE_BOUND: u128 = 1 << 100;
+
 /// A type that holds the shape of the R1CS matrices
 #[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct R1CSShape<F: JoltField> {
@@ -259,6 +265,107 @@ impl<F: JoltField> R1CSShape<F> {
         Ok(())
     }
 
+    /// Same as `multiply_vec_uniform`, for a witness whose entries are all small integers (see
+    /// `R1CSInputs::clone_to_trace_len_small_chunks`). Az, Bz and Cz are accumulated over `i128` and
+    /// each entry is bounded in absolute value by `SMALL_VALUE_BOUND`, so that the first round of
+    /// the outer sumcheck can also be computed over integers.
+    ///
+    /// Returns `None` if a coefficient of the shape is not a small integer or an entry exceeds
+    /// the bound, in which case the caller should fall back to `multiply_vec_uniform`.
+    #[tracing::instrument(skip_all, name = "R1CSShape::multiply_vec_uniform_small")]
+    pub fn multiply_vec_uniform_small(
+        &self,
+        witness_segments: &[PaddedPolynomial<i128>],
+        num_steps: usize, // padded length
+        len: usize,
+    ) -> Option<(Vec<i128>, Vec<i128>, Vec<i128>)> {
+        let small_matrix = |M: &[(usize, usize, F)]| -> Option<Vec<(usize, usize, i64)>> {
+            M.iter()
+                .map(|(row, col, val)| Some((*row, *col, i64::try_from(val.to_i128()?).ok()?)))
+                .collect()
+        };
+        let (A, B, C) = (
+            small_matrix(&self.A)?,
+            small_matrix(&self.B)?,
+            small_matrix(&self.C)?,
+        );
+
+        // Index into the full witness vector, as in `SegmentedPaddedWitness`
+        let witness = |index: usize| -> i128 {
+            witness_segments
+                .get(index / num_steps)
+                .map_or(0, |segment| segment[index % num_steps])
+        };
+
+        // Pre-processes matrix to return the indices of the start of each row
+        let get_row_pointers = |M: &[(usize, usize, i64)]| -> Vec<usize> {
+            let mut indptr = vec![0; self.num_cons + 1];
+            for &(row, _, _) in M {
+                indptr[row + 1] += 1;
+            }
+            for i in 0..self.num_cons {
+                indptr[i + 1] += indptr[i];
+            }
+            indptr
+        };
+
+        let sparse_matrix_vec_product_uniform = |M: &[(usize, usize, i64)]| -> Option<Vec<i128>> {
+            let row_pointers = get_row_pointers(M);
+            let mut result = vec![0i128; len];
+            result
+                .par_chunks_mut(num_steps)
+                .take(self.num_cons)
+                .enumerate()
+                .try_for_each(|(row_index, row_output)| {
+                    // Each product fits in an i128 since coefficients are i64s and witness
+                    // entries are (possibly negated) u64s; only the sums need checking.
+                    for &(_, col, val) in &M[row_pointers[row_index]..row_pointers[row_index + 1]] {
+                        let val = val as i128;
+                        if col == self.num_vars {
+                            row_output.par_iter_mut().try_for_each(|x| {
+                                *x = x.checked_add(val)?;
+                                Some(())
+                            })?;
+                        } else if col == 1 {
+                            // pc_out variable index is 1 (pc_in is 0)
+                            row_output
+                                .par_iter_mut()
+                                .enumerate()
+                                .try_for_each(|(i, x)| {
+                                    // pc_out[i] = pc_in[i+1] = index i+1 in z
+                                    *x = x.checked_add(val * witness(i + 1))?;
+                                    Some(())
+                                })?;
+                        } else if let Some(segment) = witness_segments.get(col) {
+                            row_output
+                                .par_iter_mut()
+                                .enumerate()
+                                .try_for_each(|(i, x)| {
+                                    *x = x.checked_add(val * segment[i])?;
+                                    Some(())
+                                })?;
+                        }
+                    }
+                    row_output
+                        .par_iter()
+                        .all(|x| x.unsigned_abs() <= SMALL_VALUE_BOUND)
+                        .then_some(())
+                })?;
+            Some(result)
+        };
+
+        let (Az, (Bz, Cz)) = rayon::join(
+            || sparse_matrix_vec_product_uniform(&A),
+            || {
+                rayon::join(
+                    || sparse_matrix_vec_product_uniform(&B),
+                    || sparse_matrix_vec_product_uniform(&C),
+                )
+            },
+        );
+        Some((Az?, Bz?, Cz?))
+    }
+
     /// Pads the R1CSShape so that the number of variables is a power of two
     /// Renumbers variables to accomodate padded variables
     pub fn pad(&self) -> Self {
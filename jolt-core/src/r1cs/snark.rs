@@ -5,14 +5,14 @@
 )]
 
 use crate::poly::commitment::commitment_scheme::{BatchType, CommitmentScheme};
+use crate::poly::compact_polynomial::{CompactPolynomial, Padding, SmallInt};
+use crate::poly::padded_polynomial::PaddedPolynomial;
+use crate::utils::math::Math;
 use crate::utils::transcript::AppendToTranscript;
 use crate::{
-    jolt::vm::{rv32i_vm::RV32I, JoltCommitments},
+    jolt::{instruction::JoltInstructionSet, vm::JoltCommitments},
     r1cs::r1cs_shape::R1CSShape,
-    utils::{
-        thread::{drop_in_background_thread, unsafe_allocate_zero_vec},
-        transcript::ProofTranscript,
-    },
+    utils::{thread::drop_in_background_thread, transcript::ProofTranscript},
 };
 
 use super::{
@@ -21,130 +21,158 @@ use super::{
 };
 
 use crate::poly::field::JoltField;
+use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use common::{constants::MEMORY_OPS_PER_INSTRUCTION, rv_trace::NUM_CIRCUIT_FLAGS};
+use common::constants::MEMORY_OPS_PER_INSTRUCTION;
 use rayon::prelude::*;
-use std::borrow::Borrow;
-use strum::EnumCount;
 
 #[tracing::instrument(name = "synthesize_witnesses", skip_all)]
-/// Returns (io, aux) = (pc_out, pc, aux)
-fn synthesize_witnesses<F: JoltField>(
-    inputs: &R1CSInputs<F>,
-    num_aux: usize,
-) -> (Vec<F>, Vec<F>, Vec<Vec<F>>) {
+/// Returns (io, aux) = ([pc_out, pc], aux), along with the whole witness (io, inputs, aux) as
+/// integers if every auxiliary variable fits in an `i128`. Only the steps whose inputs are
+/// stored are synthesized, plus one padding step whose values every later step repeats.
+fn synthesize_witnesses<const C: usize, const M: usize, InstructionSet, F>(
+    jolt_shape: &R1CSBuilder<C, M, InstructionSet>,
+    inputs: &R1CSInputs,
+) -> (
+    Vec<PaddedPolynomial<F>>,
+    Vec<PaddedPolynomial<F>>,
+    Option<Vec<PaddedPolynomial<i128>>>,
+)
+where
+    InstructionSet: JoltInstructionSet,
+    F: JoltField,
+{
     let span = tracing::span!(tracing::Level::TRACE, "synthesize_witnesses");
     let _enter = span.enter();
-    let triples_stepwise: Vec<(Vec<F>, F, F)> = (0..inputs.padded_trace_len)
+    let num_synthesized_steps = std::cmp::min(inputs.num_steps + 1, inputs.padded_trace_len);
+    let small_aux_stepwise: Option<Vec<Vec<i128>>> = (0..num_synthesized_steps)
         .into_par_iter()
         .map(|i| {
-            let step = inputs.clone_step(i);
-            let pc_cur = step.input_pc;
-            let aux = R1CSBuilder::calculate_jolt_aux(step, num_aux);
-            (aux, pc_cur, F::zero())
+            let step: Vec<i128> = inputs.clone_step(i).into_iter().map(i128::from).collect();
+            jolt_shape.compute_aux_small(&step)
+        })
+        .collect();
+    let aux_stepwise: Vec<Vec<F>> = (0..num_synthesized_steps)
+        .into_par_iter()
+        .map(|i| match &small_aux_stepwise {
+            Some(small_aux) => small_aux[i]
+                .iter()
+                .map(|&value| F::from_i128(value))
+                .collect(),
+            None => {
+                let step: Vec<F> = inputs
+                    .clone_step(i)
+                    .into_iter()
+                    .map(SmallInt::to_field)
+                    .collect();
+                jolt_shape.compute_aux(&step)
+            }
         })
         .collect();
     drop(_enter);
 
-    // TODO(sragss / arasuarun): Remove pc_out, pc from calculate_aux and triples_stepwise
+    // TODO(sragss / arasuarun): Remove pc_out, pc from calculate_aux
 
     // Convert step-wise to variable-wise
     // [[aux_var_0, aux_var_1, ...], [aux_var_0, aux_var_1, ...], ...] => [[aux_var_0, aux_var_0, ...], [aux_var_1, aux_var_1, ...], ...]
     // Aux result shape: aux[num_vars][num_steps]
 
-    let num_vars = triples_stepwise[0].0.len();
-    let mut aux: Vec<Vec<F>> = (0..num_vars)
-        .into_par_iter()
-        .map(|_| unsafe_allocate_zero_vec::<F>(inputs.padded_trace_len))
-        .collect();
-    let mut pc_out: Vec<F> = unsafe_allocate_zero_vec(inputs.padded_trace_len);
-    let mut pc: Vec<F> = unsafe_allocate_zero_vec(inputs.padded_trace_len);
-
     let other_span = tracing::span!(tracing::Level::TRACE, "aux_recombine");
     let _enter = other_span.enter();
-    aux.par_iter_mut()
-        .enumerate()
-        .for_each(|(var_index, aux_varwise)| {
-            for step_index in 0..inputs.padded_trace_len {
-                aux_varwise[step_index] = triples_stepwise[step_index].0[var_index];
-            }
-        });
+    let num_vars = aux_stepwise[0].len();
+    let aux: Vec<PaddedPolynomial<F>> = (0..num_vars)
+        .into_par_iter()
+        .map(|var_index| {
+            let values = aux_stepwise
+                .iter()
+                .map(|step_aux| step_aux[var_index])
+                .collect();
+            inputs.witness_segment(values, F::zero())
+        })
+        .collect();
+    let pc_out = inputs.witness_segment(
+        (0..num_synthesized_steps)
+            .map(|step_index| inputs.program_counter(step_index).to_field())
+            .collect(),
+        F::zero(),
+    );
+    let pc = inputs.witness_segment(vec![], F::zero());
+
+    // The small witness is laid out exactly like the field one.
+    let small_witness = small_aux_stepwise.map(|small_aux_stepwise| {
+        let small_pc_out = inputs.witness_segment(
+            (0..num_synthesized_steps)
+                .map(|step_index| i128::from(inputs.program_counter(step_index)))
+                .collect(),
+            0,
+        );
+        let mut small_witness = vec![small_pc_out, inputs.witness_segment(vec![], 0)];
+        small_witness.par_extend(inputs.clone_to_trace_len_small_chunks());
+        small_witness.par_extend((0..num_vars).into_par_iter().map(|var_index| {
+            let values = small_aux_stepwise
+                .iter()
+                .map(|step_aux| step_aux[var_index])
+                .collect();
+            inputs.witness_segment(values, 0)
+        }));
+        small_witness
+    });
     drop(_enter);
 
-    pc_out
-        .par_iter_mut()
-        .zip(pc.par_iter_mut())
-        .enumerate()
-        .for_each(|(step_index, (slot_out, slot))| {
-            *slot_out = triples_stepwise[step_index].1;
-            *slot = triples_stepwise[step_index].2;
-        });
-
-    drop_in_background_thread(triples_stepwise);
+    drop_in_background_thread(aux_stepwise);
 
-    (pc_out, pc, aux)
+    (vec![pc_out, pc], aux, small_witness)
 }
 
+/// The inputs to the R1CS that are committed by the rest of Jolt. They are all small unsigned
+/// integers, and are kept as such so that the witness can be synthesized without field
+/// arithmetic.
+///
+/// Each input is stored for the first `num_steps` steps only: the remaining steps pad the
+/// trace with no-ops, and all take the same inputs (`padding`).
 #[derive(Clone, Debug, Default)]
-pub struct R1CSInputs<'a, F: JoltField> {
+pub struct R1CSInputs {
     padded_trace_len: usize,
-    bytecode_a: Vec<F>,
-    bytecode_v: Vec<F>,
-    memreg_a_rw: &'a [F],
-    memreg_v_reads: Vec<&'a F>,
-    memreg_v_writes: Vec<&'a F>,
-    chunks_x: Vec<F>,
-    chunks_y: Vec<F>,
-    chunks_query: Vec<F>,
-    lookup_outputs: Vec<F>,
-    circuit_flags_bits: Vec<F>,
-    instruction_flags_bits: Vec<F>,
+    num_steps: usize,
+    bytecode_a: Vec<u64>,
+    bytecode_v: Vec<u64>,
+    memreg_a_rw: Vec<u64>,
+    memreg_v_reads: Vec<u64>,
+    memreg_v_writes: Vec<u64>,
+    chunks_x: Vec<u64>,
+    chunks_y: Vec<u64>,
+    chunks_query: Vec<u64>,
+    lookup_outputs: Vec<u64>,
+    circuit_flags_bits: Vec<u64>,
+    instruction_flags_bits: Vec<u64>,
+    /// The inputs to each padding step, laid out as in `clone_step` without the PCs.
+    padding: Vec<u64>,
 }
 
-#[derive(Clone, Debug, Default)]
-pub struct R1CSStepInputs<F: JoltField> {
-    pub padded_trace_len: usize,
-    pub input_pc: F,
-    pub bytecode_v: Vec<F>,
-    pub memreg_v_reads: Vec<F>,
-    pub memreg_v_writes: Vec<F>,
-    pub chunks_y: Vec<F>,
-    pub chunks_query: Vec<F>,
-    pub lookup_outputs: Vec<F>,
-    pub circuit_flags_bits: Vec<F>,
-    pub instruction_flags_bits: Vec<F>,
-}
-
-impl<'a, F: JoltField> R1CSInputs<'a, F> {
+impl R1CSInputs {
+    /// `num_steps` must be more than half of `padded_trace_len`, so that the witness segments
+    /// are over as many variables as the polynomials they're committed with.
     #[tracing::instrument(skip_all, name = "R1CSInputs::new")]
     pub fn new(
         padded_trace_len: usize,
-        bytecode_a: Vec<F>,
-        bytecode_v: Vec<F>,
-        memreg_a_rw: &'a [F],
-        memreg_v_reads: Vec<&'a F>,
-        memreg_v_writes: Vec<&'a F>,
-        chunks_x: Vec<F>,
-        chunks_y: Vec<F>,
-        chunks_query: Vec<F>,
-        lookup_outputs: Vec<F>,
-        circuit_flags_bits: Vec<F>,
-        instruction_flags_bits: Vec<F>,
+        num_steps: usize,
+        bytecode_a: Vec<u64>,
+        bytecode_v: Vec<u64>,
+        memreg_a_rw: Vec<u64>,
+        memreg_v_reads: Vec<u64>,
+        memreg_v_writes: Vec<u64>,
+        chunks_x: Vec<u64>,
+        chunks_y: Vec<u64>,
+        chunks_query: Vec<u64>,
+        lookup_outputs: Vec<u64>,
+        circuit_flags_bits: Vec<u64>,
+        instruction_flags_bits: Vec<u64>,
+        padding: Vec<u64>,
     ) -> Self {
-        assert!(bytecode_a.len() % padded_trace_len == 0);
-        assert!(bytecode_v.len() % padded_trace_len == 0);
-        assert!(memreg_a_rw.len() % padded_trace_len == 0);
-        assert!(memreg_v_reads.len() % padded_trace_len == 0);
-        assert!(memreg_v_writes.len() % padded_trace_len == 0);
-        assert!(chunks_x.len() % padded_trace_len == 0);
-        assert!(chunks_y.len() % padded_trace_len == 0);
-        assert!(chunks_query.len() % padded_trace_len == 0);
-        assert!(lookup_outputs.len() % padded_trace_len == 0);
-        assert!(circuit_flags_bits.len() % padded_trace_len == 0);
-        assert!(instruction_flags_bits.len() % padded_trace_len == 0);
-
-        Self {
+        assert_eq!(num_steps.next_power_of_two(), padded_trace_len);
+        let inputs = Self {
             padded_trace_len,
+            num_steps,
             bytecode_a,
             bytecode_v,
             memreg_a_rw,
@@ -156,138 +184,168 @@ impl<'a, F: JoltField> R1CSInputs<'a, F> {
             lookup_outputs,
             circuit_flags_bits,
             instruction_flags_bits,
+            padding,
+        };
+        for input in inputs.inputs() {
+            assert!(input.len() % num_steps == 0);
         }
+        assert_eq!(inputs.padding.len(), inputs.num_vars_per_step());
+        inputs
     }
 
-    fn push_to_step<T: Borrow<F>>(&self, data: &Vec<T>, step: &mut Vec<F>, step_index: usize) {
-        let num_vals = data.len() / self.padded_trace_len;
-        for var_index in 0..num_vals {
-            step.push(*data[var_index * self.padded_trace_len + step_index].borrow());
-        }
+    /// The inputs, in the order they are laid out in the witness.
+    fn inputs(&self) -> [&[u64]; 11] {
+        [
+            &self.bytecode_a,
+            &self.bytecode_v,
+            &self.memreg_a_rw,
+            &self.memreg_v_reads,
+            &self.memreg_v_writes,
+            &self.chunks_x,
+            &self.chunks_y,
+            &self.chunks_query,
+            &self.lookup_outputs,
+            &self.circuit_flags_bits,
+            &self.instruction_flags_bits,
+        ]
+    }
+
+    /// The stored steps of each variable, along with its value at the padding steps.
+    fn columns(&self) -> Vec<(&[u64], u64)> {
+        self.inputs()
+            .into_iter()
+            .flat_map(|input| input.chunks(self.num_steps))
+            .zip(self.padding.iter().copied())
+            .collect()
+    }
+
+    /// The columns of `input`, which must be one of `inputs()`.
+    fn input_columns(&self, input: &[u64]) -> Vec<(&[u64], u64)> {
+        let first_var: usize = self
+            .inputs()
+            .into_iter()
+            .take_while(|other| !std::ptr::eq(*other, input))
+            .map(|other| other.len() / self.num_steps)
+            .sum();
+        let columns = self.columns();
+        columns[first_var..first_var + input.len() / self.num_steps].to_vec()
     }
 
-    pub fn clone_step(&self, step_index: usize) -> R1CSStepInputs<F> {
-        let program_counter = if step_index > 0 && self.bytecode_a[step_index].is_zero() {
-            F::zero()
+    /// Creates a witness segment from the values of a variable at the synthesized steps, the
+    /// last of which is the padding step if there are more than `num_steps`.
+    fn witness_segment<T: Send + Sync>(&self, mut values: Vec<T>, zero: T) -> PaddedPolynomial<T> {
+        let padding = if values.len() > self.num_steps {
+            values.pop().unwrap()
         } else {
-            self.bytecode_a[step_index]
+            zero
         };
+        PaddedPolynomial::new(self.padded_trace_len.log_2(), values, padding)
+    }
 
-        let mut output = R1CSStepInputs {
-            padded_trace_len: self.padded_trace_len,
-            input_pc: program_counter,
-            bytecode_v: Vec::with_capacity(6),
-            memreg_v_reads: Vec::with_capacity(7),
-            memreg_v_writes: Vec::with_capacity(7),
-            chunks_y: Vec::with_capacity(4),
-            chunks_query: Vec::with_capacity(4),
-            lookup_outputs: Vec::with_capacity(2),
-            circuit_flags_bits: Vec::with_capacity(NUM_CIRCUIT_FLAGS),
-            instruction_flags_bits: Vec::with_capacity(RV32I::COUNT),
+    fn program_counter(&self, step_index: usize) -> u64 {
+        let address = match self.bytecode_a.get(step_index) {
+            Some(&address) => address,
+            None => self.padding[0],
         };
-        self.push_to_step(&self.bytecode_v, &mut output.bytecode_v, step_index);
-        self.push_to_step(&self.memreg_v_reads, &mut output.memreg_v_reads, step_index);
-        self.push_to_step(
-            &self.memreg_v_writes,
-            &mut output.memreg_v_writes,
-            step_index,
-        );
-        self.push_to_step(&self.chunks_y, &mut output.chunks_y, step_index);
-        self.push_to_step(&self.chunks_query, &mut output.chunks_query, step_index);
-        self.push_to_step(&self.lookup_outputs, &mut output.lookup_outputs, step_index);
-        self.push_to_step(
-            &self.circuit_flags_bits,
-            &mut output.circuit_flags_bits,
-            step_index,
-        );
-        self.push_to_step(
-            &self.instruction_flags_bits,
-            &mut output.instruction_flags_bits,
-            step_index,
-        );
+        if step_index > 0 && address == 0 {
+            0
+        } else {
+            address
+        }
+    }
+
+    /// Returns the inputs to step `step_index` of the R1CS, laid out as in the witness:
+    /// the input and output PC followed by the inputs committed elsewhere.
+    pub fn clone_step(&self, step_index: usize) -> Vec<u64> {
+        let mut output = Vec::with_capacity(2 + self.num_vars_per_step());
+        output.push(self.program_counter(step_index));
+        output.push(0);
+        if step_index >= self.num_steps {
+            output.extend_from_slice(&self.padding);
+            return output;
+        }
+        for input in self.inputs() {
+            output.extend(
+                input
+                    .iter()
+                    .skip(step_index)
+                    .step_by(self.num_steps)
+                    .copied(),
+            );
+        }
 
         output
     }
 
+    /// Checks every step against the uniform constraints, returning the index of the first
+    /// step that does not satisfy them along with a description of the unsatisfied constraint.
+    /// The padding steps all take the same inputs, so only the first of them is checked.
+    pub fn check_satisfied<
+        F: JoltField,
+        const C: usize,
+        const M: usize,
+        InstructionSet: JoltInstructionSet,
+    >(
+        &self,
+        memory_start: u64,
+    ) -> Result<(), (usize, String)> {
+        let mut jolt_shape = R1CSBuilder::<C, M, InstructionSet>::default();
+        R1CSBuilder::jolt_r1cs_matrices(&mut jolt_shape, memory_start);
+        (0..std::cmp::min(self.num_steps + 1, self.padded_trace_len))
+            .into_par_iter()
+            .map(|step_index| {
+                let step: Vec<F> = self
+                    .clone_step(step_index)
+                    .into_iter()
+                    .map(SmallInt::to_field)
+                    .collect();
+                jolt_shape
+                    .check_step(&step)
+                    .map_err(|unsatisfied| (step_index, jolt_shape.describe(&unsatisfied)))
+            })
+            .find_first(Result::is_err)
+            .unwrap_or(Ok(()))
+    }
+
     pub fn trace_len(&self) -> usize {
         self.padded_trace_len
     }
 
     pub fn num_vars_per_step(&self) -> usize {
-        let trace_len = self.trace_len();
-        self.bytecode_a.len() / trace_len
-            + self.bytecode_v.len() / trace_len
-            + self.memreg_a_rw.len() / trace_len
-            + self.memreg_v_reads.len() / trace_len
-            + self.memreg_v_writes.len() / trace_len
-            + self.chunks_x.len() / trace_len
-            + self.chunks_y.len() / trace_len
-            + self.chunks_query.len() / trace_len
-            + self.lookup_outputs.len() / trace_len
-            + self.circuit_flags_bits.len() / trace_len
-            + self.instruction_flags_bits.len() / trace_len
+        self.inputs()
+            .iter()
+            .map(|input| input.len() / self.num_steps)
+            .sum()
     }
 
     #[tracing::instrument(skip_all, name = "R1CSInputs::trace_len_chunks")]
-    pub fn clone_to_trace_len_chunks(&self, padded_trace_len: usize) -> Vec<Vec<F>> {
+    pub fn clone_to_trace_len_chunks<F: JoltField>(&self) -> Vec<PaddedPolynomial<F>> {
         // TODO(sragss / arasuarun): Explain why non-trace-len relevant stuff (ex: bytecode) gets chunked to padded_trace_len
-        let mut chunks: Vec<Vec<F>> = Vec::new();
-        chunks.par_extend(
-            self.bytecode_a
-                .par_chunks(padded_trace_len)
-                .map(|chunk| chunk.to_vec()),
-        );
-        chunks.par_extend(
-            self.bytecode_v
-                .par_chunks(padded_trace_len)
-                .map(|chunk| chunk.to_vec()),
-        );
-        chunks.par_extend(
-            self.memreg_a_rw
-                .par_chunks(padded_trace_len)
-                .map(|chunk| chunk.to_vec()),
-        );
-        chunks.par_extend(
-            self.memreg_v_reads
-                .par_chunks(padded_trace_len)
-                .map(|chunk| chunk.par_iter().map(|&elem| *elem).collect::<Vec<F>>()),
-        );
-        chunks.par_extend(
-            self.memreg_v_writes
-                .par_chunks(padded_trace_len)
-                .map(|chunk| chunk.par_iter().map(|&elem| *elem).collect::<Vec<F>>()),
-        );
-        chunks.par_extend(
-            self.chunks_x
-                .par_chunks(padded_trace_len)
-                .map(|chunk| chunk.to_vec()),
-        );
-        chunks.par_extend(
-            self.chunks_y
-                .par_chunks(padded_trace_len)
-                .map(|chunk| chunk.to_vec()),
-        );
-        chunks.par_extend(
-            self.chunks_query
-                .par_chunks(padded_trace_len)
-                .map(|chunk| chunk.to_vec()),
-        );
-        chunks.par_extend(
-            self.lookup_outputs
-                .par_chunks(padded_trace_len)
-                .map(|chunk| chunk.to_vec()),
-        );
-        chunks.par_extend(
-            self.circuit_flags_bits
-                .par_chunks(padded_trace_len)
-                .map(|chunk| chunk.to_vec()),
-        );
-        chunks.par_extend(
-            self.instruction_flags_bits
-                .par_chunks(padded_trace_len)
-                .map(|chunk| chunk.to_vec()),
-        );
-        chunks
+        self.columns()
+            .into_par_iter()
+            .map(|(column, padding)| {
+                PaddedPolynomial::new(
+                    self.padded_trace_len.log_2(),
+                    column.iter().map(|&value| value.to_field()).collect(),
+                    padding.to_field(),
+                )
+            })
+            .collect()
+    }
+
+    /// Same as `clone_to_trace_len_chunks`, but as integers.
+    #[tracing::instrument(skip_all, name = "R1CSInputs::trace_len_small_chunks")]
+    pub fn clone_to_trace_len_small_chunks(&self) -> Vec<PaddedPolynomial<i128>> {
+        self.columns()
+            .into_par_iter()
+            .map(|(column, padding)| {
+                PaddedPolynomial::new(
+                    self.padded_trace_len.log_2(),
+                    column.iter().map(|&value| i128::from(value)).collect(),
+                    i128::from(padding),
+                )
+            })
+            .collect()
     }
 }
 
@@ -321,27 +379,38 @@ impl<C: CommitmentScheme> AppendToTranscript for R1CSCommitment<C> {
 }
 
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
-pub struct R1CSProof<F: JoltField, C: CommitmentScheme<Field = F>> {
+pub struct R1CSProof<F: JoltField, PCS: CommitmentScheme<Field = F>> {
     pub key: UniformSpartanKey<F>,
-    proof: UniformSpartanProof<F, C>,
+    proof: UniformSpartanProof<F, PCS>,
 }
 
-impl<F: JoltField, C: CommitmentScheme<Field = F>> R1CSProof<F, C> {
+impl<F: JoltField, PCS: CommitmentScheme<Field = F>> R1CSProof<F, PCS> {
     /// Computes the full witness in segments of len `padded_trace_len`, commits to new required intermediary variables.
+    /// The witness is also returned as integers when every entry fits in an `i128`, which lets
+    /// Spartan skip field arithmetic in its first sumcheck.
     #[tracing::instrument(skip_all, name = "R1CSProof::compute_witness_commit")]
-    pub fn compute_witness_commit(
-        _W: usize,
-        _C: usize,
+    pub fn compute_witness_commit<const C: usize, const M: usize, InstructionSet>(
         padded_trace_len: usize,
         memory_start: u64,
-        inputs: &R1CSInputs<F>,
-        generators: &C::Setup,
-    ) -> Result<(UniformSpartanKey<F>, Vec<Vec<F>>, R1CSCommitment<C>), SpartanError> {
+        inputs: &R1CSInputs,
+        generators: &PCS::Setup,
+    ) -> Result<
+        (
+            UniformSpartanKey<F>,
+            Vec<PaddedPolynomial<F>>,
+            Option<Vec<PaddedPolynomial<i128>>>,
+            R1CSCommitment<PCS>,
+        ),
+        SpartanError,
+    >
+    where
+        InstructionSet: JoltInstructionSet,
+    {
         let span = tracing::span!(tracing::Level::TRACE, "shape_stuff");
         let _enter = span.enter();
-        let mut jolt_shape = R1CSBuilder::default();
+        let mut jolt_shape = R1CSBuilder::<C, M, InstructionSet>::default();
         R1CSBuilder::jolt_r1cs_matrices(&mut jolt_shape, memory_start);
-        let key = UniformSpartanProof::<F, C>::setup_precommitted(
+        let key = UniformSpartanProof::<F, PCS>::setup_precommitted(
             &jolt_shape,
             padded_trace_len,
             memory_start,
@@ -349,28 +418,38 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> R1CSProof<F, C> {
         drop(_enter);
         drop(span);
 
-        let (pc_out, pc, aux) = synthesize_witnesses(inputs, jolt_shape.num_internal);
-        let io_segments = vec![pc_out, pc];
-        let io_segments_ref = vec![io_segments[0].as_slice(), io_segments[1].as_slice()];
-        let aux_ref: Vec<&[F]> = aux.iter().map(AsRef::as_ref).collect();
-        let io_comms = C::batch_commit(io_segments_ref.as_slice(), generators, BatchType::Big);
-        let aux_comms = C::batch_commit(aux_ref.as_slice(), generators, BatchType::Big);
+        let (io_segments, aux, small_w_segments) = synthesize_witnesses(&jolt_shape, inputs);
+        let io_segments_ref: Vec<&PaddedPolynomial<F>> = io_segments.iter().collect();
+        let aux_ref: Vec<&PaddedPolynomial<F>> = aux.iter().collect();
+        let io_comms = PCS::batch_commit_padded(&io_segments_ref, generators, BatchType::Big);
+        let aux_comms = PCS::batch_commit_padded(&aux_ref, generators, BatchType::Big);
 
         let span = tracing::span!(tracing::Level::INFO, "new_commitments");
         let _guard = span.enter();
-        let chunk_batch_size =
-            inputs.chunks_x.len() / padded_trace_len + inputs.chunks_y.len() / padded_trace_len;
-        let mut chunk_batch_slices: Vec<&[F]> = Vec::with_capacity(chunk_batch_size);
-        for batchee in [&inputs.chunks_x, &inputs.chunks_y].iter() {
-            chunk_batch_slices.extend(batchee.chunks(padded_trace_len));
-        }
+        let compact_chunks = |input: &[u64]| -> Vec<CompactPolynomial<u64, F>> {
+            inputs
+                .input_columns(input)
+                .into_iter()
+                .map(|(column, padding)| {
+                    CompactPolynomial::padded(column.to_vec(), Padding::Constant(padding))
+                })
+                .collect()
+        };
+        let chunk_polys: Vec<_> = [&inputs.chunks_x, &inputs.chunks_y]
+            .into_iter()
+            .flat_map(|batchee| compact_chunks(batchee))
+            .collect();
+        let chunk_polys_ref: Vec<_> = chunk_polys.iter().collect();
         let chunks_comms =
-            C::batch_commit(chunk_batch_slices.as_slice(), generators, BatchType::Big);
+            PCS::batch_commit_compact(chunk_polys_ref.as_slice(), generators, BatchType::Big);
 
-        let circuit_flag_slices: Vec<&[F]> =
-            inputs.circuit_flags_bits.chunks(padded_trace_len).collect();
-        let circuit_flags_comms =
-            C::batch_commit(circuit_flag_slices.as_slice(), generators, BatchType::Big);
+        let circuit_flag_polys = compact_chunks(&inputs.circuit_flags_bits);
+        let circuit_flag_polys_ref: Vec<_> = circuit_flag_polys.iter().collect();
+        let circuit_flags_comms = PCS::batch_commit_compact(
+            circuit_flag_polys_ref.as_slice(),
+            generators,
+            BatchType::Big,
+        );
         drop(_guard);
 
         let r1cs_commitments = R1CSCommitment {
@@ -383,9 +462,9 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> R1CSProof<F, C> {
         let cloning_stuff_span =
             tracing::span!(tracing::Level::TRACE, "cloning_to_witness_segments");
         let _enter = cloning_stuff_span.enter();
-        let inputs_segments = inputs.clone_to_trace_len_chunks(padded_trace_len);
+        let inputs_segments = inputs.clone_to_trace_len_chunks();
 
-        let mut w_segments: Vec<Vec<F>> =
+        let mut w_segments: Vec<PaddedPolynomial<F>> =
             Vec::with_capacity(io_segments.len() + inputs_segments.len() + aux.len());
         w_segments.extend(io_segments.into_iter());
         w_segments.par_extend(inputs_segments.into_par_iter());
@@ -394,33 +473,56 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> R1CSProof<F, C> {
         drop(_enter);
         drop(cloning_stuff_span);
 
-        Ok((key, w_segments, r1cs_commitments))
+        Ok((key, w_segments, small_w_segments, r1cs_commitments))
     }
 
+    /// `small_witness_segments`, if given, must be `witness_segments` as integers.
     #[tracing::instrument(skip_all, name = "R1CSProof::prove")]
     pub fn prove(
         key: UniformSpartanKey<F>,
-        witness_segments: Vec<Vec<F>>,
+        witness_segments: Vec<PaddedPolynomial<F>>,
+        small_witness_segments: Option<Vec<PaddedPolynomial<i128>>>,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
     ) -> Result<Self, SpartanError> {
         // TODO(sragss): Fiat shamir (relevant) commitments
-        let proof = UniformSpartanProof::prove_precommitted(&key, witness_segments, transcript)?;
-        Ok(R1CSProof::<F, C> { proof, key })
+        let proof = UniformSpartanProof::prove_precommitted(
+            &key,
+            witness_segments,
+            small_witness_segments,
+            opening_accumulator,
+            transcript,
+        )?;
+        Ok(R1CSProof::<F, PCS> { proof, key })
     }
 
-    fn format_commitments(jolt_commitments: &JoltCommitments<C>, C: usize) -> Vec<&C::Commitment> {
-        let r1cs_commitments = &jolt_commitments.r1cs;
+    fn format_commitments<const C: usize, InstructionSet: JoltInstructionSet>(
+        jolt_commitments: &JoltCommitments<PCS>,
+    ) -> Result<Vec<&PCS::Commitment>, SpartanError> {
+        let r1cs_commitments = jolt_commitments
+            .r1cs
+            .as_ref()
+            .ok_or(SpartanError::InvalidWitnessLength)?;
         let bytecode_trace_commitments = &jolt_commitments.bytecode.trace_commitments;
+        // Malformed commitments must be rejected before they are indexed into below
+        if bytecode_trace_commitments.len() < 8
+            || jolt_commitments.read_write_memory.trace_commitments.len()
+                < 1 + MEMORY_OPS_PER_INSTRUCTION + 5
+            || jolt_commitments.instruction_lookups.trace_commitment.len()
+                < std::cmp::max(C, InstructionSet::COUNT + 1)
+        {
+            return Err(SpartanError::InvalidWitnessLength);
+        }
         let memory_trace_commitments = &jolt_commitments.read_write_memory.trace_commitments
             [..1 + MEMORY_OPS_PER_INSTRUCTION + 5]; // a_read_write, v_read, v_write
         let instruction_lookup_indices_commitments =
             &jolt_commitments.instruction_lookups.trace_commitment[..C];
         let instruction_flag_commitments = &jolt_commitments.instruction_lookups.trace_commitment
-            [jolt_commitments.instruction_lookups.trace_commitment.len() - RV32I::COUNT - 1
+            [jolt_commitments.instruction_lookups.trace_commitment.len() - InstructionSet::COUNT - 1
                 ..jolt_commitments.instruction_lookups.trace_commitment.len() - 1];
 
-        let mut combined_commitments: Vec<&C::Commitment> = Vec::new();
-        combined_commitments.extend(r1cs_commitments.as_ref().unwrap().io.iter());
+        let mut combined_commitments: Vec<&PCS::Commitment> = Vec::new();
+        combined_commitments.extend(r1cs_commitments.io.iter());
 
         combined_commitments.push(&bytecode_trace_commitments[0]); // "virtual" address
         combined_commitments.push(&bytecode_trace_commitments[2]); // "real" address
@@ -432,7 +534,7 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> R1CSProof<F, C> {
 
         combined_commitments.extend(memory_trace_commitments.iter());
 
-        combined_commitments.extend(r1cs_commitments.as_ref().unwrap().chunks.iter());
+        combined_commitments.extend(r1cs_commitments.chunks.iter());
 
         combined_commitments.extend(instruction_lookup_indices_commitments.iter());
 
@@ -444,38 +546,43 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> R1CSProof<F, C> {
                 .unwrap(),
         );
 
-        combined_commitments.extend(r1cs_commitments.as_ref().unwrap().circuit_flags.iter());
+        combined_commitments.extend(r1cs_commitments.circuit_flags.iter());
 
         combined_commitments.extend(instruction_flag_commitments.iter());
 
-        combined_commitments.extend(r1cs_commitments.as_ref().unwrap().aux.iter());
+        combined_commitments.extend(r1cs_commitments.aux.iter());
 
-        combined_commitments
+        Ok(combined_commitments)
     }
 
-    pub fn verify(
+    pub fn verify<const C: usize, InstructionSet: JoltInstructionSet>(
         &self,
-        generators: &C::Setup,
-        jolt_commitments: JoltCommitments<C>,
-        C: usize,
+        jolt_commitments: JoltCommitments<PCS>,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), SpartanError> {
         // TODO(sragss): Fiat shamir (relevant) commitments
-        let witness_segment_commitments = Self::format_commitments(&jolt_commitments, C);
+        let witness_segment_commitments =
+            Self::format_commitments::<C, InstructionSet>(&jolt_commitments)?;
         self.proof.verify_precommitted(
             witness_segment_commitments,
             &self.key,
             &[],
-            generators,
+            opening_accumulator,
             transcript,
         )
     }
 }
 
-impl<F: JoltField> UniformShapeBuilder<F> for R1CSBuilder {
+impl<const C: usize, const M: usize, F, InstructionSet> UniformShapeBuilder<F>
+    for R1CSBuilder<C, M, InstructionSet>
+where
+    F: JoltField,
+    InstructionSet: JoltInstructionSet,
+{
     fn single_step_shape(&self, memory_start: u64) -> R1CSShape<F> {
-        let mut jolt_shape = R1CSBuilder::default();
-        R1CSBuilder::jolt_r1cs_matrices(&mut jolt_shape, memory_start);
+        let mut jolt_shape = Self::default();
+        Self::jolt_r1cs_matrices(&mut jolt_shape, memory_start);
         let constraints_F = jolt_shape.convert_to_field();
         let shape_single = R1CSShape::<F> {
             A: constraints_F.0,
@@ -489,3 +596,224 @@ impl<F: JoltField> UniformShapeBuilder<F> for R1CSBuilder {
         shape_single.pad_vars()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jolt::instruction::{
+        add::ADDInstruction, shadd::SHADDInstruction, sll::SLLInstruction, sub::SUBInstruction,
+        xor::XORInstruction, JoltInstruction,
+    };
+    use crate::jolt::vm::{bytecode::opcode_bits, rv32i_vm::RV32I};
+    use crate::poly::commitment::{commitment_scheme::CommitShape, hyrax::HyraxScheme};
+    use crate::poly::{
+        dense_mlpoly::DensePolynomial, multilinear_polynomial::MultilinearPolynomial,
+    };
+    use ark_bn254::{Fr, G1Projective};
+    use ark_std::{log2, test_rng};
+    use common::{constants::RAM_START_ADDRESS, rv_trace::NUM_CIRCUIT_FLAGS};
+    use rand_chacha::rand_core::RngCore;
+    use strum::EnumCount;
+
+    /// Proves and verifies the R1CS for a trace of ALU instructions, with the lookup operands
+    /// and queries decomposed according to `C` and `M`. If `corrupt_step` is set, the lookup
+    /// output of that step is corrupted and the satisfaction check must report it instead.
+    fn r1cs_e2e<const C: usize, const M: usize>(corrupt_step: Option<usize>) {
+        const WRITE_LOOKUP_OUTPUT_TO_RD: usize = 6;
+        const CONCAT: usize = 8;
+        let padded_trace_len = 16;
+        let log_M = log2(M) as usize;
+
+        let mut rng = test_rng();
+        let trace: Vec<(RV32I, bool)> = (0..padded_trace_len - 3)
+            .map(|i| {
+                let (x, y) = (rng.next_u32() as u64, rng.next_u32() as u64);
+                match i % 5 {
+                    0 => (ADDInstruction::<32>(x, y).into(), false),
+                    1 => (SUBInstruction::<32>(x, y).into(), false),
+                    2 => (SHADDInstruction::<32, 2>(x, y).into(), false),
+                    3 => (XORInstruction(x, y).into(), true),
+                    _ => (SLLInstruction::<32>(x, y).into(), true),
+                }
+            })
+            .collect();
+
+        // Only the trace and the first padding step are stored; the padding steps are all zero.
+        let num_steps = trace.len() + 1;
+        let zeros = |num_vars: usize| vec![0u64; num_vars * num_steps];
+        let mut bytecode_a = zeros(1);
+        let mut bytecode_v = zeros(6);
+        let memreg_a_rw = zeros(1);
+        let mut memreg_v_reads = zeros(MEMORY_OPS_PER_INSTRUCTION);
+        let mut memreg_v_writes = zeros(MEMORY_OPS_PER_INSTRUCTION - 2);
+        let mut chunks_x = zeros(C);
+        let mut chunks_y = zeros(C);
+        let mut chunks_query = zeros(C);
+        let mut lookup_outputs = zeros(1);
+        let mut circuit_flags = zeros(NUM_CIRCUIT_FLAGS);
+        let mut instruction_flags = zeros(RV32I::COUNT);
+
+        let opcode_bits = opcode_bits(RV32I::COUNT);
+        for (step, (instruction, concat)) in trace.iter().enumerate() {
+            let index = |var_index: usize| var_index * num_steps + step;
+            // PC 0 is the no-op prepended to the bytecode.
+            let pc = step as u64 + 1;
+            let opcode = RV32I::enum_index(instruction);
+            let (x, y) = instruction.operands();
+            let lookup_output = instruction.lookup_entry();
+
+            let mut flags = vec![WRITE_LOOKUP_OUTPUT_TO_RD];
+            if *concat {
+                flags.push(CONCAT);
+            }
+            let mut op_flags_packed = opcode as u64 + 1;
+            for flag in flags {
+                circuit_flags[index(flag)] = 1;
+                op_flags_packed |= 1 << (opcode_bits + NUM_CIRCUIT_FLAGS - 1 - flag);
+            }
+            instruction_flags[index(opcode)] = 1;
+
+            bytecode_a[step] = pc;
+            let rd = 1 + step as u64 % 31;
+            for (var_index, value) in [pc, op_flags_packed, rd, 1, 2, 0].into_iter().enumerate() {
+                bytecode_v[index(var_index)] = value;
+            }
+            memreg_v_reads[index(0)] = x;
+            memreg_v_reads[index(1)] = y;
+            memreg_v_writes[index(0)] = lookup_output;
+            lookup_outputs[step] = if corrupt_step == Some(step) {
+                lookup_output + 1
+            } else {
+                lookup_output
+            };
+
+            let (x_chunks, y_chunks) = instruction.operand_chunks(C, log_M);
+            let query_chunks = instruction.to_indices(C, log_M);
+            for i in 0..C {
+                chunks_x[index(i)] = x_chunks[i];
+                chunks_y[index(i)] = y_chunks[i];
+                chunks_query[index(i)] = query_chunks[i] as u64;
+            }
+        }
+
+        // As many as allocated with `zeros` above
+        let num_vars_per_step = 1
+            + 6
+            + 1
+            + MEMORY_OPS_PER_INSTRUCTION
+            + (MEMORY_OPS_PER_INSTRUCTION - 2)
+            + 3 * C
+            + 1
+            + NUM_CIRCUIT_FLAGS
+            + RV32I::COUNT;
+        let inputs = R1CSInputs::new(
+            padded_trace_len,
+            num_steps,
+            bytecode_a,
+            bytecode_v,
+            memreg_a_rw,
+            memreg_v_reads,
+            memreg_v_writes,
+            chunks_x,
+            chunks_y,
+            chunks_query,
+            lookup_outputs,
+            circuit_flags,
+            instruction_flags,
+            vec![0; num_vars_per_step],
+        );
+
+        if let Some(corrupt_step) = corrupt_step {
+            let (step, description) = inputs
+                .check_satisfied::<Fr, C, M, RV32I>(RAM_START_ADDRESS)
+                .unwrap_err();
+            assert_eq!(step, corrupt_step);
+            assert!(description.contains("\"rd_val is lookup output\""));
+            assert!(description.contains("LookupOutput = "));
+            return;
+        }
+        inputs
+            .check_satisfied::<Fr, C, M, RV32I>(RAM_START_ADDRESS)
+            .unwrap();
+
+        let generators = HyraxScheme::<G1Projective>::setup(&[CommitShape::new(
+            padded_trace_len,
+            BatchType::Big,
+        )]);
+        let (key, witness_segments, small_witness_segments, _) =
+            R1CSProof::<Fr, HyraxScheme<G1Projective>>::compute_witness_commit::<C, M, RV32I>(
+                padded_trace_len,
+                RAM_START_ADDRESS,
+                &inputs,
+                &generators,
+            )
+            .unwrap();
+        let small_segments: Vec<PaddedPolynomial<Fr>> = small_witness_segments
+            .as_ref()
+            .expect("the witness should fit in an i128")
+            .iter()
+            .map(|segment| {
+                PaddedPolynomial::new(
+                    segment.get_num_vars(),
+                    segment
+                        .values()
+                        .iter()
+                        .map(|&value| Fr::from_i128(value))
+                        .collect(),
+                    Fr::from_i128(*segment.padding()),
+                )
+            })
+            .collect();
+        assert_eq!(small_segments, witness_segments);
+        let segments: Vec<DensePolynomial<Fr>> = witness_segments
+            .iter()
+            .map(MultilinearPolynomial::to_dense)
+            .collect();
+        let commitments =
+            HyraxScheme::<G1Projective>::batch_commit_polys(&segments, &generators, BatchType::Big);
+
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        let mut prover_accumulator = ProverOpeningAccumulator::new();
+        let proof = R1CSProof::<Fr, HyraxScheme<G1Projective>>::prove(
+            key,
+            witness_segments,
+            small_witness_segments,
+            &mut prover_accumulator,
+            &mut transcript,
+        )
+        .unwrap();
+        let opening_proof = prover_accumulator
+            .reduce_and_prove::<HyraxScheme<G1Projective>>(&generators, &mut transcript);
+
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        let mut verifier_accumulator = VerifierOpeningAccumulator::new();
+        proof
+            .proof
+            .verify_precommitted(
+                commitments.iter().collect(),
+                &proof.key,
+                &[],
+                &mut verifier_accumulator,
+                &mut transcript,
+            )
+            .unwrap();
+        verifier_accumulator
+            .reduce_and_verify(&generators, &opening_proof, &mut transcript)
+            .unwrap();
+    }
+
+    #[test]
+    fn r1cs_e2e_c4_m16() {
+        r1cs_e2e::<4, { 1 << 16 }>(None);
+    }
+
+    #[test]
+    fn r1cs_e2e_c8_m8() {
+        r1cs_e2e::<8, { 1 << 8 }>(None);
+    }
+
+    #[test]
+    fn r1cs_check_reports_unsatisfied_step() {
+        r1cs_e2e::<4, { 1 << 16 }>(Some(5));
+    }
+}
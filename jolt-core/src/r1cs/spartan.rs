@@ -1,9 +1,10 @@
 #![allow(clippy::len_without_is_empty)]
 
-use crate::poly::commitment::commitment_scheme::BatchType;
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 use crate::poly::field::JoltField;
-use crate::utils::compute_dotproduct_low_optimized;
+use crate::poly::multilinear_polynomial::MultilinearPolynomial;
+use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
+use crate::poly::padded_polynomial::PaddedPolynomial;
 use crate::utils::thread::drop_in_background_thread;
 use crate::utils::thread::unsafe_allocate_zero_vec;
 use crate::utils::transcript::ProofTranscript;
@@ -12,11 +13,15 @@ use ark_serialize::CanonicalSerialize;
 use rayon::prelude::*;
 use sha3::Digest;
 use sha3::Sha3_256;
+use std::marker::PhantomData;
 use thiserror::Error;
 
 use super::r1cs_shape::R1CSShape;
 use crate::{
-    poly::{dense_mlpoly::DensePolynomial, eq_poly::EqPolynomial},
+    poly::{
+        dense_mlpoly::DensePolynomial,
+        eq_poly::{BindingOrder, EqPolynomial, SplitEqPolynomial},
+    },
     subprotocols::sumcheck::SumcheckInstanceProof,
 };
 
@@ -60,6 +65,49 @@ impl<F: JoltField> UniformSpartanKey<F> {
         };
         map_to_field(&hasher.result())
     }
+
+    /// An estimate of the memory, in bytes, that the prover allocates for its own vectors: Az, Bz
+    /// and Cz in the outer sumcheck, and the combination of A, B and C along with the bound
+    /// witness in the inner one.
+    pub fn prover_working_set_bytes(&self) -> usize {
+        3 * std::cmp::max(self.num_cons_total, self.num_vars_total) * std::mem::size_of::<F>()
+    }
+
+    /// Checks that a key received alongside a proof is internally consistent, so that the
+    /// verifier can index into its matrices without going out of bounds.
+    fn check_well_formed(&self) -> Result<(), SpartanError> {
+        // The totals must be derived from the single-step shape as in `setup_precommitted`
+        let shape = &self.shape_single_step;
+        let padded_total = |per_step: usize| {
+            per_step
+                .checked_mul(self.num_steps)
+                .and_then(usize::checked_next_power_of_two)
+        };
+        if !self.num_steps.is_power_of_two()
+            || padded_total(shape.num_cons) != Some(self.num_cons_total)
+            || padded_total(shape.num_vars) != Some(self.num_vars_total)
+            || self.num_steps > self.num_cons_total
+            || self.num_steps > self.num_vars_total
+        {
+            return Err(SpartanError::InvalidKey);
+        }
+        if self.vk_digest != Self::compute_digest(&self.shape_single_step, self.num_steps) {
+            return Err(SpartanError::InvalidKey);
+        }
+
+        // Rows are indexed by the constraint bits of r_x, columns by the variable bits of r_y
+        let num_rows = self.num_cons_total / self.num_steps;
+        let num_cols = (self.num_vars_total / self.num_steps).saturating_mul(2);
+        if shape.num_vars >= num_cols {
+            return Err(SpartanError::InvalidIndex);
+        }
+        for (row, col, _) in shape.A.iter().chain(shape.B.iter()).chain(shape.C.iter()) {
+            if *row >= num_rows || *col >= num_cols {
+                return Err(SpartanError::InvalidIndex);
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Error)]
@@ -95,6 +143,10 @@ pub enum SpartanError {
     /// returned when an invalid PCS proof is provided
     #[error("InvalidPCSProof")]
     InvalidPCSProof,
+
+    /// returned if the verifier key sent with the proof is inconsistent
+    #[error("InvalidKey")]
+    InvalidKey,
 }
 
 // Trait which will kick out a small and big R1CS shape
@@ -106,13 +158,13 @@ pub trait UniformShapeBuilder<F: JoltField> {
 // and can run all the normal DensePolynomial ops.
 pub struct SegmentedPaddedWitness<F: JoltField> {
     total_len: usize,
-    segments: Vec<Vec<F>>,
+    segments: Vec<PaddedPolynomial<F>>,
     segment_len: usize,
     zero: F,
 }
 
 impl<F: JoltField> SegmentedPaddedWitness<F> {
-    pub fn new(total_len: usize, segments: Vec<Vec<F>>) -> Self {
+    pub fn new(total_len: usize, segments: Vec<PaddedPolynomial<F>>) -> Self {
         let segment_len = segments[0].len();
         for segment in &segments {
             assert_eq!(
@@ -138,17 +190,14 @@ impl<F: JoltField> SegmentedPaddedWitness<F> {
         let evals = self
             .segments
             .par_iter()
-            .map(|segment| compute_dotproduct_low_optimized(&chi, segment))
+            .map(|segment| segment.evaluate_at_chi(&chi))
             .collect();
         drop_in_background_thread(chi);
         evals
     }
 
-    pub fn into_dense_polys(self) -> Vec<DensePolynomial<F>> {
+    pub fn into_segments(self) -> Vec<PaddedPolynomial<F>> {
         self.segments
-            .into_iter()
-            .map(|poly| DensePolynomial::new(poly))
-            .collect()
     }
 }
 
@@ -178,6 +227,24 @@ impl<F: JoltField> IndexablePoly<F> for SegmentedPaddedWitness<F> {
     }
 }
 
+/// Checks that Az * Bz = Cz entrywise, i.e. that the witness is a satisfying assignment.
+#[cfg(test)]
+fn assert_satisfied<T>(
+    Az: &[T],
+    Bz: &[T],
+    Cz: &[T],
+    padded_segment_len: usize,
+    is_satisfied: impl Fn(&T, &T, &T) -> bool,
+) {
+    for (i, ((az, bz), cz)) in Az.iter().zip(Bz).zip(Cz).enumerate() {
+        if !is_satisfied(az, bz, cz) {
+            let error_segment_index = i / padded_segment_len;
+            let error_step_index = i % padded_segment_len;
+            panic!("witness is not a satisfying assignment. Failed on segment {error_segment_index} at step {error_step_index}");
+        }
+    }
+}
+
 /// A succinct proof of knowledge of a witness to a relaxed R1CS instance
 /// The proof is produced using Spartan's combination of the sum-check and
 /// the commitment to a vector viewed as a polynomial commitment
@@ -188,7 +255,7 @@ pub struct UniformSpartanProof<F: JoltField, C: CommitmentScheme<Field = F>> {
     inner_sumcheck_proof: SumcheckInstanceProof<F>,
     eval_arg: Vec<F>, // TODO(arasuarun / sragss): better name
     claimed_witnesss_evals: Vec<F>,
-    opening_proof: C::BatchedProof,
+    _marker: PhantomData<C>,
 }
 
 impl<F: JoltField, C: CommitmentScheme<Field = F>> UniformSpartanProof<F, C> {
@@ -219,11 +286,14 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> UniformSpartanProof<F, C> {
         Ok(key)
     }
 
-    /// produces a succinct proof of satisfiability of a `RelaxedR1CS` instance
+    /// produces a succinct proof of satisfiability of a `RelaxedR1CS` instance.
+    /// `small_witness_segments`, if given, must be `witness_segments` as integers.
     #[tracing::instrument(skip_all, name = "UniformSpartanProof::prove_precommitted")]
     pub fn prove_precommitted(
         key: &UniformSpartanKey<F>,
-        witness_segments: Vec<Vec<F>>,
+        witness_segments: Vec<PaddedPolynomial<F>>,
+        small_witness_segments: Option<Vec<PaddedPolynomial<i128>>>,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
     ) -> Result<Self, SpartanError> {
         let poly_ABC_len = 2 * key.num_vars_total;
@@ -242,73 +312,81 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> UniformSpartanProof<F, C> {
         let combined_witness_size =
             (key.num_steps * key.shape_single_step.num_cons).next_power_of_two();
 
-        let mut poly_tau = DensePolynomial::new(EqPolynomial::evals(&tau));
-
-        let span = tracing::span!(tracing::Level::TRACE, "allocate_witness_vecs");
-        let _enter = span.enter();
-        let mut A_z = unsafe_allocate_zero_vec(combined_witness_size);
-        let mut B_z = unsafe_allocate_zero_vec(combined_witness_size);
-        let mut C_z = unsafe_allocate_zero_vec(combined_witness_size);
-        drop(_enter);
-
-        key.shape_single_step.multiply_vec_uniform(
-            &segmented_padded_witness,
-            key.num_steps,
-            &mut A_z,
-            &mut B_z,
-            &mut C_z,
-        )?;
-        let mut poly_Az = DensePolynomial::new(A_z);
-        let mut poly_Bz = DensePolynomial::new(B_z);
-        let mut poly_Cz = DensePolynomial::new(C_z);
-
-        #[cfg(test)]
-        {
-            // Check that Z is a satisfying assignment
-            for (i, ((az, bz), cz)) in poly_Az
-                .evals_ref()
-                .iter()
-                .zip(poly_Bz.evals_ref())
-                .zip(poly_Cz.evals_ref())
-                .enumerate()
-            {
-                if *az * bz != *cz {
-                    let padded_segment_len = segmented_padded_witness.segment_len;
-                    let error_segment_index = i / padded_segment_len;
-                    let error_step_index = i % padded_segment_len;
-                    panic!("witness is not a satisfying assignment. Failed on segment {error_segment_index} at step {error_step_index}");
-                }
-            }
-        }
+        let mut eq_tau = SplitEqPolynomial::new(&tau, BindingOrder::Top);
 
-        let comb_func_outer = |A: &F, B: &F, C: &F, D: &F| -> F {
-            // Below is an optimized form of: *A * (*B * *C - *D)
-            if B.is_zero() || C.is_zero() {
-                if D.is_zero() {
-                    F::zero()
-                } else {
-                    *A * (-(*D))
-                }
-            } else {
-                *A * (*B * *C - *D)
-            }
-        };
+        // The entries of the Jolt witness are small integers, so given them as such, Az, Bz, Cz and
+        // the first round of the outer sumcheck can be computed without field multiplications.
+        let small_ABC = small_witness_segments.and_then(|small_segments| {
+            key.shape_single_step.multiply_vec_uniform_small(
+                &small_segments,
+                key.num_steps,
+                combined_witness_size,
+            )
+        });
 
         let (outer_sumcheck_proof, outer_sumcheck_r, outer_sumcheck_claims) =
-            SumcheckInstanceProof::prove_spartan_cubic::<_>(
-                &F::zero(), // claim is zero
-                num_rounds_x,
-                &mut poly_tau,
-                &mut poly_Az,
-                &mut poly_Bz,
-                &mut poly_Cz,
-                comb_func_outer,
-                transcript,
-            );
-        drop_in_background_thread(poly_Az);
-        drop_in_background_thread(poly_Bz);
-        drop_in_background_thread(poly_Cz);
-        drop_in_background_thread(poly_tau);
+            if let Some((A_z, B_z, C_z)) = small_ABC {
+                #[cfg(test)]
+                assert_satisfied(
+                    &A_z,
+                    &B_z,
+                    &C_z,
+                    segmented_padded_witness.segment_len,
+                    |az, bz, cz| F::from_i128(*az) * F::from_i128(*bz) == F::from_i128(*cz),
+                );
+
+                SumcheckInstanceProof::prove_spartan_cubic_small(
+                    &F::zero(), // claim is zero
+                    num_rounds_x,
+                    &mut eq_tau,
+                    A_z,
+                    B_z,
+                    C_z,
+                    transcript,
+                )
+            } else {
+                let span = tracing::span!(tracing::Level::TRACE, "allocate_witness_vecs");
+                let _enter = span.enter();
+                let mut A_z = unsafe_allocate_zero_vec(combined_witness_size);
+                let mut B_z = unsafe_allocate_zero_vec(combined_witness_size);
+                let mut C_z = unsafe_allocate_zero_vec(combined_witness_size);
+                drop(_enter);
+
+                key.shape_single_step.multiply_vec_uniform(
+                    &segmented_padded_witness,
+                    key.num_steps,
+                    &mut A_z,
+                    &mut B_z,
+                    &mut C_z,
+                )?;
+
+                #[cfg(test)]
+                assert_satisfied(
+                    &A_z,
+                    &B_z,
+                    &C_z,
+                    segmented_padded_witness.segment_len,
+                    |az, bz, cz| *az * bz == *cz,
+                );
+
+                let mut poly_Az = DensePolynomial::new(A_z);
+                let mut poly_Bz = DensePolynomial::new(B_z);
+                let mut poly_Cz = DensePolynomial::new(C_z);
+                let outer_sumcheck = SumcheckInstanceProof::prove_spartan_cubic(
+                    &F::zero(), // claim is zero
+                    num_rounds_x,
+                    &mut eq_tau,
+                    &mut poly_Az,
+                    &mut poly_Bz,
+                    &mut poly_Cz,
+                    transcript,
+                );
+                drop_in_background_thread(poly_Az);
+                drop_in_background_thread(poly_Bz);
+                drop_in_background_thread(poly_Cz);
+                outer_sumcheck
+            };
+        drop_in_background_thread(eq_tau);
 
         // claims from the end of sum-check
         // claim_Az is the (scalar) value v_A = \sum_y A(r_x, y) * z(r_x) where r_x is the sumcheck randomness
@@ -442,15 +520,14 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> UniformSpartanProof<F, C> {
         let witness_evals = segmented_padded_witness.evaluate_all(r_y_point.to_owned());
         drop(_enter);
 
-        let witness_segment_polys: Vec<DensePolynomial<F>> =
-            segmented_padded_witness.into_dense_polys();
-        let witness_segment_polys_ref: Vec<&DensePolynomial<F>> =
+        let witness_segment_polys: Vec<PaddedPolynomial<F>> =
+            segmented_padded_witness.into_segments();
+        let witness_segment_polys_ref: Vec<&PaddedPolynomial<F>> =
             witness_segment_polys.iter().collect();
-        let opening_proof = C::batch_prove(
+        opening_accumulator.append(
             &witness_segment_polys_ref,
             r_y_point,
             &witness_evals,
-            BatchType::Big,
             transcript,
         );
 
@@ -468,7 +545,7 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> UniformSpartanProof<F, C> {
             inner_sumcheck_proof,
             eval_arg: vec![],
             claimed_witnesss_evals: witness_evals,
-            opening_proof,
+            _marker: PhantomData,
         })
     }
 
@@ -479,12 +556,16 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> UniformSpartanProof<F, C> {
         witness_segment_commitments: Vec<&C::Commitment>,
         key: &UniformSpartanKey<F>,
         io: &[F],
-        generators: &C::Setup,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), SpartanError> {
         assert_eq!(io.len(), 0); // Currently not using io
+        key.check_well_formed()?;
 
         let N_SEGMENTS = witness_segment_commitments.len();
+        if self.claimed_witnesss_evals.len() != N_SEGMENTS {
+            return Err(SpartanError::InvalidWitnessLength);
+        }
 
         let (num_rounds_x, num_rounds_y) = (
             usize::try_from(key.num_cons_total.ilog2()).unwrap(),
@@ -532,6 +613,9 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> UniformSpartanProof<F, C> {
 
         // n_prefix = n_segments + 1
         let n_prefix = (key.num_vars_total.ilog2() as usize - key.num_steps.ilog2() as usize) + 1;
+        if N_SEGMENTS > 1 << (n_prefix - 1) {
+            return Err(SpartanError::InvalidWitnessLength);
+        }
 
         let eval_Z = {
             let eval_X = {
@@ -651,15 +735,14 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> UniformSpartanProof<F, C> {
         }
 
         let r_y_point = &inner_sumcheck_r[n_prefix..];
-        C::batch_verify(
-            &self.opening_proof,
-            generators,
-            r_y_point,
-            &self.claimed_witnesss_evals,
-            &witness_segment_commitments,
-            transcript,
-        )
-        .map_err(|_| SpartanError::InvalidPCSProof)?;
+        opening_accumulator
+            .append(
+                &witness_segment_commitments,
+                r_y_point,
+                &self.claimed_witnesss_evals,
+                transcript,
+            )
+            .map_err(|_| SpartanError::InvalidPCSProof)?;
 
         Ok(())
     }
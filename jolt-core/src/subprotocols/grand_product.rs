@@ -1,7 +1,11 @@
+use super::grand_product_quarks::QuarkGrandProductProof;
 use super::sumcheck::{BatchedCubicSumcheck, SumcheckInstanceProof};
-use crate::poly::eq_poly::EqPolynomial;
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::poly::eq_poly::{BindingOrder, SplitEqPolynomial};
 use crate::poly::field::JoltField;
-use crate::poly::{dense_mlpoly::DensePolynomial, unipoly::UniPoly};
+use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
+use crate::poly::unipoly::UniPoly;
+use crate::utils::errors::ProofVerifyError;
 use crate::utils::math::Math;
 use crate::utils::thread::drop_in_background_thread;
 use crate::utils::transcript::ProofTranscript;
@@ -24,19 +28,21 @@ impl<F: JoltField> BatchedGrandProductLayerProof<F> {
         num_rounds: usize,
         degree_bound: usize,
         transcript: &mut ProofTranscript,
-    ) -> (F, Vec<F>) {
+    ) -> Result<(F, Vec<F>), ProofVerifyError> {
         self.proof
             .verify(claim, num_rounds, degree_bound, transcript)
-            .unwrap()
     }
 }
 
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
-pub struct BatchedGrandProductProof<F: JoltField> {
-    pub layers: Vec<BatchedGrandProductLayerProof<F>>,
+pub struct BatchedGrandProductProof<C: CommitmentScheme> {
+    pub layers: Vec<BatchedGrandProductLayerProof<C::Field>>,
+    /// Proves the top of the circuits for grand products that don't use GKR all the way
+    /// up, e.g. `QuarkGrandProduct`.
+    pub quark_proof: Option<QuarkGrandProductProof<C>>,
 }
 
-pub trait BatchedGrandProduct<F: JoltField>: Sized {
+pub trait BatchedGrandProduct<F: JoltField, C: CommitmentScheme<Field = F>>: Sized {
     /// The bottom/input layer of the grand products
     type Leaves;
 
@@ -51,12 +57,15 @@ pub trait BatchedGrandProduct<F: JoltField>: Sized {
     /// of proving.
     fn layers(&'_ mut self) -> impl Iterator<Item = &'_ mut dyn BatchedGrandProductLayer<F>>;
 
-    /// Computes a batched grand product proof, layer by layer.
+    /// Computes a batched grand product proof, layer by layer. `opening_accumulator` and
+    /// `setup` are only needed by grand products that commit to polynomials of their own.
     #[tracing::instrument(skip_all, name = "BatchedGrandProduct::prove_grand_product")]
     fn prove_grand_product(
         &mut self,
+        _opening_accumulator: Option<&mut ProverOpeningAccumulator<F>>,
         transcript: &mut ProofTranscript,
-    ) -> (BatchedGrandProductProof<F>, Vec<F>) {
+        _setup: Option<&C::Setup>,
+    ) -> (BatchedGrandProductProof<C>, Vec<F>) {
         let mut proof_layers = Vec::with_capacity(self.num_layers());
         let mut claims_to_verify = self.claims();
         let mut r_grand_product = Vec::new();
@@ -72,6 +81,7 @@ pub trait BatchedGrandProduct<F: JoltField>: Sized {
         (
             BatchedGrandProductProof {
                 layers: proof_layers,
+                quark_proof: None,
             },
             r_grand_product,
         )
@@ -90,13 +100,15 @@ pub trait BatchedGrandProduct<F: JoltField>: Sized {
         grand_product_claims: &mut Vec<F>,
         r_grand_product: &mut Vec<F>,
         transcript: &mut ProofTranscript,
-    ) {
+    ) -> Result<(), ProofVerifyError> {
         let layer_proof = &layer_proofs[layer_index];
         let expected_sumcheck_claim: F = (0..grand_product_claims.len())
             .map(|i| coeffs[i] * layer_proof.left_claims[i] * layer_proof.right_claims[i] * eq_eval)
             .sum();
 
-        assert_eq!(expected_sumcheck_claim, sumcheck_claim);
+        if expected_sumcheck_claim != sumcheck_claim {
+            return Err(ProofVerifyError::SumcheckClaimError);
+        }
 
         // produce a random challenge to condense two claims into a single claim
         let r_layer = transcript.challenge_scalar(b"challenge_r_layer");
@@ -109,65 +121,104 @@ pub trait BatchedGrandProduct<F: JoltField>: Sized {
             .collect();
 
         r_grand_product.push(r_layer);
+        Ok(())
     }
 
-    /// Verifies the given grand product proof.
-    fn verify_grand_product(
-        proof: &BatchedGrandProductProof<F>,
+    /// Verifies the GKR layers of a grand product proof, starting from `claims` about the
+    /// layer output at `r_grand_product`. Returns the claims about the leaves and the point
+    /// they are evaluated at.
+    fn verify_layers(
+        proof_layers: &[BatchedGrandProductLayerProof<F>],
         claims: &Vec<F>,
         transcript: &mut ProofTranscript,
-    ) -> (Vec<F>, Vec<F>) {
-        let mut r_grand_product: Vec<F> = Vec::new();
+        mut r_grand_product: Vec<F>,
+    ) -> Result<(Vec<F>, Vec<F>), ProofVerifyError> {
         let mut claims_to_verify = claims.to_owned();
 
-        for (layer_index, layer_proof) in proof.layers.iter().enumerate() {
-            // produce a fresh set of coeffs
-            let coeffs: Vec<F> =
-                transcript.challenge_vector(b"rand_coeffs_next_layer", claims_to_verify.len());
-            // produce a joint claim
-            let claim = claims_to_verify
-                .iter()
-                .zip(coeffs.iter())
-                .map(|(&claim, &coeff)| claim * coeff)
-                .sum();
-
-            let (sumcheck_claim, r_sumcheck) =
-                layer_proof.verify(claim, layer_index, 3, transcript);
-            assert_eq!(claims.len(), layer_proof.left_claims.len());
-            assert_eq!(claims.len(), layer_proof.right_claims.len());
+        for layer_index in 0..proof_layers.len() {
+            Self::verify_layer(
+                proof_layers,
+                layer_index,
+                &mut claims_to_verify,
+                &mut r_grand_product,
+                transcript,
+            )
+            .map_err(|e| ProofVerifyError::GrandProductLayerError(layer_index, Box::new(e)))?;
+        }
 
-            for (left, right) in layer_proof
-                .left_claims
-                .iter()
-                .zip(layer_proof.right_claims.iter())
-            {
-                transcript.append_scalar(b"sumcheck left claim", left);
-                transcript.append_scalar(b"sumcheck right claim", right);
-            }
+        Ok((claims_to_verify, r_grand_product))
+    }
 
-            assert_eq!(r_grand_product.len(), r_sumcheck.len());
+    /// Verifies layer `layer_index` of `proof_layers`, updating `claims_to_verify` and
+    /// `r_grand_product` to the claims about the next layer and the point they are evaluated at.
+    fn verify_layer(
+        proof_layers: &[BatchedGrandProductLayerProof<F>],
+        layer_index: usize,
+        claims_to_verify: &mut Vec<F>,
+        r_grand_product: &mut Vec<F>,
+        transcript: &mut ProofTranscript,
+    ) -> Result<(), ProofVerifyError> {
+        let layer_proof = &proof_layers[layer_index];
 
-            let eq_eval: F = r_grand_product
-                .iter()
-                .zip_eq(r_sumcheck.iter().rev())
-                .map(|(&r_gp, &r_sc)| r_gp * r_sc + (F::one() - r_gp) * (F::one() - r_sc))
-                .product();
+        // produce a fresh set of coeffs
+        let coeffs: Vec<F> =
+            transcript.challenge_vector(b"rand_coeffs_next_layer", claims_to_verify.len());
+        // produce a joint claim
+        let claim = claims_to_verify
+            .iter()
+            .zip(coeffs.iter())
+            .map(|(&claim, &coeff)| claim * coeff)
+            .sum();
 
-            r_grand_product = r_sumcheck.into_iter().rev().collect();
+        let (sumcheck_claim, r_sumcheck) =
+            layer_proof.verify(claim, r_grand_product.len(), 3, transcript)?;
+        for layer_claims in [&layer_proof.left_claims, &layer_proof.right_claims] {
+            if layer_claims.len() != claims_to_verify.len() {
+                return Err(ProofVerifyError::InvalidInputLength(
+                    claims_to_verify.len(),
+                    layer_claims.len(),
+                ));
+            }
+        }
 
-            Self::verify_sumcheck_claim(
-                &proof.layers,
-                layer_index,
-                &coeffs,
-                sumcheck_claim,
-                eq_eval,
-                &mut claims_to_verify,
-                &mut r_grand_product,
-                transcript,
-            );
+        for (left, right) in layer_proof
+            .left_claims
+            .iter()
+            .zip(layer_proof.right_claims.iter())
+        {
+            transcript.append_scalar(b"sumcheck left claim", left);
+            transcript.append_scalar(b"sumcheck right claim", right);
         }
 
-        (claims_to_verify, r_grand_product)
+        let eq_eval: F = r_grand_product
+            .iter()
+            .zip_eq(r_sumcheck.iter().rev())
+            .map(|(&r_gp, &r_sc)| r_gp * r_sc + (F::one() - r_gp) * (F::one() - r_sc))
+            .product();
+
+        *r_grand_product = r_sumcheck.into_iter().rev().collect();
+
+        Self::verify_sumcheck_claim(
+            proof_layers,
+            layer_index,
+            &coeffs,
+            sumcheck_claim,
+            eq_eval,
+            claims_to_verify,
+            r_grand_product,
+            transcript,
+        )
+    }
+
+    /// Verifies the given grand product proof.
+    fn verify_grand_product(
+        proof: &BatchedGrandProductProof<C>,
+        claims: &Vec<F>,
+        _opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, C>>,
+        transcript: &mut ProofTranscript,
+        _setup: Option<&C::Setup>,
+    ) -> Result<(Vec<F>, Vec<F>), ProofVerifyError> {
+        Self::verify_layers(&proof.layers, claims, transcript, vec![])
     }
 }
 
@@ -188,7 +239,7 @@ pub trait BatchedGrandProductLayer<F: JoltField>: BatchedCubicSumcheck<F> {
             .map(|(&claim, &coeff)| claim * coeff)
             .sum();
 
-        let mut eq_poly = DensePolynomial::new(EqPolynomial::<F>::evals(r_grand_product));
+        let mut eq_poly = SplitEqPolynomial::new(r_grand_product, BindingOrder::Bottom);
 
         let (sumcheck_proof, r_sumcheck, sumcheck_claims) =
             self.prove_sumcheck(&claim, &coeffs, &mut eq_poly, transcript);
@@ -242,7 +293,7 @@ pub struct BatchedDenseGrandProductLayer<F: JoltField> {
 }
 
 impl<F: JoltField> BatchedDenseGrandProductLayer<F> {
-    fn new(values: Vec<Vec<F>>) -> Self {
+    pub fn new(values: Vec<Vec<F>>) -> Self {
         let layer_len = values[0].len();
         Self {
             layers: values,
@@ -270,7 +321,7 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedDenseGrandProductLayer<F>
     ///   0  1 2  3  4  5 6  7
     /// Left nodes have even indices, right nodes have odd indices.
     #[tracing::instrument(skip_all, name = "BatchedDenseGrandProductLayer::bind")]
-    fn bind(&mut self, eq_poly: &mut DensePolynomial<F>, r: &F) {
+    fn bind(&mut self, eq_poly: &mut SplitEqPolynomial<F>, r: &F) {
         debug_assert!(self.layer_len % 4 == 0);
         let n = self.layer_len / 4;
         // TODO(moodlezoup): parallelize over chunks instead of over batch
@@ -288,19 +339,21 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedDenseGrandProductLayer<F>
                         }
                     })
             },
-            || eq_poly.bound_poly_var_bot(r),
+            || eq_poly.bind(r),
         );
         self.layer_len /= 2;
     }
 
-    /// We want to compute the evaluations of the following univariate cubic polynomial at
-    /// points {0, 1, 2, 3}:
+    /// We want to compute the univariate cubic polynomial
     ///     Σ coeff[batch_index] * (Σ eq(r, x) * left(x) * right(x))
     /// where the inner summation is over all but the "least significant bit" of the multilinear
     /// polynomials `eq`, `left`, and `right`. We denote this "least significant" variable x_b.
+    /// Using `SplitEqPolynomial`, this polynomial is eq(r_b, x_b) (times a scalar) times the
+    /// quadratic polynomial
+    ///     q(x_b) = Σ coeff[batch_index] * (Σ eq(r', x') * left(x', x_b) * right(x', x_b))
+    /// so it suffices to compute q(0) and the leading coefficient of q.
     ///
-    /// Computing these evaluations requires processing pairs of adjacent coefficients of
-    /// `eq`, `left`, and `right`.
+    /// Computing these requires processing pairs of adjacent coefficients of `left` and `right`.
     /// Recall that the `left` and `right` polynomials are interleaved in each layer of `self.layers`,
     /// so we process each layer 4 values at a time:
     ///                  layer = [L, R, L, R, L, R, ...]
@@ -311,19 +364,14 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedDenseGrandProductLayer<F>
     fn compute_cubic(
         &self,
         coeffs: &[F],
-        eq_poly: &DensePolynomial<F>,
+        eq_poly: &SplitEqPolynomial<F>,
         previous_round_claim: F,
     ) -> UniPoly<F> {
-        let evals = (0..eq_poly.len() / 2)
+        let eq_evals = eq_poly.evals();
+        let needs_q_1 = eq_poly.needs_q_1();
+        let evals = (0..eq_evals.len())
             .into_par_iter()
             .map(|i| {
-                let eq_evals = {
-                    let eval_point_0 = eq_poly[2 * i];
-                    let m_eq = eq_poly[2 * i + 1] - eq_poly[2 * i];
-                    let eval_point_2 = eq_poly[2 * i + 1] + m_eq;
-                    let eval_point_3 = eval_point_2 + m_eq;
-                    (eval_point_0, eval_point_2, eval_point_3)
-                };
                 let mut evals = (F::zero(), F::zero(), F::zero());
 
                 self.layers
@@ -332,10 +380,8 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedDenseGrandProductLayer<F>
                     .for_each(|(batch_index, layer)| {
                         // We want to compute:
                         //     evals.0 += coeff * left.0 * right.0
-                        //     evals.1 += coeff * (2 * left.1 - left.0) * (2 * right.1 - right.0)
-                        //     evals.2 += coeff * (3 * left.1 - 2 * left.0) * (3 * right.1 - 2 * right.0)
-                        // which naively requires 3 multiplications by `coeff`.
-                        // By multiplying by the coefficient early, we only use 2 multiplications by `coeff`.
+                        //     evals.1 += coeff * (left.1 - left.0) * (right.1 - right.0)
+                        // Multiplying `left` by the coefficient early, `m_left` includes it too.
                         let left = (
                             coeffs[batch_index] * layer[4 * i],
                             coeffs[batch_index] * layer[4 * i + 2],
@@ -345,20 +391,16 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedDenseGrandProductLayer<F>
                         let m_left = left.1 - left.0;
                         let m_right = right.1 - right.0;
 
-                        let left_eval_2 = left.1 + m_left;
-                        let left_eval_3 = left_eval_2 + m_left;
-
-                        let right_eval_2 = right.1 + m_right;
-                        let right_eval_3 = right_eval_2 + m_right;
-
                         evals.0 += left.0 * right.0;
-                        evals.1 += left_eval_2 * right_eval_2;
-                        evals.2 += left_eval_3 * right_eval_3;
+                        evals.1 += m_left * m_right;
+                        if needs_q_1 {
+                            evals.2 += left.1 * right.1;
+                        }
                     });
 
-                evals.0 *= eq_evals.0;
-                evals.1 *= eq_evals.1;
-                evals.2 *= eq_evals.2;
+                evals.0 *= eq_evals[i];
+                evals.1 *= eq_evals[i];
+                evals.2 *= eq_evals[i];
                 evals
             })
             .reduce(
@@ -366,8 +408,12 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedDenseGrandProductLayer<F>
                 |sum, evals| (sum.0 + evals.0, sum.1 + evals.1, sum.2 + evals.2),
             );
 
-        let evals = [evals.0, previous_round_claim - evals.0, evals.1, evals.2];
-        UniPoly::from_evals(&evals)
+        eq_poly.gruen_cubic(
+            evals.0,
+            evals.1,
+            needs_q_1.then_some(evals.2),
+            previous_round_claim,
+        )
     }
 
     fn final_claims(&self) -> (Vec<F>, Vec<F>) {
@@ -390,7 +436,11 @@ pub struct BatchedDenseGrandProduct<F: JoltField> {
     layers: Vec<BatchedDenseGrandProductLayer<F>>,
 }
 
-impl<F: JoltField> BatchedGrandProduct<F> for BatchedDenseGrandProduct<F> {
+impl<F, C> BatchedGrandProduct<F, C> for BatchedDenseGrandProduct<F>
+where
+    F: JoltField,
+    C: CommitmentScheme<Field = F>,
+{
     type Leaves = Vec<Vec<F>>;
 
     #[tracing::instrument(skip_all, name = "BatchedDenseGrandProduct::construct")]
@@ -423,7 +473,7 @@ impl<F: JoltField> BatchedGrandProduct<F> for BatchedDenseGrandProduct<F> {
     }
 
     fn claims(&self) -> Vec<F> {
-        let last_layers = &self.layers[self.num_layers() - 1];
+        let last_layers = &self.layers[self.layers.len() - 1];
         assert_eq!(last_layers.layer_len, 2);
         last_layers
             .layers
@@ -610,7 +660,7 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedSparseGrandProductLayer<F>
     /// If `self` is sparse, we basically do the same thing but with more
     /// cases to check 😬
     #[tracing::instrument(skip_all, name = "BatchedSparseGrandProductLayer::bind")]
-    fn bind(&mut self, eq_poly: &mut DensePolynomial<F>, r: &F) {
+    fn bind(&mut self, eq_poly: &mut SplitEqPolynomial<F>, r: &F) {
         debug_assert!(self.layer_len % 4 == 0);
         rayon::join(
             || {
@@ -758,19 +808,20 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedSparseGrandProductLayer<F>
                     }
                 })
             },
-            || eq_poly.bound_poly_var_bot(r),
+            || eq_poly.bind(r),
         );
         self.layer_len /= 2;
     }
 
-    /// We want to compute the evaluations of the following univariate cubic polynomial at
-    /// points {0, 1, 2, 3}:
+    /// We want to compute the univariate cubic polynomial
     ///     Σ coeff[batch_index] * (Σ eq(r, x) * left(x) * right(x))
     /// where the inner summation is over all but the "least significant bit" of the multilinear
     /// polynomials `eq`, `left`, and `right`. We denote this "least significant" variable x_b.
+    /// As in `BatchedDenseGrandProductLayer::compute_cubic`, it suffices to compute q(0) and the
+    /// leading coefficient of
+    ///     q(x_b) = Σ coeff[batch_index] * (Σ eq(r', x') * left(x', x_b) * right(x', x_b))
     ///
-    /// Computing these evaluations requires processing pairs of adjacent coefficients of
-    /// `eq`, `left`, and `right`.
+    /// Computing these requires processing pairs of adjacent coefficients of `left` and `right`.
     /// If `self` is dense, we process each layer 4 values at a time:
     ///                  layer = [L, R, L, R, L, R, ...]
     ///                           |  |  |  |
@@ -782,54 +833,35 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedSparseGrandProductLayer<F>
     fn compute_cubic(
         &self,
         coeffs: &[F],
-        eq_poly: &DensePolynomial<F>,
+        eq_poly: &SplitEqPolynomial<F>,
         previous_round_claim: F,
     ) -> UniPoly<F> {
-        let eq_evals: Vec<(F, F, F)> = (0..eq_poly.len() / 2)
-            .into_par_iter()
-            .map(|i| {
-                let eval_point_0 = eq_poly[2 * i];
-                let m_eq = eq_poly[2 * i + 1] - eq_poly[2 * i];
-                let eval_point_2 = eq_poly[2 * i + 1] + m_eq;
-                let eval_point_3 = eval_point_2 + m_eq;
-                (eval_point_0, eval_point_2, eval_point_3)
-            })
-            .collect();
-
-        // This is what the cubic evals would be if a layer were *all 1s*
-        // We pre-emptively compute these sums to speed up sparse layers; see below.
-        let eq_eval_sums: (F, F, F) = eq_evals
-            .par_iter()
-            .fold(
-                || (F::zero(), F::zero(), F::zero()),
-                |sum, evals| (sum.0 + evals.0, sum.1 + evals.1, sum.2 + evals.2),
-            )
-            .reduce(
-                || (F::zero(), F::zero(), F::zero()),
-                |sum, evals| (sum.0 + evals.0, sum.1 + evals.1, sum.2 + evals.2),
-            );
+        let eq_evals = eq_poly.evals();
+        let needs_q_1 = eq_poly.needs_q_1();
 
         let evals: Vec<(F, F, F)> = coeffs
             .par_iter()
             .enumerate()
             .map(|(batch_index, coeff)| match &self.layers[batch_index] {
-                // If sparse, we use the pre-emptively computed `eq_eval_sums` as a starting point:
-                //     eq_eval_sum := Σ eq_evals[i]
-                // What we ultimately want to compute:
+                // If sparse, we use the fact that the eq evals sum to 1:
+                //     Σ eq_evals[i] = 1
+                // What we ultimately want to compute (for q(0)):
                 //     Σ coeff[batch_index] * (Σ eq_evals[i] * left[i] * right[i])
                 // Note that if left[i] and right[i] are all 1s, the inner sum is:
-                //     Σ eq_evals[i] = eq_eval_sum
+                //     Σ eq_evals[i] = 1
                 // To recover the actual inner sum, we find all the non-1
                 // left[i] and right[i] terms and compute the delta:
                 //     ∆ := Σ eq_evals[j] * (left[j] * right[j] - 1)    ∀j where left[j] ≠ 1 or right[j] ≠ 1
                 // Then we can compute:
-                //    coeff[batch_index] * (eq_eval_sum + ∆) = coeff[batch_index] * (Σ eq_evals[i] + Σ eq_evals[j] * (left[j] * right[j] - 1))
-                //                                           = coeff[batch_index] * (Σ eq_evals[j] * left[j] * right[j])
+                //    coeff[batch_index] * (1 + ∆) = coeff[batch_index] * (Σ eq_evals[i] + Σ eq_evals[j] * (left[j] * right[j] - 1))
+                //                                 = coeff[batch_index] * (Σ eq_evals[j] * left[j] * right[j])
                 // ...which is exactly the summand we want.
+                // The leading coefficient of q only gets contributions from pairs of nodes that
+                // differ, so it is a sum over the non-1 terms to begin with.
                 DynamicDensityGrandProductLayer::Sparse(sparse_layer) => {
                     // Computes:
                     //     ∆ := Σ eq_evals[j] * (left[j] * right[j] - 1)    ∀j where left[j] ≠ 1 or right[j] ≠ 1
-                    // for the evaluation points {0, 2, 3}
+                    // and the leading coefficient of q
                     let mut delta = (F::zero(), F::zero(), F::zero());
 
                     let mut next_index_to_process = 0usize;
@@ -908,61 +940,57 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedSparseGrandProductLayer<F>
                         let m_left = left.1 - left.0;
                         let m_right = right.1 - right.0;
 
-                        let left_eval_2 = left.1 + m_left;
-                        let left_eval_3 = left_eval_2 + m_left;
-
-                        let right_eval_2 = right.1 + m_right;
-                        let right_eval_3 = right_eval_2 + m_right;
-
-                        let (eq_eval_0, eq_eval_2, eq_eval_3) = eq_evals[index / 4];
+                        let eq_eval = eq_evals[index / 4];
                         delta.0 +=
-                            eq_eval_0.mul_0_optimized(left.0.mul_1_optimized(right.0) - F::one());
-                        delta.1 += eq_eval_2
-                            .mul_0_optimized(left_eval_2.mul_1_optimized(right_eval_2) - F::one());
-                        delta.2 += eq_eval_3
-                            .mul_0_optimized(left_eval_3.mul_1_optimized(right_eval_3) - F::one());
+                            eq_eval.mul_0_optimized(left.0.mul_1_optimized(right.0) - F::one());
+                        delta.1 += eq_eval.mul_0_optimized(m_left.mul_0_optimized(m_right));
+                        if needs_q_1 {
+                            delta.2 +=
+                                eq_eval.mul_0_optimized(left.1.mul_1_optimized(right.1) - F::one());
+                        }
                     }
 
-                    // coeff[batch_index] * (eq_eval_sum + ∆) = coeff[batch_index] * (Σ eq_evals[i] + Σ eq_evals[j] * (left[j] * right[j] - 1))
-                    //                                        = coeff[batch_index] * (Σ eq_evals[j] * left[j] * right[j])
+                    // coeff[batch_index] * (1 + ∆) = coeff[batch_index] * (Σ eq_evals[i] + Σ eq_evals[j] * (left[j] * right[j] - 1))
+                    //                              = coeff[batch_index] * (Σ eq_evals[j] * left[j] * right[j])
                     (
-                        *coeff * (eq_eval_sums.0 + delta.0),
-                        *coeff * (eq_eval_sums.1 + delta.1),
-                        *coeff * (eq_eval_sums.2 + delta.2),
+                        *coeff * (F::one() + delta.0),
+                        *coeff * delta.1,
+                        *coeff * (F::one() + delta.2),
                     )
                 }
                 // If dense, we just compute
                 //     Σ coeff[batch_index] * (Σ eq_evals[i] * left[i] * right[i])
-                // directly in `self.compute_cubic`, without using `eq_eval_sums`.
+                // directly.
                 DynamicDensityGrandProductLayer::Dense(dense_layer) => {
                     // Computes:
                     //     coeff[batch_index] * (Σ eq_evals[i] * left[i] * right[i])
-                    // for the evaluation points {0, 2, 3}
+                    // and the leading coefficient of q
                     let evals = eq_evals
                         .iter()
                         .zip(dense_layer.chunks_exact(4))
-                        .map(|(eq_evals, chunk)| {
+                        .map(|(eq_eval, chunk)| {
                             let left = (chunk[0], chunk[2]);
                             let right = (chunk[1], chunk[3]);
 
                             let m_left = left.1 - left.0;
                             let m_right = right.1 - right.0;
-
-                            let left_eval_2 = left.1 + m_left;
-                            let left_eval_3 = left_eval_2 + m_left;
-
-                            let right_eval_2 = right.1 + m_right;
-                            let right_eval_3 = right_eval_2 + m_right;
+                            let eval_1 = if needs_q_1 {
+                                *eq_eval * left.1 * right.1
+                            } else {
+                                F::zero()
+                            };
 
                             (
-                                eq_evals.0 * left.0 * right.0,
-                                eq_evals.1 * left_eval_2 * right_eval_2,
-                                eq_evals.2 * left_eval_3 * right_eval_3,
+                                *eq_eval * left.0 * right.0,
+                                *eq_eval * m_left * m_right,
+                                eval_1,
                             )
                         })
                         .fold(
                             (F::zero(), F::zero(), F::zero()),
-                            |(sum_0, sum_2, sum_3), (a, b, c)| (sum_0 + a, sum_2 + b, sum_3 + c),
+                            |(sum_0, sum_infty, sum_1), (a, b, c)| {
+                                (sum_0 + a, sum_infty + b, sum_1 + c)
+                            },
                         );
                     (*coeff * evals.0, *coeff * evals.1, *coeff * evals.2)
                 }
@@ -970,16 +998,15 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedSparseGrandProductLayer<F>
             .collect();
 
         let evals_combined_0 = evals.iter().map(|eval| eval.0).sum();
-        let evals_combined_2 = evals.iter().map(|eval| eval.1).sum();
-        let evals_combined_3 = evals.iter().map(|eval| eval.2).sum();
+        let evals_combined_infty = evals.iter().map(|eval| eval.1).sum();
+        let evals_combined_1 = evals.iter().map(|eval| eval.2).sum();
 
-        let cubic_evals = [
+        eq_poly.gruen_cubic(
             evals_combined_0,
-            previous_round_claim - evals_combined_0,
-            evals_combined_2,
-            evals_combined_3,
-        ];
-        UniPoly::from_evals(&cubic_evals)
+            evals_combined_infty,
+            needs_q_1.then_some(evals_combined_1),
+            previous_round_claim,
+        )
     }
 
     fn final_claims(&self) -> (Vec<F>, Vec<F>) {
@@ -1079,7 +1106,7 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedGrandProductToggleLayer<F>
     ///   that it has value 0. In other words, a flag with value 1 will be present in both
     ///   `self.flag_indices` and `self.flag_values`.
     #[tracing::instrument(skip_all, name = "BatchedGrandProductToggleLayer::bind")]
-    fn bind(&mut self, eq_poly: &mut DensePolynomial<F>, r: &F) {
+    fn bind(&mut self, eq_poly: &mut SplitEqPolynomial<F>, r: &F) {
         self.fingerprints
             .par_iter_mut()
             .for_each(|layer: &mut Vec<F>| {
@@ -1173,7 +1200,7 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedGrandProductToggleLayer<F>
                         // flag_values.truncate(bound_index);
                     });
             },
-            || eq_poly.bound_poly_var_bot(r),
+            || eq_poly.bind(r),
         );
         self.layer_len /= 2;
     }
@@ -1186,52 +1213,33 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedGrandProductToggleLayer<F>
     fn compute_cubic(
         &self,
         coeffs: &[F],
-        eq_poly: &DensePolynomial<F>,
+        eq_poly: &SplitEqPolynomial<F>,
         previous_round_claim: F,
     ) -> UniPoly<F> {
-        let eq_evals: Vec<(F, F, F)> = (0..eq_poly.len() / 2)
-            .into_par_iter()
-            .map(|i| {
-                let eval_point_0 = eq_poly[2 * i];
-                let m_eq = eq_poly[2 * i + 1] - eq_poly[2 * i];
-                let eval_point_2 = eq_poly[2 * i + 1] + m_eq;
-                let eval_point_3 = eval_point_2 + m_eq;
-                (eval_point_0, eval_point_2, eval_point_3)
-            })
-            .collect();
+        let eq_evals = eq_poly.evals();
+        let needs_q_1 = eq_poly.needs_q_1();
 
-        // This is what the cubic evals would be if a layer's flags were *all 0*
-        // We pre-emptively compute these sums as a starting point:
-        //     eq_eval_sum := Σ eq_evals[i]
-        // What we ultimately want to compute:
+        // If a layer's flags were *all 0*, q(0) would be the sum of the eq evals, which is 1.
+        // We use this as a starting point. What we ultimately want to compute:
         //     Σ coeff[batch_index] * (Σ eq_evals[i] * (flag[i] * fingerprint[i] + 1 - flag[i]))
-        // Note that if flag[i] is all 1s, the inner sum is:
-        //     Σ eq_evals[i] = eq_eval_sum
+        // Note that if flag[i] is all 0s, the inner sum is:
+        //     Σ eq_evals[i] = 1
         // To recover the actual inner sum, we find all the non-zero flag[i] terms
         // computes the delta:
         //     ∆ := Σ eq_evals[j] * (flag[j] * fingerprint[j] - flag[j]))    ∀j where flag[j] ≠ 0
         // Then we can compute:
-        //    coeff[batch_index] * (eq_eval_sum + ∆) = coeff[batch_index] * (Σ eq_evals[i] + Σ eq_evals[i] * (flag[i] * fingerprint[i] - flag[i])))
-        //                                           = coeff[batch_index] * (Σ eq_evals[j] * (flag[i] * fingerprint[i] + 1 - flag[i]))
+        //    coeff[batch_index] * (1 + ∆) = coeff[batch_index] * (Σ eq_evals[i] + Σ eq_evals[i] * (flag[i] * fingerprint[i] - flag[i])))
+        //                                 = coeff[batch_index] * (Σ eq_evals[j] * (flag[i] * fingerprint[i] + 1 - flag[i]))
         // ...which is exactly the summand we want.
-        let eq_eval_sums: (F, F, F) = eq_evals
-            .par_iter()
-            .fold(
-                || (F::zero(), F::zero(), F::zero()),
-                |sum, evals| (sum.0 + evals.0, sum.1 + evals.1, sum.2 + evals.2),
-            )
-            .reduce(
-                || (F::zero(), F::zero(), F::zero()),
-                |sum, evals| (sum.0 + evals.0, sum.1 + evals.1, sum.2 + evals.2),
-            );
-
+        // The leading coefficient of q is Σ eq_evals[j] * m_flag[j] * m_fingerprint[j], which
+        // only gets contributions from non-zero flags.
         let evals: Vec<(F, F, F)> = coeffs
             .par_iter()
             .enumerate()
             .map(|(batch_index, coeff)| {
                 // Computes:
                 //     ∆ := Σ eq_evals[j] * (flag[j] * fingerprint[j] - flag[j])    ∀j where flag[j] ≠ 0
-                // for the evaluation points {0, 2, 3}
+                // and the leading coefficient of q
 
                 let fingerprints = &self.fingerprints[batch_index];
                 let flag_indices = &self.flag_indices[batch_index / 2];
@@ -1284,49 +1292,41 @@ impl<F: JoltField> BatchedCubicSumcheck<F> for BatchedGrandProductToggleLayer<F>
                         (flags, fingerprints)
                     };
 
+                    // If flags are still unbound, flags and m_flag will mostly be 0s and ±1s.
+                    // Bound flags are still mostly 0s.
                     let m_flag = flags.1 - flags.0;
                     let m_fingerprint = fingerprints.1 - fingerprints.0;
 
-                    // If flags are still unbound, flag evals will mostly be 0s and 1s
-                    // Bound flags are still mostly 0s, so flag evals will mostly be 0s.
-                    let flag_eval_2 = flags.1 + m_flag;
-                    let flag_eval_3 = flag_eval_2 + m_flag;
-
-                    let fingerprint_eval_2 = fingerprints.1 + m_fingerprint;
-                    let fingerprint_eval_3 = fingerprint_eval_2 + m_fingerprint;
-
-                    let (eq_eval_0, eq_eval_2, eq_eval_3) = eq_evals[index / 2];
-                    delta.0 += eq_eval_0
-                        .mul_0_optimized(flags.0.mul_01_optimized(fingerprints.0) - flags.0);
-                    delta.1 += eq_eval_2.mul_0_optimized(
-                        flag_eval_2.mul_01_optimized(fingerprint_eval_2) - flag_eval_2,
-                    );
-                    delta.2 += eq_eval_3.mul_0_optimized(
-                        flag_eval_3.mul_01_optimized(fingerprint_eval_3) - flag_eval_3,
-                    );
+                    let eq_eval = eq_evals[index / 2];
+                    delta.0 +=
+                        eq_eval.mul_0_optimized(flags.0.mul_01_optimized(fingerprints.0) - flags.0);
+                    delta.1 += eq_eval.mul_0_optimized(m_flag.mul_01_optimized(m_fingerprint));
+                    if needs_q_1 {
+                        delta.2 += eq_eval
+                            .mul_0_optimized(flags.1.mul_01_optimized(fingerprints.1) - flags.1);
+                    }
                 }
 
-                // coeff[batch_index] * (eq_eval_sum + ∆) = coeff[batch_index] * (Σ eq_evals[i] + Σ eq_evals[i] * (flag[i] * fingerprint[i] - flag[i])))
-                //                                        = coeff[batch_index] * (Σ eq_evals[j] * (flag[i] * fingerprint[i] + 1 - flag[i]))
+                // coeff[batch_index] * (1 + ∆) = coeff[batch_index] * (Σ eq_evals[i] + Σ eq_evals[i] * (flag[i] * fingerprint[i] - flag[i])))
+                //                              = coeff[batch_index] * (Σ eq_evals[j] * (flag[i] * fingerprint[i] + 1 - flag[i]))
                 (
-                    *coeff * (eq_eval_sums.0 + delta.0),
-                    *coeff * (eq_eval_sums.1 + delta.1),
-                    *coeff * (eq_eval_sums.2 + delta.2),
+                    *coeff * (F::one() + delta.0),
+                    *coeff * delta.1,
+                    *coeff * (F::one() + delta.2),
                 )
             })
             .collect();
 
         let evals_combined_0 = evals.iter().map(|eval| eval.0).sum();
-        let evals_combined_2 = evals.iter().map(|eval| eval.1).sum();
-        let evals_combined_3 = evals.iter().map(|eval| eval.2).sum();
+        let evals_combined_infty = evals.iter().map(|eval| eval.1).sum();
+        let evals_combined_1 = evals.iter().map(|eval| eval.2).sum();
 
-        let cubic_evals = [
+        eq_poly.gruen_cubic(
             evals_combined_0,
-            previous_round_claim - evals_combined_0,
-            evals_combined_2,
-            evals_combined_3,
-        ];
-        UniPoly::from_evals(&cubic_evals)
+            evals_combined_infty,
+            needs_q_1.then_some(evals_combined_1),
+            previous_round_claim,
+        )
     }
 
     fn final_claims(&self) -> (Vec<F>, Vec<F>) {
@@ -1364,7 +1364,7 @@ impl<F: JoltField> BatchedGrandProductLayer<F> for BatchedGrandProductToggleLaye
             .map(|(&claim, &coeff)| claim * coeff)
             .sum();
 
-        let mut eq_poly = DensePolynomial::new(EqPolynomial::<F>::evals(r_grand_product));
+        let mut eq_poly = SplitEqPolynomial::new(r_grand_product, BindingOrder::Bottom);
 
         let (sumcheck_proof, r_sumcheck, sumcheck_claims) =
             self.prove_sumcheck(&claim, &coeffs, &mut eq_poly, transcript);
@@ -1395,7 +1395,11 @@ pub struct ToggledBatchedGrandProduct<F: JoltField> {
     sparse_layers: Vec<BatchedSparseGrandProductLayer<F>>,
 }
 
-impl<F: JoltField> BatchedGrandProduct<F> for ToggledBatchedGrandProduct<F> {
+impl<F, C> BatchedGrandProduct<F, C> for ToggledBatchedGrandProduct<F>
+where
+    F: JoltField,
+    C: CommitmentScheme<Field = F>,
+{
     type Leaves = (Vec<Vec<usize>>, Vec<Vec<F>>); // (flags, fingerprints)
 
     #[tracing::instrument(skip_all, name = "ToggledBatchedGrandProduct::construct")]
@@ -1461,7 +1465,7 @@ impl<F: JoltField> BatchedGrandProduct<F> for ToggledBatchedGrandProduct<F> {
         grand_product_claims: &mut Vec<F>,
         r_grand_product: &mut Vec<F>,
         transcript: &mut ProofTranscript,
-    ) {
+    ) -> Result<(), ProofVerifyError> {
         let layer_proof = &layer_proofs[layer_index];
         if layer_index != layer_proofs.len() - 1 {
             // Normal grand product layer (multiplication gates)
@@ -1471,7 +1475,9 @@ impl<F: JoltField> BatchedGrandProduct<F> for ToggledBatchedGrandProduct<F> {
                 })
                 .sum();
 
-            assert_eq!(expected_sumcheck_claim, sumcheck_claim);
+            if expected_sumcheck_claim != sumcheck_claim {
+                return Err(ProofVerifyError::SumcheckClaimError);
+            }
 
             // produce a random challenge to condense two claims into a single claim
             let r_layer = transcript.challenge_scalar(b"challenge_r_layer");
@@ -1498,7 +1504,9 @@ impl<F: JoltField> BatchedGrandProduct<F> for ToggledBatchedGrandProduct<F> {
                 })
                 .sum();
 
-            assert_eq!(expected_sumcheck_claim, sumcheck_claim);
+            if expected_sumcheck_claim != sumcheck_claim {
+                return Err(ProofVerifyError::SumcheckClaimError);
+            }
 
             *grand_product_claims = layer_proof
                 .left_claims
@@ -1509,13 +1517,15 @@ impl<F: JoltField> BatchedGrandProduct<F> for ToggledBatchedGrandProduct<F> {
                 })
                 .collect();
         }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod grand_product_tests {
     use super::*;
-    use ark_bn254::Fr;
+    use crate::poly::commitment::hyrax::HyraxScheme;
+    use ark_bn254::{Fr, G1Projective};
     use ark_std::test_rng;
     use rand_core::RngCore;
 
@@ -1532,15 +1542,23 @@ mod grand_product_tests {
         .take(BATCH_SIZE)
         .collect();
 
-        let mut batched_circuit = BatchedDenseGrandProduct::construct(leaves);
+        type GrandProduct = BatchedDenseGrandProduct<Fr>;
+        type Pcs = HyraxScheme<G1Projective>;
+        let mut batched_circuit = <GrandProduct as BatchedGrandProduct<Fr, Pcs>>::construct(leaves);
         let mut transcript: ProofTranscript = ProofTranscript::new(b"test_transcript");
 
-        let claims = batched_circuit.claims();
-        let (proof, r_prover) = batched_circuit.prove_grand_product(&mut transcript);
+        let claims = <GrandProduct as BatchedGrandProduct<Fr, Pcs>>::claims(&batched_circuit);
+        let (proof, r_prover) = <GrandProduct as BatchedGrandProduct<Fr, Pcs>>::prove_grand_product(
+            &mut batched_circuit,
+            None,
+            &mut transcript,
+            None,
+        );
 
         let mut transcript: ProofTranscript = ProofTranscript::new(b"test_transcript");
         let (_, r_verifier) =
-            BatchedDenseGrandProduct::verify_grand_product(&proof, &claims, &mut transcript);
+            GrandProduct::verify_grand_product(&proof, &claims, None, &mut transcript, None)
+                .unwrap();
         assert_eq!(r_prover, r_verifier);
     }
 
@@ -1618,7 +1636,7 @@ mod grand_product_tests {
             let r_eq = std::iter::repeat_with(|| Fr::random(&mut rng))
                 .take(4)
                 .collect::<Vec<_>>();
-            let mut eq_poly_dense = DensePolynomial::new(EqPolynomial::<Fr>::evals(&r_eq));
+            let mut eq_poly_dense = SplitEqPolynomial::new(&r_eq, BindingOrder::Bottom);
             let mut eq_poly_sparse = eq_poly_dense.clone();
 
             let r = Fr::random(&mut rng);
@@ -1660,8 +1678,7 @@ mod grand_product_tests {
                 }
             })
             .take(LAYER_SIZE)
-            .collect::<Vec<_>>()
-            .into();
+            .collect();
             DynamicDensityGrandProductLayer::Dense(layer)
         })
         .take(BATCH_SIZE)
@@ -1696,11 +1713,20 @@ mod grand_product_tests {
         let r_eq = std::iter::repeat_with(|| Fr::random(&mut rng))
             .take(LAYER_SIZE.log_2() - 1)
             .collect::<Vec<_>>();
-        let eq_poly = DensePolynomial::new(EqPolynomial::<Fr>::evals(&r_eq));
+        let eq_poly = SplitEqPolynomial::new(&r_eq, BindingOrder::Bottom);
         let claim = Fr::random(&mut rng);
 
         let dense_evals = dense_layers.compute_cubic(&coeffs, &eq_poly, claim);
         let sparse_evals = sparse_layers.compute_cubic(&coeffs, &eq_poly, claim);
         assert_eq!(dense_evals, sparse_evals);
+
+        // If the variable being bound has w_j = 0, q(1) comes from the layers instead of the claim
+        let mut r_eq = r_eq;
+        r_eq[LAYER_SIZE.log_2() - 2] = JoltField::zero();
+        let eq_poly = SplitEqPolynomial::new(&r_eq, BindingOrder::Bottom);
+        assert!(eq_poly.needs_q_1());
+        let dense_evals = dense_layers.compute_cubic(&coeffs, &eq_poly, claim);
+        let sparse_evals = sparse_layers.compute_cubic(&coeffs, &eq_poly, claim);
+        assert_eq!(dense_evals, sparse_evals);
     }
 }
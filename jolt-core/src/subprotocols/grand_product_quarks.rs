@@ -0,0 +1,577 @@
+use super::grand_product::{
+    BatchedDenseGrandProductLayer, BatchedGrandProduct, BatchedGrandProductLayer,
+    BatchedGrandProductProof,
+};
+use super::sumcheck::SumcheckInstanceProof;
+use crate::poly::commitment::commitment_scheme::{BatchType, CommitmentScheme};
+use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::poly::eq_poly::{BindingOrder, EqPolynomial, SplitEqPolynomial};
+use crate::poly::field::JoltField;
+use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
+use crate::utils::errors::ProofVerifyError;
+use crate::utils::math::Math;
+use crate::utils::thread::drop_in_background_thread;
+use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
+use ark_serialize::*;
+use rayon::prelude::*;
+use std::marker::PhantomData;
+
+/// The number of layers at the bottom of the circuits proven with GKR by default; everything
+/// above is proven by a single Quarks sumcheck.
+pub const DEFAULT_NUM_GKR_LAYERS: usize = 4;
+
+/// Proves the top of a batch of grand product circuits as in Quarks
+/// (https://eprint.iacr.org/2020/1275). For each circuit with inputs v (the values at the
+/// crossover layer, of size 2^k), the prover commits to the partial products g, laid out so that
+/// f = v || g satisfies
+///     f(1, x) = f(x, 0) * f(x, 1)    for all x in {0, 1}^k
+/// with g(1, ..., 1, 0) being the product of v and g(1, ..., 1) = 0. This is checked with a
+/// single sumcheck over x, after which the claims about f are reduced to one claim about v
+/// (which is passed on to the GKR layers below) and openings of g.
+#[derive(CanonicalSerialize, CanonicalDeserialize)]
+pub struct QuarkGrandProductProof<C: CommitmentScheme> {
+    g_commitments: Vec<C::Commitment>,
+    sumcheck_proof: SumcheckInstanceProof<C::Field>,
+    /// g(r) for each circuit, where r is the sumcheck point
+    g_openings: Vec<C::Field>,
+    /// v(r', 0) and v(r', 1) for each circuit, where r = (r_1, r')
+    v_left_openings: Vec<C::Field>,
+    v_right_openings: Vec<C::Field>,
+    /// g(r', 0) and g(r', 1) for each circuit
+    g_left_openings: Vec<C::Field>,
+    g_right_openings: Vec<C::Field>,
+}
+
+/// A hybrid batched grand product: the bottom `NUM_GKR_LAYERS` layers of the circuits are
+/// proven layer by layer with GKR, like `BatchedDenseGrandProduct`, and the layers above with a
+/// `QuarkGrandProductProof`. Compared to GKR all the way up, this trades a commitment to the
+/// partial products of the top layers for fewer sumcheck rounds, i.e. a smaller proof and
+/// a faster verifier. For circuits with at most `NUM_GKR_LAYERS` layers, only the root layer is
+/// proven with Quarks.
+pub struct QuarkGrandProduct<
+    F: JoltField,
+    C: CommitmentScheme<Field = F>,
+    const NUM_GKR_LAYERS: usize = DEFAULT_NUM_GKR_LAYERS,
+> {
+    gkr_layers: Vec<BatchedDenseGrandProductLayer<F>>,
+    /// The inputs v to the Quarks part of the circuits, i.e. the outputs of the GKR layers.
+    quark_inputs: Vec<Vec<F>>,
+    /// The partial products g of each circuit; see `QuarkGrandProductProof`.
+    partial_products: Vec<Vec<F>>,
+    _marker: PhantomData<C>,
+}
+
+impl<F, C, const NUM_GKR_LAYERS: usize> QuarkGrandProduct<F, C, NUM_GKR_LAYERS>
+where
+    F: JoltField,
+    C: CommitmentScheme<Field = F>,
+{
+    /// The point (1, ..., 1, 0) at which g is the product of v.
+    fn product_point(num_vars: usize) -> Vec<F> {
+        let mut point = vec![F::one(); num_vars];
+        point[num_vars - 1] = F::zero();
+        point
+    }
+
+    /// Computes g from v, filling in the tree of partial products bottom-up.
+    fn partial_products(v: &[F]) -> Vec<F> {
+        let n = v.len();
+        let mut g = vec![F::zero(); n];
+        for j in 0..n - 1 {
+            let f = |index: usize| if index < n { v[index] } else { g[index - n] };
+            g[j] = f(2 * j) * f(2 * j + 1);
+        }
+        g
+    }
+
+    #[tracing::instrument(skip_all, name = "QuarkGrandProduct::prove_quark")]
+    fn prove_quark(
+        &self,
+        opening_accumulator: &mut ProverOpeningAccumulator<F>,
+        transcript: &mut ProofTranscript,
+        setup: &C::Setup,
+    ) -> (QuarkGrandProductProof<C>, Vec<F>, Vec<F>) {
+        let num_vars = self.quark_inputs[0].len().log_2();
+
+        let g_polys: Vec<DensePolynomial<F>> = self
+            .partial_products
+            .par_iter()
+            .map(|g| DensePolynomial::new(g.clone()))
+            .collect();
+        let g_commitments = C::batch_commit_polys(&g_polys, setup, BatchType::Small);
+        for commitment in g_commitments.iter() {
+            commitment.append_to_transcript(b"quark_commitment", transcript);
+        }
+
+        let coeffs: Vec<F> =
+            transcript.challenge_vector(b"quark_rand_coeffs", self.quark_inputs.len());
+        let tau: Vec<F> = transcript.challenge_vector(b"quark_tau", num_vars);
+
+        // The sumcheck is over eq(tau, x) * Σ coeff * (g(x) - f(x, 0) * f(x, 1))
+        let (mut left_polys, mut right_polys): (Vec<_>, Vec<_>) = self
+            .quark_inputs
+            .par_iter()
+            .zip(self.partial_products.par_iter())
+            .map(|(v, g)| {
+                let f = |index: usize| {
+                    if index < v.len() {
+                        v[index]
+                    } else {
+                        g[index - v.len()]
+                    }
+                };
+                let left = (0..v.len()).map(|j| f(2 * j)).collect();
+                let right = (0..v.len()).map(|j| f(2 * j + 1)).collect();
+                (DensePolynomial::new(left), DensePolynomial::new(right))
+            })
+            .unzip();
+        let mut g_sumcheck_polys = g_polys.clone();
+
+        let mut eq_poly = SplitEqPolynomial::new(&tau, BindingOrder::Top);
+        let mut r: Vec<F> = Vec::with_capacity(num_vars);
+        let mut cubic_polys = Vec::with_capacity(num_vars);
+        let mut previous_claim = F::zero();
+        for _ in 0..num_vars {
+            let eq_evals = eq_poly.evals();
+            let len = eq_evals.len();
+            let needs_q_1 = eq_poly.needs_q_1();
+            let (eval_point_0, eval_point_infty, eval_point_1) = (0..len)
+                .into_par_iter()
+                .map(|j| {
+                    let mut evals = (F::zero(), F::zero(), F::zero());
+                    for (batch_index, coeff) in coeffs.iter().enumerate() {
+                        let g = &g_sumcheck_polys[batch_index];
+                        let left = &left_polys[batch_index];
+                        let right = &right_polys[batch_index];
+                        // g is linear in the variable being bound, so only the product
+                        // contributes to the leading coefficient.
+                        let m_left = left[len + j] - left[j];
+                        let m_right = right[len + j] - right[j];
+                        evals.0 += *coeff * (g[j] - left[j] * right[j]);
+                        evals.1 -= *coeff * m_left * m_right;
+                        if needs_q_1 {
+                            evals.2 += *coeff * (g[len + j] - left[len + j] * right[len + j]);
+                        }
+                    }
+                    (
+                        eq_evals[j] * evals.0,
+                        eq_evals[j] * evals.1,
+                        eq_evals[j] * evals.2,
+                    )
+                })
+                .reduce(
+                    || (F::zero(), F::zero(), F::zero()),
+                    |sum, evals| (sum.0 + evals.0, sum.1 + evals.1, sum.2 + evals.2),
+                );
+            let cubic_poly = eq_poly.gruen_cubic(
+                eval_point_0,
+                eval_point_infty,
+                needs_q_1.then_some(eval_point_1),
+                previous_claim,
+            );
+            cubic_poly.append_to_transcript(b"poly", transcript);
+            let r_j: F = transcript.challenge_scalar(b"challenge_nextround");
+            r.push(r_j);
+
+            rayon::join(
+                || eq_poly.bind(&r_j),
+                || {
+                    g_sumcheck_polys
+                        .par_iter_mut()
+                        .chain(left_polys.par_iter_mut())
+                        .chain(right_polys.par_iter_mut())
+                        .for_each(|poly| poly.bound_poly_var_top(&r_j))
+                },
+            );
+
+            previous_claim = cubic_poly.evaluate(&r_j);
+            cubic_polys.push(cubic_poly.compress());
+        }
+        drop_in_background_thread((g_sumcheck_polys, left_polys, right_polys));
+
+        // f(r, b) = (1 - r_1) * v(r', b) + r_1 * g(r', b)
+        let r_prime = &r[1..];
+        let eq_r_prime = EqPolynomial::evals(r_prime);
+        let openings_at = |poly: &[F]| -> (F, F) {
+            let (mut left, mut right) = (F::zero(), F::zero());
+            for (j, eq) in eq_r_prime.iter().enumerate() {
+                left += *eq * poly[2 * j];
+                right += *eq * poly[2 * j + 1];
+            }
+            (left, right)
+        };
+        let (v_left_openings, v_right_openings): (Vec<F>, Vec<F>) =
+            self.quark_inputs.par_iter().map(|v| openings_at(v)).unzip();
+        let (g_left_openings, g_right_openings): (Vec<F>, Vec<F>) = self
+            .partial_products
+            .par_iter()
+            .map(|g| openings_at(g))
+            .unzip();
+        let g_openings: Vec<F> = g_polys.par_iter().map(|g| g.evaluate(&r)).collect();
+
+        transcript.append_scalars(b"quark g openings", &g_openings);
+        transcript.append_scalars(b"quark v left openings", &v_left_openings);
+        transcript.append_scalars(b"quark v right openings", &v_right_openings);
+        transcript.append_scalars(b"quark g left openings", &g_left_openings);
+        transcript.append_scalars(b"quark g right openings", &g_right_openings);
+
+        // produce a random challenge to condense the left and right claims into a single claim
+        let rho: F = transcript.challenge_scalar(b"challenge_r_layer");
+        let mut r_rho = r_prime.to_vec();
+        r_rho.push(rho);
+        let condense = |left: &[F], right: &[F]| -> Vec<F> {
+            left.iter()
+                .zip(right.iter())
+                .map(|(&left, &right)| left + rho * (right - left))
+                .collect()
+        };
+        let v_claims = condense(&v_left_openings, &v_right_openings);
+        let g_rho_openings = condense(&g_left_openings, &g_right_openings);
+
+        let g_poly_refs: Vec<&DensePolynomial<F>> = g_polys.iter().collect();
+        opening_accumulator.append(&g_poly_refs, &r, &g_openings, transcript);
+        opening_accumulator.append(&g_poly_refs, &r_rho, &g_rho_openings, transcript);
+        opening_accumulator.append(
+            &g_poly_refs,
+            &Self::product_point(num_vars),
+            &self.claims_from_partial_products(),
+            transcript,
+        );
+
+        let proof = QuarkGrandProductProof {
+            g_commitments,
+            sumcheck_proof: SumcheckInstanceProof::new(cubic_polys),
+            g_openings,
+            v_left_openings,
+            v_right_openings,
+            g_left_openings,
+            g_right_openings,
+        };
+        (proof, v_claims, r_rho)
+    }
+
+    fn claims_from_partial_products(&self) -> Vec<F> {
+        self.partial_products
+            .iter()
+            .map(|g| g[g.len() - 2])
+            .collect()
+    }
+
+    /// Verifies a `QuarkGrandProductProof` for the given `claims`, and returns the claims about
+    /// the Quarks inputs v and the point they are evaluated at.
+    fn verify_quark(
+        proof: &QuarkGrandProductProof<C>,
+        claims: &[F],
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, C>,
+        transcript: &mut ProofTranscript,
+    ) -> Result<(Vec<F>, Vec<F>), ProofVerifyError> {
+        let num_vars = proof.sumcheck_proof.num_rounds();
+        if num_vars == 0 {
+            return Err(ProofVerifyError::InvalidInputLength(1, 0));
+        }
+        let batch_size = claims.len();
+        for len in [
+            proof.g_commitments.len(),
+            proof.g_openings.len(),
+            proof.v_left_openings.len(),
+            proof.v_right_openings.len(),
+            proof.g_left_openings.len(),
+            proof.g_right_openings.len(),
+        ] {
+            if len != batch_size {
+                return Err(ProofVerifyError::InvalidInputLength(batch_size, len));
+            }
+        }
+
+        for commitment in proof.g_commitments.iter() {
+            commitment.append_to_transcript(b"quark_commitment", transcript);
+        }
+        let coeffs: Vec<F> = transcript.challenge_vector(b"quark_rand_coeffs", batch_size);
+        let tau: Vec<F> = transcript.challenge_vector(b"quark_tau", num_vars);
+
+        let (sumcheck_claim, r) =
+            proof
+                .sumcheck_proof
+                .verify(F::zero(), num_vars, 3, transcript)?;
+
+        // f(r, b) = (1 - r_1) * v(r', b) + r_1 * g(r', b)
+        let f_openings = |v: &[F], g: &[F]| -> Vec<F> {
+            v.iter()
+                .zip(g.iter())
+                .map(|(&v, &g)| v + r[0] * (g - v))
+                .collect()
+        };
+        let f_left = f_openings(&proof.v_left_openings, &proof.g_left_openings);
+        let f_right = f_openings(&proof.v_right_openings, &proof.g_right_openings);
+        let expected_sumcheck_claim: F = (0..batch_size)
+            .map(|i| coeffs[i] * (proof.g_openings[i] - f_left[i] * f_right[i]))
+            .sum::<F>()
+            * EqPolynomial::new(tau).evaluate(&r);
+        if expected_sumcheck_claim != sumcheck_claim {
+            return Err(ProofVerifyError::SumcheckClaimError);
+        }
+
+        transcript.append_scalars(b"quark g openings", &proof.g_openings);
+        transcript.append_scalars(b"quark v left openings", &proof.v_left_openings);
+        transcript.append_scalars(b"quark v right openings", &proof.v_right_openings);
+        transcript.append_scalars(b"quark g left openings", &proof.g_left_openings);
+        transcript.append_scalars(b"quark g right openings", &proof.g_right_openings);
+
+        let rho: F = transcript.challenge_scalar(b"challenge_r_layer");
+        let mut r_rho = r[1..].to_vec();
+        r_rho.push(rho);
+        let condense = |left: &[F], right: &[F]| -> Vec<F> {
+            left.iter()
+                .zip(right.iter())
+                .map(|(&left, &right)| left + rho * (right - left))
+                .collect()
+        };
+        let v_claims = condense(&proof.v_left_openings, &proof.v_right_openings);
+        let g_rho_openings = condense(&proof.g_left_openings, &proof.g_right_openings);
+
+        let commitments: Vec<&C::Commitment> = proof.g_commitments.iter().collect();
+        for (opening_point, openings) in [
+            (r.clone(), proof.g_openings.as_slice()),
+            (r_rho.clone(), g_rho_openings.as_slice()),
+            (Self::product_point(num_vars), claims),
+        ] {
+            opening_accumulator.append(&commitments, &opening_point, openings, transcript)?;
+        }
+
+        Ok((v_claims, r_rho))
+    }
+}
+
+impl<F, C, const NUM_GKR_LAYERS: usize> BatchedGrandProduct<F, C>
+    for QuarkGrandProduct<F, C, NUM_GKR_LAYERS>
+where
+    F: JoltField,
+    C: CommitmentScheme<Field = F>,
+{
+    type Leaves = Vec<Vec<F>>;
+
+    #[tracing::instrument(skip_all, name = "QuarkGrandProduct::construct")]
+    fn construct(leaves: Self::Leaves) -> Self {
+        let num_layers = leaves[0].len().log_2();
+        // The Quarks part needs at least two inputs per circuit
+        let num_gkr_layers = std::cmp::min(NUM_GKR_LAYERS, num_layers - 1);
+
+        let mut gkr_layers = Vec::with_capacity(num_gkr_layers);
+        let mut current_layers = leaves;
+        for _ in 0..num_gkr_layers {
+            let len = current_layers[0].len() / 2;
+            let next_layers = current_layers
+                .par_iter()
+                .map(|layer| (0..len).map(|i| layer[2 * i] * layer[2 * i + 1]).collect())
+                .collect();
+            gkr_layers.push(BatchedDenseGrandProductLayer::new(current_layers));
+            current_layers = next_layers;
+        }
+
+        let partial_products = current_layers
+            .par_iter()
+            .map(|v| Self::partial_products(v))
+            .collect();
+
+        Self {
+            gkr_layers,
+            quark_inputs: current_layers,
+            partial_products,
+            _marker: PhantomData,
+        }
+    }
+
+    fn num_layers(&self) -> usize {
+        self.gkr_layers.len() + self.quark_inputs[0].len().log_2()
+    }
+
+    fn claims(&self) -> Vec<F> {
+        self.claims_from_partial_products()
+    }
+
+    fn layers(&'_ mut self) -> impl Iterator<Item = &'_ mut dyn BatchedGrandProductLayer<F>> {
+        self.gkr_layers
+            .iter_mut()
+            .map(|layer| layer as &mut dyn BatchedGrandProductLayer<F>)
+            .rev()
+    }
+
+    #[tracing::instrument(skip_all, name = "QuarkGrandProduct::prove_grand_product")]
+    fn prove_grand_product(
+        &mut self,
+        opening_accumulator: Option<&mut ProverOpeningAccumulator<F>>,
+        transcript: &mut ProofTranscript,
+        setup: Option<&C::Setup>,
+    ) -> (BatchedGrandProductProof<C>, Vec<F>) {
+        let opening_accumulator =
+            opening_accumulator.expect("QuarkGrandProduct opens its partial products");
+        let setup = setup.expect("QuarkGrandProduct commits to partial products");
+        let (quark_proof, mut claims_to_verify, mut r_grand_product) =
+            self.prove_quark(opening_accumulator, transcript, setup);
+
+        let mut proof_layers = Vec::with_capacity(self.gkr_layers.len());
+        for layer in self.layers() {
+            proof_layers.push(layer.prove_layer(
+                &mut claims_to_verify,
+                &mut r_grand_product,
+                transcript,
+            ));
+        }
+
+        (
+            BatchedGrandProductProof {
+                layers: proof_layers,
+                quark_proof: Some(quark_proof),
+            },
+            r_grand_product,
+        )
+    }
+
+    fn verify_grand_product(
+        proof: &BatchedGrandProductProof<C>,
+        claims: &Vec<F>,
+        opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, C>>,
+        transcript: &mut ProofTranscript,
+        _setup: Option<&C::Setup>,
+    ) -> Result<(Vec<F>, Vec<F>), ProofVerifyError> {
+        // The partial products are checked through the opening accumulator, so verifying
+        // without one would accept any of them.
+        let opening_accumulator = opening_accumulator.ok_or(ProofVerifyError::InternalError)?;
+        // A proof without its Quarks part is malformed, not a verifier bug
+        let quark_proof = proof
+            .quark_proof
+            .as_ref()
+            .ok_or(ProofVerifyError::InternalError)?;
+        let (v_claims, r_grand_product) =
+            Self::verify_quark(quark_proof, claims, opening_accumulator, transcript)?;
+        Self::verify_layers(&proof.layers, &v_claims, transcript, r_grand_product)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::commitment::commitment_scheme::CommitShape;
+    use crate::poly::commitment::hyrax::HyraxScheme;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_std::test_rng;
+
+    type Pcs = HyraxScheme<G1Projective>;
+
+    fn prove_verify<const NUM_GKR_LAYERS: usize>(layer_size: usize) {
+        const BATCH_SIZE: usize = 4;
+        let mut rng = test_rng();
+        let leaves: Vec<Vec<Fr>> = std::iter::repeat_with(|| {
+            std::iter::repeat_with(|| Fr::random(&mut rng))
+                .take(layer_size)
+                .collect()
+        })
+        .take(BATCH_SIZE)
+        .collect();
+        let expected_claims: Vec<Fr> = leaves
+            .iter()
+            .map(|leaves| leaves.iter().product())
+            .collect();
+        let setup = Pcs::setup(&[CommitShape::new(layer_size, BatchType::Small)]);
+
+        let mut circuit = QuarkGrandProduct::<Fr, Pcs, NUM_GKR_LAYERS>::construct(leaves.clone());
+        let claims = circuit.claims();
+        assert_eq!(claims, expected_claims);
+
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        let mut prover_accumulator = ProverOpeningAccumulator::new();
+        let (proof, r_prover) = circuit.prove_grand_product(
+            Some(&mut prover_accumulator),
+            &mut transcript,
+            Some(&setup),
+        );
+        let opening_proof = prover_accumulator.reduce_and_prove::<Pcs>(&setup, &mut transcript);
+
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        let mut verifier_accumulator = VerifierOpeningAccumulator::new();
+        let (leaf_claims, r_verifier) =
+            QuarkGrandProduct::<Fr, Pcs, NUM_GKR_LAYERS>::verify_grand_product(
+                &proof,
+                &claims,
+                Some(&mut verifier_accumulator),
+                &mut transcript,
+                Some(&setup),
+            )
+            .unwrap();
+        verifier_accumulator
+            .reduce_and_verify(&setup, &opening_proof, &mut transcript)
+            .unwrap();
+        assert_eq!(r_prover, r_verifier);
+        for (leaves, claim) in leaves.into_iter().zip(leaf_claims) {
+            assert_eq!(DensePolynomial::new(leaves).evaluate(&r_verifier), claim);
+        }
+    }
+
+    #[test]
+    fn quark_prove_verify() {
+        prove_verify::<0>(1 << 8);
+        prove_verify::<0>(2);
+        prove_verify::<DEFAULT_NUM_GKR_LAYERS>(1 << 8);
+        // Only the root layer is proven with Quarks
+        prove_verify::<DEFAULT_NUM_GKR_LAYERS>(1 << 3);
+    }
+
+    #[test]
+    fn quark_verify_without_accumulator() {
+        let mut rng = test_rng();
+        let leaves: Vec<Vec<Fr>> = vec![(0..1 << 6).map(|_| Fr::random(&mut rng)).collect()];
+        let setup = Pcs::setup(&[CommitShape::new(1 << 6, BatchType::Small)]);
+
+        let mut circuit = QuarkGrandProduct::<Fr, Pcs>::construct(leaves);
+        let claims = circuit.claims();
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        let (proof, _) = circuit.prove_grand_product(
+            Some(&mut ProverOpeningAccumulator::new()),
+            &mut transcript,
+            Some(&setup),
+        );
+
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        let result = QuarkGrandProduct::<Fr, Pcs>::verify_grand_product(
+            &proof,
+            &claims,
+            None,
+            &mut transcript,
+            Some(&setup),
+        );
+        assert!(matches!(result, Err(ProofVerifyError::InternalError)));
+    }
+
+    #[test]
+    fn quark_wrong_claim() {
+        let mut rng = test_rng();
+        let leaves: Vec<Vec<Fr>> = vec![(0..1 << 6).map(|_| Fr::random(&mut rng)).collect()];
+        let setup = Pcs::setup(&[CommitShape::new(1 << 6, BatchType::Small)]);
+
+        let mut circuit = QuarkGrandProduct::<Fr, Pcs>::construct(leaves);
+        let mut claims = circuit.claims();
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        let mut prover_accumulator = ProverOpeningAccumulator::new();
+        let (proof, _) = circuit.prove_grand_product(
+            Some(&mut prover_accumulator),
+            &mut transcript,
+            Some(&setup),
+        );
+        let opening_proof = prover_accumulator.reduce_and_prove::<Pcs>(&setup, &mut transcript);
+
+        claims[0] += Fr::from_u64(1).unwrap();
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        let mut verifier_accumulator = VerifierOpeningAccumulator::new();
+        let result = QuarkGrandProduct::<Fr, Pcs>::verify_grand_product(
+            &proof,
+            &claims,
+            Some(&mut verifier_accumulator),
+            &mut transcript,
+            Some(&setup),
+        )
+        .and_then(|_| {
+            verifier_accumulator.reduce_and_verify(&setup, &opening_proof, &mut transcript)
+        });
+        assert!(result.is_err());
+    }
+}
@@ -2,6 +2,7 @@
 #![allow(clippy::type_complexity)]
 
 use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::poly::eq_poly::SplitEqPolynomial;
 use crate::poly::field::JoltField;
 use crate::poly::unipoly::{CompressedUniPoly, UniPoly};
 use crate::r1cs::spartan::IndexablePoly;
@@ -15,11 +16,11 @@ use rayon::prelude::*;
 /// Batched cubic sumcheck used in grand products
 pub trait BatchedCubicSumcheck<F: JoltField>: Sync {
     fn num_rounds(&self) -> usize;
-    fn bind(&mut self, eq_poly: &mut DensePolynomial<F>, r: &F);
+    fn bind(&mut self, eq_poly: &mut SplitEqPolynomial<F>, r: &F);
     fn compute_cubic(
         &self,
         coeffs: &[F],
-        eq_poly: &DensePolynomial<F>,
+        eq_poly: &SplitEqPolynomial<F>,
         previous_round_claim: F,
     ) -> UniPoly<F>;
     fn final_claims(&self) -> (Vec<F>, Vec<F>);
@@ -29,7 +30,7 @@ pub trait BatchedCubicSumcheck<F: JoltField>: Sync {
         &mut self,
         claim: &F,
         coeffs: &[F],
-        eq_poly: &mut DensePolynomial<F>,
+        eq_poly: &mut SplitEqPolynomial<F>,
         transcript: &mut ProofTranscript,
     ) -> (SumcheckInstanceProof<F>, Vec<F>, (Vec<F>, Vec<F>)) {
         debug_assert_eq!(eq_poly.get_num_vars(), self.num_rounds());
@@ -53,7 +54,7 @@ pub trait BatchedCubicSumcheck<F: JoltField>: Sync {
             cubic_polys.push(cubic_poly.compress());
         }
 
-        debug_assert_eq!(eq_poly.len(), 1);
+        debug_assert_eq!(eq_poly.get_num_vars(), 0);
 
         (
             SumcheckInstanceProof::new(cubic_polys),
@@ -174,98 +175,84 @@ impl<F: JoltField> SumcheckInstanceProof<F> {
         (SumcheckInstanceProof::new(compressed_polys), r, final_evals)
     }
 
+    /// Computes q(0) and the leading coefficient q_infty of
+    ///     q(X) = Σ eq(w', x') * (A(X, x') * B(X, x') - C(X, x'))
+    /// where the sum is over the unbound variables x' other than the top one (see
+    /// `SplitEqPolynomial`), as well as q(1) if `SplitEqPolynomial::needs_q_1`.
     #[inline]
     #[tracing::instrument(
         skip_all,
         name = "Spartan2::sumcheck::compute_eval_points_spartan_cubic"
     )]
-    pub fn compute_eval_points_spartan_cubic<Func>(
+    pub fn compute_eval_points_spartan_cubic(
+        eq_poly: &SplitEqPolynomial<F>,
         poly_A: &DensePolynomial<F>,
         poly_B: &DensePolynomial<F>,
         poly_C: &DensePolynomial<F>,
-        poly_D: &DensePolynomial<F>,
-        comb_func: &Func,
-    ) -> (F, F, F)
-    where
-        Func: Fn(&F, &F, &F, &F) -> F + Sync,
-    {
+    ) -> (F, F, Option<F>) {
         let len = poly_A.len() / 2;
-        (0..len)
+        let eq_evals = eq_poly.evals();
+        let needs_q_1 = eq_poly.needs_q_1();
+        let (eval_point_0, eval_point_infty, eval_point_1) = (0..len)
             .into_par_iter()
             .map(|i| {
-                // eval 0: bound_func is A(low)
-                let eval_point_0 = comb_func(&poly_A[i], &poly_B[i], &poly_C[i], &poly_D[i]);
+                // q(0) is computed from A(low), B(low), C(low)
+                let (A, B, C) = (poly_A[i], poly_B[i], poly_C[i]);
+                let eval_point_0 = if A.is_zero() || B.is_zero() {
+                    eq_evals[i].mul_0_optimized(-C)
+                } else {
+                    eq_evals[i] * (A * B - C)
+                };
 
-                let m_A = poly_A[len + i] - poly_A[i];
-                let m_B = poly_B[len + i] - poly_B[i];
-                let m_C = poly_C[len + i] - poly_C[i];
-                let m_D = poly_D[len + i] - poly_D[i];
+                // C is linear in the top variable, so only A and B contribute to q_infty
+                let m_A = poly_A[len + i] - A;
+                let m_B = poly_B[len + i] - B;
+                let eval_point_infty = eq_evals[i].mul_0_optimized(m_A.mul_0_optimized(m_B));
 
-                // eval 2: bound_func is -A(low) + 2*A(high)
-                let poly_A_bound_point = poly_A[len + i] + m_A;
-                let poly_B_bound_point = poly_B[len + i] + m_B;
-                let poly_C_bound_point = poly_C[len + i] + m_C;
-                let poly_D_bound_point = poly_D[len + i] + m_D;
-                let eval_point_2 = comb_func(
-                    &poly_A_bound_point,
-                    &poly_B_bound_point,
-                    &poly_C_bound_point,
-                    &poly_D_bound_point,
-                );
+                let eval_point_1 = if needs_q_1 {
+                    eq_evals[i] * (poly_A[len + i] * poly_B[len + i] - poly_C[len + i])
+                } else {
+                    F::zero()
+                };
 
-                // eval 3: bound_func is -2A(low) + 3A(high); computed incrementally with bound_func applied to eval(2)
-                let poly_A_bound_point = poly_A_bound_point + m_A;
-                let poly_B_bound_point = poly_B_bound_point + m_B;
-                let poly_C_bound_point = poly_C_bound_point + m_C;
-                let poly_D_bound_point = poly_D_bound_point + m_D;
-                let eval_point_3 = comb_func(
-                    &poly_A_bound_point,
-                    &poly_B_bound_point,
-                    &poly_C_bound_point,
-                    &poly_D_bound_point,
-                );
-                (eval_point_0, eval_point_2, eval_point_3)
+                (eval_point_0, eval_point_infty, eval_point_1)
             })
             .reduce(
                 || (F::zero(), F::zero(), F::zero()),
                 |a, b| (a.0 + b.0, a.1 + b.1, a.2 + b.2),
-            )
+            );
+        (
+            eval_point_0,
+            eval_point_infty,
+            needs_q_1.then_some(eval_point_1),
+        )
     }
 
+    /// Proves Σ eq(w, x) * (A(x) * B(x) - C(x)) = `claim`, binding variables from the top.
+    /// Returns the final evaluations of eq, A, B and C.
     #[tracing::instrument(skip_all, name = "Spartan2::sumcheck::prove_spartan_cubic")]
-    pub fn prove_spartan_cubic<Func>(
+    pub fn prove_spartan_cubic(
         claim: &F,
         num_rounds: usize,
+        eq_poly: &mut SplitEqPolynomial<F>,
         poly_A: &mut DensePolynomial<F>,
         poly_B: &mut DensePolynomial<F>,
         poly_C: &mut DensePolynomial<F>,
-        poly_D: &mut DensePolynomial<F>,
-        comb_func: Func,
         transcript: &mut ProofTranscript,
-    ) -> (Self, Vec<F>, Vec<F>)
-    where
-        Func: Fn(&F, &F, &F, &F) -> F + Sync,
-    {
+    ) -> (Self, Vec<F>, Vec<F>) {
         let mut r: Vec<F> = Vec::new();
         let mut polys: Vec<CompressedUniPoly<F>> = Vec::new();
         let mut claim_per_round = *claim;
 
         for _ in 0..num_rounds {
-            let poly = {
-                // Make an iterator returning the contributions to the evaluations
-                let (eval_point_0, eval_point_2, eval_point_3) =
-                    Self::compute_eval_points_spartan_cubic(
-                        poly_A, poly_B, poly_C, poly_D, &comb_func,
-                    );
-
-                let evals = [
-                    eval_point_0,
-                    claim_per_round - eval_point_0,
-                    eval_point_2,
-                    eval_point_3,
-                ];
-                UniPoly::from_evals(&evals)
-            };
+            let (eval_point_0, eval_point_infty, eval_point_1) =
+                Self::compute_eval_points_spartan_cubic(eq_poly, poly_A, poly_B, poly_C);
+            let poly = eq_poly.gruen_cubic(
+                eval_point_0,
+                eval_point_infty,
+                eval_point_1,
+                claim_per_round,
+            );
 
             // append the prover's message to the transcript
             poly.append_to_transcript(b"poly", transcript);
@@ -280,14 +267,14 @@ impl<F: JoltField> SumcheckInstanceProof<F> {
 
             // bound all tables to the verifier's challenege
             rayon::join(
-                || poly_A.bound_poly_var_top_par(&r_i),
+                || eq_poly.bind(&r_i),
                 || {
                     rayon::join(
-                        || poly_B.bound_poly_var_top_zero_optimized(&r_i),
+                        || poly_A.bound_poly_var_top_zero_optimized(&r_i),
                         || {
                             rayon::join(
+                                || poly_B.bound_poly_var_top_zero_optimized(&r_i),
                                 || poly_C.bound_poly_var_top_zero_optimized(&r_i),
-                                || poly_D.bound_poly_var_top_zero_optimized(&r_i),
                             )
                         },
                     )
@@ -298,10 +285,129 @@ impl<F: JoltField> SumcheckInstanceProof<F> {
         (
             SumcheckInstanceProof::new(polys),
             r,
-            vec![poly_A[0], poly_B[0], poly_C[0], poly_D[0]],
+            vec![eq_poly.current_scalar(), poly_A[0], poly_B[0], poly_C[0]],
         )
     }
 
+    #[tracing::instrument(skip_all, name = "Spartan2::sumcheck::prove_spartan_cubic_small")]
+    /// Same as `prove_spartan_cubic`, where A, B and C are given as small integers
+    /// (see `R1CSShape::multiply_vec_uniform_small`). The 0th round is computed over `i128`s,
+    /// with one field multiplication per term (by the eq polynomial), and A, B and C are only
+    /// converted to field elements once they are bound to the first challenge.
+    pub fn prove_spartan_cubic_small(
+        claim: &F,
+        num_rounds: usize,
+        eq_poly: &mut SplitEqPolynomial<F>,
+        poly_A: Vec<i128>,
+        poly_B: Vec<i128>,
+        poly_C: Vec<i128>,
+        transcript: &mut ProofTranscript,
+    ) -> (Self, Vec<F>, Vec<F>) {
+        /*          Round 0 START         */
+
+        let len = poly_A.len() / 2;
+
+        // eq * (a * b - c), computed over the integers unless a * b overflows
+        let eval = |eq: &F, a: i128, b: i128, c: i128| -> F {
+            match a.checked_mul(b).and_then(|ab| ab.checked_sub(c)) {
+                Some(0) => F::zero(),
+                Some(1) => *eq,
+                Some(-1) => -*eq,
+                Some(ab_minus_c) => *eq * F::from_i128(ab_minus_c),
+                None => *eq * (F::from_i128(a) * F::from_i128(b) - F::from_i128(c)),
+            }
+        };
+
+        let poly = {
+            // Entries of A, B and C are bounded by `SMALL_VALUE_BOUND`, so their differences
+            // cannot overflow.
+            let eq_evals = eq_poly.evals();
+            let needs_q_1 = eq_poly.needs_q_1();
+            let (eval_point_0, eval_point_infty, eval_point_1) = (0..len)
+                .into_par_iter()
+                .map(|i| {
+                    let (a_low, a_high) = (poly_A[i], poly_A[len + i]);
+                    let (b_low, b_high) = (poly_B[i], poly_B[len + i]);
+
+                    let eval_point_0 = eval(&eq_evals[i], a_low, b_low, poly_C[i]);
+                    // C is linear in the top variable, so only A and B contribute to q_infty
+                    let eval_point_infty = eval(&eq_evals[i], a_high - a_low, b_high - b_low, 0);
+                    let eval_point_1 = if needs_q_1 {
+                        eval(&eq_evals[i], a_high, b_high, poly_C[len + i])
+                    } else {
+                        F::zero()
+                    };
+
+                    (eval_point_0, eval_point_infty, eval_point_1)
+                })
+                .reduce(
+                    || (F::zero(), F::zero(), F::zero()),
+                    |a, b| (a.0 + b.0, a.1 + b.1, a.2 + b.2),
+                );
+
+            eq_poly.gruen_cubic(
+                eval_point_0,
+                eval_point_infty,
+                needs_q_1.then_some(eval_point_1),
+                *claim,
+            )
+        };
+
+        // append the prover's message to the transcript
+        poly.append_to_transcript(b"poly", transcript);
+
+        //derive the verifier's challenge for the next round
+        let r_0: F = transcript.challenge_scalar(b"challenge_nextround");
+        let claim_per_round = poly.evaluate(&r_0);
+
+        // bound all tables to the verifier's challenge
+        let bound_small = |poly: Vec<i128>| -> DensePolynomial<F> {
+            let bound = (0..len)
+                .into_par_iter()
+                .map(|i| {
+                    let (low, high) = (poly[i], poly[len + i]);
+                    match high - low {
+                        0 => F::from_i128(low),
+                        1 => F::from_i128(low) + r_0,
+                        -1 => F::from_i128(low) - r_0,
+                        m => F::from_i128(low) + r_0 * F::from_i128(m),
+                    }
+                })
+                .collect();
+            drop_in_background_thread(poly);
+            DensePolynomial::new(bound)
+        };
+        let (_, (mut poly_A, (mut poly_B, mut poly_C))) = rayon::join(
+            || eq_poly.bind(&r_0),
+            || {
+                rayon::join(
+                    || bound_small(poly_A),
+                    || rayon::join(|| bound_small(poly_B), || bound_small(poly_C)),
+                )
+            },
+        );
+
+        /*          Round 0 END          */
+
+        let (proof, mut r, claims) = Self::prove_spartan_cubic(
+            &claim_per_round,
+            num_rounds - 1,
+            eq_poly,
+            &mut poly_A,
+            &mut poly_B,
+            &mut poly_C,
+            transcript,
+        );
+        drop_in_background_thread(poly_A);
+        drop_in_background_thread(poly_B);
+        drop_in_background_thread(poly_C);
+
+        r.insert(0, r_0);
+        let mut polys = proof.compressed_polys;
+        polys.insert(0, poly.compress());
+        (SumcheckInstanceProof::new(polys), r, claims)
+    }
+
     #[tracing::instrument(skip_all, name = "Spartan2::sumcheck::prove_spartan_quadratic")]
     // A fork of `prove_quad` with the 0th round unrolled from the rest of the
     // for loop. This allows us to pass in `W` and `X` as references instead of
@@ -474,6 +580,11 @@ impl<F: JoltField> SumcheckInstanceProof<F> {
         SumcheckInstanceProof { compressed_polys }
     }
 
+    /// The number of rounds (i.e. round polynomials) in this sumcheck proof.
+    pub fn num_rounds(&self) -> usize {
+        self.compressed_polys.len()
+    }
+
     /// Verify this sumcheck proof.
     /// Note: Verification does not execute the final check of sumcheck protocol: g_v(r_v) = oracle_g(r),
     /// as the oracle is not passed in. Expected that the caller will implement.
@@ -498,20 +609,27 @@ impl<F: JoltField> SumcheckInstanceProof<F> {
         let mut r: Vec<F> = Vec::new();
 
         // verify that there is a univariate polynomial for each round
-        assert_eq!(self.compressed_polys.len(), num_rounds);
+        if self.compressed_polys.len() != num_rounds {
+            return Err(ProofVerifyError::InvalidInputLength(
+                num_rounds,
+                self.compressed_polys.len(),
+            ));
+        }
         for i in 0..self.compressed_polys.len() {
-            let poly = self.compressed_polys[i].decompress(&e);
-
             // verify degree bound
-            if poly.degree() != degree_bound {
+            if self.compressed_polys[i].degree() != degree_bound {
                 return Err(ProofVerifyError::InvalidInputLength(
                     degree_bound,
-                    poly.degree(),
+                    self.compressed_polys[i].degree(),
                 ));
             }
 
+            let poly = self.compressed_polys[i].decompress(&e);
+
             // check if G_k(0) + G_k(1) = e
-            assert_eq!(poly.eval_at_zero() + poly.eval_at_one(), e);
+            if poly.eval_at_zero() + poly.eval_at_one() != e {
+                return Err(ProofVerifyError::SumcheckRoundError(i));
+            }
 
             // append the prover's message to the transcript
             poly.append_to_transcript(b"poly", transcript);
@@ -528,3 +646,127 @@ impl<F: JoltField> SumcheckInstanceProof<F> {
         Ok((e, r))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::eq_poly::{BindingOrder, EqPolynomial};
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_core::RngCore;
+
+    #[test]
+    fn spartan_cubic_verifies() {
+        let mut rng = test_rng();
+        let tau: Vec<Fr> = (0..8).map(|_| Fr::random(&mut rng)).collect();
+        prove_and_verify_spartan_cubic(tau);
+    }
+
+    #[test]
+    fn spartan_cubic_verifies_with_zero_tau() {
+        // Gruen's optimization can't recover q(1) from the round claim when w_j = 0
+        let mut rng = test_rng();
+        for zero_index in [0, 5, 7] {
+            let mut tau: Vec<Fr> = (0..8).map(|_| Fr::random(&mut rng)).collect();
+            tau[zero_index] = Fr::zero();
+            prove_and_verify_spartan_cubic(tau);
+        }
+    }
+
+    fn prove_and_verify_spartan_cubic(tau: Vec<Fr>) {
+        let num_rounds = tau.len();
+        let mut rng = test_rng();
+
+        let mut random_poly =
+            || DensePolynomial::new((0..1 << num_rounds).map(|_| Fr::random(&mut rng)).collect());
+        let (mut A, mut B, mut C) = (random_poly(), random_poly(), random_poly());
+        let claim: Fr = EqPolynomial::evals(&tau)
+            .iter()
+            .enumerate()
+            .map(|(i, eq)| *eq * (A[i] * B[i] - C[i]))
+            .sum();
+
+        let mut transcript = ProofTranscript::new(b"test");
+        let (proof, r, claims) = SumcheckInstanceProof::prove_spartan_cubic(
+            &claim,
+            num_rounds,
+            &mut SplitEqPolynomial::new(&tau, BindingOrder::Top),
+            &mut A,
+            &mut B,
+            &mut C,
+            &mut transcript,
+        );
+        assert_eq!(claims[0], EqPolynomial::new(tau).evaluate(&r));
+
+        let mut transcript = ProofTranscript::new(b"test");
+        let (final_claim, verifier_r) =
+            proof.verify(claim, num_rounds, 3, &mut transcript).unwrap();
+        assert_eq!(r, verifier_r);
+        assert_eq!(final_claim, claims[0] * (claims[1] * claims[2] - claims[3]));
+    }
+
+    #[test]
+    fn spartan_cubic_small_matches_field() {
+        const NUM_ROUNDS: usize = 8;
+        let mut rng = test_rng();
+
+        let tau: Vec<Fr> = (0..NUM_ROUNDS).map(|_| Fr::random(&mut rng)).collect();
+        let mut random_small = |bits: u32| -> i128 {
+            let value = (rng.next_u64() as i128) >> (64 - bits);
+            if rng.next_u32() % 2 == 0 {
+                value
+            } else {
+                -value
+            }
+        };
+        let (A, B): (Vec<i128>, Vec<i128>) = (0..1 << NUM_ROUNDS)
+            .map(|i| match i % 3 {
+                0 => (random_small(64), random_small(8)),
+                1 => (random_small(32), random_small(32)),
+                // A * B overflows an i128
+                _ => (1 << 90, -(1 << 90)),
+            })
+            .unzip();
+        let C: Vec<i128> = A
+            .iter()
+            .zip(B.iter())
+            .map(|(a, b)| a.checked_mul(*b).unwrap_or(0) + random_small(4))
+            .collect();
+        let claim = Fr::random(&mut rng);
+
+        let to_field = |poly: &[i128]| -> DensePolynomial<Fr> {
+            DensePolynomial::new(poly.iter().map(|x| Fr::from_i128(*x)).collect())
+        };
+
+        let mut transcript = ProofTranscript::new(b"test");
+        let (proof, r, claims) = SumcheckInstanceProof::prove_spartan_cubic(
+            &claim,
+            NUM_ROUNDS,
+            &mut SplitEqPolynomial::new(&tau, BindingOrder::Top),
+            &mut to_field(&A),
+            &mut to_field(&B),
+            &mut to_field(&C),
+            &mut transcript,
+        );
+
+        let mut transcript = ProofTranscript::new(b"test");
+        let (small_proof, small_r, small_claims) = SumcheckInstanceProof::prove_spartan_cubic_small(
+            &claim,
+            NUM_ROUNDS,
+            &mut SplitEqPolynomial::new(&tau, BindingOrder::Top),
+            A,
+            B,
+            C,
+            &mut transcript,
+        );
+
+        let serialize = |proof: &SumcheckInstanceProof<Fr>| {
+            let mut bytes = vec![];
+            proof.serialize_compressed(&mut bytes).unwrap();
+            bytes
+        };
+        assert_eq!(serialize(&proof), serialize(&small_proof));
+        assert_eq!(r, small_r);
+        assert_eq!(claims, small_claims);
+    }
+}
@@ -1,17 +1,57 @@
 use core::fmt::Debug;
 use thiserror::Error;
 
+use crate::r1cs::spartan::SpartanError;
+
 #[derive(Error, Debug, Default)]
 pub enum ProofVerifyError {
     #[error("Invalid input length, expected length {0} but got {1}")]
     InvalidInputLength(usize, usize),
     #[error("Input too large")]
     InputTooLarge,
+    #[error("Output too large")]
+    OutputTooLarge,
+    #[error("Memory layout doesn't match the declared input and output sizes")]
+    InvalidMemoryLayout,
     #[error("Proof verification failed")]
     #[default]
     InternalError,
     #[error("Compressed group element failed to decompress: {0:?}")]
     DecompressionError([u8; 32]),
+    #[error("Sumcheck round {0} is inconsistent with the previous round's claim")]
+    SumcheckRoundError(usize),
+    #[error("Sumcheck output claim does not match the evaluations it was reduced to")]
+    SumcheckClaimError,
+    #[error("Grand product layer {0} failed to verify: {1}")]
+    GrandProductLayerError(usize, Box<ProofVerifyError>),
+    #[error("Multiset hashes of memory {0} don't match")]
+    MultisetHashMismatch(usize),
+    #[error("Grand product claims don't match the fingerprints of the opened tuples")]
+    FingerprintMismatch,
+    #[error("Bytecode verification failed: {0}")]
+    BytecodeError(Box<ProofVerifyError>),
+    #[error("Instruction lookups verification failed: {0}")]
+    InstructionLookupsError(Box<ProofVerifyError>),
+    #[error("Surge primary sumcheck failed: {0}")]
+    PrimarySumcheckError(Box<ProofVerifyError>),
+    #[error("Read-write memory verification failed: {0}")]
+    RamError(Box<ProofVerifyError>),
+    #[error("Output check failed: {0}")]
+    OutputCheckError(Box<ProofVerifyError>),
+    #[error("Timestamp range check failed: {0}")]
+    RangeCheckError(Box<ProofVerifyError>),
     #[error("R1CS proof verification failed: {0}")]
-    SpartanError(String),
+    R1CSError(SpartanError),
+    #[error("Opening proof verification failed: {0}")]
+    OpeningProofError(Box<ProofVerifyError>),
+}
+
+impl ProofVerifyError {
+    /// Attributes this error to the component of the proof that failed to verify, e.g.
+    /// `result.map_err(ProofVerifyError::wrap(ProofVerifyError::BytecodeError))`.
+    pub fn wrap(
+        component: fn(Box<ProofVerifyError>) -> ProofVerifyError,
+    ) -> impl FnOnce(ProofVerifyError) -> ProofVerifyError {
+        move |e| component(Box::new(e))
+    }
 }
@@ -4,6 +4,15 @@ pub fn assert_valid_parameters(word_size: usize, C: usize, log_M: usize) {
     assert!(C * log_M >= word_size);
 }
 
+/// Returns the index of the chunk holding the most significant bit of a `word_size`-bit operand
+/// chunked by `chunk_and_concatenate_operands`. The chunks before it are always zero.
+pub fn operand_msb_chunk_index(word_size: usize, C: usize, log_M: usize) -> usize {
+    let operand_bits = log_M / 2;
+    assert!(C * operand_bits >= word_size);
+    assert_eq!(word_size % operand_bits, 0);
+    C - word_size / operand_bits
+}
+
 /// Concatenates `C` `vals` field elements each of max size 2^`operand_bits`-1
 /// into a single field element. `operand_bits` is the number of bits required to represent
 /// each element in `vals`. If an element of `vals` is larger it will not be truncated, which
@@ -0,0 +1,363 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use memmap2::Mmap;
+
+use crate::poly::field::JoltField;
+
+/// Bounds how much memory the prover's large intermediate values may occupy at once. Values
+/// that are not needed by the subprotocol currently being proven are written to scratch files
+/// in `scratch_dir` when keeping them resident would exceed `max_bytes`, and read back in once
+/// they are needed.
+#[derive(Clone, Debug)]
+pub struct MemoryBudget {
+    pub max_bytes: usize,
+    pub scratch_dir: PathBuf,
+    /// Memory that the prover will allocate later on, which must fit in the budget along with
+    /// the values kept resident until then.
+    reserved_bytes: usize,
+}
+
+impl MemoryBudget {
+    /// A budget of `max_bytes`, with scratch files created in the system's temporary directory.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            scratch_dir: std::env::temp_dir(),
+            reserved_bytes: 0,
+        }
+    }
+
+    pub fn with_scratch_dir(mut self, scratch_dir: PathBuf) -> Self {
+        self.scratch_dir = scratch_dir;
+        self
+    }
+
+    /// Keeps `reserved_bytes` more of the budget free for memory that will be allocated (or
+    /// kept resident) later on.
+    pub(crate) fn reserving(mut self, reserved_bytes: usize) -> Self {
+        self.reserved_bytes = self.reserved_bytes.saturating_add(reserved_bytes);
+        self
+    }
+
+    /// Whether keeping `additional_bytes` more resident would exceed the budget, given the
+    /// memory reserved for later. If the process's resident set size can't be determined,
+    /// assumes it would.
+    fn exceeded_by(&self, additional_bytes: usize) -> bool {
+        match current_rss_bytes() {
+            Some(rss) => {
+                rss.saturating_add(additional_bytes)
+                    .saturating_add(self.reserved_bytes)
+                    > self.max_bytes
+            }
+            None => true,
+        }
+    }
+}
+
+/// A scratch file, deleted when dropped.
+pub struct ScratchFile {
+    path: PathBuf,
+}
+
+impl ScratchFile {
+    fn create(scratch_dir: &std::path::Path) -> io::Result<(Self, File)> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let path = scratch_dir.join(format!(
+            "jolt-{}-{}.spill",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        Ok((Self { path }, file))
+    }
+}
+
+impl Drop for ScratchFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+fn to_io_error(error: SerializationError) -> io::Error {
+    match error {
+        SerializationError::IoError(error) => error,
+        error => io::Error::new(io::ErrorKind::InvalidData, error),
+    }
+}
+
+/// A value that is either resident in memory or has been spilled to a scratch file.
+pub enum Spillable<T> {
+    Resident(T),
+    Spilled(ScratchFile),
+}
+
+impl<T: CanonicalSerialize + CanonicalDeserialize> Spillable<T> {
+    /// Spills `value` to a scratch file if keeping it resident would exceed `budget`.
+    /// Without a budget, `value` always stays resident.
+    #[tracing::instrument(skip_all, name = "Spillable::new")]
+    pub fn new(value: T, budget: Option<&MemoryBudget>) -> io::Result<Self> {
+        let budget = match budget {
+            Some(budget) => budget,
+            None => return Ok(Self::Resident(value)),
+        };
+        if !budget.exceeded_by(value.uncompressed_size()) {
+            return Ok(Self::Resident(value));
+        }
+
+        let (scratch_file, file) = ScratchFile::create(&budget.scratch_dir)?;
+        let mut writer = BufWriter::new(file);
+        value
+            .serialize_uncompressed(&mut writer)
+            .map_err(to_io_error)?;
+        writer.flush()?;
+        Ok(Self::Spilled(scratch_file))
+    }
+
+    pub fn is_spilled(&self) -> bool {
+        matches!(self, Self::Spilled(_))
+    }
+
+    /// Returns the value, reading it back onto the heap (and deleting its scratch file) if it
+    /// was spilled. Only spill values this way that are needed in full once they are needed at
+    /// all; a `SpillableVec` is read in place instead.
+    #[tracing::instrument(skip_all, name = "Spillable::into_inner")]
+    pub fn into_inner(self) -> io::Result<T> {
+        match self {
+            Self::Resident(value) => Ok(value),
+            Self::Spilled(scratch_file) => {
+                let reader = BufReader::new(File::open(scratch_file.path.as_path())?);
+                // The file was written by this process, so there is no need to validate it.
+                T::deserialize_uncompressed_unchecked(reader).map_err(to_io_error)
+            }
+        }
+    }
+}
+
+/// Types whose values are plain bytes, which `SpillableVec` can write to a scratch file and
+/// read back in place.
+///
+/// # Safety
+///
+/// Implementors must not contain padding or pointers.
+pub unsafe trait PlainData: Copy + Send + Sync {}
+
+// Field elements are plain arrays of limbs, as `unsafe_allocate_zero_vec` also relies on.
+unsafe impl<F: JoltField> PlainData for F {}
+unsafe impl PlainData for i128 {}
+
+/// A vector that is either resident in memory, or has been spilled to a scratch file and is
+/// read in place through a memory map. A spilled vector's pages are only paged in as they are
+/// read, and since they're backed by the scratch file, the kernel can evict them again without
+/// swapping.
+pub enum SpillableVec<T> {
+    Resident(Vec<T>),
+    Spilled(MappedScratchFile<T>),
+}
+
+/// The elements of a `SpillableVec` that was spilled, mapped from their scratch file.
+pub struct MappedScratchFile<T> {
+    // Unmapped before the scratch file is deleted
+    mmap: Mmap,
+    len: usize,
+    _scratch_file: ScratchFile,
+    _marker: PhantomData<T>,
+}
+
+impl<T: PlainData> SpillableVec<T> {
+    /// Spills `values` to a scratch file if keeping them resident would exceed `budget`.
+    /// Without a budget, `values` always stay resident.
+    pub fn new(values: Vec<T>, budget: Option<&MemoryBudget>) -> io::Result<Self> {
+        let mut spillable = Self::new_batch(vec![values], budget)?;
+        Ok(spillable.pop().unwrap())
+    }
+
+    /// Same as `new`, for vectors that are read together: they are all spilled if keeping all
+    /// of them resident would exceed `budget`.
+    #[tracing::instrument(skip_all, name = "SpillableVec::new_batch")]
+    pub fn new_batch(batch: Vec<Vec<T>>, budget: Option<&MemoryBudget>) -> io::Result<Vec<Self>> {
+        let num_bytes = batch
+            .iter()
+            .map(|values| std::mem::size_of_val(values.as_slice()))
+            .sum();
+        match budget {
+            Some(budget) if budget.exceeded_by(num_bytes) => batch
+                .into_iter()
+                .map(|values| Self::spill(values, &budget.scratch_dir))
+                .collect(),
+            _ => Ok(batch.into_iter().map(Self::Resident).collect()),
+        }
+    }
+
+    fn spill(values: Vec<T>, scratch_dir: &std::path::Path) -> io::Result<Self> {
+        // An empty file can't be mapped, but there is nothing to spill anyway
+        if values.is_empty() {
+            return Ok(Self::Resident(values));
+        }
+        let (scratch_file, mut file) = ScratchFile::create(scratch_dir)?;
+        // Safety: `T` is plain data, so its bytes can be written out and read back in place by
+        // this process.
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                values.as_ptr() as *const u8,
+                std::mem::size_of_val(values.as_slice()),
+            )
+        };
+        file.write_all(bytes)?;
+        let len = values.len();
+        drop(values);
+        // Safety: the scratch file is private to this process and is not modified while it is
+        // mapped.
+        let mmap = unsafe { Mmap::map(&file) }?;
+        Ok(Self::Spilled(MappedScratchFile {
+            mmap,
+            len,
+            _scratch_file: scratch_file,
+            _marker: PhantomData,
+        }))
+    }
+}
+
+impl<T> SpillableVec<T> {
+    pub fn is_spilled(&self) -> bool {
+        matches!(self, Self::Spilled(_))
+    }
+}
+
+impl<T: Clone> SpillableVec<T> {
+    /// Returns the elements, copying them onto the heap if they were spilled.
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            Self::Resident(values) => values,
+            Self::Spilled(_) => self.to_vec(),
+        }
+    }
+}
+
+impl<T> Deref for SpillableVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            Self::Resident(values) => values,
+            // Safety: the file holds the bytes of `len` elements, written by `SpillableVec::new`,
+            // and mappings are page-aligned.
+            Self::Spilled(mapped) => unsafe {
+                std::slice::from_raw_parts(mapped.mmap.as_ptr() as *const T, mapped.len)
+            },
+        }
+    }
+}
+
+impl<T: Clone> Clone for SpillableVec<T> {
+    fn clone(&self) -> Self {
+        Self::Resident(self.to_vec())
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for SpillableVec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for SpillableVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+/// The resident set size of this process, in bytes. Only available on Linux.
+pub fn current_rss_bytes() -> Option<usize> {
+    read_proc_status_kb("VmRSS:").map(|kb| kb * 1024)
+}
+
+/// The peak resident set size of this process, in bytes. Only available on Linux.
+pub fn peak_rss_bytes() -> Option<usize> {
+    read_proc_status_kb("VmHWM:").map(|kb| kb * 1024)
+}
+
+fn read_proc_status_kb(field: &str) -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(field))
+        .and_then(|value| value.trim().strip_suffix("kB"))
+        .and_then(|kb| kb.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+
+    #[test]
+    fn spill_roundtrip() {
+        let mut rng = test_rng();
+        let segments: Vec<Vec<Fr>> = (0..4)
+            .map(|_| (0..1 << 8).map(|_| Fr::random(&mut rng)).collect())
+            .collect();
+
+        let resident = Spillable::new(segments.clone(), None).unwrap();
+        assert!(!resident.is_spilled());
+        assert_eq!(resident.into_inner().unwrap(), segments);
+
+        // A zero budget is always exceeded, so the value is spilled.
+        let budget = MemoryBudget::new(0);
+        let spilled = Spillable::new(segments.clone(), Some(&budget)).unwrap();
+        assert!(spilled.is_spilled());
+        assert_eq!(spilled.into_inner().unwrap(), segments);
+
+        // No process can exceed the largest budget, so the value stays resident wherever the
+        // resident set size is known.
+        if current_rss_bytes().is_some() {
+            let budget = MemoryBudget::new(usize::MAX);
+            let resident = Spillable::new(segments.clone(), Some(&budget)).unwrap();
+            assert!(!resident.is_spilled());
+            assert_eq!(resident.into_inner().unwrap(), segments);
+        }
+    }
+
+    #[test]
+    fn spilled_vec_is_read_in_place() {
+        let mut rng = test_rng();
+        let values: Vec<Fr> = (0..1 << 10).map(|_| Fr::random(&mut rng)).collect();
+
+        let scratch_dir =
+            std::env::temp_dir().join(format!("jolt-spillable-vec-{}", std::process::id()));
+        std::fs::create_dir_all(&scratch_dir).unwrap();
+        let budget = MemoryBudget::new(0).with_scratch_dir(scratch_dir.clone());
+        let spilled = SpillableVec::new(values.clone(), Some(&budget)).unwrap();
+        assert!(spilled.is_spilled());
+        assert_eq!(&spilled[..], &values[..]);
+        assert_eq!(std::fs::read_dir(&scratch_dir).unwrap().count(), 1);
+        assert_eq!(spilled.into_vec(), values);
+        // The scratch file is deleted along with the mapping
+        assert_eq!(std::fs::read_dir(&scratch_dir).unwrap().count(), 0);
+        std::fs::remove_dir(&scratch_dir).unwrap();
+
+        // There is nothing to map in an empty vector
+        assert!(!SpillableVec::<Fr>::new(vec![], Some(&budget))
+            .unwrap()
+            .is_spilled());
+    }
+
+    #[test]
+    fn spill_errors_are_returned() {
+        let budget = MemoryBudget::new(0).with_scratch_dir(
+            std::env::temp_dir().join(format!("jolt-missing-scratch-dir-{}", std::process::id())),
+        );
+        assert!(Spillable::new(vec![Fr::from_u64(1).unwrap()], Some(&budget)).is_err());
+        assert!(SpillableVec::new(vec![Fr::from_u64(1).unwrap()], Some(&budget)).is_err());
+    }
+}
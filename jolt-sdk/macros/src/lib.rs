@@ -30,12 +30,21 @@ struct MacroBuilder {
     attr: AttributeArgs,
     func: ItemFn,
     std: bool,
-    func_args: Vec<(Ident, Box<Type>)>,
+    func_args: Vec<FuncArg>,
+}
+
+/// An argument of the provable function.
+struct FuncArg {
+    name: Ident,
+    ty: Box<Type>,
+    /// Set by a `#[hint]` attribute: the argument is passed to the guest through the hint
+    /// stream instead of its inputs, so it is not part of the public statement.
+    hint: bool,
 }
 
 impl MacroBuilder {
-    fn new(attr: AttributeArgs, func: ItemFn) -> Self {
-        let func_args = Self::get_func_args(&func);
+    fn new(attr: AttributeArgs, mut func: ItemFn) -> Self {
+        let func_args = Self::get_func_args(&mut func);
         #[cfg(feature = "guest-std")]
         let std = true;
         #[cfg(not(feature = "guest-std"))]
@@ -82,8 +91,8 @@ impl MacroBuilder {
         let build_fn_name = Ident::new(&format!("build_{}", fn_name), fn_name.span());
         let prove_output_ty = self.get_prove_output_type();
 
-        let input_names = self.func_args.iter().map(|(name, _)| name);
-        let input_types = self.func_args.iter().map(|(_, ty)| ty);
+        let input_names = self.func_args.iter().map(|arg| &arg.name);
+        let input_types = self.func_args.iter().map(|arg| &arg.ty);
         let inputs = &self.func.sig.inputs;
         let preprocess_fn_name = Ident::new(&format!("preprocess_{}", fn_name), fn_name.span());
         let prove_fn_name = Ident::new(&format!("prove_{}", fn_name), fn_name.span());
@@ -126,8 +135,15 @@ impl MacroBuilder {
         let inputs = &self.func.sig.inputs;
         let output = &self.func.sig.output;
         let body = &self.func.block;
+        // Functions that use guest-only APIs such as `jolt::syscall` can't run natively
+        let guest_only = if self.parse_attributes().guest_only {
+            quote! { #[cfg(feature = "guest")] }
+        } else {
+            quote! {}
+        };
 
         quote! {
+             #guest_only
              pub fn #fn_name(#inputs) #output {
                  #body
              }
@@ -144,11 +160,7 @@ impl MacroBuilder {
         let fn_name_str = fn_name.to_string();
         let analyze_fn_name = Ident::new(&format!("analyze_{}", fn_name), fn_name.span());
         let inputs = &self.func.sig.inputs;
-        let set_program_args = self.func_args.iter().map(|(name, _)| {
-            quote! {
-                program.set_input(&#name);
-            }
-        });
+        let set_program_args = self.make_set_program_args();
 
         quote! {
              #[cfg(not(feature = "guest"))]
@@ -159,7 +171,7 @@ impl MacroBuilder {
                 program.set_func(#fn_name_str);
                 #set_std
                 #set_mem_size
-                #(#set_program_args;)*
+                #set_program_args
 
                 program.trace_analyze::<jolt::F>()
              }
@@ -171,6 +183,13 @@ impl MacroBuilder {
         let guest_name = self.get_guest_name();
         let imports = self.make_imports();
         let set_std = self.make_set_std();
+        let fixed_base_budget = match self.parse_attributes().fixed_base_budget {
+            Some(budget) => {
+                let budget = budget as usize;
+                quote! { Some(#budget) }
+            }
+            None => quote! { None },
+        };
 
         let fn_name = self.get_func_name();
         let fn_name_str = fn_name.to_string();
@@ -196,7 +215,8 @@ impl MacroBuilder {
                         memory_init,
                         1 << 20,
                         1 << 20,
-                        1 << 24
+                        1 << 24,
+                        #fixed_base_budget
                     );
 
                 (program, preprocessing)
@@ -216,17 +236,37 @@ impl MacroBuilder {
             },
         };
 
-        let set_program_args = self.func_args.iter().map(|(name, _)| {
-            quote! {
-                program.set_input(&#name);
-            }
-        });
+        let set_program_args = self.make_set_program_args();
 
         let fn_name = self.get_func_name();
         let inputs = &self.func.sig.inputs;
         let imports = self.make_imports();
 
         let prove_fn_name = syn::Ident::new(&format!("prove_{}", fn_name), fn_name.span());
+        // Under a memory budget, the prover can fail to write or read its scratch files, so
+        // the errors are returned to the caller (see `get_prove_output_type`).
+        let (prove, ok) = match self.parse_attributes().memory_budget {
+            Some(budget) => {
+                let budget = budget as usize;
+                (
+                    quote! {
+                        program.prove_with_memory_budget(
+                            preprocessing,
+                            Some(jolt::MemoryBudget::new(#budget)),
+                        )?
+                    },
+                    quote! { Ok((ret_val, proof)) },
+                )
+            }
+            None => (
+                quote! {
+                    program
+                        .prove_with_memory_budget(preprocessing, None)
+                        .expect("nothing is spilled without a memory budget")
+                },
+                quote! { (ret_val, proof) },
+            ),
+        };
         quote! {
             #[cfg(not(feature = "guest"))]
             pub fn #prove_fn_name(
@@ -236,19 +276,11 @@ impl MacroBuilder {
             ) -> #prove_output_ty {
                 #imports
 
-                #(#set_program_args;)*
-
-                let (io_device, trace, circuit_flags) =
-                    program.trace();
+                #set_program_args
 
-                let output_bytes = io_device.outputs.clone();
+                let (jolt_proof, jolt_commitments) = #prove;
 
-                let (jolt_proof, jolt_commitments) = RV32IJoltVM::prove(
-                    io_device,
-                    trace,
-                    circuit_flags,
-                    preprocessing,
-                );
+                let output_bytes = jolt_proof.program_io.outputs.clone();
 
                 #handle_return
 
@@ -257,7 +289,7 @@ impl MacroBuilder {
                     commitments: jolt_commitments,
                 };
 
-                (ret_val, proof)
+                #ok
             }
         }
     }
@@ -279,10 +311,16 @@ impl MacroBuilder {
         };
 
         let args = &self.func_args;
-        let args_fetch = args.iter().map(|(name, ty)| {
-            quote! {
-                let (#name, input_slice) =
-                    jolt::postcard::take_from_bytes::<#ty>(input_slice).unwrap();
+        let args_fetch = args.iter().map(|FuncArg { name, ty, hint }| {
+            if *hint {
+                quote! {
+                    let #name = jolt::syscall::hint::<#ty>();
+                }
+            } else {
+                quote! {
+                    let (#name, input_slice) =
+                        jolt::postcard::take_from_bytes::<#ty>(input_slice).unwrap();
+                }
             }
         });
 
@@ -431,6 +469,25 @@ impl MacroBuilder {
         }
     }
 
+    fn make_set_program_args(&self) -> TokenStream2 {
+        let set_program_args = self.func_args.iter().map(|arg| {
+            let name = &arg.name;
+            if arg.hint {
+                quote! {
+                    program.set_hint(&#name);
+                }
+            } else {
+                quote! {
+                    program.set_input(&#name);
+                }
+            }
+        });
+
+        quote! {
+            #(#set_program_args;)*
+        }
+    }
+
     fn make_set_std(&self) -> TokenStream2 {
         if self.std {
             quote! {
@@ -445,8 +502,12 @@ impl MacroBuilder {
 
     fn parse_attributes(&self) -> Attributes {
         let mut attributes = HashMap::<_, u64>::new();
+        let mut guest_only = false;
         for attr in &self.attr {
             match attr {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("guest_only") => {
+                    guest_only = true;
+                }
                 NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
                     let value: u64 = match lit {
                         Lit::Int(lit) => lit.base10_parse().unwrap(),
@@ -458,6 +519,8 @@ impl MacroBuilder {
                         "stack_size" => attributes.insert("stack_size", value),
                         "max_input_size" => attributes.insert("max_input_size", value),
                         "max_output_size" => attributes.insert("max_output_size", value),
+                        "fixed_base_budget" => attributes.insert("fixed_base_budget", value),
+                        "memory_budget" => attributes.insert("memory_budget", value),
                         _ => panic!("invalid attribute"),
                     };
                 }
@@ -475,32 +538,50 @@ impl MacroBuilder {
         let max_output_size = *attributes
             .get("max_output_size")
             .unwrap_or(&DEFAULT_MAX_OUTPUT_SIZE);
+        let fixed_base_budget = attributes.get("fixed_base_budget").copied();
+        let memory_budget = attributes.get("memory_budget").copied();
 
         Attributes {
             memory_size,
             stack_size,
             max_input_size,
             max_output_size,
+            fixed_base_budget,
+            memory_budget,
+            guest_only,
         }
     }
 
+    /// The output of the `prove_*` function, which is wrapped in a `std::io::Result` under a
+    /// memory budget.
     fn get_prove_output_type(&self) -> TokenStream2 {
-        match &self.func.sig.output {
+        let output_ty = match &self.func.sig.output {
             ReturnType::Default => quote! {
                 ((), jolt::Proof)
             },
             ReturnType::Type(_, ty) => quote! {
                 (#ty, jolt::Proof)
             },
+        };
+        match self.parse_attributes().memory_budget {
+            Some(_) => quote! { std::io::Result<#output_ty> },
+            None => output_ty,
         }
     }
 
-    fn get_func_args(func: &ItemFn) -> Vec<(Ident, Box<Type>)> {
+    /// Parses the function's arguments, removing the `#[hint]` attributes from its signature.
+    fn get_func_args(func: &mut ItemFn) -> Vec<FuncArg> {
         let mut args = Vec::new();
-        for arg in &func.sig.inputs {
-            if let syn::FnArg::Typed(PatType { pat, ty, .. }) = arg {
+        for arg in &mut func.sig.inputs {
+            if let syn::FnArg::Typed(PatType { attrs, pat, ty, .. }) = arg {
+                let num_attrs = attrs.len();
+                attrs.retain(|attr| !attr.path.is_ident("hint"));
                 if let syn::Pat::Ident(pat_ident) = pat.as_ref() {
-                    args.push((pat_ident.ident.clone(), ty.clone()));
+                    args.push(FuncArg {
+                        name: pat_ident.ident.clone(),
+                        ty: ty.clone(),
+                        hint: attrs.len() < num_attrs,
+                    });
                 } else {
                     panic!("cannot parse arg");
                 }
@@ -530,4 +611,11 @@ struct Attributes {
     stack_size: u64,
     max_input_size: u64,
     max_output_size: u64,
+    /// Bytes the prover may spend on a fixed-base table over the commitment generators.
+    fixed_base_budget: Option<u64>,
+    /// Bytes the prover may keep resident before spilling values to scratch files.
+    memory_budget: Option<u64>,
+    /// Set by `guest_only`: the function uses guest-only APIs such as `jolt::syscall`, so
+    /// it isn't compiled natively.
+    guest_only: bool,
 }
@@ -0,0 +1,138 @@
+//! 256-bit integer arithmetic backed by the `bigint_*` precompile instructions.
+//! Integers are eight little-endian `u32` limbs.
+
+pub type U256 = [u32; 8];
+
+/// The BN254 base field modulus.
+pub const BN254_BASE_MODULUS: U256 = [
+    0xd87cfd47, 0x3c208c16, 0x6871ca8d, 0x97816a91, 0x8181585d, 0xb85045b6, 0xe131a029, 0x30644e72,
+];
+/// The BN254 scalar field modulus.
+pub const BN254_SCALAR_MODULUS: U256 = [
+    0xf0000001, 0x43e1f593, 0x79b97091, 0x2833e848, 0x8181585d, 0xb85045b6, 0xe131a029, 0x30644e72,
+];
+/// The secp256k1 base field modulus.
+pub const SECP256K1_BASE_MODULUS: U256 = [
+    0xfffffc2f, 0xfffffffe, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+];
+/// The order of the secp256k1 group.
+pub const SECP256K1_SCALAR_MODULUS: U256 = [
+    0xd0364141, 0xbfd25e8c, 0xaf48a03b, 0xbaaedce6, 0xfffffffe, 0xffffffff, 0xffffffff, 0xffffffff,
+];
+
+/// `a += b` modulo 2^256. Returns the carry out.
+#[cfg(target_arch = "riscv32")]
+#[inline(always)]
+pub fn add(a: &mut U256, b: &U256) -> bool {
+    let carry: u32;
+    // bigint_add rd, rs1, rs2: custom-0 opcode, funct3 = 2 (must match `tracer::BIGINT_ADD_FUNCT3`)
+    unsafe {
+        core::arch::asm!(
+            ".insn r 0x0b, 2, 0, {carry}, {a}, {b}",
+            carry = out(reg) carry,
+            a = in(reg) a.as_mut_ptr(),
+            b = in(reg) b.as_ptr(),
+            options(nostack),
+        );
+    }
+    carry == 1
+}
+
+/// `a += b` modulo 2^256. Returns the carry out.
+#[cfg(not(target_arch = "riscv32"))]
+pub fn add(a: &mut U256, b: &U256) -> bool {
+    let mut carry = 0;
+    for (a, b) in a.iter_mut().zip(b) {
+        let sum = *a as u64 + *b as u64 + carry;
+        *a = sum as u32;
+        carry = sum >> 32;
+    }
+    carry == 1
+}
+
+/// The 512-bit product of `a` and `b`.
+#[cfg(target_arch = "riscv32")]
+#[inline(always)]
+pub fn mul(a: &U256, b: &U256) -> [u32; 16] {
+    let mut product = [0u32; 16];
+    product[..8].copy_from_slice(a);
+    // bigint_mul rs1, rs2: custom-0 opcode, funct3 = 3 (must match `tracer::BIGINT_MUL_FUNCT3`)
+    unsafe {
+        core::arch::asm!(
+            ".insn r 0x0b, 3, 0, x0, {product}, {b}",
+            product = in(reg) product.as_mut_ptr(),
+            b = in(reg) b.as_ptr(),
+            options(nostack),
+        );
+    }
+    product
+}
+
+/// The 512-bit product of `a` and `b`.
+#[cfg(not(target_arch = "riscv32"))]
+pub fn mul(a: &U256, b: &U256) -> [u32; 16] {
+    let mut product = [0u32; 16];
+    for (i, a) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, b) in b.iter().enumerate() {
+            let value = product[i + j] as u64 + *a as u64 * *b as u64 + carry;
+            product[i + j] = value as u32;
+            carry = value >> 32;
+        }
+        product[i + 8] = carry as u32;
+    }
+    product
+}
+
+/// `a * b mod modulus`. At least one of `a` and `b` must be less than `modulus`.
+#[cfg(target_arch = "riscv32")]
+#[inline(always)]
+pub fn mulmod(a: &U256, b: &U256, modulus: &U256) -> U256 {
+    #[repr(C)]
+    struct Operands {
+        value: U256,
+        modulus: U256,
+    }
+
+    let mut operands = Operands {
+        value: *a,
+        modulus: *modulus,
+    };
+    // bigint_mulmod rs1, rs2: custom-0 opcode, funct3 = 4 (must match
+    // `tracer::BIGINT_MULMOD_FUNCT3`)
+    unsafe {
+        core::arch::asm!(
+            ".insn r 0x0b, 4, 0, x0, {operands}, {b}",
+            operands = in(reg) &mut operands as *mut Operands,
+            b = in(reg) b.as_ptr(),
+            options(nostack),
+        );
+    }
+    operands.value
+}
+
+/// `a * b mod modulus`. At least one of `a` and `b` must be less than `modulus`.
+#[cfg(not(target_arch = "riscv32"))]
+pub fn mulmod(a: &U256, b: &U256, modulus: &U256) -> U256 {
+    assert!(modulus.iter().any(|limb| *limb != 0), "modulus is zero");
+    let product = mul(a, b);
+
+    // Binary long division, keeping only the remainder
+    let mut remainder = [0u32; 8];
+    for bit in (0..512).rev() {
+        let overflow = remainder[7] >> 31;
+        for i in (1..8).rev() {
+            remainder[i] = (remainder[i] << 1) | (remainder[i - 1] >> 31);
+        }
+        remainder[0] = (remainder[0] << 1) | ((product[bit / 32] >> (bit % 32)) & 1);
+        if overflow == 1 || remainder.iter().rev().ge(modulus.iter().rev()) {
+            let mut borrow = 0;
+            for (r, p) in remainder.iter_mut().zip(modulus) {
+                let difference = *r as i64 - *p as i64 - borrow;
+                *r = difference as u32;
+                borrow = (difference < 0) as i64;
+            }
+        }
+    }
+    remainder
+}
@@ -20,6 +20,7 @@ pub use jolt_core::jolt::vm::{
     rv32i_vm::{RV32IJoltProof, RV32IJoltVM, RV32I},
     Jolt, JoltCommitments, JoltPreprocessing, JoltProof,
 };
+pub use jolt_core::utils::memory_budget::MemoryBudget;
 pub use tracer;
 
 pub type CommitmentScheme = HyraxScheme<G>;
@@ -12,3 +12,7 @@ pub use host_utils::*;
 
 pub mod alloc;
 pub use alloc::*;
+
+pub mod bigint;
+#[cfg(target_arch = "riscv32")]
+pub mod syscall;
@@ -0,0 +1,77 @@
+//! Guest-side bindings for the `ECALL` syscall interface. Syscall numbers must match
+//! `common::syscall::Syscall`: the number is passed in a7, arguments in a0-a6, and
+//! the result is returned in a0.
+//!
+//! Syscalls are serviced by the Jolt tracer, so this module is only available when
+//! compiling for the guest.
+
+extern crate alloc;
+
+use alloc::vec;
+use serde::de::DeserializeOwned;
+
+const SYSCALL_EXIT: u32 = 0;
+const SYSCALL_WRITE: u32 = 1;
+const SYSCALL_HINT_READ: u32 = 2;
+const SYSCALL_CYCLE_COUNT: u32 = 3;
+
+pub const STDOUT: u32 = 1;
+pub const STDERR: u32 = 2;
+
+#[inline(always)]
+unsafe fn syscall(number: u32, arg0: u32, arg1: u32, arg2: u32) -> u32 {
+    let result: u32;
+    core::arch::asm!(
+        "ecall",
+        inlateout("a0") arg0 => result,
+        in("a1") arg1,
+        in("a2") arg2,
+        in("a7") number,
+    );
+    result
+}
+
+/// Terminates the guest with the given exit code.
+pub fn exit(code: u32) -> ! {
+    unsafe { syscall(SYSCALL_EXIT, code, 0, 0) };
+    #[allow(clippy::empty_loop)]
+    loop {}
+}
+
+/// Writes `bytes` to the host's stdout or stderr. Returns the number of bytes written.
+pub fn write(fd: u32, bytes: &[u8]) -> u32 {
+    unsafe { syscall(SYSCALL_WRITE, fd, bytes.as_ptr() as u32, bytes.len() as u32) }
+}
+
+/// Prints `s` to the host's stdout.
+pub fn print(s: &str) {
+    write(STDOUT, s.as_bytes());
+}
+
+/// Reads the next little-endian word of the host-provided hint stream.
+/// Hints are untrusted: the guest must check anything it derives from them.
+pub fn hint_read_word() -> u32 {
+    unsafe { syscall(SYSCALL_HINT_READ, 0, 0, 0) }
+}
+
+/// Fills `buf` from the host-provided hint stream.
+pub fn hint_read(buf: &mut [u8]) {
+    for chunk in buf.chunks_mut(4) {
+        let word = hint_read_word().to_le_bytes();
+        chunk.copy_from_slice(&word[..chunk.len()]);
+    }
+}
+
+/// Reads the next value passed to `Program::set_hint`, which is how the `#[hint]` arguments
+/// of a `#[jolt::provable]` function are passed in.
+pub fn hint<T: DeserializeOwned>() -> T {
+    let len = hint_read_word() as usize;
+    let mut bytes = vec![0; len];
+    hint_read(&mut bytes);
+    postcard::from_bytes(&bytes).expect("malformed hint")
+}
+
+/// Returns the number of instructions the guest has executed so far.
+pub fn cycle_count() -> u32 {
+    unsafe { syscall(SYSCALL_CYCLE_COUNT, 0, 0, 0) }
+}
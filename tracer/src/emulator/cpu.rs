@@ -6,8 +6,16 @@ use std::convert::TryInto;
 use std::rc::Rc;
 use std::str::FromStr;
 
+use crate::syscall::{SyscallContext, SyscallHandler};
 use crate::trace::Tracer;
+use common::precompiles::{
+    bigint_add, bigint_mul, bigint_mulmod, BIGINT_ADD_FUNCT3, BIGINT_MULMOD_FUNCT3,
+    BIGINT_MUL_FUNCT3,
+};
 use common::rv_trace::*;
+use common::syscall::{
+    Syscall, SYSCALL_ARGUMENT_REGISTERS, SYSCALL_NUMBER_REGISTER, SYSCALL_RETURN_REGISTER,
+};
 
 use self::fnv::FnvHashMap;
 
@@ -84,6 +92,22 @@ pub struct Cpu {
     decode_cache: DecodeCache,
     unsigned_data_mask: u64,
     pub tracer: Rc<Tracer>,
+    syscall_handlers: FnvHashMap<u32, Box<dyn SyscallHandler>>,
+    exit_code: Option<u32>,
+    bare_metal: bool,
+    /// Decoded instructions by address, used in bare-metal mode only.
+    pc_decode_cache: FnvHashMap<u64, DecodedInstruction>,
+}
+
+/// An instruction fetched and decoded at some address, cached by the bare-metal fast path.
+#[derive(Clone, Copy)]
+struct DecodedInstruction {
+    /// The instruction word, uncompressed if it was compressed.
+    word: u32,
+    /// The length of the instruction in bytes, 2 if it was compressed and 4 otherwise.
+    length: u64,
+    /// Index into [`INSTRUCTIONS`](constant.INSTRUCTIONS.html).
+    index: usize,
 }
 
 #[derive(Clone)]
@@ -245,6 +269,10 @@ impl Cpu {
             decode_cache: DecodeCache::new(),
             unsigned_data_mask: 0xffffffffffffffff,
             tracer,
+            syscall_handlers: FnvHashMap::default(),
+            exit_code: None,
+            bare_metal: false,
+            pc_decode_cache: FnvHashMap::default(),
         };
         cpu.x[0xb] = 0x1020; // I don't know why but Linux boot seems to require this initialization
         cpu.write_csr_raw(CSR_MISA_ADDRESS, 0x800000008014312f);
@@ -289,8 +317,66 @@ impl Cpu {
         self.pc
     }
 
+    /// Registers a host handler for `syscall`. `ECALL`s with a matching number in a7
+    /// are serviced by the handler instead of trapping.
+    ///
+    /// # Arguments
+    /// * `syscall`
+    /// * `handler`
+    pub fn register_syscall(&mut self, syscall: Syscall, handler: Box<dyn SyscallHandler>) {
+        self.syscall_handlers.insert(syscall as u32, handler);
+    }
+
+    /// Returns the exit code passed to `Syscall::Exit`, if the program has exited.
+    pub fn exit_code(&self) -> Option<u32> {
+        self.exit_code
+    }
+
+    /// Dispatches an `ECALL` to the handler registered for the number in a7.
+    /// Returns `None` if no handler is registered.
+    fn handle_syscall(&mut self) -> Option<u64> {
+        let number = self.x[SYSCALL_NUMBER_REGISTER as usize] as u32;
+        let mut handler = self.syscall_handlers.remove(&number)?;
+
+        let mut args = [0; 7];
+        for (arg, reg) in args.iter_mut().zip(SYSCALL_ARGUMENT_REGISTERS) {
+            *arg = self.unsigned_data(self.x[reg as usize]);
+        }
+        // The `ECALL` itself has already been pushed to the trace, if one is being recorded
+        let cycle_count = (self.tracer.num_rows() as u64).saturating_sub(1);
+
+        let mut context = SyscallContext {
+            args,
+            mmu: &mut self.mmu,
+            cycle_count,
+            exit_code: &mut self.exit_code,
+        };
+        let result = handler.handle(&mut context);
+
+        self.syscall_handlers.insert(number, handler);
+        Some(result)
+    }
+
+    /// Enables or disables bare-metal mode, for programs that run in machine mode without
+    /// address translation, interrupts or devices, as Jolt guests do. In this mode `tick`
+    /// skips device ticks and interrupt checks, the `Mmu` skips address translation, and
+    /// decoded instructions are cached by address until a store overwrites any of them. The
+    /// trace is identical to the one produced otherwise.
+    ///
+    /// # Arguments
+    /// * `enabled`
+    pub fn enable_bare_metal(&mut self, enabled: bool) {
+        self.bare_metal = enabled;
+        self.pc_decode_cache.clear();
+        self.mmu.enable_bare_metal(enabled);
+    }
+
     /// Runs program one cycle. Fetch, decode, and execution are completed in a cycle so far.
     pub fn tick(&mut self) {
+        if self.bare_metal {
+            return self.tick_bare_metal();
+        }
+
         let instruction_address = self.pc;
         match self.tick_operate() {
             Ok(()) => {}
@@ -332,31 +418,94 @@ impl Cpu {
         };
 
         match self.decode(word).cloned() {
-            Ok(inst) => {
-                // setup trace
-                let trace_inst = inst.trace.unwrap()(&inst, &self.xlen, word, instruction_address);
-                self.tracer.start_instruction(trace_inst);
-                self.tracer.capture_pre_state(self.x, &self.xlen);
+            Ok(inst) => self.execute_traced(&inst, word, instruction_address),
+            Err(()) => {
+                panic!(
+                    "Unknown instruction PC:{:x} WORD:{:x}",
+                    instruction_address, original_word
+                );
+            }
+        }
+    }
 
-                // execute
-                let result = (inst.operation)(self, word, instruction_address);
-                self.x[0] = 0; // hardwired zero
+    /// `tick` in bare-metal mode: no wait-for-interrupt, device ticks or interrupt checks, and
+    /// each address is fetched and decoded only once.
+    fn tick_bare_metal(&mut self) {
+        let instruction_address = self.pc;
+        let decoded = match self.pc_decode_cache.get(&instruction_address) {
+            Some(decoded) => *decoded,
+            None => match self.fetch_and_decode() {
+                Ok(decoded) => {
+                    self.pc_decode_cache.insert(instruction_address, decoded);
+                    self.mmu.watch_code(instruction_address, decoded.length);
+                    decoded
+                }
+                Err(e) => {
+                    self.handle_exception(e, instruction_address);
+                    self.clock = self.clock.wrapping_add(1);
+                    self.write_csr_raw(CSR_CYCLE_ADDRESS, self.clock * 8);
+                    return;
+                }
+            },
+        };
 
-                // complete trace
-                self.tracer.capture_post_state(self.x, &self.xlen);
-                self.tracer.end_instruction();
+        self.pc = self.pc.wrapping_add(decoded.length);
+        let inst = &INSTRUCTIONS[decoded.index];
+        if let Err(e) = self.execute_traced(inst, decoded.word, instruction_address) {
+            self.handle_exception(e, instruction_address);
+        }
+        if self.mmu.take_code_written() {
+            self.pc_decode_cache.clear();
+        }
+        self.clock = self.clock.wrapping_add(1);
+        self.write_csr_raw(CSR_CYCLE_ADDRESS, self.clock * 8);
+    }
 
-                result
-            }
+    /// Fetches and decodes the instruction at the program counter, without advancing it.
+    fn fetch_and_decode(&mut self) -> Result<DecodedInstruction, Trap> {
+        let original_word = self.fetch()?;
+        let (word, length) = match (original_word & 0x3) == 0x3 {
+            true => (original_word, 4),
+            false => (self.uncompress(original_word & 0xffff), 2),
+        };
+        match self.decode_and_get_instruction_index(word) {
+            Ok(index) => Ok(DecodedInstruction {
+                word,
+                length,
+                index,
+            }),
             Err(()) => {
                 panic!(
                     "Unknown instruction PC:{:x} WORD:{:x}",
-                    instruction_address, original_word
+                    self.pc, original_word
                 );
             }
         }
     }
 
+    /// Executes `inst`, recording it and its register accesses in the trace.
+    fn execute_traced(
+        &mut self,
+        inst: &Instruction,
+        word: u32,
+        instruction_address: u64,
+    ) -> Result<(), Trap> {
+        // setup trace
+        let trace_inst = inst.trace.unwrap()(inst, &self.xlen, word, instruction_address);
+        self.tracer.start_instruction(trace_inst);
+        self.tracer.capture_pre_state(self.x, &self.xlen);
+
+        // execute
+        let result = (inst.operation)(self, word, instruction_address);
+        self.x[0] = 0; // hardwired zero
+
+        // complete trace
+        self.tracer.capture_post_state(self.x, &self.xlen);
+        self.tracer.end_instruction();
+
+        result
+    }
+
     /// Decodes a word instruction data and returns a reference to
     /// [`Instruction`](struct.Instruction.html). Using [`DecodeCache`](struct.DecodeCache.html)
     /// so if cache hits this method returns the result very quickly.
@@ -1864,6 +2013,36 @@ fn trace_i(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
     }
 }
 
+// Zbb's unary instructions are encoded as OP-IMM, but their immediate field
+// selects the operation. They are traced with a zero immediate, the second
+// operand of their lookups.
+fn trace_unary(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
+    let f = parse_format_r(word);
+    ELFInstruction {
+        opcode: RV32IM::from_str(inst.name).unwrap(),
+        address: normalize_u64(address, xlen),
+        imm: Some(0),
+        rs1: Some(normalize_register(f.rs1)),
+        rs2: None,
+        rd: Some(normalize_register(f.rd)),
+        virtual_sequence_index: None,
+    }
+}
+
+// ZEXT.H is traced as an ANDI with a 16-bit mask
+fn trace_zext_h(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
+    let f = parse_format_r(word);
+    ELFInstruction {
+        opcode: RV32IM::from_str(inst.name).unwrap(),
+        address: normalize_u64(address, xlen),
+        imm: Some(0xffff),
+        rs1: Some(normalize_register(f.rs1)),
+        rs2: None,
+        rd: Some(normalize_register(f.rd)),
+        virtual_sequence_index: None,
+    }
+}
+
 fn trace_s(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
     let f = parse_format_s(word);
     ELFInstruction {
@@ -1917,7 +2096,46 @@ fn trace_j(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
     }
 }
 
-const INSTRUCTION_NUM: usize = 116;
+// ECALL reads the syscall number from a7 and writes the syscall's result to a0
+fn trace_ecall(inst: &Instruction, xlen: &Xlen, _word: u32, address: u64) -> ELFInstruction {
+    ELFInstruction {
+        opcode: RV32IM::from_str(inst.name).unwrap(),
+        address: normalize_u64(address, xlen),
+        imm: None,
+        rs1: Some(SYSCALL_NUMBER_REGISTER),
+        rs2: None,
+        rd: Some(SYSCALL_RETURN_REGISTER),
+        virtual_sequence_index: None,
+    }
+}
+
+// Precompiles read their operands from (and write their results to) the memory
+// pointed to by rs1 and rs2. Only some precompiles write to rd
+fn trace_precompile(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
+    let f = parse_format_r(word);
+    ELFInstruction {
+        opcode: RV32IM::from_str(inst.name).unwrap(),
+        address: normalize_u64(address, xlen),
+        imm: None,
+        rs1: Some(normalize_register(f.rs1)),
+        rs2: Some(normalize_register(f.rs2)),
+        rd: Some(normalize_register(f.rd)),
+        virtual_sequence_index: None,
+    }
+}
+
+fn load_bigint(cpu: &mut Cpu, address: u64) -> [u32; 8] {
+    std::array::from_fn(|i| cpu.mmu.load_word_raw(address.wrapping_add(4 * i as u64)))
+}
+
+fn store_words(cpu: &mut Cpu, address: u64, words: &[u32]) {
+    for (i, word) in words.iter().enumerate() {
+        cpu.mmu
+            .store_word_raw(address.wrapping_add(4 * i as u64), *word);
+    }
+}
+
+const INSTRUCTION_NUM: usize = 140;
 
 // @TODO: Reorder in often used order as
 pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
@@ -2222,6 +2440,18 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         disassemble: dump_format_i,
         trace: Some(trace_i),
     },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x40007033,
+        name: "ANDN",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.sign_extend(cpu.x[f.rs1] & !cpu.x[f.rs2]);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
     Instruction {
         mask: 0x0000007f,
         data: 0x00000017,
@@ -2441,6 +2671,48 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         disassemble: dump_format_csr,
         trace: None,
     },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x60001013,
+        name: "CLZ",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = match cpu.xlen {
+                Xlen::Bit32 => (cpu.x[f.rs1] as u32).leading_zeros() as i64,
+                Xlen::Bit64 => (cpu.x[f.rs1] as u64).leading_zeros() as i64,
+            };
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_unary),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x60201013,
+        name: "CPOP",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.unsigned_data(cpu.x[f.rs1]).count_ones() as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_unary),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x60101013,
+        name: "CTZ",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = match cpu.xlen {
+                Xlen::Bit32 => (cpu.x[f.rs1] as u32).trailing_zeros() as i64,
+                Xlen::Bit64 => (cpu.x[f.rs1] as u64).trailing_zeros() as i64,
+            };
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_unary),
+    },
     Instruction {
         mask: 0xfe00707f,
         data: 0x02004033,
@@ -2533,6 +2805,10 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         data: 0x00000073,
         name: "ECALL",
         operation: |cpu, _word, address| {
+            if let Some(result) = cpu.handle_syscall() {
+                cpu.x[SYSCALL_RETURN_REGISTER as usize] = cpu.sign_extend(result as i64);
+                return Ok(());
+            }
             let exception_type = match cpu.privilege_mode {
                 PrivilegeMode::User => TrapType::EnvironmentCallFromUMode,
                 PrivilegeMode::Supervisor => TrapType::EnvironmentCallFromSMode,
@@ -2545,7 +2821,71 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             })
         },
         disassemble: dump_empty,
-        trace: None,
+        trace: Some(trace_ecall),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0000000b | (BIGINT_ADD_FUNCT3 << 12),
+        name: "BIGINT_ADD",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let a_address = cpu.x[f.rs1] as u64;
+            let a = load_bigint(cpu, a_address);
+            let b = load_bigint(cpu, cpu.x[f.rs2] as u64);
+            cpu.tracer
+                .push_precompile_input(a.iter().chain(b.iter()).copied().collect());
+
+            let mut sum = a;
+            let carry = bigint_add(&mut sum, &b);
+            store_words(cpu, a_address, &sum);
+            cpu.x[f.rd] = carry as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_precompile),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0000000b | (BIGINT_MUL_FUNCT3 << 12),
+        name: "BIGINT_MUL",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let a_address = cpu.x[f.rs1] as u64;
+            let a = load_bigint(cpu, a_address);
+            let b = load_bigint(cpu, cpu.x[f.rs2] as u64);
+            cpu.tracer
+                .push_precompile_input(a.iter().chain(b.iter()).copied().collect());
+
+            store_words(cpu, a_address, &bigint_mul(&a, &b));
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_precompile),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0000000b | (BIGINT_MULMOD_FUNCT3 << 12),
+        name: "BIGINT_MULMOD",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let a_address = cpu.x[f.rs1] as u64;
+            let a = load_bigint(cpu, a_address);
+            let modulus = load_bigint(cpu, a_address.wrapping_add(32));
+            let b = load_bigint(cpu, cpu.x[f.rs2] as u64);
+            cpu.tracer.push_precompile_input(
+                a.iter()
+                    .chain(modulus.iter())
+                    .chain(b.iter())
+                    .copied()
+                    .collect(),
+            );
+
+            let (_, remainder) = bigint_mulmod(&a, &b, &modulus);
+            store_words(cpu, a_address, &remainder);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_precompile),
     },
     Instruction {
         mask: 0xfe00007f,
@@ -3120,6 +3460,60 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         disassemble: dump_format_i_mem,
         trace: None,
     },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0a006033,
+        name: "MAX",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.x[f.rs1].max(cpu.x[f.rs2]);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0a007033,
+        name: "MAXU",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let max = cpu
+                .unsigned_data(cpu.x[f.rs1])
+                .max(cpu.unsigned_data(cpu.x[f.rs2]));
+            cpu.x[f.rd] = cpu.sign_extend(max as i64);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0a004033,
+        name: "MIN",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.x[f.rs1].min(cpu.x[f.rs2]);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0a005033,
+        name: "MINU",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let min = cpu
+                .unsigned_data(cpu.x[f.rs1])
+                .min(cpu.unsigned_data(cpu.x[f.rs2]));
+            cpu.x[f.rd] = cpu.sign_extend(min as i64);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
     Instruction {
         mask: 0xfe00707f,
         data: 0x02000033,
@@ -3255,6 +3649,34 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         disassemble: dump_format_i,
         trace: Some(trace_i),
     },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x28705013,
+        name: "ORC_B",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let value = cpu.unsigned_data(cpu.x[f.rs1]);
+            let result = (0..8)
+                .filter(|byte| (value >> (8 * byte)) & 0xff != 0)
+                .fold(0u64, |result, byte| result | (0xff << (8 * byte)));
+            cpu.x[f.rd] = cpu.sign_extend(result as i64);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_unary),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x40006033,
+        name: "ORN",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.sign_extend(cpu.x[f.rs1] | !cpu.x[f.rs2]);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
     Instruction {
         mask: 0xfe00707f,
         data: 0x02006033,
@@ -3329,6 +3751,78 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         disassemble: dump_format_r,
         trace: None,
     },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x69805013,
+        name: "REV8",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = match cpu.xlen {
+                Xlen::Bit32 => (cpu.x[f.rs1] as u32).swap_bytes() as i32 as i64,
+                Xlen::Bit64 => (cpu.x[f.rs1] as u64).swap_bytes() as i64,
+            };
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_unary),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x60001033,
+        name: "ROL",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let shamt = match cpu.xlen {
+                Xlen::Bit32 => cpu.x[f.rs2] & 0x1f,
+                Xlen::Bit64 => cpu.x[f.rs2] & 0x3f,
+            };
+            cpu.x[f.rd] = match cpu.xlen {
+                Xlen::Bit32 => (cpu.x[f.rs1] as u32).rotate_left(shamt as u32) as i32 as i64,
+                Xlen::Bit64 => (cpu.x[f.rs1] as u64).rotate_left(shamt as u32) as i64,
+            };
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x60005033,
+        name: "ROR",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let shamt = match cpu.xlen {
+                Xlen::Bit32 => cpu.x[f.rs2] & 0x1f,
+                Xlen::Bit64 => cpu.x[f.rs2] & 0x3f,
+            };
+            cpu.x[f.rd] = match cpu.xlen {
+                Xlen::Bit32 => (cpu.x[f.rs1] as u32).rotate_right(shamt as u32) as i32 as i64,
+                Xlen::Bit64 => (cpu.x[f.rs1] as u64).rotate_right(shamt as u32) as i64,
+            };
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x60005013,
+        name: "RORI",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let shamt = match cpu.xlen {
+                Xlen::Bit32 => (word >> 20) & 0x1f,
+                Xlen::Bit64 => (word >> 20) & 0x3f,
+            };
+            cpu.x[f.rd] = match cpu.xlen {
+                Xlen::Bit32 => (cpu.x[f.rs1] as u32).rotate_right(shamt) as i32 as i64,
+                Xlen::Bit64 => (cpu.x[f.rs1] as u64).rotate_right(shamt) as i64,
+            };
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_i),
+    },
     Instruction {
         mask: 0x0000707f,
         data: 0x00000023,
@@ -3400,6 +3894,30 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         disassemble: dump_format_s,
         trace: None,
     },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x60401013,
+        name: "SEXT_B",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.x[f.rs1] as i8 as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_unary),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x60501013,
+        name: "SEXT_H",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.x[f.rs1] as i16 as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_unary),
+    },
     Instruction {
         mask: 0xfe007fff,
         data: 0x12000073,
@@ -3423,6 +3941,42 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         disassemble: dump_format_s,
         trace: Some(trace_s),
     },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x20002033,
+        name: "SH1ADD",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.sign_extend((cpu.x[f.rs1] << 1).wrapping_add(cpu.x[f.rs2]));
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x20004033,
+        name: "SH2ADD",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.sign_extend((cpu.x[f.rs1] << 2).wrapping_add(cpu.x[f.rs2]));
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x20006033,
+        name: "SH3ADD",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.sign_extend((cpu.x[f.rs1] << 3).wrapping_add(cpu.x[f.rs2]));
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
     Instruction {
         mask: 0xfe00707f,
         data: 0x00001033,
@@ -3742,6 +4296,18 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         disassemble: dump_empty,
         trace: None,
     },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x40004033,
+        name: "XNOR",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.sign_extend(!(cpu.x[f.rs1] ^ cpu.x[f.rs2]));
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
     Instruction {
         mask: 0xfe00707f,
         data: 0x00004033,
@@ -3766,6 +4332,18 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         disassemble: dump_format_i,
         trace: Some(trace_i),
     },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x08004033,
+        name: "ZEXT_H",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.x[f.rs1] & 0xffff;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_zext_h),
+    },
 ];
 
 /// The number of results [`DecodeCache`](struct.DecodeCache.html) holds.
@@ -4080,6 +4658,70 @@ mod test_cpu {
         // @TODO: Test compressed instruction operation
     }
 
+    #[test]
+    fn bare_metal_trace_matches() {
+        // auipc a1, 0; addi a3, x0, 3;
+        // loop: addi a0, a0, 12; sw a0, 64(a1); lw a2, 64(a1); addi a3, a3, -1; bne a3, x0, loop;
+        // c.addi a0, 1
+        let program = [
+            0x00000597, 0x00300693, 0x00c50513, 0x04a5a023, 0x0405a603, 0xfff68693, 0xfe0698e3,
+            0x00130505,
+        ];
+        let run = |bare_metal: bool| {
+            let mut cpu = create_cpu();
+            cpu.get_mut_mmu().init_memory(128);
+            for (i, word) in program.iter().enumerate() {
+                cpu.get_mut_mmu()
+                    .store_word(DRAM_BASE + 4 * i as u64, *word)
+                    .unwrap_or_else(|_| panic!("Failed to store"));
+            }
+            cpu.update_pc(DRAM_BASE);
+            cpu.enable_bare_metal(bare_metal);
+            for _ in 0..18 {
+                cpu.tick();
+            }
+            (cpu.read_pc(), cpu.x, cpu.tracer.drain_rows())
+        };
+
+        let (pc, registers, rows) = run(false);
+        assert_eq!(DRAM_BASE + 30, pc);
+        assert_eq!(37, registers[10]);
+        assert_eq!(18, rows.len());
+        assert_eq!((pc, registers, rows), run(true));
+    }
+
+    #[test]
+    fn bare_metal_stores_invalidate_decoded_instructions() {
+        // auipc a1, 0; lw a2, 32(a1);
+        // target: addi a0, a0, 1; sw a2, 8(a1); addi a3, a3, 1; addi a4, x0, 2; bne a3, a4, target;
+        // .word 0; .word addi a0, a0, 100
+        let program = [
+            0x00000597, 0x0205a603, 0x00150513, 0x00c5a423, 0x00168693, 0x00200713, 0xfee698e3,
+            0x00000000, 0x06450513,
+        ];
+        let run = |bare_metal: bool| {
+            let mut cpu = create_cpu();
+            cpu.get_mut_mmu().init_memory(128);
+            for (i, word) in program.iter().enumerate() {
+                cpu.get_mut_mmu()
+                    .store_word(DRAM_BASE + 4 * i as u64, *word)
+                    .unwrap_or_else(|_| panic!("Failed to store"));
+            }
+            cpu.update_pc(DRAM_BASE);
+            cpu.enable_bare_metal(bare_metal);
+            for _ in 0..12 {
+                cpu.tick();
+            }
+            (cpu.read_pc(), cpu.x, cpu.tracer.drain_rows())
+        };
+
+        let (pc, registers, rows) = run(false);
+        assert_eq!(DRAM_BASE + 28, pc);
+        // The second pass executes the overwritten instruction
+        assert_eq!(101, registers[10]);
+        assert_eq!((pc, registers, rows), run(true));
+    }
+
     #[test]
     fn fetch() {
         // .fetch() reads four bytes from the memory
@@ -4238,6 +4880,194 @@ mod test_cpu {
         // @TODO: Test vector type handlers
     }
 
+    #[test]
+    fn syscall() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(8);
+        cpu.update_pc(DRAM_BASE);
+        for (syscall, handler) in crate::syscall::default_handlers(&[1, 2, 3, 4, 5]) {
+            cpu.register_syscall(syscall, handler);
+        }
+        // Write ECALL instructions
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x00000073) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        match cpu.get_mut_mmu().store_word(DRAM_BASE + 4, 0x00000073) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+
+        cpu.x[SYSCALL_NUMBER_REGISTER as usize] = Syscall::HintRead as i64;
+        cpu.tick();
+
+        // Handled by the host rather than trapping
+        assert_eq!(DRAM_BASE + 4, cpu.read_pc());
+        assert_eq!(0x04030201, cpu.read_register(SYSCALL_RETURN_REGISTER as u8));
+        let rows = cpu.tracer.rows.try_borrow().unwrap();
+        let row = rows.last().unwrap();
+        assert_eq!(RV32IM::ECALL, row.instruction.opcode);
+        assert_eq!(Some(Syscall::HintRead as u64), row.register_state.rs1_val);
+        assert_eq!(Some(0x04030201), row.register_state.rd_post_val);
+        drop(rows);
+
+        cpu.x[SYSCALL_NUMBER_REGISTER as usize] = Syscall::Exit as i64;
+        cpu.x[SYSCALL_ARGUMENT_REGISTERS[0] as usize] = 3;
+        assert_eq!(None, cpu.exit_code());
+        cpu.tick();
+        assert_eq!(Some(3), cpu.exit_code());
+    }
+
+    #[test]
+    fn syscall_without_trace() {
+        let mut cpu = create_cpu();
+        for (syscall, handler) in crate::syscall::default_handlers(&[]) {
+            cpu.register_syscall(syscall, handler);
+        }
+        cpu.x[SYSCALL_NUMBER_REGISTER as usize] = Syscall::CycleCount as i64;
+        assert_eq!(Some(0), cpu.handle_syscall());
+    }
+
+    #[test]
+    fn bigint() {
+        let a_address = DRAM_BASE + 0x100;
+        let b_address = DRAM_BASE + 0x200;
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(0x300);
+        cpu.update_pc(DRAM_BASE);
+        // bigint_add x12, x10, x11; bigint_mul x10, x11; bigint_mulmod x10, x11
+        let instructions = [
+            (12 << 7) | (11 << 20) | (10 << 15) | 0x0000000b | (BIGINT_ADD_FUNCT3 << 12),
+            (11 << 20) | (10 << 15) | 0x0000000b | (BIGINT_MUL_FUNCT3 << 12),
+            (11 << 20) | (10 << 15) | 0x0000000b | (BIGINT_MULMOD_FUNCT3 << 12),
+        ];
+        for (i, word) in instructions.iter().enumerate() {
+            match cpu
+                .get_mut_mmu()
+                .store_word(DRAM_BASE + 4 * i as u64, *word)
+            {
+                Ok(()) => {}
+                Err(_e) => panic!("Failed to store"),
+            };
+        }
+        let load = |cpu: &mut Cpu, address: u64, len: usize| -> Vec<u32> {
+            (0..len)
+                .map(|i| cpu.get_mut_mmu().load_word_raw(address + 4 * i as u64))
+                .collect()
+        };
+        let store = |cpu: &mut Cpu, address: u64, words: &[u32]| {
+            for (i, word) in words.iter().enumerate() {
+                cpu.get_mut_mmu()
+                    .store_word_raw(address + 4 * i as u64, *word);
+            }
+        };
+        cpu.x[10] = a_address as i64;
+        cpu.x[11] = b_address as i64;
+
+        let a = [u32::MAX; 8];
+        let b = [2, 0, 0, 0, 0, 0, 0, 0];
+        store(&mut cpu, a_address, &a);
+        store(&mut cpu, b_address, &b);
+        cpu.tick();
+        assert_eq!(1, cpu.read_register(12));
+        assert_eq!(vec![1, 0, 0, 0, 0, 0, 0, 0], load(&mut cpu, a_address, 8));
+
+        store(&mut cpu, a_address, &a);
+        cpu.tick();
+        assert_eq!(bigint_mul(&a, &b).to_vec(), load(&mut cpu, a_address, 16));
+
+        // 3 * 2 mod 5, with the modulus following the first operand
+        store(
+            &mut cpu,
+            a_address,
+            &[3, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0],
+        );
+        cpu.tick();
+        assert_eq!(DRAM_BASE + 12, cpu.read_pc());
+        assert_eq!(
+            vec![1, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0],
+            load(&mut cpu, a_address, 16)
+        );
+
+        let rows = cpu.tracer.rows.try_borrow().unwrap();
+        let row = rows.last().unwrap();
+        assert_eq!(RV32IM::BIGINT_MULMOD, row.instruction.opcode);
+        let input = row.precompile_input.as_ref().unwrap();
+        assert_eq!(&[3, 0, 0, 0, 0, 0, 0, 0], &input[..8]);
+        assert_eq!(&[5, 0, 0, 0, 0, 0, 0, 0], &input[8..16]);
+        assert_eq!(&b, &input[16..]);
+    }
+
+    #[test]
+    fn zbb_zba() {
+        let mut cpu = create_cpu();
+        cpu.update_xlen(Xlen::Bit32);
+        let r = |data: u32, rs1: u32| data | (13 << 7) | (rs1 << 15) | (11 << 20);
+        let unary = |data: u32, rs1: u32| data | (13 << 7) | (rs1 << 15);
+        // (instruction, expected value of x13), with x10 = 0x80f00100, x11 = 0xff4
+        // and x12 = 0xf0
+        let cases: [(u32, u32); 27] = [
+            (r(0x40007033, 10), 0x80f00000),                  // andn
+            (r(0x40006033, 10), 0xfffff10b),                  // orn
+            (r(0x40004033, 10), 0x7f0ff10b),                  // xnor
+            (unary(0x60001013, 10), 0),                       // clz
+            (unary(0x60001013, 12), 24),                      // clz
+            (unary(0x60101013, 10), 8),                       // ctz
+            (unary(0x60101013, 12), 4),                       // ctz
+            (unary(0x60201013, 10), 6),                       // cpop
+            (unary(0x60201013, 12), 4),                       // cpop
+            (unary(0x60401013, 10), 0),                       // sext.b
+            (unary(0x60401013, 12), 0xfffffff0),              // sext.b
+            (unary(0x60501013, 10), 0x100),                   // sext.h
+            (unary(0x60501013, 12), 0xf0),                    // sext.h
+            (r(0x0a006033, 10), 0xff4),                       // max
+            (r(0x0a007033, 10), 0x80f00100),                  // maxu
+            (r(0x0a004033, 10), 0x80f00100),                  // min
+            (r(0x0a005033, 10), 0xff4),                       // minu
+            (r(0x60001033, 10), 0x10080f00),                  // rol
+            (r(0x60005033, 10), 0x0010080f),                  // ror
+            (unary(0x60005013 | (12 << 20), 10), 0x10080f00), // rori
+            (unary(0x28705013, 10), 0xffffff00),              // orc.b
+            (unary(0x69805013, 10), 0x0001f080),              // rev8
+            (unary(0x69805013, 12), 0xf0000000),              // rev8
+            (unary(0x08004033, 10), 0x100),                   // zext.h
+            (r(0x20002033, 10), 0x01e011f4),                  // sh1add
+            (r(0x20004033, 10), 0x03c013f4),                  // sh2add
+            (r(0x20006033, 10), 0x078017f4),                  // sh3add
+        ];
+        cpu.get_mut_mmu().init_memory(4 * cases.len() as u64);
+        cpu.update_pc(DRAM_BASE);
+        for (i, (word, _)) in cases.iter().enumerate() {
+            match cpu
+                .get_mut_mmu()
+                .store_word(DRAM_BASE + 4 * i as u64, *word)
+            {
+                Ok(()) => {}
+                Err(_e) => panic!("Failed to store"),
+            };
+        }
+        cpu.x[10] = 0x80f00100u32 as i32 as i64;
+        cpu.x[11] = 0xff4;
+        cpu.x[12] = 0xf0;
+        for (word, expected) in cases {
+            cpu.tick();
+            assert_eq!(
+                expected as i32 as i64,
+                cpu.read_register(13),
+                "{:08x}",
+                word
+            );
+        }
+
+        let rows = cpu.tracer.rows.try_borrow().unwrap();
+        assert_eq!(RV32IM::ANDN, rows[0].instruction.opcode);
+        // Unary instructions are traced with a zero immediate
+        assert_eq!(RV32IM::CLZ, rows[3].instruction.opcode);
+        assert_eq!(Some(0), rows[3].instruction.imm);
+        assert_eq!(RV32IM::ZEXT_H, rows[23].instruction.opcode);
+        assert_eq!(Some(0xffff), rows[23].instruction.imm);
+    }
+
     #[test]
     fn hardocded_zero() {
         let mut cpu = create_cpu();
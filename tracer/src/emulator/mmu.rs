@@ -61,6 +61,15 @@ pub struct Mmu {
     fetch_page_cache: FnvHashMap<u64, u64>,
     load_page_cache: FnvHashMap<u64, u64>,
     store_page_cache: FnvHashMap<u64, u64>,
+
+    /// In bare-metal mode, virtual addresses are physical addresses and
+    /// translation is skipped entirely. See `Cpu::enable_bare_metal`.
+    bare_metal: bool,
+    /// The range of addresses holding instructions decoded since the last
+    /// `take_code_written`, and whether any of them has been stored to since.
+    code_start: u64,
+    code_end: u64,
+    code_written: bool,
 }
 
 pub enum AddressingMode {
@@ -119,6 +128,10 @@ impl Mmu {
             fetch_page_cache: FnvHashMap::default(),
             load_page_cache: FnvHashMap::default(),
             store_page_cache: FnvHashMap::default(),
+            bare_metal: false,
+            code_start: u64::MAX,
+            code_end: 0,
+            code_written: false,
         }
     }
 
@@ -167,6 +180,47 @@ impl Mmu {
         self.clear_page_cache();
     }
 
+    /// Enables or disables bare-metal mode, in which address translation is skipped.
+    ///
+    /// # Arguments
+    /// * `enabled`
+    pub fn enable_bare_metal(&mut self, enabled: bool) {
+        self.bare_metal = enabled;
+    }
+
+    /// Records that the instruction at `address`, `length` bytes long, has been
+    /// decoded, so that stores to it are reported by `take_code_written`.
+    ///
+    /// # Arguments
+    /// * `address`
+    /// * `length`
+    pub fn watch_code(&mut self, address: u64, length: u64) {
+        self.code_start = self.code_start.min(address);
+        self.code_end = self.code_end.max(address.wrapping_add(length));
+    }
+
+    /// Returns whether any address passed to `watch_code` may have been stored
+    /// to since the last call, and stops watching all addresses if so.
+    pub fn take_code_written(&mut self) -> bool {
+        if !self.code_written {
+            return false;
+        }
+        self.code_start = u64::MAX;
+        self.code_end = 0;
+        self.code_written = false;
+        true
+    }
+
+    /// Notes a store of `width` bytes at `effective_address`, if it overlaps
+    /// watched code.
+    fn check_code_write(&mut self, effective_address: u64, width: u64) {
+        if effective_address < self.code_end
+            && effective_address.wrapping_add(width) > self.code_start
+        {
+            self.code_written = true;
+        }
+    }
+
     /// Clears page cache entries
     fn clear_page_cache(&mut self) {
         self.fetch_page_cache.clear();
@@ -603,6 +657,18 @@ impl Mmu {
         }
     }
 
+    /// Loads `len` bytes from main memory or peripheral devices depending on
+    /// physical address. Unlike `load`, this does not record the access in the trace.
+    ///
+    /// # Arguments
+    /// * `p_address` Physical address
+    /// * `len` Number of bytes
+    pub fn load_slice_raw(&mut self, p_address: u64, len: usize) -> Vec<u8> {
+        (0..len as u64)
+            .map(|i| self.load_raw(p_address.wrapping_add(i)))
+            .collect()
+    }
+
     /// Loads eight bytes from main memory or peripheral devices depending on
     /// physical address.
     ///
@@ -633,6 +699,7 @@ impl Mmu {
     /// * `value` data written
     pub fn store_raw(&mut self, p_address: u64, value: u8) {
         let effective_address = self.get_effective_address(p_address);
+        self.check_code_write(effective_address, 1);
         // @TODO: Mapping should be configurable with dtb
         match effective_address >= DRAM_BASE {
             true => self.memory.write_byte(effective_address, value),
@@ -662,6 +729,7 @@ impl Mmu {
     /// * `value` data written
     fn store_halfword_raw(&mut self, p_address: u64, value: u16) {
         let effective_address = self.get_effective_address(p_address);
+        self.check_code_write(effective_address, 2);
         match effective_address >= DRAM_BASE
             && effective_address.wrapping_add(1) > effective_address
         {
@@ -684,8 +752,9 @@ impl Mmu {
     /// # Arguments
     /// * `p_address` Physical address
     /// * `value` data written
-    fn store_word_raw(&mut self, p_address: u64, value: u32) {
+    pub fn store_word_raw(&mut self, p_address: u64, value: u32) {
         let effective_address = self.get_effective_address(p_address);
+        self.check_code_write(effective_address, 4);
         match effective_address >= DRAM_BASE
             && effective_address.wrapping_add(3) > effective_address
         {
@@ -710,6 +779,7 @@ impl Mmu {
     /// * `value` data written
     fn store_doubleword_raw(&mut self, p_address: u64, value: u64) {
         let effective_address = self.get_effective_address(p_address);
+        self.check_code_write(effective_address, 8);
         match effective_address >= DRAM_BASE
             && effective_address.wrapping_add(7) > effective_address
         {
@@ -758,6 +828,9 @@ impl Mmu {
         access_type: &MemoryAccessType,
     ) -> Result<u64, ()> {
         let address = self.get_effective_address(v_address);
+        if self.bare_metal {
+            return Ok(address);
+        }
         let v_page = address & !0xfff;
         let cache = match self.page_cache_enabled {
             true => match access_type {
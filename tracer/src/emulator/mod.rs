@@ -128,6 +128,14 @@ impl Emulator {
         }
     }
 
+    /// Enables or disables the CPU's bare-metal fast path. See `Cpu::enable_bare_metal`.
+    ///
+    /// # Arguments
+    /// * `enabled`
+    pub fn enable_bare_metal(&mut self, enabled: bool) {
+        self.cpu.enable_bare_metal(enabled);
+    }
+
     /// Runs CPU one cycle
     pub fn tick(&mut self) {
         self.cpu.tick();
@@ -1,6 +1,11 @@
 #![allow(dead_code)]
 
-use std::{fs::File, io::Read, path::PathBuf};
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::mpsc::SyncSender,
+};
 
 use common::{self, constants::RAM_START_ADDRESS};
 use emulator::{
@@ -13,28 +18,122 @@ use object::{Object, ObjectSection, SectionKind};
 
 mod decode;
 mod emulator;
+mod syscall;
 mod trace;
+mod trace_file;
 
+pub use common::precompiles::{
+    bigint_add, bigint_mul, bigint_mulmod, BIGINT_ADD_FUNCT3, BIGINT_MULMOD_FUNCT3,
+    BIGINT_MUL_FUNCT3,
+};
 pub use common::rv_trace::{
     ELFInstruction, JoltDevice, MemoryState, RVTraceRow, RegisterState, RV32IM,
 };
+pub use syscall::{SyscallContext, SyscallHandler};
+pub use trace_file::{
+    program_digest, replay_trace_file, TraceFileReader, TraceFileWriter, TRACE_FILE_VERSION,
+};
 
 use crate::decode::decode_raw;
 
+/// Receives the rows of an execution trace in chunks, as the emulator produces them.
+/// An error stops the emulator, and is returned by `trace_to_sink`.
+pub trait TraceSink {
+    fn push_rows(&mut self, rows: Vec<RVTraceRow>) -> io::Result<()>;
+}
+
+impl TraceSink for Vec<RVTraceRow> {
+    fn push_rows(&mut self, mut rows: Vec<RVTraceRow>) -> io::Result<()> {
+        self.append(&mut rows);
+        Ok(())
+    }
+}
+
+impl TraceSink for SyncSender<Vec<RVTraceRow>> {
+    fn push_rows(&mut self, rows: Vec<RVTraceRow>) -> io::Result<()> {
+        self.send(rows)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "trace receiver was dropped"))
+    }
+}
+
+/// The number of rows `trace_to_sink` pushes to its sink at a time.
+pub const TRACE_CHUNK_SIZE: usize = 1 << 16;
+
 #[tracing::instrument(skip_all)]
 pub fn trace(
     elf: &PathBuf,
     inputs: &[u8],
+    hints: &[u8],
     input_size: u64,
     output_size: u64,
 ) -> (Vec<RVTraceRow>, JoltDevice) {
+    let mut rows = Vec::new();
+    let device = trace_to_sink(elf, inputs, hints, input_size, output_size, &mut rows)
+        .expect("pushing to a Vec never fails");
+    (rows, device)
+}
+
+/// Same as `trace`, but pushes the rows to `sink` in chunks of `TRACE_CHUNK_SIZE` as the
+/// program runs, so the full trace is never held by the emulator. Stops at the first error
+/// returned by `sink`.
+#[tracing::instrument(skip_all)]
+pub fn trace_to_sink(
+    elf: &PathBuf,
+    inputs: &[u8],
+    hints: &[u8],
+    input_size: u64,
+    output_size: u64,
+    sink: &mut impl TraceSink,
+) -> io::Result<JoltDevice> {
+    // Guests run in machine mode without translation, interrupts or devices
+    run_to_sink(elf, inputs, hints, input_size, output_size, sink, true)
+}
+
+/// Same as `trace`, but runs every instruction through the full emulator (address
+/// translation, interrupt checks and devices) instead of the bare-metal fast path.
+/// Much slower; only useful to check that the fast path produces the same trace.
+#[tracing::instrument(skip_all)]
+pub fn trace_full_emulation(
+    elf: &PathBuf,
+    inputs: &[u8],
+    hints: &[u8],
+    input_size: u64,
+    output_size: u64,
+) -> (Vec<RVTraceRow>, JoltDevice) {
+    let mut rows = Vec::new();
+    let device = run_to_sink(
+        elf,
+        inputs,
+        hints,
+        input_size,
+        output_size,
+        &mut rows,
+        false,
+    )
+    .expect("pushing to a Vec never fails");
+    (rows, device)
+}
+
+fn run_to_sink(
+    elf: &PathBuf,
+    inputs: &[u8],
+    hints: &[u8],
+    input_size: u64,
+    output_size: u64,
+    sink: &mut impl TraceSink,
+    bare_metal: bool,
+) -> io::Result<JoltDevice> {
     let term = DefaultTerminal::new();
     let mut emulator = Emulator::new(Box::new(term));
     emulator.update_xlen(get_xlen());
+    emulator.enable_bare_metal(bare_metal);
 
     let mut jolt_device = JoltDevice::new(input_size, output_size);
     jolt_device.inputs = inputs.to_vec();
     emulator.get_mut_cpu().get_mut_mmu().jolt_device = jolt_device;
+    for (syscall, handler) in syscall::default_handlers(hints) {
+        emulator.get_mut_cpu().register_syscall(syscall, handler);
+    }
 
     let mut elf_file = File::open(elf).unwrap();
 
@@ -48,24 +147,45 @@ pub fn trace(
         let pc = emulator.get_cpu().read_pc();
         emulator.tick();
 
-        // This is a trick to see if the program has terminated by throwing itself
-        // into an infinite loop. It seems to be a good heuristic for now but we
-        // should eventually migrate to an explicit shutdown signal.
+        if emulator.get_cpu().exit_code().is_some() {
+            break;
+        }
+
+        // Programs that don't call `Syscall::Exit` are detected with a trick: see if
+        // the program has terminated by throwing itself into an infinite loop.
         if prev_pc == pc {
             break;
         }
 
         prev_pc = pc;
+
+        let tracer = &emulator.get_cpu().tracer;
+        if tracer.rows.try_borrow().unwrap().len() >= TRACE_CHUNK_SIZE {
+            sink.push_rows(tracer.drain_rows())?;
+        }
     }
 
-    let mut rows = emulator.get_mut_cpu().tracer.rows.try_borrow_mut().unwrap();
-    let mut output = Vec::new();
-    output.append(&mut rows);
-    drop(rows);
+    sink.push_rows(emulator.get_cpu().tracer.drain_rows())?;
 
-    let device = emulator.get_mut_cpu().get_mut_mmu().jolt_device.clone();
+    Ok(emulator.get_mut_cpu().get_mut_mmu().jolt_device.clone())
+}
 
-    (output, device)
+/// Same as `trace`, but records the trace to a trace file at `path` as the program runs,
+/// for `replay_trace_file` to read back later.
+#[tracing::instrument(skip_all)]
+pub fn trace_to_file(
+    elf: &PathBuf,
+    inputs: &[u8],
+    hints: &[u8],
+    input_size: u64,
+    output_size: u64,
+    path: &Path,
+    compress: bool,
+) -> io::Result<JoltDevice> {
+    let mut writer = TraceFileWriter::create(path, &program_digest(elf)?, compress)?;
+    let device = trace_to_sink(elf, inputs, hints, input_size, output_size, &mut writer)?;
+    writer.finish(&device)?;
+    Ok(device)
 }
 
 #[tracing::instrument(skip_all)]
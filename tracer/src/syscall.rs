@@ -0,0 +1,108 @@
+use std::io::Write;
+
+use common::syscall::{Syscall, STDERR_FD, STDOUT_FD};
+
+use crate::emulator::mmu::Mmu;
+
+/// View of the machine handed to a `SyscallHandler`.
+pub struct SyscallContext<'a> {
+    /// Values of a0-a6 at the time of the `ECALL`.
+    pub args: [u64; 7],
+    pub(crate) mmu: &'a mut Mmu,
+    pub(crate) cycle_count: u64,
+    pub(crate) exit_code: &'a mut Option<u32>,
+}
+
+impl<'a> SyscallContext<'a> {
+    /// Reads `len` bytes of guest memory starting at `address`. The read is not
+    /// recorded in the trace, so its result must not influence guest-visible state.
+    pub fn read_memory(&mut self, address: u64, len: usize) -> Vec<u8> {
+        self.mmu.load_slice_raw(address, len)
+    }
+
+    /// Number of instructions executed before this `ECALL`.
+    pub fn cycle_count(&self) -> u64 {
+        self.cycle_count
+    }
+
+    /// Halts the emulator after the current instruction.
+    pub fn exit(&mut self, code: u32) {
+        *self.exit_code = Some(code);
+    }
+}
+
+/// A host-side implementation of a syscall.
+pub trait SyscallHandler {
+    /// Services the syscall and returns the value written back to a0.
+    fn handle(&mut self, context: &mut SyscallContext) -> u64;
+}
+
+pub struct ExitHandler;
+
+impl SyscallHandler for ExitHandler {
+    fn handle(&mut self, context: &mut SyscallContext) -> u64 {
+        let code = context.args[0];
+        context.exit(code as u32);
+        code
+    }
+}
+
+pub struct WriteHandler;
+
+impl SyscallHandler for WriteHandler {
+    fn handle(&mut self, context: &mut SyscallContext) -> u64 {
+        let [fd, ptr, len, ..] = context.args;
+        let bytes = context.read_memory(ptr, len as usize);
+        let result = match fd {
+            STDOUT_FD => std::io::stdout().write_all(&bytes),
+            STDERR_FD => std::io::stderr().write_all(&bytes),
+            _ => return 0,
+        };
+        match result {
+            Ok(()) => len,
+            Err(_) => 0,
+        }
+    }
+}
+
+pub struct HintReadHandler {
+    hints: Vec<u8>,
+    cursor: usize,
+}
+
+impl HintReadHandler {
+    pub fn new(hints: &[u8]) -> Self {
+        Self {
+            hints: hints.to_vec(),
+            cursor: 0,
+        }
+    }
+}
+
+impl SyscallHandler for HintReadHandler {
+    fn handle(&mut self, _context: &mut SyscallContext) -> u64 {
+        let mut word = [0u8; 4];
+        let remaining = self.hints.len().saturating_sub(self.cursor).min(4);
+        word[..remaining].copy_from_slice(&self.hints[self.cursor..self.cursor + remaining]);
+        self.cursor += remaining;
+        u32::from_le_bytes(word) as u64
+    }
+}
+
+pub struct CycleCountHandler;
+
+impl SyscallHandler for CycleCountHandler {
+    fn handle(&mut self, context: &mut SyscallContext) -> u64 {
+        context.cycle_count()
+    }
+}
+
+/// The handlers registered by `tracer::trace`, one per `Syscall` variant.
+pub fn default_handlers(hints: &[u8]) -> Vec<(Syscall, Box<dyn SyscallHandler>)> {
+    vec![
+        (Syscall::Exit, Box::new(ExitHandler)),
+        (Syscall::Write, Box::new(WriteHandler)),
+        (Syscall::HintRead, Box::new(HintReadHandler::new(hints))),
+        (Syscall::CycleCount, Box::new(CycleCountHandler)),
+    ]
+}
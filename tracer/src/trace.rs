@@ -7,6 +7,8 @@ use crate::emulator::cpu::Xlen;
 pub struct Tracer {
     pub rows: RefCell<Vec<RVTraceRow>>,
     open: RefCell<bool>,
+    /// The number of rows previously taken out of `rows` by `drain_rows`.
+    num_drained: RefCell<usize>,
 }
 
 impl Tracer {
@@ -14,9 +16,23 @@ impl Tracer {
         Self {
             rows: RefCell::new(Vec::new()),
             open: RefCell::new(false),
+            num_drained: RefCell::new(0),
         }
     }
 
+    /// The number of rows traced so far, including those already drained.
+    pub fn num_rows(&self) -> usize {
+        *self.num_drained.try_borrow().unwrap() + self.rows.try_borrow().unwrap().len()
+    }
+
+    /// Takes the rows traced since the last call. Must only be called between instructions.
+    pub fn drain_rows(&self) -> Vec<RVTraceRow> {
+        assert!(!*self.open.try_borrow().unwrap());
+        let rows = std::mem::take(&mut *self.rows.try_borrow_mut().unwrap());
+        *self.num_drained.try_borrow_mut().unwrap() += rows.len();
+        rows
+    }
+
     pub fn start_instruction(&self, inst: ELFInstruction) {
         let mut inst = inst;
         inst.address = inst.address as u32 as u64;
@@ -25,6 +41,7 @@ impl Tracer {
             instruction: inst,
             register_state: RegisterState::default(),
             memory_state: None,
+            precompile_input: None,
         });
     }
 
@@ -68,6 +85,16 @@ impl Tracer {
         }
     }
 
+    pub fn push_precompile_input(&self, input: Vec<u32>) {
+        if !*self.open.try_borrow().unwrap() {
+            return;
+        }
+
+        if let Some(row) = self.rows.try_borrow_mut().unwrap().last_mut() {
+            row.precompile_input = Some(input);
+        }
+    }
+
     pub fn end_instruction(&self) {
         *self.open.try_borrow_mut().unwrap() = false;
     }
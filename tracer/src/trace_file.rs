@@ -0,0 +1,679 @@
+//! A compact, versioned binary format for execution traces, so that an execution can be
+//! recorded once and replayed later (e.g. to regenerate a proof) without re-running the guest.
+//!
+//! A trace file consists of:
+//! - an 8-byte magic, a version byte, a flags byte and the SHA-256 digest of the program's ELF
+//!   file, all uncompressed;
+//! - a (possibly compressed) body of chunks, each a varint row count followed by that many
+//!   rows, terminated by an empty chunk;
+//! - the program's `JoltDevice`, in ark-serialize's compressed encoding.
+//!
+//! Each row is encoded as its opcode, two bytes marking which of its optional fields are
+//! present, the zigzag varint difference between its address and the previous row's, and
+//! then its present fields as varints.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use common::rv_trace::{
+    ELFInstruction, JoltDevice, MemoryState, RVTraceRow, RegisterState, RV32IM,
+};
+
+use crate::{TraceSink, TRACE_CHUNK_SIZE};
+
+const MAGIC: [u8; 8] = *b"JOLTTRC\0";
+pub const TRACE_FILE_VERSION: u8 = 1;
+
+/// Set in the header's flags byte if the body is deflate-compressed.
+const FLAG_COMPRESSED: u8 = 1;
+
+// Bits of a row's first presence byte
+const HAS_RS1: u8 = 1 << 0;
+const HAS_RS2: u8 = 1 << 1;
+const HAS_RD: u8 = 1 << 2;
+const HAS_IMM: u8 = 1 << 3;
+const HAS_VIRTUAL_SEQUENCE_INDEX: u8 = 1 << 4;
+const HAS_RS1_VAL: u8 = 1 << 5;
+const HAS_RS2_VAL: u8 = 1 << 6;
+const HAS_RD_POST_VAL: u8 = 1 << 7;
+
+// Bits of a row's second presence byte
+const MEMORY_STATE_MASK: u8 = 0b11;
+const MEMORY_STATE_READ: u8 = 1;
+const MEMORY_STATE_WRITE: u8 = 2;
+const HAS_PRECOMPILE_INPUT: u8 = 1 << 2;
+
+/// More words than any precompile's input, which is at most three 256-bit operands.
+const MAX_PRECOMPILE_INPUT_WORDS: usize = 32;
+
+/// The SHA-256 digest of the ELF file at `elf`, which identifies the program a trace file was
+/// recorded from.
+pub fn program_digest(elf: &Path) -> io::Result<[u8; 32]> {
+    Ok(sha256(&std::fs::read(elf)?))
+}
+
+fn sha256(message: &[u8]) -> [u8; 32] {
+    // Append a 1 bit, then zeros up to 8 bytes short of a block, then the length in bits
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    padded.resize(padded.len() + (120 - padded.len() % 64) % 64, 0);
+    padded.extend_from_slice(&(message.len() as u64 * 8).to_be_bytes());
+
+    let mut state = SHA256_IV;
+    for block in padded.chunks_exact(64) {
+        let words = std::array::from_fn(|i| {
+            u32::from_be_bytes(block[4 * i..4 * i + 4].try_into().unwrap())
+        });
+        sha256_compress(&mut state, &words);
+    }
+
+    let mut digest = [0u8; 32];
+    for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// SHA-256 round constants (FIPS 180-4, Section 4.2.2).
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256 initial hash value (FIPS 180-4, Section 5.3.3).
+const SHA256_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The SHA-256 compression function.
+fn sha256_compress(state: &mut [u32; 8], block: &[u32; 16]) {
+    let mut w = [0u32; 64];
+    w[..16].copy_from_slice(block);
+    for t in 16..64 {
+        let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+        let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+        w[t] = w[t - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for t in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(SHA256_K[t])
+            .wrapping_add(w[t]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn write_varint(writer: &mut impl Write, mut value: u64) -> io::Result<()> {
+    let mut bytes = [0u8; 10];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes[len] = byte;
+            len += 1;
+            break;
+        }
+        bytes[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.write_all(&bytes[..len])
+}
+
+fn read_varint(reader: &mut impl Read) -> io::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_u8(reader)?;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(invalid_data("varint is too long"))
+}
+
+fn read_u8(reader: &mut impl Read) -> io::Result<u8> {
+    let mut byte = [0u8];
+    reader.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+/// Where the body of a trace file is written to.
+enum Output {
+    Plain(BufWriter<File>),
+    #[cfg(feature = "compression")]
+    Deflate(flate2::write::DeflateEncoder<BufWriter<File>>),
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::Plain(writer) => writer.write(buf),
+            #[cfg(feature = "compression")]
+            Output::Deflate(writer) => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::Plain(writer) => writer.flush(),
+            #[cfg(feature = "compression")]
+            Output::Deflate(writer) => writer.flush(),
+        }
+    }
+}
+
+impl Output {
+    /// Ends the compressed stream, if any, and flushes the file.
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Output::Plain(mut writer) => writer.flush(),
+            #[cfg(feature = "compression")]
+            Output::Deflate(encoder) => encoder.finish()?.flush(),
+        }
+    }
+}
+
+/// Writes a trace file incrementally. As a `TraceSink`, it can be passed straight to
+/// `trace_to_sink`; once the program has run, `finish` writes its `JoltDevice`.
+pub struct TraceFileWriter {
+    output: Output,
+    prev_address: u64,
+}
+
+impl TraceFileWriter {
+    /// Creates the trace file at `path`, for the program whose ELF file has the digest
+    /// `program_digest`. Compression requires the `compression` feature.
+    pub fn create(path: &Path, program_digest: &[u8; 32], compress: bool) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[
+            TRACE_FILE_VERSION,
+            if compress { FLAG_COMPRESSED } else { 0 },
+        ])?;
+        writer.write_all(program_digest)?;
+
+        let output = if compress {
+            #[cfg(feature = "compression")]
+            {
+                Output::Deflate(flate2::write::DeflateEncoder::new(
+                    writer,
+                    flate2::Compression::fast(),
+                ))
+            }
+            #[cfg(not(feature = "compression"))]
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "trace compression requires the `compression` feature",
+                ));
+            }
+        } else {
+            Output::Plain(writer)
+        };
+
+        Ok(Self {
+            output,
+            prev_address: 0,
+        })
+    }
+
+    pub fn write_rows(&mut self, rows: &[RVTraceRow]) -> io::Result<()> {
+        if rows.is_empty() {
+            // An empty chunk marks the end of the trace
+            return Ok(());
+        }
+        write_varint(&mut self.output, rows.len() as u64)?;
+        for row in rows {
+            self.write_row(row)?;
+        }
+        Ok(())
+    }
+
+    fn write_row(&mut self, row: &RVTraceRow) -> io::Result<()> {
+        let instruction = &row.instruction;
+        let register_state = &row.register_state;
+
+        let mut presence = 0u8;
+        for (field, bit) in [
+            (instruction.rs1.is_some(), HAS_RS1),
+            (instruction.rs2.is_some(), HAS_RS2),
+            (instruction.rd.is_some(), HAS_RD),
+            (instruction.imm.is_some(), HAS_IMM),
+            (
+                instruction.virtual_sequence_index.is_some(),
+                HAS_VIRTUAL_SEQUENCE_INDEX,
+            ),
+            (register_state.rs1_val.is_some(), HAS_RS1_VAL),
+            (register_state.rs2_val.is_some(), HAS_RS2_VAL),
+            (register_state.rd_post_val.is_some(), HAS_RD_POST_VAL),
+        ] {
+            if field {
+                presence |= bit;
+            }
+        }
+        let mut extra = match row.memory_state {
+            None => 0,
+            Some(MemoryState::Read { .. }) => MEMORY_STATE_READ,
+            Some(MemoryState::Write { .. }) => MEMORY_STATE_WRITE,
+        };
+        if row.precompile_input.is_some() {
+            extra |= HAS_PRECOMPILE_INPUT;
+        }
+
+        let address_delta = instruction.address.wrapping_sub(self.prev_address) as i64;
+        self.prev_address = instruction.address;
+
+        self.output
+            .write_all(&[instruction.opcode as u8, presence, extra])?;
+        write_varint(
+            &mut self.output,
+            ((address_delta << 1) ^ (address_delta >> 63)) as u64,
+        )?;
+        for value in [
+            instruction.rs1,
+            instruction.rs2,
+            instruction.rd,
+            instruction.imm.map(|imm| imm as u64),
+            instruction.virtual_sequence_index.map(|index| index as u64),
+            register_state.rs1_val,
+            register_state.rs2_val,
+            register_state.rd_post_val,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            write_varint(&mut self.output, value)?;
+        }
+        match row.memory_state {
+            Some(MemoryState::Read { address, value }) => {
+                write_varint(&mut self.output, address)?;
+                write_varint(&mut self.output, value)?;
+            }
+            Some(MemoryState::Write {
+                address,
+                post_value,
+            }) => {
+                write_varint(&mut self.output, address)?;
+                write_varint(&mut self.output, post_value)?;
+            }
+            None => {}
+        }
+        if let Some(input) = &row.precompile_input {
+            write_varint(&mut self.output, input.len() as u64)?;
+            for word in input {
+                write_varint(&mut self.output, *word as u64)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Ends the trace, writes `device` and flushes the file.
+    pub fn finish(mut self, device: &JoltDevice) -> io::Result<()> {
+        write_varint(&mut self.output, 0)?;
+        device
+            .serialize_compressed(&mut self.output)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        self.output.finish()
+    }
+}
+
+impl TraceSink for TraceFileWriter {
+    fn push_rows(&mut self, rows: Vec<RVTraceRow>) -> io::Result<()> {
+        self.write_rows(&rows)
+    }
+}
+
+/// Reads a trace file written by `TraceFileWriter`.
+pub struct TraceFileReader {
+    input: Box<dyn Read>,
+    program_digest: [u8; 32],
+    prev_address: u64,
+    done: bool,
+}
+
+impl TraceFileReader {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(invalid_data("not a Jolt trace file"));
+        }
+        let version = read_u8(&mut reader)?;
+        if version != TRACE_FILE_VERSION {
+            return Err(invalid_data(&format!(
+                "unsupported trace file version {} (expected {})",
+                version, TRACE_FILE_VERSION
+            )));
+        }
+        let flags = read_u8(&mut reader)?;
+        let mut program_digest = [0u8; 32];
+        reader.read_exact(&mut program_digest)?;
+
+        let input: Box<dyn Read> = if flags & FLAG_COMPRESSED != 0 {
+            #[cfg(feature = "compression")]
+            {
+                Box::new(flate2::read::DeflateDecoder::new(reader))
+            }
+            #[cfg(not(feature = "compression"))]
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "trace file is compressed, which requires the `compression` feature",
+                ));
+            }
+        } else {
+            Box::new(reader)
+        };
+
+        Ok(Self {
+            input,
+            program_digest,
+            prev_address: 0,
+            done: false,
+        })
+    }
+
+    /// The digest of the ELF file of the program the trace was recorded from.
+    pub fn program_digest(&self) -> &[u8; 32] {
+        &self.program_digest
+    }
+
+    /// Reads the next chunk of rows, or returns `None` once the trace has ended.
+    pub fn read_rows(&mut self) -> io::Result<Option<Vec<RVTraceRow>>> {
+        if self.done {
+            return Ok(None);
+        }
+        let num_rows = read_varint(&mut self.input)? as usize;
+        if num_rows == 0 {
+            self.done = true;
+            return Ok(None);
+        }
+        // Don't trust the length prefix with a huge allocation
+        let mut rows = Vec::with_capacity(num_rows.min(TRACE_CHUNK_SIZE));
+        for _ in 0..num_rows {
+            rows.push(self.read_row()?);
+        }
+        Ok(Some(rows))
+    }
+
+    fn read_row(&mut self) -> io::Result<RVTraceRow> {
+        let input = &mut self.input;
+        let opcode =
+            RV32IM::from_repr(read_u8(input)?).ok_or_else(|| invalid_data("unknown opcode"))?;
+        let presence = read_u8(input)?;
+        let extra = read_u8(input)?;
+
+        let zigzag = read_varint(input)?;
+        let address_delta = ((zigzag >> 1) as i64) ^ -((zigzag & 1) as i64);
+        let address = self.prev_address.wrapping_add(address_delta as u64);
+        self.prev_address = address;
+
+        let mut read_field = |bit: u8| -> io::Result<Option<u64>> {
+            if presence & bit != 0 {
+                read_varint(input).map(Some)
+            } else {
+                Ok(None)
+            }
+        };
+        let instruction = ELFInstruction {
+            address,
+            opcode,
+            rs1: read_field(HAS_RS1)?,
+            rs2: read_field(HAS_RS2)?,
+            rd: read_field(HAS_RD)?,
+            imm: read_field(HAS_IMM)?.map(|imm| imm as u32),
+            virtual_sequence_index: read_field(HAS_VIRTUAL_SEQUENCE_INDEX)?
+                .map(|index| index as usize),
+        };
+        let register_state = RegisterState {
+            rs1_val: read_field(HAS_RS1_VAL)?,
+            rs2_val: read_field(HAS_RS2_VAL)?,
+            rd_post_val: read_field(HAS_RD_POST_VAL)?,
+        };
+
+        let memory_state = match extra & MEMORY_STATE_MASK {
+            0 => None,
+            MEMORY_STATE_READ => Some(MemoryState::Read {
+                address: read_varint(input)?,
+                value: read_varint(input)?,
+            }),
+            MEMORY_STATE_WRITE => Some(MemoryState::Write {
+                address: read_varint(input)?,
+                post_value: read_varint(input)?,
+            }),
+            _ => return Err(invalid_data("unknown memory state")),
+        };
+        let precompile_input = if extra & HAS_PRECOMPILE_INPUT != 0 {
+            let len = read_varint(input)? as usize;
+            // As with the row count, don't trust the length prefix with a huge allocation
+            let mut words = Vec::with_capacity(len.min(MAX_PRECOMPILE_INPUT_WORDS));
+            for _ in 0..len {
+                words.push(read_varint(input)? as u32);
+            }
+            Some(words)
+        } else {
+            None
+        };
+
+        Ok(RVTraceRow {
+            instruction,
+            register_state,
+            memory_state,
+            precompile_input,
+        })
+    }
+
+    /// Reads the program's `JoltDevice`, skipping any rows that haven't been read yet.
+    pub fn finish(mut self) -> io::Result<JoltDevice> {
+        while self.read_rows()?.is_some() {}
+        JoltDevice::deserialize_compressed(&mut self.input)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Replays the trace file at `path` into `sink`, in the chunks it was recorded in, and
+/// returns the recorded `JoltDevice`. Fails without replaying anything if the trace wasn't
+/// recorded from the program whose ELF file has the digest `program_digest`.
+pub fn replay_trace_file(
+    path: &Path,
+    program_digest: &[u8; 32],
+    sink: &mut impl TraceSink,
+) -> io::Result<JoltDevice> {
+    let mut reader = TraceFileReader::open(path)?;
+    if reader.program_digest() != program_digest {
+        return Err(invalid_data(
+            "trace file was recorded from a different program",
+        ));
+    }
+    while let Some(rows) = reader.read_rows()? {
+        sink.push_rows(rows)?;
+    }
+    reader.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample_rows() -> Vec<RVTraceRow> {
+        let row = |address: u64, opcode: RV32IM| RVTraceRow {
+            instruction: ELFInstruction {
+                address,
+                opcode,
+                rs1: None,
+                rs2: None,
+                rd: None,
+                imm: None,
+                virtual_sequence_index: None,
+            },
+            register_state: RegisterState::default(),
+            memory_state: None,
+            precompile_input: None,
+        };
+
+        let mut addi = row(0x80000000, RV32IM::ADDI);
+        addi.instruction.rs1 = Some(10);
+        addi.instruction.rd = Some(10);
+        addi.instruction.imm = Some(0xfffffff4);
+        addi.register_state.rs1_val = Some(5);
+        addi.register_state.rd_post_val = Some(u32::MAX as u64);
+
+        let mut sw = row(0x80000004, RV32IM::SW);
+        sw.instruction.rs1 = Some(2);
+        sw.instruction.rs2 = Some(10);
+        sw.instruction.imm = Some(8);
+        sw.register_state.rs1_val = Some(0x80001000);
+        sw.register_state.rs2_val = Some(7);
+        sw.memory_state = Some(MemoryState::Write {
+            address: 0x80001008,
+            post_value: 7,
+        });
+
+        let mut lw = row(0x7ffffff0, RV32IM::LW);
+        lw.instruction.virtual_sequence_index = Some(3);
+        lw.memory_state = Some(MemoryState::Read {
+            address: 0x80001008,
+            value: 7,
+        });
+
+        let mut bigint_add = row(0x80000010, RV32IM::BIGINT_ADD);
+        bigint_add.precompile_input = Some((0..16).collect());
+
+        vec![addi, sw, lw, bigint_add]
+    }
+
+    fn roundtrip(compress: bool) {
+        let path = std::env::temp_dir().join(format!(
+            "jolt-trace-file-test-{}-{}.trace",
+            std::process::id(),
+            compress
+        ));
+        let rows = sample_rows();
+        let device = JoltDevice::new(64, 64);
+
+        let program_digest = sha256(b"program");
+
+        let mut writer = TraceFileWriter::create(&path, &program_digest, compress).unwrap();
+        writer.push_rows(rows[..2].to_vec()).unwrap();
+        writer.push_rows(rows[2..].to_vec()).unwrap();
+        writer.finish(&device).unwrap();
+
+        let mut replayed = Vec::new();
+        let replayed_device = replay_trace_file(&path, &program_digest, &mut replayed).unwrap();
+
+        // A trace recorded from another program is rejected before any rows are replayed
+        let mut other_replayed = Vec::new();
+        let other_result = replay_trace_file(&path, &sha256(b"other"), &mut other_replayed);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(replayed, rows);
+        assert_eq!(replayed_device, device);
+        assert_eq!(
+            other_result.err().unwrap().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert!(other_replayed.is_empty());
+    }
+
+    #[test]
+    fn trace_file_roundtrip() {
+        roundtrip(false);
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compressed_trace_file_roundtrip() {
+        roundtrip(true);
+    }
+
+    #[test]
+    fn sha256_matches_test_vectors() {
+        let from_hex = |hex: &str| -> Vec<u8> {
+            (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+                .collect()
+        };
+        assert_eq!(
+            sha256(b"").to_vec(),
+            from_hex("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+        );
+        assert_eq!(
+            sha256(b"abc").to_vec(),
+            from_hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        // Two blocks, as the length doesn't fit after the first block's padding
+        assert_eq!(
+            sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_vec(),
+            from_hex("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1")
+        );
+    }
+
+    #[test]
+    fn write_errors_are_returned() {
+        let path = std::env::temp_dir().join(format!(
+            "jolt-trace-file-error-test-{}.trace",
+            std::process::id()
+        ));
+        let mut writer = TraceFileWriter::create(&path, &[0; 32], false).unwrap();
+        // Swap in a file opened read-only, so that writing to it fails
+        writer.output = Output::Plain(BufWriter::with_capacity(0, File::open(&path).unwrap()));
+        let result = writer.push_rows(sample_rows());
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        let path = std::env::temp_dir().join(format!(
+            "jolt-trace-file-version-test-{}.trace",
+            std::process::id()
+        ));
+        let mut header = MAGIC.to_vec();
+        header.extend([TRACE_FILE_VERSION + 1, 0]);
+        std::fs::write(&path, header).unwrap();
+
+        let result = TraceFileReader::open(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(result.err().unwrap().kind(), io::ErrorKind::InvalidData);
+    }
+}